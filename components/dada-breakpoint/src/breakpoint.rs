@@ -104,8 +104,11 @@ impl TreeTraversal<'_> {
             | syntax::ExprData::Id(_)
             | syntax::ExprData::BooleanLiteral(_)
             | syntax::ExprData::IntegerLiteral(..)
-            | syntax::ExprData::FloatLiteral(_, _)
-            | syntax::ExprData::StringLiteral(_) => Some(expr),
+            | syntax::ExprData::FloatLiteral(_)
+            | syntax::ExprData::CharLiteral(_)
+            | syntax::ExprData::StringLiteral(_)
+            | syntax::ExprData::Continue(_)
+            | syntax::ExprData::Fn(_) => Some(expr),
 
             syntax::ExprData::Var(_, base_expr)
             | syntax::ExprData::Dot(base_expr, _)
@@ -114,18 +117,27 @@ impl TreeTraversal<'_> {
             | syntax::ExprData::Shlease(base_expr)
             | syntax::ExprData::Give(base_expr)
             | syntax::ExprData::Await(base_expr)
-            | syntax::ExprData::Loop(base_expr)
+            | syntax::ExprData::Loop(_, base_expr)
             | syntax::ExprData::Atomic(base_expr)
             | syntax::ExprData::Unary(_, base_expr)
+            | syntax::ExprData::Closure(_, base_expr)
             | syntax::ExprData::Parenthesized(base_expr) => {
                 self.find_in_children(expr, Some(base_expr))
             }
 
-            syntax::ExprData::Return(base_expr) => self.find_in_children(expr, base_expr),
+            syntax::ExprData::Return(base_expr)
+            | syntax::ExprData::Yield(base_expr)
+            | syntax::ExprData::Break(_, base_expr) => {
+                self.find_in_children(expr, base_expr)
+            }
 
             syntax::ExprData::Concatenate(child_exprs)
             | syntax::ExprData::Tuple(child_exprs)
-            | syntax::ExprData::Seq(child_exprs) => self.find_in_children(expr, child_exprs),
+            | syntax::ExprData::List(child_exprs) => self.find_in_children(expr, child_exprs),
+
+            syntax::ExprData::Seq(statements, tail) => {
+                self.find_in_children(expr, statements.iter().chain(tail))
+            }
 
             syntax::ExprData::Call(func_expr, arg_exprs) => self.find_in_children(
                 expr,
@@ -136,6 +148,13 @@ impl TreeTraversal<'_> {
                 ),
             ),
 
+            syntax::ExprData::ClassLiteral(_, field_exprs) => self.find_in_children(
+                expr,
+                field_exprs
+                    .iter()
+                    .map(|named_expr| &named_expr.data(self.tables).expr),
+            ),
+
             syntax::ExprData::If(condition_expr, if_true_expr, if_false_expr) => {
                 // Because `if` has alternate control flow, it's a bit different from `find_in_children`.
                 // If the cursor is on the `else` keyword, for example, where do we go? We settle on "start of the if"
@@ -156,11 +175,30 @@ impl TreeTraversal<'_> {
                 Some(expr)
             }
 
-            syntax::ExprData::While(condition_expr, body_expr) => {
+            syntax::ExprData::VarElse(_, initializer_expr, else_expr) => {
+                self.find_in_children(expr, [initializer_expr, else_expr])
+            }
+
+            syntax::ExprData::While(_, condition_expr, body_expr) => {
                 self.find_in_children(expr, [condition_expr, body_expr])
             }
 
+            syntax::ExprData::For(_, _, iterable_expr, body_expr) => {
+                self.find_in_children(expr, [iterable_expr, body_expr])
+            }
+
+            syntax::ExprData::Match(scrutinee_expr, arms) => self.find_in_children(
+                expr,
+                std::iter::once(scrutinee_expr)
+                    .chain(arms.iter().map(|arm| &arm.data(self.tables).body)),
+            ),
+
+            syntax::ExprData::Range(start_expr, end_expr, _) => {
+                self.find_in_children(expr, start_expr.iter().chain(end_expr))
+            }
+
             syntax::ExprData::Assign(lhs, rhs)
+            | syntax::ExprData::Index(lhs, rhs)
             | syntax::ExprData::Op(lhs, _, rhs)
             | syntax::ExprData::OpEq(lhs, _, rhs) => self.find_in_children(expr, [lhs, rhs]),
         }
@@ -165,6 +165,7 @@ impl Cursor {
             | validated::ExprData::UnsignedIntegerLiteral(_)
             | validated::ExprData::SignedIntegerLiteral(_)
             | validated::ExprData::FloatLiteral(_)
+            | validated::ExprData::CharLiteral(_)
             | validated::ExprData::StringLiteral(_)
             | validated::ExprData::Call(_, _)
             | validated::ExprData::Reserve(_)
@@ -173,6 +174,9 @@ impl Cursor {
             | validated::ExprData::Shlease(_)
             | validated::ExprData::Give(_)
             | validated::ExprData::Tuple(_)
+            | validated::ExprData::List(_)
+            | validated::ExprData::Range(..)
+            | validated::ExprData::Closure(..)
             | validated::ExprData::Concatenate(_)
             | validated::ExprData::Atomic(_) => {
                 let _ = self.brew_expr_to_temporary(brewery, expr);
@@ -195,6 +199,19 @@ impl Cursor {
         Some(brewery.place_from_target_place(temp_place))
     }
 
+    /// Like `brew_expr_to_temporary`, but for an expression that may be absent
+    /// (e.g., the bounds of a range). Returns `Some(None)` if there is no expression.
+    fn brew_optional_expr_to_temporary(
+        &mut self,
+        brewery: &mut Brewery<'_>,
+        expr: Option<validated::Expr>,
+    ) -> Option<Option<bir::Place>> {
+        match expr {
+            Some(expr) => self.brew_expr_to_temporary(brewery, expr).map(Some),
+            None => Some(None),
+        }
+    }
+
     /// Compiles an expression down to the value it produces.
     ///
     /// Returns `None` if this is an expression (like `break`) that
@@ -362,6 +379,12 @@ impl Cursor {
                 self.push_breakpoint_end(brewery, Some(target), origin);
             }
 
+            validated::ExprData::CharLiteral(value) => {
+                self.push_breakpoint_start(brewery, origin);
+                self.push_assignment(brewery, target, bir::ExprData::CharLiteral(*value), origin);
+                self.push_breakpoint_end(brewery, Some(target), origin);
+            }
+
             validated::ExprData::StringLiteral(value) => {
                 self.push_breakpoint_start(brewery, origin);
                 self.push_assignment(
@@ -401,13 +424,73 @@ impl Cursor {
                     if values.is_empty() {
                         self.push_assignment(brewery, target, bir::ExprData::Unit, origin);
                     } else {
-                        assert_ne!(values.len(), 1);
                         self.push_assignment(brewery, target, bir::ExprData::Tuple(values), origin);
                     }
                     self.push_breakpoint_end(brewery, Some(target), origin);
                 }
             }
 
+            validated::ExprData::List(exprs) => {
+                self.push_breakpoint_start(brewery, origin);
+                if let Some(values) = exprs
+                    .iter()
+                    .map(|expr| self.brew_expr_to_temporary(brewery, *expr))
+                    .collect::<Option<Vec<_>>>()
+                {
+                    assert_eq!(values.len(), exprs.len());
+                    self.push_assignment(brewery, target, bir::ExprData::List(values), origin);
+                    self.push_breakpoint_end(brewery, Some(target), origin);
+                }
+            }
+
+            validated::ExprData::Range(start, end, inclusive) => {
+                self.push_breakpoint_start(brewery, origin);
+                if let Some(start) = self.brew_optional_expr_to_temporary(brewery, *start) {
+                    if let Some(end) = self.brew_optional_expr_to_temporary(brewery, *end) {
+                        self.push_assignment(
+                            brewery,
+                            target,
+                            bir::ExprData::Range(start, end, *inclusive),
+                            origin,
+                        );
+                        self.push_breakpoint_end(brewery, Some(target), origin);
+                    }
+                }
+            }
+
+            validated::ExprData::Closure(parameters, captures, body) => {
+                self.push_breakpoint_start(brewery, origin);
+                let parameters = parameters.iter().map(|&p| brewery.variable(p)).collect();
+                let captures = captures
+                    .iter()
+                    .map(|&place| self.brew_place(brewery, place).0)
+                    .collect();
+
+                // The body is brewed into its own blocks, which begin executing
+                // in a fresh frame when the closure is called.
+                let start_basic_block = {
+                    let body_brewery = &mut brewery.subbrewery();
+                    let body_origin = body_brewery.origin(*body);
+                    let mut body_cursor = Cursor::new(body_brewery, body_origin);
+                    if let Some(place) = body_cursor.brew_expr_to_temporary(body_brewery, *body) {
+                        body_cursor.terminate_and_diverge(
+                            body_brewery,
+                            bir::TerminatorData::Return(place),
+                            body_origin,
+                        );
+                    }
+                    body_cursor.complete()
+                };
+
+                self.push_assignment(
+                    brewery,
+                    target,
+                    bir::ExprData::Closure(parameters, captures, start_basic_block),
+                    origin,
+                );
+                self.push_breakpoint_end(brewery, Some(target), origin);
+            }
+
             validated::ExprData::Op(lhs, op, rhs) => {
                 self.push_breakpoint_start(brewery, origin);
                 if let Some(lhs) = self.brew_expr_to_temporary(brewery, *lhs) {
@@ -552,6 +635,14 @@ impl Cursor {
                 origins.push(origin);
                 (place, origins)
             }
+            validated::PlaceData::Index(base, index) => {
+                let (base, mut origins) = self.brew_place(brewery, *base);
+                let (index, index_origins) = self.brew_place(brewery, *index);
+                let place = brewery.add(bir::PlaceData::Index(base, index), origin);
+                origins.extend(index_origins);
+                origins.push(origin);
+                (place, origins)
+            }
         }
     }
 
@@ -14,9 +14,20 @@ pub fn check_filename(db: &dyn crate::Db, filename: Filename) {
                 function.parameters(db);
                 function.syntax_tree(db);
                 function.validated_tree(db);
+                function.check_const_calls(db);
+                function.numeric_types(db);
+                function.folded_tree(db);
             }
             Item::Class(class) => {
                 class.fields(db);
+                for &method in class.methods(db) {
+                    method.parameters(db);
+                    method.syntax_tree(db);
+                    method.validated_tree(db);
+                    method.check_const_calls(db);
+                    method.numeric_types(db);
+                    method.folded_tree(db);
+                }
             }
         }
     }
@@ -0,0 +1,333 @@
+use std::fmt::Write;
+
+use dada_collections::Set;
+use dada_id::prelude::*;
+use dada_ir::code::validated::{self, op::Op};
+use dada_ir::filename::Filename;
+use dada_ir::function::Function;
+use dada_ir::item::Item;
+use dada_parse::prelude::*;
+use dada_validate::prelude::*;
+
+/// Lowers `function` to a WAT `func` that is exported under its own name.
+/// The parameters and the result are all `i64`.
+#[salsa::memoized(in crate::Jar ref)]
+#[tracing::instrument(level = "debug", skip(db))]
+pub fn emit_wat(db: &dyn crate::Db, function: Function) -> String {
+    let tree_data = function.validated_tree(db).data(db);
+    let mut emitter = Emitter {
+        db,
+        tables: &tree_data.tables,
+        local_names: local_names(db, &tree_data.tables),
+        loops: vec![],
+        output: String::new(),
+        indent: 0,
+    };
+
+    let name = function.name(db).as_str(db);
+    let mut header = format!("(func ${} (export \"{}\")", name, name);
+    for parameter in tree_data.parameters() {
+        write!(header, " (param {} i64)", emitter.local_name(parameter)).unwrap();
+    }
+    header.push_str(" (result i64)");
+    emitter.line(header);
+
+    emitter.indent += 1;
+    for local_variable in validated::LocalVariable::max_key(emitter.tables)
+        .iter()
+        .skip(tree_data.num_parameters)
+    {
+        let line = format!("(local {} i64)", emitter.local_name(local_variable));
+        emitter.line(line);
+    }
+    emitter.emit_value(tree_data.root_expr);
+    emitter.indent -= 1;
+
+    emitter.line(")");
+    emitter.output
+}
+
+/// Lowers all the functions in `filename` to a WAT module.
+pub fn emit_wat_module(db: &dyn crate::Db, filename: Filename) -> String {
+    let mut output = String::from("(module\n");
+    for item in filename.items(db) {
+        if let Item::Function(function) = item {
+            for line in emit_wat(db, *function).lines() {
+                writeln!(output, "  {}", line).unwrap();
+            }
+        }
+    }
+    output.push_str(")\n");
+    output
+}
+
+/// Picks a WAT identifier for each local variable. Variables are named after
+/// themselves unless the name was already taken (e.g., by a shadowed
+/// variable); temporaries have no name. Dada identifiers cannot contain a
+/// `.`, so the generated names cannot clash with the user's.
+fn local_names(db: &dyn crate::Db, tables: &validated::Tables) -> Vec<String> {
+    let mut seen = Set::default();
+    validated::LocalVariable::max_key(tables)
+        .iter()
+        .map(|local_variable| match tables[local_variable].name {
+            Some(name) if seen.insert(name) => format!("${}", name.as_str(db)),
+            Some(name) => format!("${}.{}", name.as_str(db), usize::from(local_variable)),
+            None => format!("$tmp.{}", usize::from(local_variable)),
+        })
+        .collect()
+}
+
+struct Emitter<'me> {
+    db: &'me dyn crate::Db,
+    tables: &'me validated::Tables,
+    local_names: Vec<String>,
+
+    /// The loops that enclose the current expression, innermost last.
+    /// The labels of a loop are numbered by its position in this stack.
+    loops: Vec<validated::Expr>,
+
+    output: String,
+    indent: usize,
+}
+
+impl Emitter<'_> {
+    fn line(&mut self, text: impl AsRef<str>) {
+        for _ in 0..self.indent {
+            self.output.push_str("  ");
+        }
+        self.output.push_str(text.as_ref());
+        self.output.push('\n');
+    }
+
+    /// Emits code that evaluates `expr` for its side effects, leaving nothing on the stack.
+    fn emit_effect(&mut self, expr: validated::Expr) {
+        match expr.data(self.tables) {
+            validated::ExprData::Seq(exprs) => {
+                for expr in exprs {
+                    self.emit_effect(*expr);
+                }
+            }
+
+            validated::ExprData::Declare(_, expr) => self.emit_effect(*expr),
+
+            validated::ExprData::AssignTemporary(local_variable, expr) => {
+                self.emit_value(*expr);
+                self.line(format!("local.set {}", self.local_name(*local_variable)));
+            }
+
+            validated::ExprData::AssignFromPlace(target_place, place) => {
+                match target_place.data(self.tables) {
+                    validated::TargetPlaceData::LocalVariable(local_variable) => {
+                        self.emit_place(*place);
+                        self.line(format!("local.set {}", self.local_name(*local_variable)));
+                    }
+                    validated::TargetPlaceData::Dot(..) => self.unsupported("fields"),
+                }
+            }
+
+            _ => {
+                self.emit_value(expr);
+                self.line("drop");
+            }
+        }
+    }
+
+    /// Emits code that evaluates `expr`, leaving its value on the stack.
+    fn emit_value(&mut self, expr: validated::Expr) {
+        match expr.data(self.tables) {
+            validated::ExprData::BooleanLiteral(value) => {
+                self.line(format!("i64.const {}", u8::from(*value)))
+            }
+            validated::ExprData::SignedIntegerLiteral(value) => {
+                self.line(format!("i64.const {}", value))
+            }
+            validated::ExprData::UnsignedIntegerLiteral(value)
+            | validated::ExprData::IntegerLiteral(value) => {
+                self.line(format!("i64.const {}", value))
+            }
+            validated::ExprData::FloatLiteral(_) => self.unsupported("floats"),
+            validated::ExprData::CharLiteral(_) => self.unsupported("characters"),
+            validated::ExprData::StringLiteral(_) | validated::ExprData::Concatenate(_) => {
+                self.unsupported("strings")
+            }
+
+            validated::ExprData::Reserve(place)
+            | validated::ExprData::Lease(place)
+            | validated::ExprData::Shlease(place)
+            | validated::ExprData::Give(place) => self.emit_place(*place),
+
+            validated::ExprData::Share(expr)
+            | validated::ExprData::Atomic(expr)
+            | validated::ExprData::Declare(_, expr) => self.emit_value(*expr),
+
+            validated::ExprData::Call(func_expr, named_exprs) => {
+                let validated::ExprData::Reserve(place) = self.tables[*func_expr] else {
+                    return self.unsupported("calls of computed values");
+                };
+                let validated::PlaceData::Function(function) = self.tables[place] else {
+                    return self.unsupported("calls of intrinsics, classes, and closures");
+                };
+
+                // FIXME: arguments are passed in the order they are written,
+                // so named arguments must be in order and defaults are not filled in.
+                for named_expr in named_exprs {
+                    self.emit_value(named_expr.data(self.tables).expr);
+                }
+                let name = function.name(self.db).as_str(self.db);
+                self.line(format!("call ${}", name));
+            }
+
+            validated::ExprData::Tuple(exprs) if exprs.is_empty() => self.line("i64.const 0"),
+            validated::ExprData::Tuple(_) => self.unsupported("tuples"),
+            validated::ExprData::List(_) => self.unsupported("lists"),
+            validated::ExprData::Range(..) => self.unsupported("ranges"),
+            validated::ExprData::Closure(..) => self.unsupported("closures"),
+            validated::ExprData::Await(_) => self.unsupported("await"),
+
+            validated::ExprData::If(condition_expr, then_expr, else_expr) => {
+                self.emit_value(*condition_expr);
+                self.line("i32.wrap_i64");
+                self.line("if (result i64)");
+                self.indent += 1;
+                self.emit_value(*then_expr);
+                self.indent -= 1;
+                self.line("else");
+                self.indent += 1;
+                self.emit_value(*else_expr);
+                self.indent -= 1;
+                self.line("end");
+            }
+
+            validated::ExprData::Loop(body_expr) => {
+                // The loop itself never falls through: it is exited by a
+                // `br` to the enclosing block, which carries the value of the `break`.
+                let depth = self.loops.len();
+                self.loops.push(expr);
+                self.line(format!("block $break{} (result i64)", depth));
+                self.indent += 1;
+                self.line(format!("loop $continue{}", depth));
+                self.indent += 1;
+                self.emit_effect(*body_expr);
+                self.line(format!("br $continue{}", depth));
+                self.indent -= 1;
+                self.line("end");
+                self.line("unreachable");
+                self.indent -= 1;
+                self.line("end");
+                self.loops.pop();
+            }
+
+            validated::ExprData::Break {
+                from_expr,
+                with_value,
+            } => {
+                let depth = self.loop_depth(*from_expr);
+                self.emit_value(*with_value);
+                self.line(format!("br $break{}", depth));
+            }
+
+            validated::ExprData::Continue(from_expr) => {
+                let depth = self.loop_depth(*from_expr);
+                self.line(format!("br $continue{}", depth));
+            }
+
+            validated::ExprData::Return(with_value) => {
+                self.emit_value(*with_value);
+                self.line("return");
+            }
+
+            validated::ExprData::Seq(exprs) => match exprs.split_last() {
+                Some((last_expr, exprs)) => {
+                    for expr in exprs {
+                        self.emit_effect(*expr);
+                    }
+                    self.emit_value(*last_expr);
+                }
+                None => self.line("i64.const 0"),
+            },
+
+            validated::ExprData::Op(lhs_expr, op, rhs_expr) => {
+                let Some((instruction, is_comparison)) = binary_instruction(*op) else {
+                    return self.unsupported("this operator");
+                };
+                self.emit_value(*lhs_expr);
+                self.emit_value(*rhs_expr);
+                self.line(instruction);
+                if is_comparison {
+                    self.line("i64.extend_i32_u");
+                }
+            }
+
+            validated::ExprData::Unary(Op::Minus, rhs_expr) => {
+                self.line("i64.const 0");
+                self.emit_value(*rhs_expr);
+                self.line("i64.sub");
+            }
+            validated::ExprData::Unary(Op::Not, rhs_expr) => {
+                self.emit_value(*rhs_expr);
+                self.line("i64.eqz");
+                self.line("i64.extend_i32_u");
+            }
+            validated::ExprData::Unary(..) => self.unsupported("this operator"),
+
+            validated::ExprData::AssignTemporary(..) | validated::ExprData::AssignFromPlace(..) => {
+                self.emit_effect(expr);
+                self.line("i64.const 0");
+            }
+
+            validated::ExprData::Error => self.line("unreachable"),
+        }
+    }
+
+    fn emit_place(&mut self, place: validated::Place) {
+        match place.data(self.tables) {
+            validated::PlaceData::LocalVariable(local_variable) => {
+                self.line(format!("local.get {}", self.local_name(*local_variable)))
+            }
+            validated::PlaceData::Function(_)
+            | validated::PlaceData::Intrinsic(_)
+            | validated::PlaceData::Class(_) => self.unsupported("first-class functions"),
+            validated::PlaceData::Dot(..) => self.unsupported("fields"),
+            validated::PlaceData::Index(..) => self.unsupported("indexing"),
+        }
+    }
+
+    fn local_name(&self, local_variable: validated::LocalVariable) -> &str {
+        &self.local_names[usize::from(local_variable)]
+    }
+
+    fn loop_depth(&self, loop_expr: validated::Expr) -> usize {
+        self.loops
+            .iter()
+            .position(|&e| e == loop_expr)
+            .expect("`break` or `continue` outside of its loop")
+    }
+
+    /// Emits a trap in place of an expression that cannot be lowered yet.
+    fn unsupported(&mut self, what: &str) {
+        self.line(format!("unreachable ;; unsupported: {}", what));
+    }
+}
+
+/// The instruction for a binary operator and whether it is a comparison
+/// (whose `i32` result must be extended to an `i64`).
+fn binary_instruction(op: Op) -> Option<(&'static str, bool)> {
+    Some(match op {
+        Op::Plus => ("i64.add", false),
+        Op::Minus => ("i64.sub", false),
+        Op::Times => ("i64.mul", false),
+        Op::DividedBy => ("i64.div_s", false),
+        Op::BitAnd => ("i64.and", false),
+        Op::BitOr => ("i64.or", false),
+        Op::BitXor => ("i64.xor", false),
+        Op::ShiftLeft => ("i64.shl", false),
+        Op::ShiftRight => ("i64.shr_s", false),
+        Op::EqualEqual => ("i64.eq", true),
+        Op::NotEqual => ("i64.ne", true),
+        Op::LessThan => ("i64.lt_s", true),
+        Op::GreaterThan => ("i64.gt_s", true),
+        Op::LessEqual => ("i64.le_s", true),
+        Op::GreaterEqual => ("i64.ge_s", true),
+        Op::Not => return None,
+    })
+}
@@ -0,0 +1,20 @@
+//! Lowers validated trees to the WebAssembly text format (WAT).
+//!
+//! Every value is represented as an `i64`: integers as themselves, booleans
+//! as 0 or 1, and `()` as 0. Anything else (floats, strings, classes, ...)
+//! is not yet supported and lowers to `unreachable`.
+
+#![feature(trait_upcasting)]
+#![feature(let_else)]
+#![allow(incomplete_features)]
+
+#[salsa::jar(Db)]
+pub struct Jar(emit::emit_wat);
+
+pub trait Db: salsa::DbWithJar<Jar> + dada_ir::Db + dada_parse::Db + dada_validate::Db {}
+
+impl<T> Db for T where T: salsa::DbWithJar<Jar> + dada_ir::Db + dada_parse::Db + dada_validate::Db {}
+
+mod emit;
+
+pub use emit::{emit_wat, emit_wat_module};
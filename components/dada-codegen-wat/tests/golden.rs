@@ -0,0 +1,54 @@
+//! Compares the WAT emitted for each `tests/golden/*.dada` file against the
+//! `.wat` file next to it, and checks that the output is valid WAT.
+//! Run with `BLESS=1` to update the `.wat` files.
+
+use std::path::Path;
+
+#[test]
+fn golden() {
+    let golden_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let bless = std::env::var_os("BLESS").is_some();
+
+    let mut sources: Vec<_> = std::fs::read_dir(&golden_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some("dada".as_ref()))
+        .collect();
+    sources.sort();
+    assert!(!sources.is_empty(), "no golden files in {}", golden_dir.display());
+
+    let mut failures = vec![];
+    for source_path in sources {
+        let mut db = dada_db::Db::default();
+        let filename = dada_ir::filename::Filename::from(&db, &source_path);
+        db.update_file(filename, std::fs::read_to_string(&source_path).unwrap());
+        let actual = db.wat_module(filename);
+
+        if let Err(error) = wat::parse_str(&actual) {
+            failures.push(format!(
+                "{}: invalid WAT: {}\n{}",
+                source_path.display(),
+                error,
+                actual
+            ));
+            continue;
+        }
+
+        let wat_path = source_path.with_extension("wat");
+        if bless {
+            std::fs::write(&wat_path, &actual).unwrap();
+            continue;
+        }
+        let expected = std::fs::read_to_string(&wat_path).unwrap_or_default();
+        if actual != expected {
+            failures.push(format!(
+                "{}: expected\n{}\nfound\n{}",
+                wat_path.display(),
+                expected,
+                actual
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
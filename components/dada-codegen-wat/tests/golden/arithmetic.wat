@@ -0,0 +1,18 @@
+(module
+  (func $add (export "add") (param $a i64) (param $b i64) (result i64)
+    local.get $a
+    local.get $b
+    i64.add
+  )
+  (func $negate_unless (export "negate_unless") (param $x i64) (param $flag i64) (result i64)
+    local.get $flag
+    i32.wrap_i64
+    if (result i64)
+      local.get $x
+    else
+      i64.const 0
+      local.get $x
+      i64.sub
+    end
+  )
+)
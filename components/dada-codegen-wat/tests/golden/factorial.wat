@@ -0,0 +1,19 @@
+(module
+  (func $factorial (export "factorial") (param $n i64) (result i64)
+    local.get $n
+    i64.const 0
+    i64.eq
+    i64.extend_i32_u
+    i32.wrap_i64
+    if (result i64)
+      i64.const 1
+    else
+      local.get $n
+      local.get $n
+      i64.const 1
+      i64.sub
+      call $factorial
+      i64.mul
+    end
+  )
+)
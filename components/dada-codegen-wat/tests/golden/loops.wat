@@ -0,0 +1,79 @@
+(module
+  (func $sum_to (export "sum_to") (param $n i64) (result i64)
+    (local $sum i64)
+    (local $tmp.2 i64)
+    (local $i i64)
+    (local $tmp.4 i64)
+    (local $tmp.5 i64)
+    (local $tmp.6 i64)
+    i64.const 0
+    local.set $tmp.2
+    local.get $tmp.2
+    local.set $sum
+    i64.const 1
+    local.set $tmp.4
+    local.get $tmp.4
+    local.set $i
+    block $break0 (result i64)
+      loop $continue0
+        local.get $sum
+        local.get $i
+        i64.add
+        local.set $tmp.5
+        local.get $tmp.5
+        local.set $sum
+        local.get $i
+        i64.const 1
+        i64.add
+        local.set $tmp.6
+        local.get $tmp.6
+        local.set $i
+        local.get $i
+        local.get $n
+        i64.le_s
+        i64.extend_i32_u
+        i32.wrap_i64
+        if (result i64)
+          i64.const 0
+        else
+          i64.const 0
+          br $break0
+        end
+        drop
+        br $continue0
+      end
+      unreachable
+    end
+    drop
+    local.get $sum
+  )
+  (func $count_down (export "count_down") (param $n i64) (result i64)
+    (local $tmp.1 i64)
+    block $break0 (result i64)
+      loop $continue0
+        local.get $n
+        i64.const 0
+        i64.eq
+        i64.extend_i32_u
+        i32.wrap_i64
+        if (result i64)
+          i64.const 0
+          br $break0
+        else
+          i64.const 0
+        end
+        drop
+        local.get $n
+        i64.const 1
+        i64.sub
+        local.set $tmp.1
+        local.get $tmp.1
+        local.set $n
+        br $continue0
+      end
+      unreachable
+    end
+    drop
+    local.get $n
+  )
+)
@@ -0,0 +1,96 @@
+use std::cell::RefCell;
+
+use salsa::DebugWithDb;
+
+use crate::Db;
+
+thread_local! {
+    /// The queries that salsa executed on this thread while an
+    /// [`InstrumentedDb`] was live, or `None` if there is none.
+    static EXECUTIONS: RefCell<Option<Vec<salsa::DatabaseKeyIndex>>> = RefCell::new(None);
+}
+
+/// Records the query that `event` is about to execute, if recording is on.
+/// Called by [`Db`] for every salsa event.
+pub(crate) fn record(event: &salsa::Event) {
+    if let salsa::EventKind::WillExecute { database_key } = event.kind {
+        EXECUTIONS.with(|executions| {
+            if let Some(executions) = &mut *executions.borrow_mut() {
+                executions.push(database_key);
+            }
+        })
+    }
+}
+
+/// A [`Db`] that records which queries execute, for tests that check how
+/// much work an edit invalidates. Executions are reported by salsa's
+/// `WillExecute` event, so every query is counted.
+///
+/// Recording is per thread, so only one `InstrumentedDb` should be live
+/// on a thread at a time.
+pub struct InstrumentedDb {
+    db: Db,
+    executions: Vec<salsa::DatabaseKeyIndex>,
+}
+
+impl Default for InstrumentedDb {
+    fn default() -> Self {
+        EXECUTIONS.with(|executions| *executions.borrow_mut() = Some(vec![]));
+        Self {
+            db: Db::default(),
+            executions: vec![],
+        }
+    }
+}
+
+impl InstrumentedDb {
+    /// Number of times `query` (e.g., `"validate_function"`) has executed for
+    /// `key` since the last call to [`Self::clear_executions`].
+    pub fn executions(&mut self, query: &str, key: impl salsa::AsId) -> usize {
+        self.take_executions();
+        let key = key.as_id();
+        self.executions
+            .iter()
+            .filter(|database_key| database_key.key_index() == key)
+            .filter(|database_key| {
+                // Salsa describes an execution as `query(key)`.
+                let description = format!("{:?}", database_key.debug(&self.db));
+                description.split('(').next() == Some(query)
+            })
+            .count()
+    }
+
+    /// Forgets all executions recorded so far.
+    pub fn clear_executions(&mut self) {
+        self.take_executions();
+        self.executions.clear();
+    }
+
+    fn take_executions(&mut self) {
+        EXECUTIONS.with(|executions| {
+            if let Some(executions) = &mut *executions.borrow_mut() {
+                self.executions.append(executions);
+            }
+        })
+    }
+}
+
+impl Drop for InstrumentedDb {
+    fn drop(&mut self) {
+        EXECUTIONS.with(|executions| *executions.borrow_mut() = None);
+    }
+}
+
+impl std::ops::Deref for InstrumentedDb {
+    type Target = Db;
+
+    fn deref(&self) -> &Db {
+        &self.db
+    }
+}
+
+impl std::ops::DerefMut for InstrumentedDb {
+    fn deref_mut(&mut self) -> &mut Db {
+        &mut self.db
+    }
+}
@@ -5,16 +5,19 @@ use dada_ir::{
     filename::Filename,
     item::Item,
     span::{FileSpan, LineColumn, Offset},
-    word::Word,
 };
 use dada_parse::prelude::*;
 use dada_validate::prelude::*;
 use salsa::DebugWithDb;
 
+mod instrumented;
+pub use instrumented::InstrumentedDb;
+
 #[salsa::db(
     dada_breakpoint::Jar,
     dada_brew::Jar,
     dada_check::Jar,
+    dada_codegen_wat::Jar,
     dada_error_format::Jar,
     dada_execute::Jar,
     dada_ir::Jar,
@@ -31,6 +34,10 @@ impl salsa::Database for Db {
     fn salsa_runtime(&self) -> &salsa::Runtime {
         self.storage.runtime()
     }
+
+    fn salsa_event(&self, event: salsa::Event) {
+        instrumented::record(&event);
+    }
 }
 
 impl salsa::ParallelDatabase for Db {
@@ -43,7 +50,37 @@ impl salsa::ParallelDatabase for Db {
 
 impl Db {
     pub fn update_file(&mut self, filename: Filename, source_text: String) {
-        dada_ir::manifest::source_text::set(self, filename, source_text)
+        dada_ir::manifest::source_text::set(self, filename, source_text);
+        dada_ir::manifest::has_source_text::set(self, filename, true);
+    }
+
+    /// Loads the files of the modules that `filename` imports, and those that
+    /// they import in turn, using `read` to get the text of the file with a
+    /// given name. Files that are already loaded are kept as they are. If
+    /// `read` returns `None`, the module is not loaded, and importing from it
+    /// is reported as an error.
+    pub fn load_imports(
+        &mut self,
+        filename: Filename,
+        mut read: impl FnMut(&str) -> Option<String>,
+    ) {
+        let mut stack = vec![filename];
+        while let Some(filename) = stack.pop() {
+            let modules: Vec<Filename> = filename
+                .imports(self)
+                .iter()
+                .map(|import| import.module_filename(self, filename))
+                .collect();
+            for module in modules {
+                if dada_ir::manifest::has_source_text(self, module) {
+                    continue;
+                }
+                if let Some(source_text) = read(module.as_str(self)) {
+                    self.update_file(module, source_text);
+                    stack.push(module);
+                }
+            }
+        }
     }
 
     pub fn file_source(&self, filename: Filename) -> &String {
@@ -55,6 +92,23 @@ impl Db {
         dada_breakpoint::locations::breakpoint_locations::set(self, filename, locations);
     }
 
+    /// Set whether checking `filename` reports functions that call themselves.
+    pub fn set_debug_recursion(&mut self, filename: Filename, enabled: bool) {
+        dada_validate::debug_recursion::set(self, filename, enabled);
+    }
+
+    /// Set whether checking `filename` warns about functions that are never
+    /// used from its entry point.
+    pub fn set_warn_dead_functions(&mut self, filename: Filename, enabled: bool) {
+        dada_validate::warn_dead_functions::set(self, filename, enabled);
+    }
+
+    /// Set whether checking `filename` warns about variables that shadow a
+    /// variable from an enclosing scope.
+    pub fn set_warn_shadowing(&mut self, filename: Filename, enabled: bool) {
+        dada_validate::warn_shadowing::set(self, filename, enabled);
+    }
+
     /// Checks `filename` for compilation errors and returns all relevant diagnostics.
     pub fn diagnostics(&self, filename: Filename) -> Vec<Diagnostic> {
         dada_check::check_filename::accumulated::<dada_ir::diagnostic::Diagnostics>(self, filename)
@@ -69,18 +123,9 @@ impl Db {
             return Some(main_fn.brew(self));
         }
 
-        // Otherwise, search for a function named `main`.
-        let name = Word::from(self, "main");
-        for item in filename.items(self) {
-            if let Item::Function(function) = item {
-                let function_name = function.name(self);
-                if name == function_name.word(self) {
-                    return Some(function.brew(self));
-                }
-            }
-        }
-
-        None
+        // Otherwise, use the function named `main` (if it is a valid entry point).
+        let main_fn = filename.main_function(self).as_ref().ok()?;
+        Some(main_fn.brew(self))
     }
 
     /// Parses `filename` and returns a list of the items within.
@@ -93,6 +138,31 @@ impl Db {
         Some(item.syntax_tree(self)?.into_debug(self))
     }
 
+    /// Like [`Self::debug_syntax_tree`], but includes the span of each expression.
+    pub fn debug_syntax_tree_with_spans(&self, item: Item) -> Option<impl std::fmt::Debug + '_> {
+        Some(item.syntax_tree(self)?.debug_with_spans(self))
+    }
+
+    /// Returns the source of `filename` with the body of each function replaced
+    /// by its formatted syntax tree. Everything else (including top-level
+    /// expressions) is copied as is.
+    pub fn formatted_source(&self, filename: Filename) -> String {
+        let source = self.file_source(filename);
+        let mut output = String::new();
+        let mut copied_up_to = 0;
+        for item in filename.items(self) {
+            if let Item::Function(function) = item {
+                if let Some((span, text)) = dada_parse::format::format_function(self, *function) {
+                    output.push_str(&source[copied_up_to..usize::from(span.start)]);
+                    output.push_str(&text);
+                    copied_up_to = usize::from(span.end);
+                }
+            }
+        }
+        output.push_str(&source[copied_up_to..]);
+        output
+    }
+
     /// Returns the validated tree for `item`.
     pub fn debug_validated_tree(&self, item: Item) -> Option<impl std::fmt::Debug + '_> {
         Some(item.validated_tree(self)?.into_debug(self))
@@ -103,6 +173,11 @@ impl Db {
         Some(item.maybe_brew(self)?.into_debug(self))
     }
 
+    /// Lowers the functions in `filename` to a WebAssembly text-format module.
+    pub fn wat_module(&self, filename: Filename) -> String {
+        dada_codegen_wat::emit_wat_module(self, filename)
+    }
+
     /// Converts a given offset in a given file into line/column information.
     pub fn line_column(&self, filename: Filename, offset: Offset) -> LineColumn {
         dada_ir::lines::line_column(self, filename, offset)
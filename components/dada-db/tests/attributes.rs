@@ -0,0 +1,59 @@
+//! Checks that attributes are parsed onto the function that follows them.
+
+mod common;
+
+const SOURCE: &str = r#"## Doubles `x`.
+@test
+@export(double, 2)
+fn double(x) -> { x * 2 }
+
+fn plain() {}
+"#;
+
+#[test]
+fn parsed_attributes() {
+    let (db, filename) = common::db_with_file("attributes.dada", SOURCE);
+    let double = common::function_named(&db, filename, "double");
+    let plain = common::function_named(&db, filename, "plain");
+
+    let attributes: Vec<(&str, Vec<&str>, &str)> = double
+        .attributes(&db)
+        .iter()
+        .map(|attribute| {
+            (
+                attribute.name.as_str(&db),
+                attribute.args.iter().map(|arg| arg.as_str(&db)).collect(),
+                attribute.span.snippet(&db),
+            )
+        })
+        .collect();
+    assert_eq!(
+        attributes,
+        [
+            ("test", vec![], "@test"),
+            ("export", vec!["double", "2"], "@export(double, 2)"),
+        ]
+    );
+    assert_eq!(double.doc_text(&db).as_deref(), Some("Doubles `x`."));
+
+    assert!(plain.attributes(&db).is_empty());
+}
+
+#[test]
+fn doc_comment_between_attributes() {
+    let source =
+        "@test\n## Checks the answer.\n@export(answer)\n## It is 42.\nfn answer() -> { 42 }\n";
+    let (db, filename) = common::db_with_file("attributes.dada", source);
+    let answer = common::function_named(&db, filename, "answer");
+
+    let names: Vec<&str> = answer
+        .attributes(&db)
+        .iter()
+        .map(|attribute| attribute.name.as_str(&db))
+        .collect();
+    assert_eq!(names, ["test", "export"]);
+    assert_eq!(
+        answer.doc_text(&db).as_deref(),
+        Some("Checks the answer.\nIt is 42.")
+    );
+}
@@ -0,0 +1,54 @@
+//! Checks which expression the parser takes as the tail (the value) of a block.
+
+use dada_ir::code::syntax::{self, ExprData};
+use dada_parse::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn statement_then_tail(foo, bar) -> {
+    foo(); bar
+}
+
+fn no_semicolon(foo) -> {
+    foo()
+}
+
+fn trailing_semicolon(foo) {
+    foo();
+}
+"#;
+
+/// Returns the text of the statements and the tail of the body of the function named `name`.
+fn block(name: &str) -> (Vec<String>, Option<String>) {
+    let (db, filename) = common::db_with_file("block_tail.dada", SOURCE);
+    let function = common::function_named(&db, filename, name);
+
+    let tree_data = function.syntax_tree(&db).data(&db);
+    let spans = function.spans(&db);
+    let snippet = |expr: syntax::Expr| spans[expr].in_file(filename).snippet(&db).to_string();
+    match &tree_data.tables[tree_data.root_expr] {
+        ExprData::Seq(statements, tail) => (
+            statements.iter().map(|&expr| snippet(expr)).collect(),
+            tail.map(snippet),
+        ),
+        data => panic!("expected a block, found {:?}", data.kind_str()),
+    }
+}
+
+#[test]
+fn expression_after_semicolon_is_tail() {
+    assert_eq!(
+        block("statement_then_tail"),
+        (vec!["foo()".to_string()], Some("bar".to_string()))
+    );
+}
+
+#[test]
+fn expression_without_semicolon_is_tail() {
+    assert_eq!(block("no_semicolon"), (vec![], Some("foo()".to_string())));
+}
+
+#[test]
+fn trailing_semicolon_leaves_no_tail() {
+    assert_eq!(block("trailing_semicolon"), (vec!["foo()".to_string()], None));
+}
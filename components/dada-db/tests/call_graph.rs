@@ -0,0 +1,48 @@
+//! Checks that the call graph is recomputed only for the functions that
+//! an edit touches.
+
+use dada_db::InstrumentedDb;
+use dada_ir::filename::Filename;
+use dada_validate::prelude::*;
+
+mod common;
+
+fn source(body_of_third: &str) -> String {
+    format!(
+        r#"fn first() -> {{
+    second()
+}}
+
+fn second() -> {{
+    1
+}}
+
+fn third() -> {{
+    {body_of_third}
+}}
+"#
+    )
+}
+
+#[test]
+fn editing_an_unrelated_function_does_not_recompute_callees() {
+    let mut db = InstrumentedDb::default();
+    let filename = Filename::from(&*db, "call_graph.dada");
+    db.update_file(filename, source("1"));
+    let first = common::function_named(&db, filename, "first");
+    let second = common::function_named(&db, filename, "second");
+    let third = common::function_named(&db, filename, "third");
+    assert_eq!(filename.call_graph(&*db)[&first], vec![second]);
+    assert_eq!(db.executions("callees", first), 1);
+    assert_eq!(db.executions("callees", third), 1);
+
+    db.clear_executions();
+    db.update_file(filename, source("second() + 2"));
+    let first = common::function_named(&db, filename, "first");
+    let second = common::function_named(&db, filename, "second");
+    let third = common::function_named(&db, filename, "third");
+    assert_eq!(filename.call_graph(&*db)[&first], vec![second]);
+    assert_eq!(filename.call_graph(&*db)[&third], vec![second]);
+    assert_eq!(db.executions("callees", first), 0);
+    assert_eq!(db.executions("callees", third), 1);
+}
@@ -0,0 +1,72 @@
+//! Checks the shape of the control-flow graphs built from validated trees.
+
+use dada_validate::cfg::{build_cfg, Cfg};
+
+mod common;
+
+const SOURCE: &str = r#"fn straight_line(a, b) -> {
+    c = a + b
+    c * 2
+}
+
+fn find(n) -> {
+    i = 0
+    loop {
+        if i == n {
+            return i
+        }
+        i += 1
+    }
+}
+
+fn sum(n) -> {
+    total = 0
+    while n > 0 {
+        total += n
+        n -= 1
+    }
+    total
+}
+
+fn dead_code() -> {
+    return 1
+    if true { 2 } else { 3 }
+}
+"#;
+
+fn cfg(name: &str) -> Cfg {
+    let (db, filename) = common::db_with_file("cfg.dada", SOURCE);
+    let function = common::function_named(&db, filename, name);
+    build_cfg(&db, function)
+}
+
+#[test]
+fn straight_line_code_is_one_block() {
+    let cfg = cfg("straight_line");
+    assert_eq!(cfg.num_basic_blocks(), 1);
+    assert_eq!(cfg.num_edges(), 0);
+}
+
+#[test]
+fn loop_with_early_return() {
+    // start -> loop head -> (return | rest of body -> loop head);
+    // the loop is never broken out of, so nothing follows it.
+    let cfg = cfg("find");
+    assert_eq!(cfg.num_basic_blocks(), 4);
+    assert_eq!(cfg.num_edges(), 4);
+}
+
+#[test]
+fn while_loop() {
+    // start -> loop head -> (continue | break -> exit), and continue -> loop head.
+    let cfg = cfg("sum");
+    assert_eq!(cfg.num_basic_blocks(), 5);
+    assert_eq!(cfg.num_edges(), 5);
+}
+
+#[test]
+fn code_after_return_has_no_blocks() {
+    let cfg = cfg("dead_code");
+    assert_eq!(cfg.num_basic_blocks(), 1);
+    assert_eq!(cfg.successors(cfg.start_block), vec![]);
+}
@@ -0,0 +1,56 @@
+//! Checks that methods declared in a class body are parsed as functions
+//! of the class, with an implicit `self` parameter.
+
+use dada_ir::item::Item;
+use dada_parse::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"class Counter(value) {
+    fn get() -> {
+        self.value
+    }
+
+    fn set(value) {
+        self.value := value
+    }
+}
+"#;
+
+#[test]
+fn methods_have_implicit_self() {
+    let (db, filename) = common::db_with_file("class_methods.dada", SOURCE);
+    let class = db
+        .items(filename)
+        .iter()
+        .find_map(|item| match item {
+            Item::Class(class) => Some(*class),
+            Item::Function(_) => None,
+        })
+        .unwrap();
+
+    let methods: Vec<(&str, Vec<&str>)> = class
+        .methods(&db)
+        .iter()
+        .map(|method| {
+            assert_eq!(method.class(&db), Some(class));
+            let parameters = method
+                .parameters(&db)
+                .iter()
+                .map(|parameter| parameter.name(&db).as_str(&db))
+                .collect();
+            (method.name(&db).as_str(&db), parameters)
+        })
+        .collect();
+    assert_eq!(
+        methods,
+        [("get", vec!["self"]), ("set", vec!["self", "value"])]
+    );
+
+    let messages: Vec<String> = db
+        .diagnostics(filename)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect();
+    assert!(messages.is_empty(), "unexpected diagnostics: {:?}", messages);
+}
@@ -0,0 +1,47 @@
+//! Checks that syntax tables and spans can be cleared and reused.
+
+use dada_id::prelude::*;
+use dada_ir::code::syntax::{Expr, ExprData, LocalVariableDecl, Spans, Tables};
+use dada_ir::span::Span;
+use dada_parse::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn add(a, b) -> {
+    c = a + b
+    c
+}
+"#;
+
+/// The syntax tables and spans of the function in `SOURCE`.
+fn parsed_tables() -> (Tables, Spans) {
+    let (db, filename) = common::db_with_file("clear_tables.dada", SOURCE);
+    let function = common::function_named(&db, filename, "add");
+    let tables = function.syntax_tree(&db).data(&db).tables.clone();
+    let spans = function.spans(&db).clone();
+    (tables, spans)
+}
+
+#[test]
+fn cleared_tables_start_over() {
+    let (mut tables, mut spans) = parsed_tables();
+    assert!(usize::from(Expr::max_key(&tables)) > 0);
+    assert!(usize::from(LocalVariableDecl::max_key(&tables)) > 0);
+
+    tables.clear();
+    spans.clear();
+    assert_eq!(Expr::max_key(&tables), Expr::from(0_u32));
+    assert_eq!(
+        LocalVariableDecl::max_key(&tables),
+        LocalVariableDecl::from(0_u32)
+    );
+    assert_eq!(spans, Spans::default());
+
+    // Ids are handed out from zero again, with nothing left over from before.
+    let expr = tables.add(ExprData::BooleanLiteral(true));
+    spans.push(expr, Span::from(0_u32, 4_u32));
+    assert_eq!(expr, Expr::from(0_u32));
+    assert_eq!(tables[expr], ExprData::BooleanLiteral(true));
+    assert_eq!(spans[expr], Span::from(0_u32, 4_u32));
+    assert_eq!(Expr::max_key(&tables), Expr::from(1_u32));
+}
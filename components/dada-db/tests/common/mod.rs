@@ -0,0 +1,67 @@
+//! Fixtures shared by the tests in this directory.
+
+// Each test file uses only some of these helpers.
+#![allow(dead_code)]
+
+use dada_ir::{
+    code::syntax::{self, ExprData},
+    filename::Filename,
+    function::Function,
+    item::Item,
+};
+use dada_parse::prelude::*;
+
+/// Returns a database in which the file `name` contains `source`.
+pub fn db_with_file(name: &str, source: &str) -> (dada_db::Db, Filename) {
+    let mut db = dada_db::Db::default();
+    let filename = Filename::from(&db, name);
+    db.update_file(filename, source.to_string());
+    (db, filename)
+}
+
+/// Returns the function or method named `name` in `filename`.
+pub fn function_named(db: &dada_db::Db, filename: Filename, name: &str) -> Function {
+    db.items(filename)
+        .iter()
+        .flat_map(|item| match item {
+            Item::Function(function) => vec![*function],
+            Item::Class(class) => class.methods(db).clone(),
+        })
+        .find(|function| function.name(db).as_str(db) == name)
+        .unwrap_or_else(|| panic!("no function named {:?}", name))
+}
+
+/// Returns the last expression in the body of the function `name` in
+/// `filename`, with every operator application wrapped in parentheses,
+/// so that `a + b * c` is `(a + (b * c))`.
+pub fn parenthesized_tail(db: &dada_db::Db, filename: Filename, name: &str) -> String {
+    let function = function_named(db, filename, name);
+    let tree_data = function.syntax_tree(db).data(db);
+    let mut expr = tree_data.root_expr;
+    while let ExprData::Seq(_, Some(tail_expr)) = &tree_data.tables[expr] {
+        expr = *tail_expr;
+    }
+    parenthesized(db, filename, function, &tree_data.tables, expr)
+}
+
+fn parenthesized(
+    db: &dada_db::Db,
+    filename: Filename,
+    function: Function,
+    tables: &syntax::Tables,
+    expr: syntax::Expr,
+) -> String {
+    let p = |expr| parenthesized(db, filename, function, tables, expr);
+    match &tables[expr] {
+        ExprData::Op(lhs, op, rhs) | ExprData::OpEq(lhs, op, rhs) => {
+            format!("({} {} {})", p(*lhs), op, p(*rhs))
+        }
+        ExprData::Assign(lhs, rhs) => format!("({} := {})", p(*lhs), p(*rhs)),
+        ExprData::Unary(op, operand) => format!("({}{})", op, p(*operand)),
+        ExprData::Parenthesized(inner) => p(*inner),
+        _ => function.spans(db)[expr]
+            .in_file(filename)
+            .snippet(db)
+            .to_string(),
+    }
+}
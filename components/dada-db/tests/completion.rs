@@ -0,0 +1,79 @@
+//! Checks which names are offered for completion at different positions.
+
+use dada_ir::span::Offset;
+use dada_validate::completion::completions;
+
+mod common;
+
+const SOURCE: &str = r#"class Point(x, y)
+
+fn helper() {}
+
+fn main(n) -> {
+    a = 1
+    if n > a {
+        b = 2
+        print(b).await
+    }
+    c = 3
+    while c > 0 {
+        for i in [1, 2] {
+            print(i).await
+        }
+        c := c - 1
+    }
+}
+"#;
+
+/// The local variables offered with the cursor at the start of `needle`.
+/// Root definitions are filtered out to keep the expectations short.
+fn locals_at(needle: &str) -> Vec<String> {
+    let (db, filename) = common::db_with_file("main.dada", SOURCE);
+    let offset = SOURCE
+        .find(needle)
+        .unwrap_or_else(|| panic!("no {:?} in the source", needle));
+    completions(&db, filename, Offset::from(offset))
+        .into_iter()
+        .map(|name| name.as_str(&db).to_string())
+        .filter(|name| !["Point", "helper", "len", "main", "print"].contains(&&name[..]))
+        .collect()
+}
+
+#[test]
+fn before_any_declaration() {
+    assert_eq!(locals_at("a = 1"), ["n"]);
+}
+
+#[test]
+fn inside_nested_block() {
+    assert_eq!(locals_at("print(b)"), ["a", "b", "n"]);
+}
+
+#[test]
+fn after_nested_block() {
+    // `b` went out of scope with the block of the `if`.
+    assert_eq!(locals_at("c = 3"), ["a", "n"]);
+    assert_eq!(locals_at("while"), ["a", "c", "n"]);
+}
+
+#[test]
+fn inside_loops() {
+    assert_eq!(locals_at("print(i)"), ["a", "c", "i", "n"]);
+    assert_eq!(locals_at("c := c - 1"), ["a", "c", "n"]);
+}
+
+#[test]
+fn root_definitions_are_offered() {
+    let (db, filename) = common::db_with_file("main.dada", SOURCE);
+
+    // Outside of any function, only the root definitions are in scope.
+    let names: Vec<&str> = completions(&db, filename, Offset::from(0usize))
+        .into_iter()
+        .map(|name| name.as_str(&db))
+        .collect();
+    assert!(names.contains(&"Point"));
+    assert!(names.contains(&"helper"));
+    assert!(names.contains(&"print"));
+    assert!(!names.contains(&"n"));
+    assert!(names.windows(2).all(|w| w[0] < w[1]));
+}
@@ -0,0 +1,71 @@
+//! Checks that "go to definition" finds the nearest declaration.
+
+use dada_ir::span::Offset;
+use dada_validate::definition::definition;
+
+mod common;
+
+const SOURCE: &str = r#"fn main(n) -> {
+    x = 1
+    if n > 0 {
+        x = 2
+        print(x).await
+    }
+    print(x).await
+    x = 3
+    print(x + n).await
+}
+"#;
+
+/// Offset of the `nth` occurrence of `needle` in the source.
+fn offset_of(needle: &str, nth: usize) -> usize {
+    SOURCE
+        .match_indices(needle)
+        .nth(nth)
+        .unwrap_or_else(|| panic!("no occurrence {} of {:?}", nth, needle))
+        .0
+}
+
+/// The offset of the declaration that the cursor at `offset` refers to.
+fn definition_of(offset: usize) -> Option<usize> {
+    let (db, filename) = common::db_with_file("main.dada", SOURCE);
+    definition(&db, filename, Offset::from(offset)).map(|span| usize::from(span.start))
+}
+
+#[test]
+fn shadowed_in_block() {
+    assert_eq!(
+        definition_of(offset_of("print(x)", 0) + 6),
+        Some(offset_of("x = 2", 0))
+    );
+}
+
+#[test]
+fn visible_again_after_block() {
+    assert_eq!(
+        definition_of(offset_of("print(x)", 1) + 6),
+        Some(offset_of("x = 1", 0))
+    );
+}
+
+#[test]
+fn shadowed_in_same_block() {
+    assert_eq!(
+        definition_of(offset_of("print(x + n)", 0) + 6),
+        Some(offset_of("x = 3", 0))
+    );
+}
+
+#[test]
+fn parameter() {
+    assert_eq!(
+        definition_of(offset_of("n > 0", 0)),
+        Some(offset_of("n)", 0))
+    );
+}
+
+#[test]
+fn not_a_local_variable() {
+    assert_eq!(definition_of(offset_of("print", 0)), None);
+    assert_eq!(definition_of(offset_of("0 {", 0)), None);
+}
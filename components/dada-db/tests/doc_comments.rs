@@ -0,0 +1,50 @@
+//! Checks that `##` doc comments are attached to the function below them.
+
+mod common;
+
+const SOURCE: &str = r#"## Adds one to `x`.
+##
+## Returns the result.
+fn documented(x) -> { x + 1 }
+
+# Just a comment, not documentation.
+fn commented() {}
+
+## Separated from the function by a blank line.
+
+fn undocumented() {}
+
+async fn with_effect() {} # belongs to `with_effect`, not `last`
+## Documents `last`.
+fn last() {}
+"#;
+
+fn doc_text(name: &str) -> Option<String> {
+    let (db, filename) = common::db_with_file("docs.dada", SOURCE);
+    let function = common::function_named(&db, filename, name);
+    function.doc_text(&db)
+}
+
+#[test]
+fn documented_function() {
+    assert_eq!(
+        doc_text("documented").as_deref(),
+        Some("Adds one to `x`.\n\nReturns the result.")
+    );
+}
+
+#[test]
+fn plain_comment_is_not_documentation() {
+    assert_eq!(doc_text("commented"), None);
+}
+
+#[test]
+fn blank_line_ends_documentation() {
+    assert_eq!(doc_text("undocumented"), None);
+}
+
+#[test]
+fn trailing_comment_of_previous_item() {
+    assert_eq!(doc_text("with_effect"), None);
+    assert_eq!(doc_text("last").as_deref(), Some("Documents `last`."));
+}
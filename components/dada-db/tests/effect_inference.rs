@@ -0,0 +1,32 @@
+//! Checks the effects inferred for functions that do not declare one.
+
+use dada_ir::effect::Effect;
+use dada_validate::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn pure() -> {
+    1 + 2
+}
+
+fn prints() {
+    print("Hello, world")
+}
+
+fn awaits() {
+    print("Hello, world").await
+}
+
+async fn declared() {
+}
+"#;
+
+#[test]
+fn inferred_effects() {
+    let (db, filename) = common::db_with_file("effect_inference.dada", SOURCE);
+    let effect = |name| common::function_named(&db, filename, name).inferred_effect(&db);
+    assert_eq!(effect("pure"), Effect::Const);
+    assert_eq!(effect("prints"), Effect::Default);
+    assert_eq!(effect("awaits"), Effect::Async);
+    assert_eq!(effect("declared"), Effect::Async);
+}
@@ -0,0 +1,50 @@
+//! Checks that `else if` is parsed as an `if` directly in the `else` branch.
+
+use dada_ir::code::syntax::{self, ExprData};
+use dada_parse::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn sign(x) -> {
+    if x < 0 {
+        -1
+    } else if x == 0 {
+        0
+    } else {
+        1
+    }
+}
+"#;
+
+#[test]
+fn else_if_chain() {
+    let (db, filename) = common::db_with_file("else_if.dada", SOURCE);
+    let function = common::function_named(&db, filename, "sign");
+
+    let tree_data = function.syntax_tree(&db).data(&db);
+    let tables = &tree_data.tables;
+    let spans = function.spans(&db);
+    let snippet = |expr: syntax::Expr| spans[expr].in_file(filename).snippet(&db).to_string();
+
+    let if_expr = match &tables[tree_data.root_expr] {
+        ExprData::Seq(_, Some(tail_expr)) => *tail_expr,
+        data => panic!("expected a block with a tail, found {:?}", data.kind_str()),
+    };
+    let (condition, else_expr) = match tables[if_expr] {
+        ExprData::If(condition, _, Some(else_expr)) => (condition, else_expr),
+        ref data => panic!("expected an `if` with an `else`, found {:?}", data.kind_str()),
+    };
+    assert_eq!(snippet(condition), "x < 0");
+
+    // The `else if` is not wrapped in a block.
+    let (else_if_condition, final_else_expr) = match tables[else_expr] {
+        ExprData::If(condition, _, Some(else_expr)) => (condition, else_expr),
+        ref data => panic!("expected an `if` with an `else`, found {:?}", data.kind_str()),
+    };
+    assert!(snippet(else_expr).starts_with("if x == 0 {"));
+    assert_eq!(snippet(else_if_condition), "x == 0");
+
+    assert!(matches!(tables[final_else_expr], ExprData::Seq(..)));
+    let final_else_text = snippet(final_else_expr);
+    assert_eq!(final_else_text.split_whitespace().collect::<Vec<_>>(), ["{", "1", "}"]);
+}
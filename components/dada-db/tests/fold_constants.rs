@@ -0,0 +1,125 @@
+//! Checks constant folding over validated trees.
+
+use dada_ir::code::validated::{self, ExprData};
+use dada_validate::fold::fold_constants_counted;
+use dada_validate::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn arithmetic() -> {
+    2 + 3 * 4
+}
+
+fn comparison() -> {
+    1 + 1 == 2
+}
+
+fn side_effect() -> {
+    (1 + 1) + count()
+}
+
+fn count() -> {
+    1
+}
+
+fn short_circuit(x) -> {
+    true && x
+}
+
+fn divide_by_zero() -> {
+    1 / 0
+}
+
+fn overflows_int32() -> Int32 {
+    2_000_000_000 + 2_000_000_000
+}
+
+fn fits_int32() -> Int32 {
+    1_000_000_000 + 1_000_000_000
+}
+"#;
+
+/// Folds the function named `name` and returns its result expression, along
+/// with the tables it lives in and the number of folded expressions.
+fn fold(name: &str) -> (validated::Tables, validated::Expr, usize) {
+    let (db, filename) = common::db_with_file("fold.dada", SOURCE);
+    let function = common::function_named(&db, filename, name);
+
+    let (tree, folded) = fold_constants_counted(&db, function.validated_tree(&db));
+    let data = tree.data(&db);
+    let expr = result_expr(&data.tables, data.root_expr);
+    (data.tables.clone(), expr, folded)
+}
+
+/// The expression that produces the value of `expr`, looking through
+/// declarations and sequences.
+fn result_expr(tables: &validated::Tables, expr: validated::Expr) -> validated::Expr {
+    match &tables[expr] {
+        ExprData::Declare(_, expr) => result_expr(tables, *expr),
+        ExprData::Seq(exprs) if !exprs.is_empty() => result_expr(tables, *exprs.last().unwrap()),
+        _ => expr,
+    }
+}
+
+#[test]
+fn folds_arithmetic() {
+    let (tables, expr, folded) = fold("arithmetic");
+    assert_eq!(tables[expr], ExprData::IntegerLiteral(14));
+    assert_eq!(folded, 2);
+}
+
+#[test]
+fn folds_comparison() {
+    let (tables, expr, folded) = fold("comparison");
+    assert_eq!(tables[expr], ExprData::BooleanLiteral(true));
+    assert_eq!(folded, 2);
+}
+
+#[test]
+fn preserves_side_effects() {
+    let (tables, expr, folded) = fold("side_effect");
+    assert_eq!(folded, 1);
+    match tables[expr] {
+        ExprData::Op(lhs, _, rhs) => {
+            assert_eq!(tables[lhs], ExprData::IntegerLiteral(2));
+            assert!(matches!(tables[rhs], ExprData::Call(..)));
+        }
+        ref data => panic!("expected an operator, found {:?}", data),
+    }
+}
+
+#[test]
+fn folds_short_circuit_with_constant_lhs() {
+    // `true && x` keeps only the test of `x`.
+    let (tables, expr, folded) = fold("short_circuit");
+    assert_eq!(folded, 1);
+    match tables[expr] {
+        ExprData::If(_, then_expr, else_expr) => {
+            assert_eq!(tables[then_expr], ExprData::BooleanLiteral(true));
+            assert_eq!(tables[else_expr], ExprData::BooleanLiteral(false));
+        }
+        ref data => panic!("expected an `if`, found {:?}", data),
+    }
+}
+
+#[test]
+fn leaves_runtime_errors() {
+    let (tables, expr, folded) = fold("divide_by_zero");
+    assert_eq!(folded, 0);
+    assert!(matches!(tables[expr], ExprData::Op(..)));
+}
+
+#[test]
+fn leaves_overflow_at_declared_width() {
+    // The warning for this is tested by `dada_tests/validate/constant-overflow.dada`.
+    let (tables, expr, folded) = fold("overflows_int32");
+    assert_eq!(folded, 0);
+    assert!(matches!(tables[expr], ExprData::Op(..)));
+}
+
+#[test]
+fn folds_when_declared_width_fits() {
+    let (tables, expr, folded) = fold("fits_int32");
+    assert_eq!(tables[expr], ExprData::IntegerLiteral(2_000_000_000));
+    assert_eq!(folded, 1);
+}
@@ -0,0 +1,80 @@
+//! Checks that a single function can be formatted without touching the
+//! rest of the file.
+
+use dada_parse::format::format_function;
+
+mod common;
+
+const SOURCE: &str = r#"async fn first(a) {
+    print((a)).await
+}
+
+async fn second(b) {
+    print( ( b ) ).await
+}
+
+class Point(x) {
+    async fn show() {
+        print("a point
+on a line").await
+    }
+
+    fn get() -> {
+          ( self.x )
+    }
+}
+"#;
+
+/// Formats the function `name` in `SOURCE`, and returns the edited source.
+fn format_only(name: &str) -> String {
+    let (db, filename) = common::db_with_file("format_function.dada", SOURCE);
+
+    let function = common::function_named(&db, filename, name);
+    let (span, text) = format_function(&db, function).unwrap();
+    let function_span = function.span(&db);
+    assert!(function_span.start <= span.start && span.end == function_span.end);
+
+    let start = usize::from(span.start);
+    let end = usize::from(span.end);
+    format!("{}{}{}", &SOURCE[..start], text, &SOURCE[end..])
+}
+
+#[test]
+fn only_the_function_body_is_replaced() {
+    let edited = format_only("first");
+    let second_start = SOURCE.find("async fn second").unwrap();
+    assert_eq!(
+        edited,
+        format!(
+            "async fn first(a) {{\n    print(a).await\n}}\n\n{}",
+            &SOURCE[second_start..]
+        )
+    );
+}
+
+#[test]
+fn edited_source_reparses() {
+    let edited = format_only("second");
+    let (db, filename) = common::db_with_file("format_function.dada", &edited);
+    assert!(db.diagnostics(filename).is_empty(), "{}", edited);
+
+    // Formatting again changes nothing.
+    let second = common::function_named(&db, filename, "second");
+    let (span, text) = format_function(&db, second).unwrap();
+    assert_eq!(span.snippet(&db), text);
+}
+
+#[test]
+fn methods_keep_their_indentation() {
+    let edited = format_only("get");
+    assert!(
+        edited.ends_with("class Point(x) {\n    fn get() -> {\n        self.x\n    }\n}\n"),
+        "{}",
+        edited
+    );
+}
+
+#[test]
+fn strings_in_methods_are_not_indented() {
+    assert_eq!(format_only("show"), SOURCE);
+}
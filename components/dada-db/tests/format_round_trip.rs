@@ -0,0 +1,200 @@
+//! Formats randomly generated function bodies and checks that parsing the
+//! result yields the same syntax tree (ignoring spans and parentheses), and
+//! that formatting the result again changes nothing.
+
+use dada_parse::prelude::*;
+
+mod common;
+
+/// A small xorshift generator, so that each case can be reproduced from its seed.
+struct Rng(u64);
+
+impl Rng {
+    /// A number in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, choices: &[&'a str]) -> &'a str {
+        choices[self.below(choices.len())]
+    }
+}
+
+const NAMES: &[&str] = &["a", "b", "c"];
+
+const BINARY_OPS: &[&str] = &[
+    "+", "-", "*", "/", "==", "<", ">", "<=", ">=", "&", "|", "^", "<<", ">>", "&&", "||",
+];
+
+/// Generates the source of an expression. Only atoms (and postfix
+/// expressions on them) are written without parentheses where they are
+/// used as operands; everything else is wrapped in (possibly redundant)
+/// parentheses, which the formatter must drop or keep as needed.
+struct Generator {
+    rng: Rng,
+}
+
+impl Generator {
+    fn expr(&mut self, depth: usize) -> String {
+        if depth == 0 {
+            return self.atom(depth);
+        }
+        match self.rng.below(7) {
+            0 | 1 => self.atom(depth),
+            2 => self.postfix(depth),
+            3 => format!(
+                "{} {} {}",
+                self.operand(depth - 1),
+                self.rng.pick(BINARY_OPS),
+                self.operand(depth - 1)
+            ),
+            4 => format!("{}{}", self.rng.pick(&["-", "!"]), self.operand(depth - 1)),
+            5 => format!(
+                "{}{}{}",
+                self.operand(depth - 1),
+                self.rng.pick(&["..", "..="]),
+                self.operand(depth - 1)
+            ),
+            _ => format!("|x| {}", self.expr(depth - 1)),
+        }
+    }
+
+    /// An expression used as the operand of an operator.
+    fn operand(&mut self, depth: usize) -> String {
+        let expr = self.expr(depth);
+        let is_atom = expr.chars().all(|c| c.is_alphanumeric());
+        if is_atom && self.rng.below(2) == 0 {
+            expr
+        } else {
+            format!("({})", expr)
+        }
+    }
+
+    fn atom(&mut self, depth: usize) -> String {
+        match self.rng.below(if depth == 0 { 4 } else { 7 }) {
+            0 | 1 => self.rng.pick(NAMES).to_string(),
+            2 => self.rng.pick(&["1", "22", "0x1F", "3u"]).to_string(),
+            3 => self
+                .rng
+                .pick(&["true", "false", "\"hi\"", "()"])
+                .to_string(),
+            4 => format!("({},)", self.expr(depth - 1)),
+            5 => format!("({}, {})", self.expr(depth - 1), self.expr(depth - 1)),
+            _ => format!("[{}, {}]", self.expr(depth - 1), self.expr(depth - 1)),
+        }
+    }
+
+    fn postfix(&mut self, depth: usize) -> String {
+        let base = match self.rng.below(2) {
+            0 => self.rng.pick(NAMES).to_string(),
+            _ => format!("({})", self.expr(depth - 1)),
+        };
+        match self.rng.below(5) {
+            0 => format!("{}.f", base),
+            1 => format!("{}[{}]", base, self.expr(depth - 1)),
+            2 => format!("{}({})", base, self.expr(depth - 1)),
+            3 => format!(
+                "{}(x: {}, y: {})",
+                base,
+                self.expr(depth - 1),
+                self.expr(depth - 1)
+            ),
+            _ => format!("{}.{}", base, self.rng.pick(&["share", "lease", "give"])),
+        }
+    }
+
+    fn block(&mut self, depth: usize, in_loop: bool) -> String {
+        let statements: Vec<String> = (0..self.rng.below(3) + 1)
+            .map(|_| self.statement(depth, in_loop))
+            .collect();
+        format!("{{\n{}\n}}", statements.join(";\n"))
+    }
+
+    /// The condition of an `if` or a loop. A closure is put in parentheses,
+    /// as its body would otherwise run into the block that follows.
+    fn condition(&mut self, depth: usize) -> String {
+        let expr = self.expr(depth);
+        if expr.starts_with('|') {
+            format!("({})", expr)
+        } else {
+            expr
+        }
+    }
+
+    fn statement(&mut self, depth: usize, in_loop: bool) -> String {
+        let choices = if depth == 0 { 4 } else { 8 };
+        match self.rng.below(choices) {
+            0 => self.expr(depth),
+            1 => format!(
+                "{}x = {}",
+                self.rng.pick(&["", "my ", "our ", "shared "]),
+                self.expr(depth)
+            ),
+            2 => format!(
+                "{} {} {}",
+                self.rng.pick(NAMES),
+                self.rng.pick(&[":=", "+=", "-=", "*="]),
+                self.expr(depth)
+            ),
+            3 if in_loop => self.rng.pick(&["break", "continue"]).to_string(),
+            3 => format!("return {}", self.expr(depth)),
+            4 => format!(
+                "if {} {} else {}",
+                self.condition(depth - 1),
+                self.block(depth - 1, in_loop),
+                self.block(depth - 1, in_loop)
+            ),
+            5 => format!(
+                "while {} {}",
+                self.condition(depth - 1),
+                self.block(depth - 1, true)
+            ),
+            6 => format!(
+                "for x in {} {}",
+                self.condition(depth - 1),
+                self.block(depth - 1, true)
+            ),
+            _ => format!("loop {}", self.block(depth - 1, true)),
+        }
+    }
+}
+
+#[test]
+fn formatting_generated_bodies_preserves_their_syntax_trees() {
+    for seed in 1..=200_u64 {
+        let mut generator = Generator {
+            rng: Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+        };
+        let source = format!("fn f(a, b, c) {}\n", generator.block(3, false));
+
+        let (mut db, filename) = common::db_with_file("original.dada", &source);
+        let formatted = db.formatted_source(filename);
+        assert!(
+            !formatted.contains("<error>"),
+            "seed {}: generated code does not parse:\n{}",
+            seed,
+            source
+        );
+
+        let formatted_filename = dada_ir::filename::Filename::from(&db, "formatted.dada");
+        db.update_file(formatted_filename, formatted.clone());
+        let original_tree = common::function_named(&db, filename, "f").syntax_tree(&db);
+        let formatted_tree = common::function_named(&db, formatted_filename, "f").syntax_tree(&db);
+        assert!(
+            original_tree.eq_modulo_formatting(formatted_tree, &db),
+            "seed {}: formatting changed the syntax tree of\n{}\ninto\n{}",
+            seed,
+            source,
+            formatted
+        );
+        assert_eq!(
+            db.formatted_source(formatted_filename),
+            formatted,
+            "seed {}: formatting is not idempotent",
+            seed
+        );
+    }
+}
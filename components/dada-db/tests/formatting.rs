@@ -0,0 +1,178 @@
+//! Checks that formatting drops the parentheses that the operator precedence
+//! makes redundant, and keeps the ones that change how the code parses, and
+//! that it follows the given [`FormatOptions`] and keeps comments in place.
+
+use dada_ir::code::syntax::to_source::FormatOptions;
+use dada_parse::prelude::*;
+
+mod common;
+
+/// Formats a function whose body is `body` and returns the formatted body
+/// (without the surrounding braces or indentation).
+fn format_body(body: &str) -> String {
+    let source = format!("fn f(a, b, c) {{\n{}\n}}\n", body);
+    let (db, filename) = common::db_with_file("formatting.dada", &source);
+    let formatted = db.formatted_source(filename);
+    let lines: Vec<&str> = formatted.lines().collect();
+    assert_eq!(
+        lines[0], "fn f(a, b, c) {",
+        "unexpected output: {}",
+        formatted
+    );
+    lines[1..lines.len() - 1]
+        .iter()
+        .map(|line| line.strip_prefix("    ").unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn redundant_parentheses_are_removed() {
+    for (body, expected) in [
+        ("((a))", "a"),
+        ("(a) + b", "a + b"),
+        ("a + (b * c)", "a + b * c"),
+        ("(a + b) - c", "a + b - c"),
+        ("(a | b) == c", "a | b == c"),
+        ("-(a)", "-a"),
+        ("(a.b).c", "a.b.c"),
+        ("print((a)).await", "print(a).await"),
+        ("x = (a + b)", "x = a + b"),
+        ("a := (b := c)", "a := b := c"),
+    ] {
+        assert_eq!(format_body(body), expected, "formatting `{}`", body);
+    }
+}
+
+#[test]
+fn required_parentheses_are_kept() {
+    for (body, expected) in [
+        ("(a + b) * c", "(a + b) * c"),
+        ("((a + b)) * c", "(a + b) * c"),
+        ("a - (b - c)", "a - (b - c)"),
+        ("a * -(b + c)", "a * -(b + c)"),
+        ("(-a).b", "(-a).b"),
+        ("(a..b).c", "(a..b).c"),
+        ("(a := b) + c", "(a := b) + c"),
+        ("(a && b) == c", "(a && b) == c"),
+    ] {
+        assert_eq!(format_body(body), expected, "formatting `{}`", body);
+    }
+}
+
+#[test]
+fn statements_that_would_continue_the_previous_one_are_separated() {
+    // On their own lines, these would parse as `a - b` and `a(b, c)`.
+    assert_eq!(format_body("a; (-b)"), "a;\n-b");
+    assert_eq!(format_body("a; (b, c)"), "a;\n(b, c)");
+    assert_eq!(format_body("a; (b)"), "a\nb");
+}
+
+#[test]
+fn class_literals_in_conditions_keep_parentheses() {
+    assert_eq!(
+        format_body("if (Point { x: a }).x { b }"),
+        "if (Point { x: a }).x {\n    b\n}"
+    );
+    assert_eq!(format_body("if ((a)) { b }"), "if a {\n    b\n}");
+}
+
+const CALL_SOURCE: &str = r#"fn f(first_argument, second_argument) {
+    print(first_argument, second_argument, label: "third").await
+}
+"#;
+
+/// Formats the body of the function in `CALL_SOURCE` using `options`.
+fn format_with(options: &FormatOptions) -> String {
+    let (db, filename) = common::db_with_file("format_options.dada", CALL_SOURCE);
+    let function = common::function_named(&db, filename, "f");
+    function.syntax_tree(&db).to_source_with(&db, options)
+}
+
+#[test]
+fn calls_that_fit_stay_on_one_line() {
+    let options = FormatOptions {
+        max_width: 120,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        format_with(&options),
+        "{\n    print(first_argument, second_argument, label: \"third\").await\n}"
+    );
+}
+
+#[test]
+fn long_calls_get_one_argument_per_line() {
+    let options = FormatOptions {
+        max_width: 40,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        format_with(&options),
+        "{\n    print(\n        first_argument,\n        second_argument,\n        label: \"third\",\n    ).await\n}"
+    );
+}
+
+#[test]
+fn indentation_can_use_tabs_or_other_widths() {
+    let tabs = FormatOptions {
+        use_tabs: true,
+        max_width: 40,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        format_with(&tabs),
+        "{\n\tprint(\n\t\tfirst_argument,\n\t\tsecond_argument,\n\t\tlabel: \"third\",\n\t).await\n}"
+    );
+
+    let two_spaces = FormatOptions {
+        indent_width: 2,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        format_with(&two_spaces),
+        "{\n  print(first_argument, second_argument, label: \"third\").await\n}"
+    );
+}
+
+const COMMENTED_SOURCE: &str = r#"fn f(a) {
+    # say hello first
+    print("hello").await   # greeting
+
+    # then the value,
+    # twice
+    b = (a)
+    if b { # nothing before this
+        print(b).await
+        # done
+    }
+    # all done
+}
+"#;
+
+#[test]
+fn comments_between_statements_are_kept() {
+    let (mut db, filename) = common::db_with_file("comments.dada", COMMENTED_SOURCE);
+    let formatted = db.formatted_source(filename);
+    assert_eq!(
+        formatted,
+        r#"fn f(a) {
+    # say hello first
+    print("hello").await # greeting
+    # then the value,
+    # twice
+    b = a
+    if b {
+        # nothing before this
+        print(b).await
+        # done
+    }
+    # all done
+}
+"#
+    );
+
+    // Formatting the result again changes nothing.
+    db.update_file(filename, formatted.clone());
+    assert_eq!(db.formatted_source(filename), formatted);
+}
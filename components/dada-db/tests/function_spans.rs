@@ -0,0 +1,35 @@
+//! Checks that the spans of a function are kept in a query of their own,
+//! and that reading them does not reparse the function.
+
+use dada_db::InstrumentedDb;
+use dada_ir::filename::Filename;
+use dada_parse::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn first() -> {
+    22 + 44
+}
+
+fn second() -> {
+    1
+}
+"#;
+
+#[test]
+fn requesting_spans_does_not_reparse() {
+    let mut db = InstrumentedDb::default();
+    let filename = Filename::from(&*db, "function_spans.dada");
+    db.update_file(filename, SOURCE.to_string());
+    let second = common::function_named(&db, filename, "second");
+    let second_text = second.text(&*db);
+
+    second.spans(&*db);
+    assert_eq!(db.executions("parse_function_body", second_text), 1);
+    assert_eq!(db.executions("function_spans", second), 1);
+
+    second.syntax_tree(&*db);
+    second.spans(&*db);
+    assert_eq!(db.executions("parse_function_body", second_text), 1);
+    assert_eq!(db.executions("function_spans", second), 1);
+}
@@ -0,0 +1,57 @@
+//! Checks the description of the expression under the cursor.
+
+use dada_ir::{filename::Filename, span::Offset};
+use dada_validate::hover::{hover, Binding};
+
+mod common;
+
+const SOURCE: &str = r#"fn compute(a) -> {
+    b = a + 1
+    print(b * (a - 2)).await
+}
+"#;
+
+/// Hovers over the `nth` occurrence of `needle` in the third line of the source.
+fn hover_at(db: &dada_db::Db, filename: Filename, needle: &str, nth: usize) -> String {
+    let line_start = SOURCE.find("print").unwrap();
+    let (offset, _) = SOURCE[line_start..]
+        .match_indices(needle)
+        .nth(nth)
+        .unwrap_or_else(|| panic!("no occurrence {} of {:?}", nth, needle));
+    let hover = hover(db, filename, Offset::from(line_start + offset))
+        .unwrap_or_else(|| panic!("nothing to hover over at {:?}", needle));
+    hover.describe(db)
+}
+
+#[test]
+fn nested_expressions() {
+    let (db, filename) = common::db_with_file("compute.dada", SOURCE);
+
+    assert_eq!(hover_at(&db, filename, "print", 0), "Id: intrinsic `print`");
+    assert_eq!(hover_at(&db, filename, "b", 0), "Id: local variable `b`");
+    assert_eq!(hover_at(&db, filename, "*", 0), "Op");
+    assert_eq!(hover_at(&db, filename, "(", 1), "Parenthesized");
+    assert_eq!(hover_at(&db, filename, "a", 0), "Id: parameter `a`");
+    assert_eq!(hover_at(&db, filename, "-", 0), "Op");
+    assert_eq!(hover_at(&db, filename, "2", 0), "IntegerLiteral");
+}
+
+#[test]
+fn binding_points_to_declaration() {
+    let (db, filename) = common::db_with_file("compute.dada", SOURCE);
+
+    let use_offset = SOURCE.find("b *").unwrap();
+    let hover = hover(&db, filename, Offset::from(use_offset)).unwrap();
+    let decl_span = match hover.binding {
+        Some(Binding::LocalVariable(_, decl_span)) => decl_span,
+        binding => panic!("expected a local variable, found {:?}", binding),
+    };
+    assert_eq!(usize::from(decl_span.start), SOURCE.find("b =").unwrap());
+}
+
+#[test]
+fn outside_of_any_function() {
+    let (db, filename) = common::db_with_file("compute.dada", SOURCE);
+
+    assert!(hover(&db, filename, Offset::from(SOURCE.len())).is_none());
+}
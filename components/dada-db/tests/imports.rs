@@ -0,0 +1,100 @@
+//! Checks that `use module.name` brings items from other files into scope.
+
+use dada_ir::{filename::Filename, item::Item};
+use dada_parse::prelude::*;
+use dada_validate::prelude::*;
+
+const MATH: &str = r#"pub fn double(x) -> {
+    x * 2
+}
+
+fn helper() {}
+"#;
+
+/// Checks `main.dada` (with source `main`) next to `math.dada`, returning
+/// the diagnostics for `main.dada` as `(message, snippet)` pairs.
+fn check(main: &str) -> (dada_db::Db, Filename, Vec<(String, String)>) {
+    let mut db = dada_db::Db::default();
+    let math = Filename::from(&db, "src/math.dada");
+    db.update_file(math, MATH.to_string());
+    let filename = Filename::from(&db, "src/main.dada");
+    db.update_file(filename, main.to_string());
+    let diagnostics = db
+        .diagnostics(filename)
+        .into_iter()
+        .map(|diagnostic| {
+            let snippet = diagnostic.span.snippet(&db).to_string();
+            (diagnostic.message, snippet)
+        })
+        .collect();
+    (db, filename, diagnostics)
+}
+
+#[test]
+fn call_imported_function() {
+    let (db, filename, diagnostics) = check(
+        r#"use math.double
+
+async fn main() {
+    print(double(21)).await
+}
+"#,
+    );
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+    let main = db
+        .items(filename)
+        .iter()
+        .find_map(|item| match item {
+            Item::Function(function) => Some(*function),
+            Item::Class(_) => None,
+        })
+        .unwrap();
+    let callees: Vec<(&str, &str)> = main
+        .callees(&db)
+        .iter()
+        .map(|callee| {
+            (
+                callee.name(&db).as_str(&db),
+                callee.filename(&db).as_str(&db),
+            )
+        })
+        .collect();
+    assert_eq!(callees, [("double", "src/math.dada")]);
+}
+
+#[test]
+fn missing_module() {
+    let (_, _, diagnostics) = check("use geometry.area\n");
+    assert_eq!(
+        diagnostics,
+        [(
+            "cannot find module `geometry`".to_string(),
+            "geometry".to_string()
+        )]
+    );
+}
+
+#[test]
+fn missing_item() {
+    let (_, _, diagnostics) = check("use math.triple\n");
+    assert_eq!(
+        diagnostics,
+        [(
+            "module `math` has no item named `triple`".to_string(),
+            "triple".to_string()
+        )]
+    );
+}
+
+#[test]
+fn private_item() {
+    let (_, _, diagnostics) = check("use math.helper\n");
+    assert_eq!(
+        diagnostics,
+        [(
+            "function `helper` is private to module `math`".to_string(),
+            "helper".to_string()
+        )]
+    );
+}
@@ -0,0 +1,87 @@
+//! Checks that editing one function does not re-execute queries for others.
+
+use dada_db::InstrumentedDb;
+use dada_ir::filename::Filename;
+use dada_parse::prelude::*;
+
+mod common;
+
+fn source(body_of_second: &str) -> String {
+    source_with_bodies("22 + 44", body_of_second)
+}
+
+fn source_with_bodies(body_of_first: &str, body_of_second: &str) -> String {
+    format!(
+        r#"fn first() -> {{
+    {body_of_first}
+}}
+
+fn second() -> {{
+    {body_of_second}
+}}
+"#
+    )
+}
+
+#[test]
+fn editing_a_function_does_not_revalidate_others() {
+    let mut db = InstrumentedDb::default();
+    let filename = Filename::from(&*db, "incremental.dada");
+    db.update_file(filename, source("1"));
+    db.diagnostics(filename);
+    let first = common::function_named(&db, filename, "first");
+    let second = common::function_named(&db, filename, "second");
+    assert_eq!(db.executions("validate_function", first), 1);
+    assert_eq!(db.executions("validate_function", second), 1);
+
+    db.clear_executions();
+    db.update_file(filename, source("1 + 2 + 3"));
+    db.diagnostics(filename);
+    let first = common::function_named(&db, filename, "first");
+    let second = common::function_named(&db, filename, "second");
+    let (first_text, second_text) = (first.text(&*db), second.text(&*db));
+    assert_eq!(db.executions("parse_function_body", first_text), 0);
+    assert_eq!(db.executions("validate_function", first), 0);
+    assert_eq!(db.executions("parse_function_body", second_text), 1);
+    assert_eq!(db.executions("validate_function", second), 1);
+}
+
+#[test]
+fn spans_of_other_functions_survive_an_edit() {
+    let mut db = InstrumentedDb::default();
+    let filename = Filename::from(&*db, "incremental.dada");
+    db.update_file(filename, source("1"));
+    let first = common::function_named(&db, filename, "first");
+    first.spans(&*db);
+
+    db.clear_executions();
+    db.update_file(filename, source("1 + 2 + 3"));
+    let first = common::function_named(&db, filename, "first");
+    first.spans(&*db);
+    let first_text = first.text(&*db);
+    assert_eq!(db.executions("parse_function_body", first_text), 0);
+}
+
+#[test]
+fn moving_a_function_does_not_reparse_it() {
+    let mut db = InstrumentedDb::default();
+    let filename = Filename::from(&*db, "incremental.dada");
+    db.update_file(filename, source_with_bodies("22 + 44", "1 +"));
+    let diagnostics = db.diagnostics(filename);
+    assert!(!diagnostics.is_empty());
+
+    db.clear_executions();
+    db.update_file(filename, source_with_bodies("22 + 44 + 66", "1 +"));
+    let moved_diagnostics = db.diagnostics(filename);
+    let first_text = common::function_named(&db, filename, "first").text(&*db);
+    let second_text = common::function_named(&db, filename, "second").text(&*db);
+    assert_eq!(db.executions("parse_function_body", first_text), 1);
+    assert_eq!(db.executions("parse_function_body", second_text), 0);
+
+    // The errors in `second` are still reported where it now is.
+    let shifted_diagnostics: Vec<_> = diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.shifted(" + 66".len() as u32))
+        .collect();
+    assert_eq!(moved_diagnostics, shifted_diagnostics);
+}
@@ -0,0 +1,124 @@
+//! Checks the numeric types inferred for literals and local variables.
+
+use dada_id::prelude::*;
+use dada_ir::code::validated;
+use dada_ir::ty::NumericTy;
+use dada_parse::prelude::*;
+use dada_validate::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn area(width, height) -> Int {
+    w = width * 2
+    h = height + 1
+    w * h
+}
+
+fn half(x) -> {
+    y = x / 2.0
+    y
+}
+
+fn unknown(f) -> {
+    z = f()
+    z
+}
+
+fn sum(a, b) -> {
+    c = a + b
+    c
+}
+"#;
+
+/// Returns the inferred type of each named local variable of the function
+/// `name`, along with the messages of all diagnostics in the file.
+fn local_variable_types(name: &str) -> (Vec<(String, Option<NumericTy>)>, Vec<String>) {
+    let (db, filename) = common::db_with_file("numeric_types.dada", SOURCE);
+    let function = common::function_named(&db, filename, name);
+
+    let tree_data = function.validated_tree(&db).data(&db);
+    let tables = &tree_data.tables;
+    let numeric_types = function.numeric_types(&db);
+    let types = tree_data
+        .max_local_variable()
+        .iter()
+        .filter_map(|local_variable| {
+            let name = tables[local_variable].name?;
+            Some((
+                name.as_str(&db).to_string(),
+                numeric_types.local_variable(local_variable),
+            ))
+        })
+        .collect();
+
+    let messages = db
+        .diagnostics(filename)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect();
+    (types, messages)
+}
+
+#[test]
+fn integer_program() {
+    let (types, messages) = local_variable_types("area");
+    assert!(
+        messages.is_empty(),
+        "unexpected diagnostics: {:?}",
+        messages
+    );
+    assert_eq!(
+        types,
+        [
+            ("width".to_string(), Some(NumericTy::Integer)),
+            ("height".to_string(), Some(NumericTy::Integer)),
+            ("w".to_string(), Some(NumericTy::Integer)),
+            ("h".to_string(), Some(NumericTy::Integer)),
+        ]
+    );
+}
+
+#[test]
+fn float_literal_makes_operands_floats() {
+    let (types, _) = local_variable_types("half");
+    assert_eq!(
+        types,
+        [
+            ("x".to_string(), Some(NumericTy::Float)),
+            ("y".to_string(), Some(NumericTy::Float)),
+        ]
+    );
+}
+
+#[test]
+fn unconstrained_variables_have_no_numeric_type() {
+    let (types, _) = local_variable_types("unknown");
+    assert_eq!(types, [("f".to_string(), None), ("z".to_string(), None)]);
+}
+
+#[test]
+fn unconstrained_numbers_default_to_integers() {
+    let (types, _) = local_variable_types("sum");
+    assert_eq!(
+        types,
+        [
+            ("a".to_string(), Some(NumericTy::Integer)),
+            ("b".to_string(), Some(NumericTy::Integer)),
+            ("c".to_string(), Some(NumericTy::Integer)),
+        ]
+    );
+}
+
+#[test]
+fn literals_have_numeric_types() {
+    let (db, filename) = common::db_with_file("literals.dada", "fn f() -> { 1 + 2 }");
+    let function = common::function_named(&db, filename, "f");
+
+    let tables = &function.validated_tree(&db).data(&db).tables;
+    let numeric_types = function.numeric_types(&db);
+    for expr in validated::Expr::max_key(tables).iter() {
+        if let validated::ExprData::IntegerLiteral(_) = tables[expr] {
+            assert_eq!(numeric_types.expr(expr), Some(NumericTy::Integer));
+        }
+    }
+}
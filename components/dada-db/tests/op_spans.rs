@@ -0,0 +1,62 @@
+//! Checks the spans that the parser records for operators.
+
+use dada_ir::code::syntax::{self, ExprData};
+use dada_parse::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn add(a, b) -> {
+    a + b
+}
+
+fn increment(a) {
+    a += 1
+}
+
+fn assign(a) {
+    a := 1
+}
+
+fn negate(a) -> {
+    -a
+}
+"#;
+
+/// Returns the text of the operator span of the last expression in the
+/// function named `name`, or `None` if it has no operator span.
+fn op_span_text(name: &str) -> Option<String> {
+    let (db, filename) = common::db_with_file("op_spans.dada", SOURCE);
+    let function = common::function_named(&db, filename, name);
+
+    let tree_data = function.syntax_tree(&db).data(&db);
+    let expr = last_expr(&tree_data.tables, tree_data.root_expr);
+    let span = function.spans(&db).op_span(expr)?;
+    Some(span.in_file(filename).snippet(&db).to_string())
+}
+
+fn last_expr(tables: &syntax::Tables, expr: syntax::Expr) -> syntax::Expr {
+    match &tables[expr] {
+        ExprData::Seq(_, Some(tail_expr)) => last_expr(tables, *tail_expr),
+        _ => expr,
+    }
+}
+
+#[test]
+fn binary_operator() {
+    assert_eq!(op_span_text("add").as_deref(), Some("+"));
+}
+
+#[test]
+fn compound_assignment_operator() {
+    assert_eq!(op_span_text("increment").as_deref(), Some("+="));
+}
+
+#[test]
+fn assignment_operator() {
+    assert_eq!(op_span_text("assign").as_deref(), Some(":="));
+}
+
+#[test]
+fn unary_operator_has_no_op_span() {
+    assert_eq!(op_span_text("negate"), None);
+}
@@ -0,0 +1,39 @@
+//! Checks the span of each function's parameter list.
+
+mod common;
+
+const SOURCE: &str = r#"fn add(x: Int, y: Int) -> Int {
+    x + y
+}
+
+fn none() {
+}
+
+class Point(x, y) {
+    fn scale( factor ) -> {
+        self.x * factor
+    }
+}
+"#;
+
+/// Returns the text of the parameter list of the function or method `name`.
+fn params_text(name: &str) -> String {
+    let (db, filename) = common::db_with_file("params_span.dada", SOURCE);
+    let function = common::function_named(&db, filename, name);
+    function.params_span(&db).snippet(&db).to_string()
+}
+
+#[test]
+fn covers_the_parameter_list() {
+    assert_eq!(params_text("add"), "(x: Int, y: Int)");
+}
+
+#[test]
+fn empty_parameter_list() {
+    assert_eq!(params_text("none"), "()");
+}
+
+#[test]
+fn method_parameter_list() {
+    assert_eq!(params_text("scale"), "( factor )");
+}
@@ -0,0 +1,86 @@
+//! Checks how the parser groups binary operators by precedence and
+//! associativity.
+
+mod common;
+
+/// Returns the body of `fn f(a, b, c, d) -> { <expr> }` with every
+/// operator application in parentheses.
+fn grouped(expr: &str) -> String {
+    let source = format!("fn f(a, b, c, d) -> {{\n    {}\n}}\n", expr);
+    let (db, filename) = common::db_with_file("precedence.dada", &source);
+    common::parenthesized_tail(&db, filename, "f")
+}
+
+#[test]
+fn multiplication_binds_tighter_than_addition() {
+    assert_eq!(grouped("a + b * c"), "(a + (b * c))");
+    assert_eq!(grouped("a * b + c * d"), "((a * b) + (c * d))");
+}
+
+#[test]
+fn arithmetic_is_left_associative() {
+    assert_eq!(grouped("a - b - c"), "((a - b) - c)");
+    assert_eq!(grouped("a / b / c"), "((a / b) / c)");
+}
+
+#[test]
+fn unary_operators_bind_tightest() {
+    assert_eq!(grouped("-a * b"), "((-a) * b)");
+}
+
+#[test]
+fn comparison_binds_looser_than_arithmetic() {
+    assert_eq!(grouped("a + b == c * d"), "((a + b) == (c * d))");
+}
+
+#[test]
+fn parentheses_override_precedence() {
+    assert_eq!(grouped("(a + b) * c"), "((a + b) * c)");
+}
+
+#[test]
+fn assignment_binds_loosest() {
+    assert_eq!(grouped("a := b + c"), "(a := (b + c))");
+    assert_eq!(grouped("a += b * c"), "(a += (b * c))");
+}
+
+#[test]
+fn assignment_is_right_associative() {
+    assert_eq!(grouped("a := b := c"), "(a := (b := c))");
+}
+
+#[test]
+fn and_binds_tighter_than_or() {
+    assert_eq!(grouped("a && b || c"), "((a && b) || c)");
+    assert_eq!(grouped("a || b && c"), "(a || (b && c))");
+}
+
+#[test]
+fn logical_operators_bind_looser_than_comparisons() {
+    assert_eq!(grouped("a == b && c < d"), "((a == b) && (c < d))");
+}
+
+#[test]
+fn logical_operators_are_left_associative() {
+    assert_eq!(grouped("a || b || c"), "((a || b) || c)");
+    assert_eq!(grouped("a && b && c"), "((a && b) && c)");
+}
+
+#[test]
+fn bitwise_operators_bind_and_then_xor_then_or() {
+    assert_eq!(grouped("a | b & c"), "(a | (b & c))");
+    assert_eq!(grouped("a ^ b & c"), "(a ^ (b & c))");
+    assert_eq!(grouped("a | b ^ c"), "(a | (b ^ c))");
+}
+
+#[test]
+fn shifts_bind_looser_than_addition() {
+    assert_eq!(grouped("a << b + c"), "(a << (b + c))");
+    assert_eq!(grouped("a & b << c"), "(a & (b << c))");
+}
+
+#[test]
+fn comparisons_take_bitwise_operands_on_both_sides() {
+    assert_eq!(grouped("a | b == c"), "((a | b) == c)");
+    assert_eq!(grouped("a == b | c"), "(a == (b | c))");
+}
@@ -0,0 +1,38 @@
+//! Checks which functions are found to call themselves.
+
+use dada_validate::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn factorial(n) -> {
+    if n == 0 { 1 } else { n * factorial(n - 1) }
+}
+
+fn is_even(n) -> {
+    if n == 0 { true } else { is_odd(n - 1) }
+}
+
+fn is_odd(n) -> {
+    if n == 0 { false } else { is_even(n - 1) }
+}
+
+fn square(n) -> {
+    n * n
+}
+
+fn square_twice(n) -> {
+    square(square(n))
+}
+"#;
+
+#[test]
+fn recursive_functions() {
+    let (db, filename) = common::db_with_file("recursion.dada", SOURCE);
+    let is_recursive = |name: &str| common::function_named(&db, filename, name).is_recursive(&db);
+
+    assert!(is_recursive("factorial"));
+    assert!(is_recursive("is_even"));
+    assert!(is_recursive("is_odd"));
+    assert!(!is_recursive("square"));
+    assert!(!is_recursive("square_twice"));
+}
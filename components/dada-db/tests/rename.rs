@@ -0,0 +1,62 @@
+//! Checks which spans are edited when a local variable is renamed.
+
+use dada_ir::span::Offset;
+use dada_validate::rename::rename_spans;
+
+mod common;
+
+const SOURCE: &str = r#"fn main() -> {
+    x = 1
+    if x > 0 {
+        x = 2
+        print(x).await
+    }
+    x := x + 1
+    print(x).await
+}
+"#;
+
+/// Offsets of every occurrence of `x` in the source, numbered from zero.
+fn occurrences() -> Vec<usize> {
+    SOURCE
+        .match_indices('x')
+        .map(|(offset, _)| offset)
+        .collect()
+}
+
+fn rename_at(offset: usize) -> Vec<usize> {
+    let (db, filename) = common::db_with_file("main.dada", SOURCE);
+    rename_spans(&db, filename, Offset::from(offset))
+        .into_iter()
+        .map(|span| {
+            assert_eq!(span.snippet(&db), "x");
+            usize::from(span.start)
+        })
+        .collect()
+}
+
+#[test]
+fn outer_variable_excludes_inner_shadow() {
+    let x = occurrences();
+    let outer = vec![x[0], x[1], x[4], x[5], x[6]];
+
+    // From the declaration, from a use, and from an assignment.
+    assert_eq!(rename_at(x[0]), outer);
+    assert_eq!(rename_at(x[1]), outer);
+    assert_eq!(rename_at(x[4]), outer);
+}
+
+#[test]
+fn inner_shadow_excludes_outer_variable() {
+    let x = occurrences();
+    assert_eq!(rename_at(x[2]), vec![x[2], x[3]]);
+    assert_eq!(rename_at(x[3]), vec![x[2], x[3]]);
+}
+
+#[test]
+fn not_a_variable() {
+    assert_eq!(
+        rename_at(SOURCE.find("print").unwrap()),
+        Vec::<usize>::new()
+    );
+}
@@ -0,0 +1,67 @@
+//! Checks the plain-text rendering of diagnostics with source snippets.
+
+use dada_error_format::render_diagnostic;
+use dada_ir::span::Span;
+
+mod common;
+
+const SOURCE: &str = "fn main() {\n\tx = 1 +\n        2\n    print(x).await\n}\n";
+
+fn render(start: usize, end: usize, label: Option<&str>) -> String {
+    let (db, filename) = common::db_with_file("render.dada", SOURCE);
+
+    let span = Span::from(start, end).in_file(filename);
+    let mut builder = dada_ir::error!(span, "something is wrong");
+    builder = match label {
+        Some(label) => builder.primary_label(label),
+        None => builder.skip_primary_label(),
+    };
+    render_diagnostic(&db, &builder.finish())
+}
+
+#[test]
+fn single_line_span() {
+    let start = SOURCE.find("print").unwrap();
+    assert_eq!(
+        render(start, start + "print(x)".len(), Some("this call")),
+        "\
+error: something is wrong
+ --> render.dada:4:5
+  |
+4 |     print(x).await
+  |     ^^^^^^^^ this call
+"
+    );
+}
+
+#[test]
+fn single_line_span_after_tab() {
+    let start = SOURCE.find("x =").unwrap();
+    assert_eq!(
+        render(start, start + 1, None),
+        "\
+error: something is wrong
+ --> render.dada:2:5
+  |
+2 |     x = 1 +
+  |     ^
+"
+    );
+}
+
+#[test]
+fn multi_line_span() {
+    let start = SOURCE.find("1 +").unwrap();
+    let end = SOURCE.find("2\n").unwrap() + 1;
+    assert_eq!(
+        render(start, end, Some("this sum")),
+        "\
+error: something is wrong
+ --> render.dada:2:9
+  |
+2 |     x = 1 +
+  |         ^^^ this sum
+  | ... (continues to line 3)
+"
+    );
+}
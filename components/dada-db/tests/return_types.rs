@@ -0,0 +1,83 @@
+//! Checks how return types are declared, and the type inferred for a
+//! function that has `->` without a type.
+
+use dada_ir::return_type::ReturnTypeKind;
+use dada_ir::ty::NumericTy;
+use dada_parse::prelude::*;
+use dada_validate::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn declared() -> Int {
+    1
+}
+
+fn tuple() -> (Int, Int) {
+    (1, 2)
+}
+
+fn explicit_unit() -> () {
+}
+
+fn inferred(flag) -> {
+    if flag {
+        return 1
+    }
+    2
+}
+
+fn unit() {
+}
+"#;
+
+#[test]
+fn return_type_kinds() {
+    let (db, filename) = common::db_with_file("return_types.dada", SOURCE);
+    let kinds: Vec<_> = ["declared", "tuple", "explicit_unit", "inferred", "unit"]
+        .into_iter()
+        .map(|name| {
+            let function = common::function_named(&db, filename, name);
+            function.return_type(&db).kind(&db)
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            ReturnTypeKind::Declared,
+            ReturnTypeKind::Declared,
+            ReturnTypeKind::ExplicitUnit,
+            ReturnTypeKind::Inferred,
+            ReturnTypeKind::Unit,
+        ]
+    );
+}
+
+#[test]
+fn omitted_return_type_is_inferred_from_body() {
+    let (db, filename) = common::db_with_file("return_types.dada", SOURCE);
+    let inferred = common::function_named(&db, filename, "inferred");
+    assert_eq!(
+        inferred.numeric_types(&db).return_ty(),
+        Some(NumericTy::Integer)
+    );
+
+    let messages: Vec<String> = db
+        .diagnostics(filename)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect();
+    assert!(
+        messages.is_empty(),
+        "unexpected diagnostics: {:?}",
+        messages
+    );
+}
+
+#[test]
+fn unit_functions_return_no_number() {
+    let (db, filename) = common::db_with_file("return_types.dada", SOURCE);
+    for name in ["explicit_unit", "unit"] {
+        let function = common::function_named(&db, filename, name);
+        assert_eq!(function.numeric_types(&db).return_ty(), None);
+    }
+}
@@ -0,0 +1,55 @@
+//! Checks that errors about two places in the code label both of them.
+
+mod common;
+
+/// Returns the text and message of each label of the one error whose
+/// message starts with `message`, the primary label first.
+fn labels(source: &str, message: &str) -> Vec<(String, String)> {
+    let (db, filename) = common::db_with_file("secondary_labels.dada", source);
+    let errors: Vec<_> = db
+        .diagnostics(filename)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.message.starts_with(message))
+        .collect();
+    assert_eq!(errors.len(), 1, "diagnostics: {:?}", errors);
+    errors[0]
+        .labels
+        .iter()
+        .map(|label| (label.span.snippet(&db).to_string(), label.message.clone()))
+        .collect()
+}
+
+#[test]
+fn assignment_to_shared_variable() {
+    let source = r#"fn assign() {
+    shared x = 1
+    x := 2
+}
+"#;
+    assert_eq!(
+        labels(source, "cannot assign to `x`"),
+        vec![
+            (":=".to_string(), "assignment is here".to_string()),
+            ("x".to_string(), "`x` is declared `shared` here".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn use_after_give() {
+    let source = r#"class Point(x, y)
+
+fn give_then_use() {
+    my p = Point(1, 2)
+    my _q = p.give
+    print(p)
+}
+"#;
+    assert_eq!(
+        labels(source, "`p` is used after it was given"),
+        vec![
+            ("p".to_string(), "`p` is used here".to_string()),
+            ("p.give".to_string(), "`p` was given here".to_string()),
+        ]
+    );
+}
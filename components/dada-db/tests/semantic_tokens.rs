@@ -0,0 +1,58 @@
+//! Checks the classification of tokens for semantic highlighting.
+
+use dada_parse::{prelude::*, semantic_tokens::SemanticTokenKind};
+
+mod common;
+
+const SOURCE: &str = r#"
+fn sample(my x) -> {
+    our y = 22
+    while x < y {
+        x := x + 1
+    }
+    atomic { loop { if true { break } } }
+    y.give
+}
+"#;
+
+#[test]
+fn representative_function() {
+    use SemanticTokenKind::*;
+
+    let (db, filename) = common::db_with_file("sample.dada", SOURCE);
+    let function = common::function_named(&db, filename, "sample");
+
+    let tokens = function.semantic_tokens(&db);
+    assert!(tokens.windows(2).all(|w| w[0].0.start <= w[1].0.start));
+
+    let actual: Vec<(&str, SemanticTokenKind)> = tokens
+        .iter()
+        .map(|&(span, kind)| (span.snippet(&db), kind))
+        .collect();
+    assert_eq!(
+        actual,
+        [
+            ("my", StorageMode),
+            ("x", Identifier),
+            ("our", StorageMode),
+            ("y", Identifier),
+            ("22", Literal),
+            ("while", Keyword),
+            ("x", Identifier),
+            ("<", Operator),
+            ("y", Identifier),
+            ("x", Identifier),
+            (":=", Operator),
+            ("x", Identifier),
+            ("+", Operator),
+            ("1", Literal),
+            ("atomic", Keyword),
+            ("loop", Keyword),
+            ("if", Keyword),
+            ("true", Literal),
+            ("break", Keyword),
+            ("y", Identifier),
+            ("give", StorageMode),
+        ]
+    );
+}
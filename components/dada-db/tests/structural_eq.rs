@@ -0,0 +1,67 @@
+//! Checks that syntax trees compare equal by structure, regardless of
+//! where their source text appears.
+
+use dada_ir::code::syntax::Tree;
+use dada_ir::filename::Filename;
+use dada_parse::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn first() {
+    my x = 22
+    print(x, label: "x").await
+}
+
+fn second() {
+    my x = 22
+    print(x, label: "x").await
+}
+
+fn third() {
+    my x = 23
+    print(x, label: "x").await
+}
+
+fn fourth() {
+    x = 22
+    print(x, label: "x").await
+}
+"#;
+
+fn syntax_tree(db: &dada_db::Db, filename: Filename, name: &str) -> Tree {
+    common::function_named(db, filename, name).syntax_tree(db)
+}
+
+#[test]
+fn identical_bodies_are_structurally_eq() {
+    let (db, filename) = common::db_with_file("structural_eq.dada", SOURCE);
+
+    let first = syntax_tree(&db, filename, "first");
+    let second = syntax_tree(&db, filename, "second");
+    assert!(first.structurally_eq(second, &db));
+    assert!(second.structurally_eq(first, &db));
+}
+
+#[test]
+fn identical_bodies_in_different_files_are_structurally_eq() {
+    let mut db = dada_db::Db::default();
+    let filename_a = Filename::from(&db, "a.dada");
+    let filename_b = Filename::from(&db, "b.dada");
+    db.update_file(filename_a, SOURCE.to_string());
+    db.update_file(filename_b, format!("\n\n{SOURCE}"));
+
+    let first_a = syntax_tree(&db, filename_a, "first");
+    let first_b = syntax_tree(&db, filename_b, "first");
+    assert!(first_a.structurally_eq(first_b, &db));
+}
+
+#[test]
+fn different_bodies_are_not_structurally_eq() {
+    let (db, filename) = common::db_with_file("structural_eq.dada", SOURCE);
+
+    let first = syntax_tree(&db, filename, "first");
+    let third = syntax_tree(&db, filename, "third");
+    let fourth = syntax_tree(&db, filename, "fourth");
+    assert!(!first.structurally_eq(third, &db));
+    assert!(!first.structurally_eq(fourth, &db));
+}
@@ -0,0 +1,67 @@
+//! Checks the shape of the JSON produced by `syntax::Tree::to_json`.
+
+use dada_parse::prelude::*;
+use serde_json::Value;
+
+mod common;
+
+const SOURCE: &str = r#"
+fn describe(x) -> {
+    if x > 0 { "big \"and\" positive" } else { "not positive" }
+}
+"#;
+
+fn describe_json() -> Value {
+    let (db, filename) = common::db_with_file("describe.dada", SOURCE);
+    let function = common::function_named(&db, filename, "describe");
+    let json = function.syntax_tree(&db).to_json(&db);
+    serde_json::from_str(&json).unwrap()
+}
+
+/// Collects the text of every `StringLiteral` within `value`.
+fn string_literals(value: &Value, output: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if map["kind"] == "StringLiteral" {
+                output.push(map["text"].as_str().unwrap().to_string());
+            }
+            map.values().for_each(|v| string_literals(v, output));
+        }
+        Value::Array(values) => values.iter().for_each(|v| string_literals(v, output)),
+        _ => {}
+    }
+}
+
+#[test]
+fn structure() {
+    let json = describe_json();
+
+    let parameters = json["parameters"].as_array().unwrap();
+    assert_eq!(parameters.len(), 1);
+    assert_eq!(parameters[0]["name"], "x");
+
+    assert_eq!(json["root"]["kind"], "Seq");
+    assert_eq!(json["root"]["statements"], Value::Array(vec![]));
+    let if_expr = &json["root"]["tail"];
+    assert_eq!(if_expr["kind"], "If");
+
+    let condition = &if_expr["condition"];
+    assert_eq!(condition["kind"], "Op");
+    assert_eq!(condition["op"], ">");
+    assert_eq!(condition["lhs"]["kind"], "Id");
+    assert_eq!(condition["lhs"]["name"], "x");
+    assert_eq!(condition["rhs"]["kind"], "IntegerLiteral");
+    assert_eq!(condition["rhs"]["digits"], "0");
+    assert_eq!(condition["rhs"]["suffix"], Value::Null);
+
+    assert_eq!(if_expr["then"]["kind"], "Seq");
+    assert_eq!(if_expr["else"]["kind"], "Seq");
+}
+
+#[test]
+fn string_literals_are_verbatim() {
+    let mut literals = vec![];
+    string_literals(&describe_json(), &mut literals);
+    literals.sort();
+    assert_eq!(literals, [r#"big \"and\" positive"#, "not positive"]);
+}
@@ -0,0 +1,30 @@
+//! Checks which functions are discovered as tests.
+
+use dada_validate::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"@test
+fn first() {}
+
+fn not_a_test() {}
+
+@test
+fn with_parameter(x) {}
+
+@export(second)
+@test
+fn second() {}
+"#;
+
+#[test]
+fn discovered_tests() {
+    let (db, filename) = common::db_with_file("tests.dada", SOURCE);
+
+    let names: Vec<&str> = filename
+        .test_functions(&db)
+        .iter()
+        .map(|function| function.name(&db).as_str(&db))
+        .collect();
+    assert_eq!(names, ["first", "second"]);
+}
@@ -0,0 +1,60 @@
+//! Checks the tokens (including whitespace and comments) returned by `tokenize`.
+
+use dada_parse::tokenize::{tokenize, TokenKind};
+
+mod common;
+
+/// Returns the kind and text of each token of `source`.
+fn tokens(source: &str) -> Vec<(TokenKind, String)> {
+    let (db, filename) = common::db_with_file("tokenize.dada", source);
+    tokenize(&db, filename)
+        .into_iter()
+        .map(|(kind, span)| (kind, span.snippet(&db, filename).to_string()))
+        .collect()
+}
+
+#[test]
+fn comments_strings_and_whitespace() {
+    use TokenKind::*;
+    let expected = [
+        (Identifier, "x"),
+        (Whitespace, " "),
+        (Op, "="),
+        (Whitespace, " "),
+        (StringLiteral, "\"hi\""),
+        (Whitespace, " "),
+        (Comment, "# greet"),
+        (Whitespace, "\n"),
+    ];
+    assert_eq!(
+        tokens("x = \"hi\" # greet\n"),
+        expected.map(|(kind, text)| (kind, text.to_string()))
+    );
+}
+
+#[test]
+fn delimited_tokens_are_flattened() {
+    use TokenKind::*;
+    let expected = [
+        (Keyword, "if"),
+        (Whitespace, " "),
+        (Identifier, "f"),
+        (Delimiter, "("),
+        (Number, "1"),
+        (Comma, ","),
+        (Whitespace, " "),
+        (CharLiteral, "'a'"),
+        (Delimiter, ")"),
+    ];
+    assert_eq!(
+        tokens("if f(1, 'a')"),
+        expected.map(|(kind, text)| (kind, text.to_string()))
+    );
+}
+
+#[test]
+fn spans_cover_the_source() {
+    let source = "fn main() {\n    print(\"hello\").await # say hi\n}\n";
+    let text: String = tokens(source).into_iter().map(|(_, text)| text).collect();
+    assert_eq!(text, source);
+}
@@ -0,0 +1,77 @@
+//! Checks which expressions `diff_trees` reports as changed between two
+//! versions of a function.
+
+use dada_ir::code::syntax::diff::diff_trees;
+use dada_ir::code::syntax::Tree;
+use dada_ir::{filename::Filename, item::Item};
+use dada_parse::prelude::*;
+
+const OLD: &str = r#"fn compute(a) -> {
+    b = a * 2
+    print(b + 1).await
+    b
+}
+"#;
+
+fn compute_tree(db: &dada_db::Db, filename: Filename) -> Tree {
+    db.items(filename)
+        .iter()
+        .find_map(|item| match item {
+            Item::Function(function) => Some(function.syntax_tree(db)),
+            _ => None,
+        })
+        .expect("no function")
+}
+
+/// Returns the source text of each change reported between `OLD` and `new`.
+fn changed_text(new: &str) -> Vec<String> {
+    let mut db = dada_db::Db::default();
+    let old_filename = Filename::from(&db, "old.dada");
+    let new_filename = Filename::from(&db, "new.dada");
+    db.update_file(old_filename, OLD.to_string());
+    db.update_file(new_filename, new.to_string());
+
+    let old_tree = compute_tree(&db, old_filename);
+    let new_tree = compute_tree(&db, new_filename);
+    diff_trees(&db, old_tree, new_tree)
+        .into_iter()
+        .map(|change| change.span.in_file(new_filename).snippet(&db).to_string())
+        .collect()
+}
+
+#[test]
+fn identical_trees_have_no_changes() {
+    assert!(changed_text(OLD).is_empty());
+}
+
+#[test]
+fn changed_literal_is_the_only_change() {
+    let new = OLD.replace("a * 2", "a * 3");
+    assert_eq!(changed_text(&new), vec!["3"]);
+}
+
+#[test]
+fn moved_code_has_no_changes() {
+    let new = format!("\n\n{}", OLD.replace("b + 1", "b+1"));
+    assert!(changed_text(&new).is_empty());
+}
+
+#[test]
+fn changed_operator_reports_the_operation() {
+    let new = OLD.replace("b + 1", "b - 1");
+    assert_eq!(changed_text(&new), vec!["b - 1"]);
+}
+
+#[test]
+fn changes_are_reported_in_source_order() {
+    let new = OLD
+        .replace("a * 2", "a * 3")
+        .replace("    b\n}", "    b.x\n}");
+    assert_eq!(changed_text(&new), vec!["3", "b.x"]);
+}
+
+#[test]
+fn changed_parameters_report_the_whole_body() {
+    let new = OLD.replace("compute(a)", "compute(a, c)");
+    assert_eq!(changed_text(&new).len(), 1);
+}
@@ -0,0 +1,32 @@
+//! Checks the spans that the parser records for `x = value else { ... }`.
+
+use dada_id::prelude::*;
+use dada_ir::code::syntax::{self, ExprData};
+use dada_parse::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"fn f() -> {
+    x = 22 else { return 0 }
+    x
+}
+"#;
+
+#[test]
+fn binding_and_else_spans() {
+    let (db, filename) = common::db_with_file("var_else.dada", SOURCE);
+    let function = common::function_named(&db, filename, "f");
+    let tables = &function.syntax_tree(&db).data(&db).tables;
+    let spans = function.spans(&db);
+
+    let (expr, decl) = syntax::Expr::max_key(tables)
+        .iter()
+        .find_map(|expr| match tables[expr] {
+            ExprData::VarElse(decl, ..) => Some((expr, decl)),
+            _ => None,
+        })
+        .unwrap();
+    let snippet = |span: dada_ir::span::Span| span.in_file(filename).snippet(&db).to_string();
+    assert_eq!(snippet(spans[decl].name_span), "x");
+    assert_eq!(snippet(spans.else_span(expr).unwrap()), "else");
+}
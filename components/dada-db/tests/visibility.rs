@@ -0,0 +1,38 @@
+//! Checks that `pub` is parsed into the visibility of items.
+
+use dada_ir::visibility::Visibility;
+use dada_parse::prelude::*;
+
+mod common;
+
+const SOURCE: &str = r#"pub fn exported() {}
+
+fn private() {}
+
+pub async fn exported_async() {}
+
+pub class Point(x, y)
+
+class Pair(a, b)
+"#;
+
+#[test]
+fn item_visibility() {
+    let (db, filename) = common::db_with_file("visibility.dada", SOURCE);
+
+    let visibilities: Vec<(&str, Visibility)> = db
+        .items(filename)
+        .iter()
+        .map(|item| (item.name(&db).as_str(&db), item.visibility(&db)))
+        .collect();
+    assert_eq!(
+        visibilities,
+        [
+            ("exported", Visibility::Pub),
+            ("private", Visibility::Private),
+            ("exported_async", Visibility::Pub),
+            ("Point", Visibility::Pub),
+            ("Pair", Visibility::Private),
+        ]
+    );
+}
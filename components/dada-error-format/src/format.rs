@@ -1,4 +1,4 @@
-use std::io::Cursor;
+use std::io::{Cursor, Write};
 
 use ariadne::{Config, Label, Report, ReportKind, Source};
 use dada_ir::filename::Filename;
@@ -23,7 +23,8 @@ pub fn print_diagnostic(
     db: &dyn crate::Db,
     diagnostic: &dada_ir::diagnostic::Diagnostic,
 ) -> eyre::Result<()> {
-    Ok(ariadne_diagnostic(db, diagnostic, DEFAULT_FORMATTING)?.print(SourceCache::new(db))?)
+    ariadne_diagnostic(db, diagnostic, DEFAULT_FORMATTING)?.print(SourceCache::new(db))?;
+    write_suggestions(db, diagnostic, &mut std::io::stderr())
 }
 
 pub fn format_diagnostics(
@@ -44,10 +45,34 @@ pub fn format_diagnostics_with_options(
     for diagnostic in diagnostics {
         let ariadne = ariadne_diagnostic(db, diagnostic, options)?;
         ariadne.write(&mut cache, &mut cursor)?;
+        write_suggestions(db, diagnostic, &mut cursor)?;
     }
     Ok(String::from_utf8(output)?)
 }
 
+/// Ariadne has no notion of suggested fixes, so we write them out
+/// after the report, one per line.
+fn write_suggestions(
+    db: &dyn crate::Db,
+    diagnostic: &dada_ir::diagnostic::Diagnostic,
+    out: &mut impl Write,
+) -> eyre::Result<()> {
+    for suggestion in &diagnostic.suggestions {
+        let span = suggestion.span();
+        let start = dada_ir::lines::line_column(db, span.filename, span.start);
+        writeln!(
+            out,
+            "Suggestion ({}:{}:{}): replace `{}` with `{}`",
+            span.filename.as_str(db),
+            start.line1(),
+            start.column1(),
+            span.snippet(db),
+            suggestion.replacement(),
+        )?;
+    }
+    Ok(())
+}
+
 fn ariadne_diagnostic(
     _db: &dyn crate::Db,
     diagnostic: &dada_ir::diagnostic::Diagnostic,
@@ -0,0 +1,94 @@
+//! Renders a diagnostic as plain text with the line of source it points at,
+//! in the style of rustc:
+//!
+//! ```text
+//! error: unknown attribute `@tset`
+//!  --> example.dada:1:1
+//!   |
+//! 1 | @tset
+//!   | ^^^^^ the known attributes are `@export`, `@test`
+//! ```
+//!
+//! Unlike [`crate::format_diagnostics`], only the diagnostic's own span is
+//! shown (along with its label, if it has one). A span that covers several
+//! lines underlines the rest of its first line and notes where it ends.
+
+use dada_ir::diagnostic::{Diagnostic, Severity};
+use dada_ir::lines::line_column_with_tab_width;
+
+/// Tabs are expanded to this many columns so that the carets line up.
+const TAB_WIDTH: u32 = 4;
+
+pub fn render_diagnostic(db: &dyn crate::Db, diagnostic: &Diagnostic) -> String {
+    let span = diagnostic.span;
+    let start = line_column_with_tab_width(db, span.filename, span.start, TAB_WIDTH);
+    let end = line_column_with_tab_width(db, span.filename, span.end, TAB_WIDTH);
+
+    let source_text = dada_ir::manifest::source_text(db, span.filename);
+    let line_text = expand_tabs(source_text.lines().nth(start.line0_usize()).unwrap_or(""));
+
+    // The text of the line number, and the same width of blanks for the other lines.
+    let line_number = start.line1().to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    let underline_end = if end.line0() == start.line0() {
+        end.column0()
+    } else {
+        line_text.chars().count() as u32
+    };
+    let underline_len = underline_end.saturating_sub(start.column0()).max(1);
+    let mut underline = format!(
+        "{}{}",
+        " ".repeat(start.column0() as usize),
+        "^".repeat(underline_len as usize),
+    );
+    if let Some(label) = diagnostic.labels.iter().find(|label| label.span == span) {
+        underline.push(' ');
+        underline.push_str(&label.message);
+    }
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "{}: {}\n",
+        severity_str(diagnostic.severity),
+        diagnostic.message
+    ));
+    output.push_str(&format!(
+        "{gutter}--> {}:{}:{}\n",
+        span.filename.as_str(db),
+        start.line1(),
+        start.column1(),
+    ));
+    output.push_str(&format!("{gutter} |\n"));
+    output.push_str(&format!("{line_number} | {line_text}\n"));
+    output.push_str(&format!("{gutter} | {underline}\n"));
+    if end.line0() > start.line0() {
+        output.push_str(&format!(
+            "{gutter} | ... (continues to line {})\n",
+            end.line1()
+        ));
+    }
+    output
+}
+
+fn severity_str(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Help => "help",
+        Severity::Note => "note",
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+fn expand_tabs(line: &str) -> String {
+    let mut expanded = String::new();
+    for c in line.chars() {
+        if c == '\t' {
+            let width = TAB_WIDTH as usize - expanded.chars().count() % TAB_WIDTH as usize;
+            expanded.push_str(&" ".repeat(width));
+        } else {
+            expanded.push(c);
+        }
+    }
+    expanded
+}
@@ -112,6 +112,9 @@ impl<'me> HeapGraphCapture<'me> {
                 &thunk.arguments,
             )),
             ObjectData::Tuple(_tuple) => self.data_target(db, object, &"<tuple>"), // FIXME
+            ObjectData::List(_list) => self.data_target(db, object, &"<list>"),    // FIXME
+            ObjectData::Range(_range) => self.data_target(db, object, &"<range>"), // FIXME
+            ObjectData::Closure(_closure) => self.data_target(db, object, &"<closure>"), // FIXME
             ObjectData::Reservation(reservation) => {
                 ValueEdgeTarget::Object(self.reservation_node(object, *reservation))
             }
@@ -123,6 +126,7 @@ impl<'me> HeapGraphCapture<'me> {
             | ObjectData::Int(_)
             | ObjectData::SignedInt(_)
             | ObjectData::Float(_)
+            | ObjectData::Char(_)
             | ObjectData::String(_)
             | ObjectData::Unit(_) => {
                 let string = DefaultStringify::stringify_object(self.machine, self.db, "", object);
@@ -200,6 +200,15 @@ pub enum ObjectData {
     /// A tuple of objects like `(a, b, c)`.
     Tuple(Tuple),
 
+    /// A list of objects like `[a, b, c]`.
+    List(List),
+
+    /// A range like `a..b` or `a..=b`.
+    Range(Range),
+
+    /// A closure like `|x| x + 1`, along with the values it captured.
+    Closure(Closure),
+
     /// Boolean.
     Bool(bool),
 
@@ -215,6 +224,9 @@ pub enum ObjectData {
     /// Floating point.
     Float(f64),
 
+    /// Character.
+    Char(char),
+
     /// String.
     String(String),
 
@@ -235,11 +247,15 @@ impl ObjectData {
             }
             ObjectData::ThunkRust(_) => "a thunk".to_string(),
             ObjectData::Tuple(_) => "a tuple".to_string(),
+            ObjectData::List(_) => "a list".to_string(),
+            ObjectData::Range(_) => "a range".to_string(),
+            ObjectData::Closure(_) => "a closure".to_string(),
             ObjectData::Bool(_) => "a boolean".to_string(),
             ObjectData::UnsignedInt(_) => "an unsigned integer".to_string(),
             ObjectData::Int(_) => "an integer".to_string(),
             ObjectData::SignedInt(_) => "a signed integer".to_string(),
             ObjectData::Float(_) => "a float".to_string(),
+            ObjectData::Char(_) => "a character".to_string(),
             ObjectData::String(_) => "a string".to_string(),
             ObjectData::Unit(()) => "nothing".to_string(),
         }
@@ -267,10 +283,14 @@ object_data_from_impls! {
     ThunkFn(ThunkFn),
     ThunkRust(RustThunk),
     Tuple(Tuple),
+    List(List),
+    Range(Range),
+    Closure(Closure),
     Bool(bool),
     UnsignedInt(u64),
     SignedInt(i64),
     Float(f64),
+    Char(char),
     String(String),
     Unit(()),
 }
@@ -296,6 +316,46 @@ pub struct Tuple {
     pub fields: Vec<Value>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct List {
+    pub elements: Vec<Value>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Range {
+    pub start: Option<Value>,
+    pub end: Option<Value>,
+    pub inclusive: bool,
+}
+
+impl Range {
+    /// The bounds that are present (start first, then end).
+    pub fn bounds(&self) -> Vec<Value> {
+        self.start.iter().chain(&self.end).copied().collect()
+    }
+}
+
+/// Created by evaluating a closure expression like `|x| x + 1`.
+/// The body of the closure lives in the same bir as the function
+/// that contains it; calling the closure pushes a frame that starts
+/// at `start_basic_block`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Closure {
+    pub bir: bir::Bir,
+    pub start_basic_block: bir::BasicBlock,
+    pub parameters: Vec<bir::LocalVariable>,
+
+    /// Variables captured from the enclosing function, along with
+    /// the (shleased) values they had when the closure was created.
+    pub captures: Vec<(bir::LocalVariable, Value)>,
+}
+
+impl Closure {
+    pub fn captured_values(&self) -> Vec<Value> {
+        self.captures.iter().map(|&(_, value)| value).collect()
+    }
+}
+
 /// A *reservation* is issued for a place when
 /// we evaluate the place before we actually consume it and
 /// we wish to ensure that the place is not invalidated in the
@@ -64,6 +64,9 @@ impl<'me> AssertInvariants<'me> {
             ObjectData::ThunkFn(f) => self.assert_values_ok(&f.arguments)?,
             ObjectData::ThunkRust(f) => self.assert_values_ok(&f.arguments)?,
             ObjectData::Tuple(t) => self.assert_values_ok(&t.fields)?,
+            ObjectData::List(l) => self.assert_values_ok(&l.elements)?,
+            ObjectData::Range(r) => self.assert_values_ok(&r.bounds())?,
+            ObjectData::Closure(c) => self.assert_values_ok(&c.captured_values())?,
 
             ObjectData::Reservation(r) => {
                 let _object = self.assert_reservation_ok(*r)?;
@@ -77,6 +80,7 @@ impl<'me> AssertInvariants<'me> {
             | ObjectData::Int(_)
             | ObjectData::SignedInt(_)
             | ObjectData::Float(_)
+            | ObjectData::Char(_)
             | ObjectData::String(_)
             | ObjectData::Unit(_) => {
                 // no reachable data
@@ -156,6 +160,35 @@ impl<'me> AssertInvariants<'me> {
                     }
                 }
             }
+
+            bir::PlaceData::Index(owner, index) => {
+                let object = self.assert_reserved_place(reservation, frame, *owner)?;
+                let index_value = match &table[*index] {
+                    bir::PlaceData::LocalVariable(lv) => frame.locals[*lv],
+                    _ => eyre::bail!(
+                        "reservation `{:?}` references place `{:?}` whose index is not a temporary",
+                        reservation,
+                        place.debug(&bir.in_ir_db(self.db)),
+                    ),
+                };
+                let index = match self.machine[index_value.object] {
+                    ObjectData::Int(i) | ObjectData::UnsignedInt(i) => usize::try_from(i).ok(),
+                    ObjectData::SignedInt(i) => usize::try_from(i).ok(),
+                    _ => None,
+                };
+                let element = match (&self.machine[object], index) {
+                    (ObjectData::List(list), Some(index)) => list.elements.get(index).copied(),
+                    _ => None,
+                };
+                match element {
+                    Some(value) => self.assert_reserved_value(reservation, value),
+                    None => eyre::bail!(
+                        "reservation `{:?}` references place `{:?}` with an invalid index",
+                        reservation,
+                        place.debug(&bir.in_ir_db(self.db)),
+                    ),
+                }
+            }
         }
     }
 
@@ -4,7 +4,7 @@ use dada_collections::IndexVec;
 use dada_ir::code::bir;
 
 use super::{
-    assert_invariants::AssertInvariants, Frame, FrameIndex, Machine, Object, ObjectData,
+    assert_invariants::AssertInvariants, Closure, Frame, FrameIndex, Machine, Object, ObjectData,
     Permission, PermissionData, ProgramCounter, Reservation, ReservationData, ValidPermissionData,
     Value,
 };
@@ -22,6 +22,7 @@ pub(crate) trait MachineOp:
 
     fn frames(&self) -> &IndexVec<FrameIndex, Frame>;
     fn push_frame(&mut self, db: &dyn crate::Db, bir: bir::Bir, arguments: Vec<Value>);
+    fn push_closure_frame(&mut self, db: &dyn crate::Db, closure: &Closure, arguments: Vec<Value>);
     fn clear_frame(&mut self);
     fn pop_frame(&mut self) -> Frame;
     fn top_frame(&self) -> Option<&Frame>;
@@ -109,6 +110,45 @@ impl MachineOp for Machine {
         });
     }
 
+    fn push_closure_frame(&mut self, db: &dyn crate::Db, closure: &Closure, arguments: Vec<Value>) {
+        let bir_data = closure.bir.data(db);
+
+        let expired_permission = self.expired_permission(None);
+
+        // As with `push_frame`, every local variable starts out expired;
+        // only the captured variables and the parameters are initialized.
+        let mut locals: IndexVec<bir::LocalVariable, Value> = bir_data
+            .max_local_variable()
+            .iter()
+            .map(|_| Value {
+                object: self.unit_object(),
+                permission: expired_permission,
+            })
+            .collect();
+
+        for &(local_variable, value) in &closure.captures {
+            locals[local_variable] = value;
+        }
+
+        assert_eq!(
+            closure.parameters.len(),
+            arguments.len(),
+            "wrong number of parameters provided"
+        );
+        for (&local_variable, argument) in closure.parameters.iter().zip(arguments) {
+            locals[local_variable] = argument;
+        }
+
+        self.stack.frames.push(Frame {
+            pc: ProgramCounter {
+                bir: closure.bir,
+                basic_block: closure.start_basic_block,
+                statement: 0,
+            },
+            locals,
+        });
+    }
+
     /// Clear the permission from all local variables on the frame.
     #[track_caller]
     fn clear_frame(&mut self) {
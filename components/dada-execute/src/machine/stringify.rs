@@ -3,7 +3,7 @@ use dada_ir::{
     word::Word,
 };
 
-use crate::machine::{ObjectData, Permission, PermissionData, Value};
+use crate::machine::{ObjectData, Permission, PermissionData, Range, Value};
 
 use super::{op::MachineOp, Object};
 
@@ -33,6 +33,7 @@ pub(crate) impl<T: ?Sized + MachineOp> DefaultStringify for T {
             ObjectData::Bool(v) => format!("{}", v),
             ObjectData::SignedInt(v) => format!("{}_i", v),
             ObjectData::Float(v) => format!("{}", v),
+            ObjectData::Char(v) => v.to_string(),
             ObjectData::UnsignedInt(v) => format!("{}_u", v),
             ObjectData::Int(v) => format!("{}", v),
             ObjectData::Unit(_) => "()".to_string(),
@@ -50,6 +51,9 @@ pub(crate) impl<T: ?Sized + MachineOp> DefaultStringify for T {
             ObjectData::Class(c) => c.name(db).as_str(db).to_string(),
             ObjectData::ThunkRust(r) => format!("{permission} {r:?}"),
             ObjectData::Tuple(t) => self.object_string(db, permission, None, &t.fields),
+            ObjectData::List(l) => self.list_string(db, permission, &l.elements),
+            ObjectData::Range(r) => self.range_string(db, permission, r),
+            ObjectData::Closure(_) => format!("{permission} closure"),
             ObjectData::Reservation(r) => format!("{r:?}"), // can prob do better than this :)
         }
     }
@@ -80,6 +84,39 @@ pub(crate) impl<T: ?Sized + MachineOp> DefaultStringify for T {
         output
     }
 
+    fn list_string(&self, db: &dyn crate::Db, permission: &str, elements: &[Value]) -> String {
+        let mut output = String::new();
+        output.push_str(permission);
+        if !permission.is_empty() {
+            output.push(' ');
+        }
+        output.push('[');
+        for (element, index) in elements.iter().zip(0..) {
+            if index > 0 {
+                output.push_str(", ");
+            }
+            output.push_str(&self.stringify_value(db, *element));
+        }
+        output.push(']');
+        output
+    }
+
+    fn range_string(&self, db: &dyn crate::Db, permission: &str, range: &Range) -> String {
+        let mut output = String::new();
+        output.push_str(permission);
+        if !permission.is_empty() {
+            output.push(' ');
+        }
+        if let Some(start) = range.start {
+            output.push_str(&self.stringify_value(db, start));
+        }
+        output.push_str(if range.inclusive { "..=" } else { ".." });
+        if let Some(end) = range.end {
+            output.push_str(&self.stringify_value(db, end));
+        }
+        output
+    }
+
     fn permission_str(&self, permission: Permission) -> Option<&str> {
         match &self[permission] {
             PermissionData::Expired(_) => None,
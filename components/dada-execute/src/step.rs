@@ -19,7 +19,8 @@ use crate::{
     heap_graph::HeapGraph,
     kernel::Kernel,
     machine::{
-        op::MachineOp, Object, ObjectData, ProgramCounter, Tuple, ValidPermissionData, Value,
+        op::MachineOp, Closure, List, Object, ObjectData, ProgramCounter, Range, Tuple,
+        ValidPermissionData, Value,
     },
     thunk::RustThunk,
 };
@@ -522,6 +523,10 @@ impl<'me> Stepper<'me> {
                 object: self.machine.new_object(ObjectData::Float(v.0)),
                 permission: self.machine.new_permission(ValidPermissionData::our()),
             }),
+            bir::ExprData::CharLiteral(v) => Ok(Value {
+                object: self.machine.new_object(ObjectData::Char(*v)),
+                permission: self.machine.new_permission(ValidPermissionData::our()),
+            }),
             bir::ExprData::StringLiteral(v) => Ok(Value {
                 object: self
                     .machine
@@ -547,6 +552,52 @@ impl<'me> Stepper<'me> {
                     permission: self.machine.new_permission(ValidPermissionData::my()),
                 })
             }
+            bir::ExprData::List(places) => {
+                let elements = places
+                    .iter()
+                    .map(|place| self.give_place(table, *place))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value {
+                    object: self.machine.new_object(ObjectData::List(List { elements })),
+                    permission: self.machine.new_permission(ValidPermissionData::my()),
+                })
+            }
+            bir::ExprData::Range(start, end, inclusive) => {
+                let start = start
+                    .map(|place| self.give_place(table, place))
+                    .transpose()?;
+                let end = end.map(|place| self.give_place(table, place)).transpose()?;
+                Ok(Value {
+                    object: self.machine.new_object(ObjectData::Range(Range {
+                        start,
+                        end,
+                        inclusive: *inclusive,
+                    })),
+                    permission: self.machine.new_permission(ValidPermissionData::my()),
+                })
+            }
+            bir::ExprData::Closure(parameters, captures, start_basic_block) => {
+                let captures = captures
+                    .iter()
+                    .map(|&place| {
+                        let local_variable = match table[place] {
+                            bir::PlaceData::LocalVariable(lv) => lv,
+                            _ => unreachable!("closures only capture local variables"),
+                        };
+                        Ok((local_variable, self.shlease_place(table, place)?))
+                    })
+                    .collect::<eyre::Result<Vec<_>>>()?;
+                let closure = Closure {
+                    bir: self.machine.pc().bir,
+                    start_basic_block: *start_basic_block,
+                    parameters: parameters.clone(),
+                    captures,
+                };
+                Ok(Value {
+                    object: self.machine.new_object(ObjectData::Closure(closure)),
+                    permission: self.machine.new_permission(ValidPermissionData::my()),
+                })
+            }
             bir::ExprData::Concatenate(places) => self.concatenate(table, places),
             bir::ExprData::Op(lhs, op, rhs) => {
                 let lhs_traversal = self.traverse_to_object(table, *lhs)?;
@@ -603,6 +654,13 @@ impl<'me> Stepper<'me> {
         let bir = self.machine.pc().bir;
         bir.span_of(self.db, syntax_expr)
     }
+
+    /// Like [`Self::span_from_bir`], but only the operator of an `a + b` expression.
+    fn op_span_from_bir(&self, expr: bir::Expr) -> FileSpan {
+        let bir = self.machine.pc().bir;
+        let syntax_expr = bir.origins(self.db)[expr];
+        bir.op_span_of(self.db, syntax_expr)
+    }
 }
 
 trait IntoSpecifierAndSpan: std::fmt::Debug {
@@ -256,10 +256,24 @@ impl Stepper<'_> {
                     self.push_reachable_via_fields(&v.fields, &mut reachable, &mut queue);
                 }
 
+                ObjectData::List(v) => {
+                    self.push_reachable_via_fields(&v.elements, &mut reachable, &mut queue);
+                }
+
+                ObjectData::Range(v) => {
+                    self.push_reachable_via_fields(&v.bounds(), &mut reachable, &mut queue);
+                }
+
+                ObjectData::Closure(v) => {
+                    let captured_values = v.captured_values();
+                    self.push_reachable_via_fields(&captured_values, &mut reachable, &mut queue);
+                }
+
                 ObjectData::Reservation(_)
                 | ObjectData::Bool(_)
                 | ObjectData::Class(_)
                 | ObjectData::Float(_)
+                | ObjectData::Char(_)
                 | ObjectData::Function(_)
                 | ObjectData::Intrinsic(_)
                 | ObjectData::SignedInt(_)
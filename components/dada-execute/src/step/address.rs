@@ -18,8 +18,8 @@ pub(super) enum Address {
 
     /// A field with the given index of the given object.
     /// If this is a field of a user-declared class (as opposed,
-    /// say, to a tuple), then includes the [`Parameter`]
-    /// representing that field.
+    /// say, to a tuple or the element of a list), then includes
+    /// the [`Parameter`] representing that field.
     Field(Object, usize, Option<Parameter>),
 }
 
@@ -45,6 +45,7 @@ impl Stepper<'_> {
             Address::Field(o, f, _) => match &self.machine[o] {
                 ObjectData::Instance(i) => i.fields[f],
                 ObjectData::Tuple(v) => v.fields[f],
+                ObjectData::List(v) => v.elements[f],
                 d => panic!("unexpected thing with fields: {d:?}"),
             },
         }
@@ -64,6 +65,7 @@ impl Stepper<'_> {
             Address::Field(o, f, _) => match &mut self.machine[o] {
                 ObjectData::Instance(i) => i.fields[f] = value,
                 ObjectData::Tuple(v) => v.fields[f] = value,
+                ObjectData::List(v) => v.elements[f] = value,
                 d => panic!("unexpected thing with fields: {d:?}"),
             },
         }
@@ -20,7 +20,7 @@ impl Stepper<'_> {
         rhs: Object,
     ) -> eyre::Result<Value> {
         let op_error = || {
-            let span = self.span_from_bir(expr);
+            let span = self.op_span_from_bir(expr);
             Err(error!(
                 span,
                 "cannot apply operator {} to {} and {}",
@@ -41,6 +41,7 @@ impl Stepper<'_> {
         match (&self.machine[lhs], &self.machine[rhs]) {
             (&ObjectData::Bool(lhs), &ObjectData::Bool(rhs)) => match op {
                 Op::EqualEqual => Ok(self.machine.our_value(lhs == rhs)),
+                Op::NotEqual => Ok(self.machine.our_value(lhs != rhs)),
                 Op::GreaterEqual => Ok(self.machine.our_value(lhs >= rhs)),
                 Op::LessEqual => Ok(self.machine.our_value(lhs <= rhs)),
                 _ => op_error(),
@@ -49,6 +50,7 @@ impl Stepper<'_> {
             | (&ObjectData::UnsignedInt(lhs), &ObjectData::Int(rhs))
             | (&ObjectData::Int(lhs), &ObjectData::UnsignedInt(rhs)) => match op {
                 Op::EqualEqual => Ok(self.machine.our_value(lhs == rhs)),
+                Op::NotEqual => Ok(self.machine.our_value(lhs != rhs)),
                 Op::GreaterEqual => Ok(self.machine.our_value(lhs >= rhs)),
                 Op::LessEqual => Ok(self.machine.our_value(lhs <= rhs)),
                 Op::Plus => match lhs.checked_add(rhs) {
@@ -69,9 +71,22 @@ impl Stepper<'_> {
                 },
                 Op::LessThan => Ok(self.machine.our_value(lhs < rhs)),
                 Op::GreaterThan => Ok(self.machine.our_value(lhs > rhs)),
+                Op::BitAnd => Ok(self.machine.our_value(lhs & rhs)),
+                Op::BitOr => Ok(self.machine.our_value(lhs | rhs)),
+                Op::BitXor => Ok(self.machine.our_value(lhs ^ rhs)),
+                Op::ShiftLeft => match shift_amount(rhs).and_then(|rhs| lhs.checked_shl(rhs)) {
+                    Some(value) => Ok(self.machine.our_value(value)),
+                    None => overflow_error(),
+                },
+                Op::ShiftRight => match shift_amount(rhs).and_then(|rhs| lhs.checked_shr(rhs)) {
+                    Some(value) => Ok(self.machine.our_value(value)),
+                    None => overflow_error(),
+                },
+                Op::Not => op_error(),
             },
             (&ObjectData::Int(lhs), &ObjectData::Int(rhs)) => match op {
                 Op::EqualEqual => Ok(self.machine.our_value(lhs == rhs)),
+                Op::NotEqual => Ok(self.machine.our_value(lhs != rhs)),
                 Op::GreaterEqual => Ok(self.machine.our_value(lhs >= rhs)),
                 Op::LessEqual => Ok(self.machine.our_value(lhs <= rhs)),
                 Op::Plus => match lhs.checked_add(rhs) {
@@ -92,6 +107,18 @@ impl Stepper<'_> {
                 },
                 Op::LessThan => Ok(self.machine.our_value(lhs < rhs)),
                 Op::GreaterThan => Ok(self.machine.our_value(lhs > rhs)),
+                Op::BitAnd => Ok(self.machine.our_value(ObjectData::Int(lhs & rhs))),
+                Op::BitOr => Ok(self.machine.our_value(ObjectData::Int(lhs | rhs))),
+                Op::BitXor => Ok(self.machine.our_value(ObjectData::Int(lhs ^ rhs))),
+                Op::ShiftLeft => match shift_amount(rhs).and_then(|rhs| lhs.checked_shl(rhs)) {
+                    Some(value) => Ok(self.machine.our_value(ObjectData::Int(value))),
+                    None => overflow_error(),
+                },
+                Op::ShiftRight => match shift_amount(rhs).and_then(|rhs| lhs.checked_shr(rhs)) {
+                    Some(value) => Ok(self.machine.our_value(ObjectData::Int(value))),
+                    None => overflow_error(),
+                },
+                Op::Not => op_error(),
             },
             (&ObjectData::SignedInt(lhs), &ObjectData::SignedInt(rhs)) => {
                 self.apply_signed_int(expr, op, lhs, rhs)
@@ -106,6 +133,7 @@ impl Stepper<'_> {
             },
             (&ObjectData::Float(lhs), &ObjectData::Float(rhs)) => match op {
                 Op::EqualEqual => Ok(self.machine.our_value(lhs == rhs)),
+                Op::NotEqual => Ok(self.machine.our_value(lhs != rhs)),
                 Op::GreaterEqual => Ok(self.machine.our_value(lhs >= rhs)),
                 Op::LessEqual => Ok(self.machine.our_value(lhs <= rhs)),
                 Op::Plus => Ok(self.machine.our_value(lhs + rhs)),
@@ -114,12 +142,28 @@ impl Stepper<'_> {
                 Op::DividedBy => Ok(self.machine.our_value(lhs / rhs)),
                 Op::LessThan => Ok(self.machine.our_value(lhs < rhs)),
                 Op::GreaterThan => Ok(self.machine.our_value(lhs > rhs)),
+                Op::BitAnd | Op::BitOr | Op::BitXor | Op::ShiftLeft | Op::ShiftRight | Op::Not => {
+                    op_error()
+                }
+            },
+            (&ObjectData::Char(lhs), &ObjectData::Char(rhs)) => match op {
+                Op::EqualEqual => Ok(self.machine.our_value(lhs == rhs)),
+                Op::NotEqual => Ok(self.machine.our_value(lhs != rhs)),
+                Op::GreaterEqual => Ok(self.machine.our_value(lhs >= rhs)),
+                Op::LessEqual => Ok(self.machine.our_value(lhs <= rhs)),
+                Op::LessThan => Ok(self.machine.our_value(lhs < rhs)),
+                Op::GreaterThan => Ok(self.machine.our_value(lhs > rhs)),
+                _ => op_error(),
             },
             (ObjectData::String(lhs), ObjectData::String(rhs)) => match op {
                 Op::EqualEqual => {
                     let val = lhs == rhs;
                     Ok(self.machine.our_value(val))
                 }
+                Op::NotEqual => {
+                    let val = lhs != rhs;
+                    Ok(self.machine.our_value(val))
+                }
                 Op::GreaterEqual => {
                     let val = lhs >= rhs;
                     Ok(self.machine.our_value(val))
@@ -132,6 +176,7 @@ impl Stepper<'_> {
             },
             (&ObjectData::Unit(()), &ObjectData::Unit(())) => match op {
                 Op::EqualEqual => Ok(self.machine.our_value(true)),
+                Op::NotEqual => Ok(self.machine.our_value(false)),
                 Op::GreaterEqual => Ok(self.machine.our_value(lhs >= rhs)),
                 Op::LessEqual => Ok(self.machine.our_value(lhs <= rhs)),
                 _ => op_error(),
@@ -157,6 +202,7 @@ impl Stepper<'_> {
         };
         match op {
             Op::EqualEqual => Ok(self.machine.our_value(lhs == rhs)),
+            Op::NotEqual => Ok(self.machine.our_value(lhs != rhs)),
             Op::GreaterEqual => Ok(self.machine.our_value(lhs >= rhs)),
             Op::LessEqual => Ok(self.machine.our_value(lhs <= rhs)),
             Op::Plus => match lhs.checked_add(rhs) {
@@ -184,6 +230,24 @@ impl Stepper<'_> {
             },
             Op::LessThan => Ok(self.machine.our_value(lhs < rhs)),
             Op::GreaterThan => Ok(self.machine.our_value(lhs > rhs)),
+            Op::BitAnd => Ok(self.machine.our_value(lhs & rhs)),
+            Op::BitOr => Ok(self.machine.our_value(lhs | rhs)),
+            Op::BitXor => Ok(self.machine.our_value(lhs ^ rhs)),
+            Op::ShiftLeft => match shift_amount(rhs).and_then(|rhs| lhs.checked_shl(rhs)) {
+                Some(value) => Ok(self.machine.our_value(value)),
+                None => overflow_error(),
+            },
+            Op::ShiftRight => match shift_amount(rhs).and_then(|rhs| lhs.checked_shr(rhs)) {
+                Some(value) => Ok(self.machine.our_value(value)),
+                None => overflow_error(),
+            },
+            Op::Not => unreachable!("`!` is not a binary operator"),
         }
     }
 }
+
+/// Converts the right-hand side of a shift into a shift amount,
+/// or `None` if it is negative or too large.
+fn shift_amount(rhs: impl TryInto<u32>) -> Option<u32> {
+    rhs.try_into().ok()
+}
@@ -37,6 +37,8 @@ impl Stepper<'_> {
                     Err(error!(span, "overflow").eyre(self.db))
                 }
             },
+            (Op::Minus, &ObjectData::Float(rhs)) => Ok(self.machine.our_value(-rhs)),
+            (Op::Not, &ObjectData::Bool(rhs)) => Ok(self.machine.our_value(!rhs)),
             _ => op_error(),
         }
     }
@@ -1,16 +1,17 @@
 use dada_brew::prelude::*;
 use dada_ir::{
-    code::{bir, syntax},
+    code::{bir, syntax, validated},
     error,
     origin_table::HasOriginIn,
     parameter::Parameter,
     word::{SpannedOptionalWord, Word},
 };
 use dada_parse::prelude::*;
+use dada_validate::prelude::*;
 
 use crate::{
     error::DiagnosticBuilderExt,
-    machine::{op::MachineOpExtMut, Instance, ObjectData, ThunkFn, Value},
+    machine::{op::MachineOpExtMut, Instance, ObjectData, ThunkFn, ValidPermissionData, Value},
     step::intrinsic::IntrinsicDefinition,
 };
 
@@ -51,10 +52,25 @@ impl Stepper<'_> {
             }
             &ObjectData::Function(function) => {
                 let parameters = function.parameters(self.db);
-                self.match_labels(terminator, labels, parameters)?;
+                let supplied_parameters = self.supplied_parameters(parameters, labels.len());
+                self.match_labels(terminator, labels, supplied_parameters)?;
 
-                let arguments =
+                let mut arguments =
                     self.prepare_arguments_for_parameters(table, parameters, argument_places)?;
+                for &parameter in &parameters[arguments.len()..] {
+                    arguments.push(self.default_argument(terminator, parameter)?);
+                }
+
+                if function.effect(self.db).permits_yield() {
+                    // FIXME: there is no generator protocol yet.
+                    let span = self.span_from_bir(callee);
+                    return Err(error!(span, "generator functions cannot be called yet")
+                        .primary_label(format!(
+                            "`{}` is a generator function",
+                            function.name(self.db).as_str(self.db)
+                        ))
+                        .eyre(self.db));
+                }
 
                 if function.effect(self.db).permits_await() {
                     // If the function can await, then it must be an async function.
@@ -72,6 +88,31 @@ impl Stepper<'_> {
                     Ok(CallResult::PushedNewFrame)
                 }
             }
+            ObjectData::Closure(closure) => {
+                let closure = closure.clone();
+                let closure_tables = closure.bir.data(self.db).tables();
+                let parameter_data = closure
+                    .parameters
+                    .iter()
+                    .map(|&lv| &closure_tables[lv])
+                    .collect::<Vec<_>>();
+                let parameter_names = parameter_data
+                    .iter()
+                    .map(|data| data.name.unwrap())
+                    .collect::<Vec<_>>();
+                self.match_labels(terminator, labels, &parameter_names)?;
+
+                let arguments = self.prepare_arguments(
+                    table,
+                    parameter_data.iter().map(|data| data.specifier.unwrap()),
+                    argument_places,
+                )?;
+
+                // Closures are never async, so the body begins executing immediately.
+                self.machine
+                    .push_closure_frame(self.db, &closure, arguments);
+                Ok(CallResult::PushedNewFrame)
+            }
             &ObjectData::Intrinsic(intrinsic) => {
                 let definition = IntrinsicDefinition::for_intrinsic(self.db, intrinsic);
                 self.match_labels(callee, labels, &definition.argument_names)?;
@@ -99,6 +140,44 @@ impl Stepper<'_> {
         }
     }
 
+    /// Returns the prefix of `parameters` that a call with `num_arguments`
+    /// arguments supplies: trailing parameters with a default may be omitted.
+    fn supplied_parameters<'p>(
+        &self,
+        parameters: &'p [Parameter],
+        num_arguments: usize,
+    ) -> &'p [Parameter] {
+        let num_required = parameters
+            .iter()
+            .position(|parameter| parameter.default(self.db).is_some())
+            .unwrap_or(parameters.len());
+        &parameters[..num_arguments.clamp(num_required, parameters.len())]
+    }
+
+    /// Creates the value for a parameter whose argument was omitted.
+    fn default_argument(
+        &mut self,
+        call_terminator: bir::Terminator,
+        parameter: Parameter,
+    ) -> eyre::Result<Value> {
+        let object = match parameter.default_value(self.db) {
+            Some(validated::ExprData::BooleanLiteral(v)) => ObjectData::Bool(v),
+            Some(validated::ExprData::IntegerLiteral(v)) => ObjectData::Int(v),
+            Some(validated::ExprData::UnsignedIntegerLiteral(v)) => ObjectData::UnsignedInt(v),
+            Some(validated::ExprData::SignedIntegerLiteral(v)) => ObjectData::SignedInt(v),
+            Some(validated::ExprData::FloatLiteral(v)) => ObjectData::Float(v.0),
+            _ => {
+                // The default was invalid, which validation reported.
+                let span = self.span_from_bir(call_terminator);
+                return Err(error!(span, "compilation error encountered 😢").eyre(self.db));
+            }
+        };
+        Ok(Value {
+            object: self.machine.new_object(object),
+            permission: self.machine.new_permission(ValidPermissionData::our()),
+        })
+    }
+
     /// Prepare the arguments according to the given specifiers.
     fn prepare_arguments_for_parameters(
         &mut self,
@@ -130,6 +130,9 @@ impl<'me> Marker<'me> {
             ObjectData::ThunkFn(f) => self.mark_values(&f.arguments),
             ObjectData::ThunkRust(f) => self.mark_values(&f.arguments),
             ObjectData::Tuple(t) => self.mark_values(&t.fields),
+            ObjectData::List(l) => self.mark_values(&l.elements),
+            ObjectData::Range(r) => self.mark_values(&r.bounds()),
+            ObjectData::Closure(c) => self.mark_values(&c.captured_values()),
 
             ObjectData::Reservation(r) => self.mark_reservation(*r),
 
@@ -141,6 +144,7 @@ impl<'me> Marker<'me> {
             | ObjectData::SignedInt(_)
             | ObjectData::Int(_)
             | ObjectData::Float(_)
+            | ObjectData::Char(_)
             | ObjectData::String(_)
             | ObjectData::Unit(_) => {
                 // no reachable data
@@ -4,7 +4,7 @@ use eyre::Context;
 use crate::{
     error::DiagnosticBuilderExt,
     machine::stringify::DefaultStringify,
-    machine::{op::MachineOpExtMut, ProgramCounter, Value},
+    machine::{op::MachineOpExtMut, ObjectData, ProgramCounter, Value},
     thunk::RustThunk,
 };
 
@@ -27,6 +27,11 @@ impl IntrinsicDefinition {
                 function: |s, v| s.intrinsic_print(v),
                 // FIXME: Stepper::intrinsic_write doesn't type check, why?
             },
+            Intrinsic::Len => IntrinsicDefinition {
+                argument_names: vec![Word::from(db, "list")],
+                argument_specifiers: vec![Specifier::Any],
+                function: |s, v| s.intrinsic_len(v),
+            },
         }
     }
 }
@@ -46,6 +51,7 @@ impl Stepper<'_> {
                 let await_pc = self.machine.pc();
                 self.intrinsic_print_async(await_pc, value).await
             }
+            Intrinsic::Len => panic!("`len` does not yield a thunk"),
         }
     }
 
@@ -55,6 +61,21 @@ impl Stepper<'_> {
             .my_value(RustThunk::new("print", values, Intrinsic::Print)))
     }
 
+    /// Returns the number of elements in a list.
+    fn intrinsic_len(&mut self, values: Vec<Value>) -> eyre::Result<Value> {
+        let object = values[0].object;
+        match &self.machine[object] {
+            ObjectData::List(list) => {
+                let len = list.elements.len() as u64;
+                Ok(self.machine.our_value(ObjectData::Int(len)))
+            }
+            data => {
+                let span_now = self.machine.pc().span(self.db);
+                Err(Self::unexpected_kind(self.db, span_now, data, "a list"))
+            }
+        }
+    }
+
     #[tracing::instrument(level = "Debug", skip(self, await_pc))]
     pub(super) async fn intrinsic_print_async(
         &mut self,
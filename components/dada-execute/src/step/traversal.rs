@@ -152,6 +152,20 @@ impl Stepper<'_> {
                     address: Address::Field(owner_object, field_index, Some(field)),
                 })
             }
+            bir::PlaceData::Index(owner_place, index_place) => {
+                let index_traversal = self.traverse_to_object(table, *index_place)?;
+                let ObjectTraversal {
+                    accumulated_permissions,
+                    object: owner_object,
+                } = self.traverse_to_object(table, *owner_place)?;
+                let element_index =
+                    self.list_element(place, owner_object, index_traversal.object)?;
+
+                Ok(PlaceTraversal {
+                    accumulated_permissions,
+                    address: Address::Field(owner_object, element_index, None),
+                })
+            }
         }
     }
 
@@ -292,6 +306,49 @@ impl Stepper<'_> {
         }
     }
 
+    /// Checks that `owner_object` is a list and that `index_object` is an
+    /// in-bounds index for it, returning the index.
+    fn list_element(
+        &mut self,
+        place: impl HasOriginIn<bir::Origins, Origin = syntax::Expr>,
+        owner_object: Object,
+        index_object: Object,
+    ) -> eyre::Result<usize> {
+        let place_span = self.span_from_bir(place);
+        let (index, index_str) = match self.machine[index_object] {
+            ObjectData::Int(i) | ObjectData::UnsignedInt(i) => {
+                (usize::try_from(i).ok(), i.to_string())
+            }
+            ObjectData::SignedInt(i) => (usize::try_from(i).ok(), i.to_string()),
+            ref index_data => {
+                return Err(Self::unexpected_kind(
+                    self.db,
+                    place_span,
+                    index_data,
+                    "an integer index",
+                ))
+            }
+        };
+        match &self.machine[owner_object] {
+            ObjectData::List(list) => match index {
+                Some(index) if index < list.elements.len() => Ok(index),
+                _ => Err(error!(
+                    place_span,
+                    "index out of bounds: the length is {} but the index is {}",
+                    list.elements.len(),
+                    index_str,
+                )
+                .eyre(self.db)),
+            },
+            owner_data => Err(Self::unexpected_kind(
+                self.db,
+                place_span,
+                owner_data,
+                "something that can be indexed",
+            )),
+        }
+    }
+
     fn traverse_to_constant(&mut self, object_data: ObjectData) -> PlaceTraversal {
         let object = self.machine.our_value(object_data);
         let permissions = AccumulatedPermissions {
@@ -43,6 +43,11 @@ impl<K: salsa::AsId, V> AllocTable<K, V> {
         &mut self.vec[key.as_id()]
     }
 
+    /// Removes all values, keeping the allocation. The next key is zero again.
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+
     /// Replace the value for K with V.
     pub fn all_keys(&self) -> impl Iterator<Item = K> {
         (0..self.vec.len()).map(|i| K::from_id(salsa::Id::from(i)))
@@ -33,6 +33,11 @@ impl<K: salsa::AsId, V: Hash + Eq> InternTable<K, V> {
         let index: usize = key.as_id().as_u32().try_into().unwrap();
         self.map.get_index(index).unwrap()
     }
+
+    /// Removes all values, keeping the allocation. The next key is zero again.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
 }
 
 impl<K: salsa::AsId, V: Hash + Eq> std::ops::Index<K> for InternTable<K, V> {
@@ -174,6 +174,14 @@ macro_rules! tables {
             {
                 dada_id::InternValue::add(value, self)
             }
+
+            /// Removes everything from the tables but keeps their allocations,
+            /// so they can be reused. Ids start again from zero afterwards, so
+            /// ids from before the `clear` must not be used with the tables.
+            #[allow(dead_code)] // not every set of tables gets reused
+            pub fn clear(&mut self) {
+                $(self.$f.clear();)*
+            }
         }
 
         $(
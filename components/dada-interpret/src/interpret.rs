@@ -0,0 +1,415 @@
+use dada_id::prelude::*;
+use dada_ir::code::syntax;
+use dada_ir::code::validated::{self, op::Op};
+use dada_ir::diagnostic::Diagnostic;
+use dada_ir::error;
+use dada_ir::function::Function;
+use dada_ir::span::FileSpan;
+use dada_parse::prelude::*;
+use dada_validate::prelude::*;
+
+use crate::value::Value;
+
+/// Runs `function`, which is called with the defaults of its parameters, and
+/// returns its result, or the runtime error that stopped it.
+pub fn interpret_function(db: &dyn crate::Db, function: Function) -> Result<Value, Diagnostic> {
+    call(db, function, vec![], function.name(db).span(db))
+}
+
+/// Runs `function` with `arguments`, filling in the defaults of the
+/// parameters that were not supplied. `call_span` is where errors in the
+/// arguments are reported.
+#[tracing::instrument(level = "debug", skip(db))]
+fn call(
+    db: &dyn crate::Db,
+    function: Function,
+    mut arguments: Vec<Value>,
+    call_span: FileSpan,
+) -> Result<Value, Diagnostic> {
+    let parameters = function.parameters(db);
+    if arguments.len() > parameters.len() {
+        return Err(error!(
+            call_span,
+            "expected at most {} arguments, found {}",
+            parameters.len(),
+            arguments.len()
+        )
+        .finish());
+    }
+    for &parameter in &parameters[arguments.len()..] {
+        let value = parameter
+            .default_value(db)
+            .and_then(|data| literal(db, &data));
+        let Some(value) = value else {
+            return Err(error!(
+                call_span,
+                "no value for parameter `{}`",
+                parameter.name(db).as_str(db)
+            )
+            .finish());
+        };
+        arguments.push(value);
+    }
+
+    let tree = function.validated_tree(db);
+    let tree_data = tree.data(db);
+    let mut locals = vec![None; usize::from(tree_data.max_local_variable())];
+    for (parameter, argument) in tree_data.parameters().zip(arguments) {
+        locals[usize::from(parameter)] = Some(argument);
+    }
+
+    let mut frame = Frame {
+        db,
+        function,
+        tables: &tree_data.tables,
+        origins: tree.origins(db),
+        locals,
+    };
+    match frame.eval(tree_data.root_expr) {
+        Ok(value) | Err(Unwind::Return(value)) => Ok(value),
+        Err(Unwind::Error(diagnostic)) => Err(diagnostic),
+        Err(Unwind::Break(..) | Unwind::Continue(_)) => {
+            unreachable!("`break` or `continue` outside of its loop")
+        }
+    }
+}
+
+/// The value of a literal, or `None` if `data` is not a literal that the
+/// interpreter supports (or, for integers, does not fit in an `i64`).
+fn literal(db: &dyn crate::Db, data: &validated::ExprData) -> Option<Value> {
+    match *data {
+        validated::ExprData::BooleanLiteral(value) => Some(Value::Bool(value)),
+        validated::ExprData::SignedIntegerLiteral(value) => Some(Value::Int(value)),
+        validated::ExprData::UnsignedIntegerLiteral(value)
+        | validated::ExprData::IntegerLiteral(value) => i64::try_from(value).ok().map(Value::Int),
+        validated::ExprData::StringLiteral(word) => {
+            Some(Value::String(word.as_str(db).to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Why the evaluation of an expression stopped without a value.
+enum Unwind {
+    /// `break` from the given loop, which then produces the value.
+    Break(validated::Expr, Value),
+
+    /// `continue` with the given loop.
+    Continue(validated::Expr),
+
+    Return(Value),
+
+    Error(Diagnostic),
+}
+
+impl From<Diagnostic> for Unwind {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Unwind::Error(diagnostic)
+    }
+}
+
+/// The state of one call of a function.
+struct Frame<'me> {
+    db: &'me dyn crate::Db,
+    function: Function,
+    tables: &'me validated::Tables,
+    origins: &'me validated::Origins,
+
+    /// The value of each local variable, or `None` if it has not been assigned yet.
+    locals: Vec<Option<Value>>,
+}
+
+impl Frame<'_> {
+    fn eval(&mut self, expr: validated::Expr) -> Result<Value, Unwind> {
+        let tables = self.tables;
+        match expr.data(tables) {
+            data @ (validated::ExprData::BooleanLiteral(_)
+            | validated::ExprData::SignedIntegerLiteral(_)
+            | validated::ExprData::UnsignedIntegerLiteral(_)
+            | validated::ExprData::IntegerLiteral(_)
+            | validated::ExprData::StringLiteral(_)) => match literal(self.db, data) {
+                Some(value) => Ok(value),
+                None => Err(self.error(expr, "integer does not fit in 64 bits")),
+            },
+            validated::ExprData::FloatLiteral(_) => Err(self.unsupported(expr, "floats")),
+            validated::ExprData::CharLiteral(_) => Err(self.unsupported(expr, "characters")),
+
+            validated::ExprData::Concatenate(exprs) => {
+                let mut string = String::new();
+                for &expr in exprs {
+                    let value = self.eval(expr)?;
+                    string.push_str(&self.stringify(value));
+                }
+                Ok(Value::String(string))
+            }
+
+            validated::ExprData::Await(_) => Err(error!(
+                self.span(expr),
+                "`await` is not supported by the interpreter"
+            )
+            .primary_label("only synchronous code can be interpreted")
+            .finish()
+            .into()),
+
+            validated::ExprData::Call(func_expr, named_exprs) => {
+                let callee = self.eval(*func_expr)?;
+
+                // FIXME: arguments are passed in the order they are written,
+                // so named arguments must be in order.
+                let mut arguments = vec![];
+                for named_expr in named_exprs {
+                    arguments.push(self.eval(named_expr.data(tables).expr)?);
+                }
+
+                let Value::Function(function) = &callee else {
+                    return Err(self.error(
+                        *func_expr,
+                        format!("expected a function, found {}", callee.kind_str()),
+                    ));
+                };
+                Ok(call(self.db, *function, arguments, self.span(expr))?)
+            }
+
+            validated::ExprData::Reserve(place)
+            | validated::ExprData::Lease(place)
+            | validated::ExprData::Shlease(place)
+            | validated::ExprData::Give(place) => self.read_place(*place),
+
+            validated::ExprData::Share(expr)
+            | validated::ExprData::Atomic(expr)
+            | validated::ExprData::Declare(_, expr) => self.eval(*expr),
+
+            validated::ExprData::Tuple(exprs) if exprs.is_empty() => Ok(Value::Unit),
+            validated::ExprData::Tuple(_) => Err(self.unsupported(expr, "tuples")),
+            validated::ExprData::List(_) => Err(self.unsupported(expr, "lists")),
+            validated::ExprData::Range(..) => Err(self.unsupported(expr, "ranges")),
+            validated::ExprData::Closure(..) => Err(self.unsupported(expr, "closures")),
+
+            validated::ExprData::If(condition_expr, then_expr, else_expr) => {
+                match self.eval(*condition_expr)? {
+                    Value::Bool(true) => self.eval(*then_expr),
+                    Value::Bool(false) => self.eval(*else_expr),
+                    value => Err(self.error(
+                        *condition_expr,
+                        format!("expected a boolean, found {}", value.kind_str()),
+                    )),
+                }
+            }
+
+            validated::ExprData::Loop(body_expr) => loop {
+                match self.eval(*body_expr) {
+                    Ok(_) => {}
+                    Err(Unwind::Break(from_expr, value)) if from_expr == expr => return Ok(value),
+                    Err(Unwind::Continue(from_expr)) if from_expr == expr => {}
+                    Err(unwind) => return Err(unwind),
+                }
+            },
+
+            validated::ExprData::Break {
+                from_expr,
+                with_value,
+            } => {
+                let value = self.eval(*with_value)?;
+                Err(Unwind::Break(*from_expr, value))
+            }
+
+            validated::ExprData::Continue(from_expr) => Err(Unwind::Continue(*from_expr)),
+
+            validated::ExprData::Return(with_value) => {
+                let value = self.eval(*with_value)?;
+                Err(Unwind::Return(value))
+            }
+
+            validated::ExprData::Seq(exprs) => {
+                let mut value = Value::Unit;
+                for &expr in exprs {
+                    value = self.eval(expr)?;
+                }
+                Ok(value)
+            }
+
+            validated::ExprData::Op(lhs_expr, op, rhs_expr) => {
+                let lhs = self.eval(*lhs_expr)?;
+                let rhs = self.eval(*rhs_expr)?;
+                self.apply_op(expr, *op, lhs, rhs)
+            }
+
+            validated::ExprData::Unary(op, rhs_expr) => {
+                let rhs = self.eval(*rhs_expr)?;
+                self.apply_unary(expr, *op, rhs)
+            }
+
+            validated::ExprData::AssignTemporary(local_variable, expr) => {
+                let value = self.eval(*expr)?;
+                self.locals[usize::from(*local_variable)] = Some(value);
+                Ok(Value::Unit)
+            }
+
+            validated::ExprData::AssignFromPlace(target_place, place) => {
+                let value = self.read_place(*place)?;
+                match target_place.data(tables) {
+                    validated::TargetPlaceData::LocalVariable(local_variable) => {
+                        self.locals[usize::from(*local_variable)] = Some(value);
+                        Ok(Value::Unit)
+                    }
+                    validated::TargetPlaceData::Dot(..) => Err(self.unsupported(expr, "fields")),
+                }
+            }
+
+            validated::ExprData::Error => Err(self.error(expr, "compilation error encountered 😢")),
+        }
+    }
+
+    /// Returns a copy of the value in `place`. Permissions are not tracked,
+    /// so giving, leasing, and sharing a place all read it.
+    fn read_place(&self, place: validated::Place) -> Result<Value, Unwind> {
+        let span = || self.span_of(self.origins[place].syntax_expr);
+        match place.data(self.tables) {
+            validated::PlaceData::LocalVariable(local_variable) => {
+                match &self.locals[usize::from(*local_variable)] {
+                    Some(value) => Ok(value.clone()),
+                    None => Err(error!(span(), "variable read before it is assigned")
+                        .finish()
+                        .into()),
+                }
+            }
+            validated::PlaceData::Function(function) => Ok(Value::Function(*function)),
+            validated::PlaceData::Intrinsic(intrinsic) => Err(error!(
+                span(),
+                "`{}` is not supported by the interpreter",
+                intrinsic.as_str(self.db)
+            )
+            .finish()
+            .into()),
+            validated::PlaceData::Class(_) => Err(unsupported(span(), "classes")),
+            validated::PlaceData::Dot(..) => Err(unsupported(span(), "fields")),
+            validated::PlaceData::Index(..) => Err(unsupported(span(), "indexing")),
+        }
+    }
+
+    fn apply_op(
+        &self,
+        expr: validated::Expr,
+        op: Op,
+        lhs: Value,
+        rhs: Value,
+    ) -> Result<Value, Unwind> {
+        let op_span = || {
+            let syntax_expr = self.origins[expr].syntax_expr;
+            let spans = self.function.spans(self.db);
+            spans
+                .op_span(syntax_expr)
+                .unwrap_or(spans[syntax_expr])
+                .in_file(self.function.filename(self.db))
+        };
+        let comparison = match (&lhs, &rhs) {
+            (Value::Unit, Value::Unit) => compare(op, (), ()),
+            (Value::Bool(lhs), Value::Bool(rhs)) => compare(op, lhs, rhs),
+            (Value::Int(lhs), Value::Int(rhs)) => compare(op, lhs, rhs),
+            (Value::String(lhs), Value::String(rhs)) => compare(op, lhs, rhs),
+            _ => None,
+        };
+        if let Some(value) = comparison {
+            return Ok(Value::Bool(value));
+        }
+
+        let (Value::Int(lhs), Value::Int(rhs)) = (&lhs, &rhs) else {
+            return Err(error!(
+                op_span(),
+                "cannot apply operator {} to {} and {}",
+                op,
+                lhs.kind_str(),
+                rhs.kind_str(),
+            )
+            .finish()
+            .into());
+        };
+        let (lhs, rhs) = (*lhs, *rhs);
+        let shift_amount = u32::try_from(rhs).ok();
+        let value = match op {
+            Op::Plus => lhs.checked_add(rhs),
+            Op::Minus => lhs.checked_sub(rhs),
+            Op::Times => lhs.checked_mul(rhs),
+            Op::DividedBy if rhs == 0 => return Err(self.error(expr, "divide by zero")),
+            Op::DividedBy => lhs.checked_div(rhs),
+            Op::BitAnd => Some(lhs & rhs),
+            Op::BitOr => Some(lhs | rhs),
+            Op::BitXor => Some(lhs ^ rhs),
+            Op::ShiftLeft => shift_amount.and_then(|rhs| lhs.checked_shl(rhs)),
+            Op::ShiftRight => shift_amount.and_then(|rhs| lhs.checked_shr(rhs)),
+            Op::EqualEqual
+            | Op::NotEqual
+            | Op::LessThan
+            | Op::GreaterThan
+            | Op::LessEqual
+            | Op::GreaterEqual => unreachable!("comparisons of integers are applied above"),
+            Op::Not => unreachable!("`!` is not a binary operator"),
+        };
+        value
+            .map(Value::Int)
+            .ok_or_else(|| self.error(expr, "overflow"))
+    }
+
+    fn apply_unary(&self, expr: validated::Expr, op: Op, rhs: Value) -> Result<Value, Unwind> {
+        match (op, &rhs) {
+            (Op::Minus, &Value::Int(rhs)) => match rhs.checked_neg() {
+                Some(value) => Ok(Value::Int(value)),
+                None => Err(self.error(expr, "overflow")),
+            },
+            (Op::Not, &Value::Bool(rhs)) => Ok(Value::Bool(!rhs)),
+            _ => Err(self.error(
+                expr,
+                format!("cannot apply operator {} to {}", op, rhs.kind_str()),
+            )),
+        }
+    }
+
+    /// The text of `value` in a string like `"{value}"`.
+    fn stringify(&self, value: Value) -> String {
+        match value {
+            Value::Unit => "()".to_string(),
+            Value::Bool(value) => value.to_string(),
+            Value::Int(value) => value.to_string(),
+            Value::String(value) => value,
+            Value::Function(function) => function.name(self.db).as_str(self.db).to_string(),
+        }
+    }
+
+    fn span(&self, expr: validated::Expr) -> FileSpan {
+        self.span_of(self.origins[expr].syntax_expr)
+    }
+
+    fn span_of(&self, syntax_expr: syntax::Expr) -> FileSpan {
+        self.function.spans(self.db)[syntax_expr].in_file(self.function.filename(self.db))
+    }
+
+    fn error(&self, expr: validated::Expr, message: impl ToString) -> Unwind {
+        error!(self.span(expr), "{}", message.to_string())
+            .finish()
+            .into()
+    }
+
+    fn unsupported(&self, expr: validated::Expr, what: &str) -> Unwind {
+        unsupported(self.span(expr), what)
+    }
+}
+
+/// The error for code that the interpreter cannot run yet.
+fn unsupported(span: FileSpan, what: &str) -> Unwind {
+    error!(span, "{} are not supported by the interpreter", what)
+        .finish()
+        .into()
+}
+
+/// Applies `op` to `lhs` and `rhs` if it is a comparison.
+fn compare<T: Ord>(op: Op, lhs: T, rhs: T) -> Option<bool> {
+    Some(match op {
+        Op::EqualEqual => lhs == rhs,
+        Op::NotEqual => lhs != rhs,
+        Op::LessThan => lhs < rhs,
+        Op::GreaterThan => lhs > rhs,
+        Op::LessEqual => lhs <= rhs,
+        Op::GreaterEqual => lhs >= rhs,
+        _ => return None,
+    })
+}
@@ -0,0 +1,20 @@
+//! Executes validated trees directly, without brewing them into BIR.
+//!
+//! This is a simple, synchronous interpreter: values are copied rather than
+//! tracked with permissions, and `await`, classes, closures, and intrinsics
+//! are reported as runtime errors. Programs that need those run on
+//! `dada-execute`.
+
+#![feature(trait_upcasting)]
+#![feature(let_else)]
+#![allow(incomplete_features)]
+
+pub trait Db: dada_ir::Db + dada_parse::Db + dada_validate::Db {}
+
+impl<T> Db for T where T: dada_ir::Db + dada_parse::Db + dada_validate::Db {}
+
+mod interpret;
+mod value;
+
+pub use interpret::interpret_function;
+pub use value::Value;
@@ -0,0 +1,50 @@
+use dada_ir::function::Function;
+
+/// A value computed by the interpreter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    /// `()`, the value of expressions like assignments and empty blocks.
+    Unit,
+
+    Bool(bool),
+
+    /// An integer of any kind (`22`, `22i`, or `22u`). Arithmetic whose
+    /// result does not fit in an `i64` is a runtime error.
+    Int(i64),
+
+    String(String),
+
+    /// A function named in the code, which can be called.
+    Function(Function),
+}
+
+impl Value {
+    /// Describes the kind of value, for error messages.
+    pub fn kind_str(&self) -> &'static str {
+        match self {
+            Value::Unit => "unit",
+            Value::Bool(_) => "a boolean",
+            Value::Int(_) => "an integer",
+            Value::String(_) => "a string",
+            Value::Function(_) => "a function",
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
@@ -0,0 +1,83 @@
+//! Runs small programs with the interpreter.
+
+use dada_interpret::Value;
+use dada_ir::{diagnostic::Diagnostic, filename::Filename, item::Item};
+
+/// Interprets the function `main` in `source`.
+fn interpret_main(source: &str) -> Result<Value, Diagnostic> {
+    let mut db = dada_db::Db::default();
+    let filename = Filename::from(&db, "interpret.dada");
+    db.update_file(filename, source.to_string());
+    let main = db
+        .items(filename)
+        .iter()
+        .find_map(|item| match item {
+            Item::Function(function) if function.name(&db).as_str(&db) == "main" => Some(*function),
+            _ => None,
+        })
+        .expect("no function named main");
+    dada_interpret::interpret_function(&db, main)
+}
+
+#[test]
+fn factorial() {
+    let source = r#"fn factorial(n) -> {
+    if n == 0 { 1 } else { n * factorial(n - 1) }
+}
+
+fn main() -> {
+    factorial(10)
+}
+"#;
+    assert_eq!(interpret_main(source), Ok(Value::Int(3628800)));
+}
+
+#[test]
+fn loop_sum() {
+    let source = r#"fn sum_while(n) -> {
+    sum = 0
+    i = 1
+    while i <= n {
+        sum += i
+        i += 1
+    }
+    sum
+}
+
+fn sum_loop(n) -> {
+    sum = 0
+    i = 1
+    loop {
+        if i > n { break }
+        sum += i
+        i += 1
+    }
+    sum
+}
+
+fn main() -> {
+    "{sum_while(10)} {sum_loop(100)}"
+}
+"#;
+    assert_eq!(
+        interpret_main(source),
+        Ok(Value::String("55 5050".to_string()))
+    );
+}
+
+#[test]
+fn await_is_rejected() {
+    let source = r#"async fn helper() -> {
+    1
+}
+
+async fn main() -> {
+    helper().await
+}
+"#;
+    let diagnostic = interpret_main(source).unwrap_err();
+    assert_eq!(
+        diagnostic.message,
+        "`await` is not supported by the interpreter"
+    );
+}
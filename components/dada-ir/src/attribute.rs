@@ -0,0 +1,22 @@
+use crate::{
+    span::FileSpan,
+    word::{SpannedWord, Word},
+};
+
+/// An attribute on an item, like `@test` or `@export(name)`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Attribute {
+    /// The name of the attribute, without the `@`.
+    pub name: SpannedWord,
+
+    /// The arguments in parentheses, if any.
+    pub args: Vec<Word>,
+
+    /// The span of the entire attribute, from the `@` to the closing `)`.
+    pub span: FileSpan,
+}
+
+impl Attribute {
+    /// The names of the attributes that the compiler understands.
+    pub const KNOWN: &'static [&'static str] = &["export", "test"];
+}
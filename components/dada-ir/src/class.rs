@@ -1,10 +1,22 @@
-use crate::{span::FileSpan, token_tree::TokenTree, word::SpannedWord};
+use crate::{span::FileSpan, token_tree::TokenTree, visibility::Visibility, word::SpannedWord};
+
+/// The name of the implicit first parameter of a method, through which it
+/// accesses the fields of its class.
+pub const SELF_PARAMETER: &str = "self";
 
 salsa::entity2! {
     entity Class in crate::Jar {
         #[id] name: SpannedWord,
+
+        /// `Pub` if the class was declared with `pub class`.
+        visibility: Visibility,
+
         field_tokens: TokenTree,
 
+        /// The tokens between the `{}` that follow the fields, which declare
+        /// the methods of the class. `None` if the class has no body.
+        body_tokens: Option<TokenTree>,
+
         /// Overall span of the class (including any body)
         span: FileSpan,
     }
@@ -62,6 +62,17 @@ impl Bir {
         let syntax_tree = self.syntax_tree(db);
         syntax_tree.spans(db)[syntax_expr].in_file(filename)
     }
+
+    /// Like [`Self::span_of`], but narrowed to the operator if `syntax_expr`
+    /// is an `Op` or `OpEq` expression (e.g., just the `+` in `a + b`).
+    pub fn op_span_of(self, db: &dyn crate::Db, syntax_expr: syntax::Expr) -> FileSpan {
+        let filename = self.filename(db);
+        let spans = self.syntax_tree(db).spans(db);
+        spans
+            .op_span(syntax_expr)
+            .unwrap_or(spans[syntax_expr])
+            .in_file(filename)
+    }
 }
 
 /// Stores the ast for a function.
@@ -438,6 +449,9 @@ pub enum ExprData {
     /// `2.2`
     FloatLiteral(eq_float::F64),
 
+    /// `'a'`
+    CharLiteral(char),
+
     /// `"foo"` with no format strings
     StringLiteral(Word),
 
@@ -461,9 +475,19 @@ pub enum ExprData {
     /// `()`
     Unit,
 
-    /// `(a, b, ...)` (i.e., at least 2)
+    /// `(a,)` or `(a, b, ...)` (i.e., at least 1)
     Tuple(Vec<Place>),
 
+    /// `[a, b, ...]`
+    List(Vec<Place>),
+
+    /// `a..b`, `a..=b`, `a..`, `..b`, or `..` (the flag is true for `..=`)
+    Range(Option<Place>, Option<Place>, bool),
+
+    /// `|a, b| expr`: the parameters, the captured places, and the
+    /// basic block where the body starts when the closure is called.
+    Closure(Vec<LocalVariable>, Vec<Place>, BasicBlock),
+
     /// Concatenates a bunch of strings together from a format literal like
     /// `foo{bar}baz`
     Concatenate(Vec<Place>),
@@ -487,6 +511,7 @@ impl DebugWithDb<InIrDb<'_, Bir>> for ExprData {
             ExprData::SignedIntegerLiteral(w) => write!(f, "{}", w),
             ExprData::StringLiteral(w) => write!(f, "{:?}", w.as_str(db.db())),
             ExprData::FloatLiteral(w) => write!(f, "{}", w),
+            ExprData::CharLiteral(c) => write!(f, "{:?}", c),
             ExprData::Reserve(p) => write!(f, "{:?}.reserve", p.debug(db)),
             ExprData::Share(p) => write!(f, "{:?}.share", p.debug(db)),
             ExprData::Lease(p) => write!(f, "{:?}.lease", p.debug(db)),
@@ -494,6 +519,26 @@ impl DebugWithDb<InIrDb<'_, Bir>> for ExprData {
             ExprData::Give(p) => write!(f, "{:?}.give", p.debug(db)),
             ExprData::Unit => write!(f, "()"),
             ExprData::Tuple(vars) => write_parenthesized_places(f, vars, db),
+            ExprData::List(vars) => {
+                write!(f, "List")?;
+                write_parenthesized_places(f, vars, db)
+            }
+            ExprData::Range(start, end, inclusive) => {
+                if let Some(start) = start {
+                    write!(f, "{:?}", start.debug(db))?;
+                }
+                write!(f, "{}", if *inclusive { "..=" } else { ".." })?;
+                if let Some(end) = end {
+                    write!(f, "{:?}", end.debug(db))?;
+                }
+                Ok(())
+            }
+            ExprData::Closure(parameters, captures, start_basic_block) => f
+                .debug_tuple("Closure")
+                .field(&parameters.debug(db))
+                .field(&captures.debug(db))
+                .field(start_basic_block)
+                .finish(),
             ExprData::Concatenate(vars) => {
                 write!(f, "Concatenate")?;
                 write_parenthesized_places(f, vars, db)
@@ -540,6 +585,7 @@ pub enum PlaceData {
     Class(Class),
     Intrinsic(Intrinsic),
     Dot(Place, Word),
+    Index(Place, Place),
 }
 
 impl DebugWithDb<InIrDb<'_, Bir>> for PlaceData {
@@ -550,6 +596,7 @@ impl DebugWithDb<InIrDb<'_, Bir>> for PlaceData {
             PlaceData::Class(class) => write!(f, "{:?}", class.debug(db.db())),
             PlaceData::Intrinsic(intrinsic) => write!(f, "{:?}", intrinsic),
             PlaceData::Dot(p, id) => write!(f, "{:?}.{}", p.debug(db), id.as_str(db.db())),
+            PlaceData::Index(p, i) => write!(f, "{:?}[{:?}]", p.debug(db), i.debug(db)),
         }
     }
 }
@@ -1,13 +1,13 @@
 use crate::{
-    code::syntax::op::Op,
-    in_ir_db::InIrDb,
-    in_ir_db::InIrDbExt,
+    code::syntax::{integer::IntegerLiteral, op::Op},
+    function::Function,
     span::Span,
-    storage::{Atomic, SpannedSpecifier},
+    storage::{Atomic, Mutable, SpannedSpecifier, Weak},
     word::{SpannedOptionalWord, Word},
 };
 use dada_id::{id, prelude::*, tables};
 use salsa::DebugWithDb;
+use std::collections::BTreeMap;
 
 salsa::entity2! {
     entity Tree in crate::Jar {
@@ -18,15 +18,52 @@ salsa::entity2! {
 
 impl DebugWithDb<dyn crate::Db> for Tree {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &dyn crate::Db) -> std::fmt::Result {
-        f.debug_struct("syntax::Tree")
-            .field("data", &self.data(db).debug(&self.in_ir_db(db)))
-            .finish()
+        let data = self.data(db);
+        let cx = &DebugCx::new(db, &data.tables, None);
+        DebugWithDb::fmt(data, f, cx)
+    }
+}
+
+impl Tree {
+    /// Like `self.debug(db)`, but also prints the span of each expression.
+    pub fn debug_with_spans(self, db: &dyn crate::Db) -> impl std::fmt::Debug + '_ {
+        DebugTreeWithSpans { tree: self, db }
     }
 }
 
-impl InIrDb<'_, Tree> {
-    fn tables(&self) -> &Tables {
-        &self.data(self.db()).tables
+struct DebugTreeWithSpans<'me> {
+    tree: Tree,
+    db: &'me dyn crate::Db,
+}
+
+impl std::fmt::Debug for DebugTreeWithSpans<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = self.tree.data(self.db);
+        let cx = &DebugCx::new(self.db, &data.tables, Some(self.tree.spans(self.db)));
+        DebugWithDb::fmt(data, f, cx)
+    }
+}
+
+/// Context for debug-printing the contents of a syntax tree.
+pub struct DebugCx<'me> {
+    db: &'me dyn crate::Db,
+    tables: &'me Tables,
+
+    /// If present, each expression is printed along with its span.
+    spans: Option<&'me Spans>,
+}
+
+impl<'me> DebugCx<'me> {
+    pub fn new(db: &'me dyn crate::Db, tables: &'me Tables, spans: Option<&'me Spans>) -> Self {
+        Self { db, tables, spans }
+    }
+
+    fn db(&self) -> &'me dyn crate::Db {
+        self.db
+    }
+
+    fn tables(&self) -> &'me Tables {
+        self.tables
     }
 }
 
@@ -43,10 +80,11 @@ pub struct TreeData {
     pub root_expr: Expr,
 }
 
-impl DebugWithDb<InIrDb<'_, Tree>> for TreeData {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &InIrDb<'_, Tree>) -> std::fmt::Result {
+impl DebugWithDb<DebugCx<'_>> for TreeData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
         f.debug_struct("syntax::Tree")
-            .field("root_expr", &self.root_expr.debug(db)) // FIXME
+            .field("parameter_decls", &self.parameter_decls.debug(db))
+            .field("root_expr", &self.root_expr.debug(db))
             .finish()
     }
 }
@@ -59,6 +97,8 @@ tables! {
         exprs: alloc Expr => ExprData,
         named_exprs: alloc NamedExpr => NamedExprData,
         local_variable_decls: alloc LocalVariableDecl => LocalVariableDeclData,
+        match_arms: alloc MatchArm => MatchArmData,
+        patterns: alloc Pattern => PatternData,
     }
 }
 
@@ -73,14 +113,126 @@ origin_table! {
         expr_spans: Expr => Span,
         named_expr_spans: NamedExpr => Span,
         local_variable_decl_spans: LocalVariableDecl => LocalVariableDeclSpan,
+        match_arm_spans: MatchArm => Span,
+        pattern_spans: Pattern => Span,
+        ..
+        /// The span of the operator token in each `Op`, `OpEq`, and `Assign` expression.
+        op_spans: BTreeMap<Expr, Span>,
+
+        /// The span of the `else` keyword in each `VarElse` expression.
+        else_spans: BTreeMap<Expr, Span>,
+
+        /// The comments on the lines before each statement of a block.
+        leading_comments: BTreeMap<Expr, Vec<Comment>>,
+
+        /// The comment at the end of the line of a statement, if any.
+        trailing_comments: BTreeMap<Expr, Comment>,
+
+        /// The comments after the last statement of each block (`Seq`).
+        dangling_comments: BTreeMap<Expr, Vec<Comment>>,
+    }
+}
+
+/// A `# ...` comment within a block, recorded so that formatting can keep it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Comment {
+    pub span: Span,
+
+    /// The text of the comment, including the `#`.
+    pub text: Word,
+}
+
+impl Spans {
+    /// The span of just the operator in `expr`, like the `+` in `a + b`.
+    /// Returns `None` if `expr` is not an `Op`, `OpEq`, or `Assign` expression.
+    pub fn op_span(&self, expr: Expr) -> Option<Span> {
+        self.op_spans.get(&expr).copied()
+    }
+
+    pub fn push_op_span(&mut self, expr: Expr, span: Span) {
+        self.op_spans.insert(expr, span);
+    }
+
+    /// The span of the `else` keyword in `expr`, like the one in
+    /// `x = value else { return }`. Returns `None` if `expr` is not a
+    /// `VarElse` expression.
+    pub fn else_span(&self, expr: Expr) -> Option<Span> {
+        self.else_spans.get(&expr).copied()
+    }
+
+    pub fn push_else_span(&mut self, expr: Expr, span: Span) {
+        self.else_spans.insert(expr, span);
+    }
+
+    /// The comments on the lines before the statement `expr`.
+    pub fn leading_comments(&self, expr: Expr) -> &[Comment] {
+        self.leading_comments.get(&expr).map_or(&[], |c| &c[..])
+    }
+
+    /// The comment at the end of the line of the statement `expr`.
+    pub fn trailing_comment(&self, expr: Expr) -> Option<Comment> {
+        self.trailing_comments.get(&expr).copied()
+    }
+
+    /// The comments after the last statement of the block `expr`.
+    pub fn dangling_comments(&self, expr: Expr) -> &[Comment] {
+        self.dangling_comments.get(&expr).map_or(&[], |c| &c[..])
+    }
+
+    pub fn push_leading_comments(&mut self, expr: Expr, comments: Vec<Comment>) {
+        if !comments.is_empty() {
+            self.leading_comments.insert(expr, comments);
+        }
+    }
+
+    pub fn push_trailing_comment(&mut self, expr: Expr, comment: Comment) {
+        self.trailing_comments.insert(expr, comment);
+    }
+
+    pub fn push_dangling_comments(&mut self, expr: Expr, comments: Vec<Comment>) {
+        if !comments.is_empty() {
+            self.dangling_comments.insert(expr, comments);
+        }
+    }
+
+    /// Returns a copy of these spans with each one moved `amount` bytes
+    /// later. See [`Span::shifted`].
+    pub fn shifted(&self, amount: u32) -> Spans {
+        let mut spans = self.clone();
+        let shift = |span: &mut Span| *span = span.shifted(amount);
+        spans.expr_spans.iter_mut().for_each(shift);
+        spans.named_expr_spans.iter_mut().for_each(shift);
+        spans.match_arm_spans.iter_mut().for_each(shift);
+        spans.pattern_spans.iter_mut().for_each(shift);
+        spans.op_spans.values_mut().for_each(shift);
+        spans.else_spans.values_mut().for_each(shift);
+        for decl_span in spans.local_variable_decl_spans.iter_mut() {
+            shift(&mut decl_span.atomic_span);
+            shift(&mut decl_span.name_span);
+            decl_span.ty_span.iter_mut().for_each(shift);
+        }
+        let comments = spans
+            .leading_comments
+            .values_mut()
+            .chain(spans.dangling_comments.values_mut())
+            .flatten()
+            .chain(spans.trailing_comments.values_mut());
+        for comment in comments {
+            shift(&mut comment.span);
+        }
+        spans
     }
 }
 
 id!(pub struct Expr);
 
-impl DebugWithDb<InIrDb<'_, Tree>> for Expr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &InIrDb<'_, Tree>) -> std::fmt::Result {
-        f.debug_tuple(&format!("{self:?}"))
+impl DebugWithDb<DebugCx<'_>> for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
+        let name = match db.spans {
+            Some(spans) => format!("{self:?} @ {:?}", spans[*self]),
+            None => format!("{self:?}"),
+        };
+        f.debug_tuple(&name)
             .field(&self.data(db.tables()).debug(db))
             .finish()
     }
@@ -93,31 +245,42 @@ pub enum ExprData {
     /// true, false
     BooleanLiteral(bool),
 
-    /// (`22`, suffix: `u`), (`22_222`, suffix: `i`), etc
-    IntegerLiteral(Word, Option<Word>),
+    /// `22`, `22_222i`, `0xFF_u`, etc
+    IntegerLiteral(IntegerLiteral),
+
+    /// `1.5`, `1e10`, `2.5e-3`, etc -- the word is the literal's text,
+    /// including any underscores
+    FloatLiteral(Word),
 
-    /// `integer-part.fractional-part`
-    FloatLiteral(Word, Word),
+    /// `'a'`, `'\n'`, `'\u{1F600}'` -- the word is the text between the quotes,
+    /// with any escape not yet interpreted
+    CharLiteral(Word),
 
-    /// `"foo"` with no format strings
-    ///
-    /// FIXME: We should replace the FormatString token with a Concatenate
-    /// that has parsed expressions.
+    /// A chunk of literal text from a format string, with escapes
+    /// like `\n` or `\{` not yet interpreted.
     StringLiteral(Word),
 
     /// Generated by a format string like `"foo{x}bar"`, which would
-    /// yield `Concatenate(StringLiteral("foo"), x, StringLiteral("bar"))`
+    /// yield `Concatenate(StringLiteral("foo"), x, StringLiteral("bar"))`.
+    /// The expressions in `{...}` are parsed like any other expression, so
+    /// they can contain blocks or even other format strings.
     Concatenate(Vec<Expr>),
 
     /// `expr.ident`
     Dot(Expr, Word),
 
+    /// `expr[index]`
+    Index(Expr, Expr),
+
     /// `expr.await`
     Await(Expr),
 
     /// `expr(id: expr, ...)`
     Call(Expr, Vec<NamedExpr>),
 
+    /// `Class { id: expr, ... }`
+    ClassLiteral(Word, Vec<NamedExpr>),
+
     /// `expr.share`
     Share(Expr),
 
@@ -133,26 +296,50 @@ pub enum ExprData {
     /// `[shared|var|atomic] x = expr`
     Var(LocalVariableDecl, Expr),
 
+    /// `[shared|var|atomic] x = expr else { block }`, where the block must
+    /// diverge. The block runs if the value does not match the binding;
+    /// until there are refutable patterns, that is when the value is an error.
+    VarElse(LocalVariableDecl, Expr, Expr),
+
     /// `(expr)`
     Parenthesized(Expr),
 
-    /// `()` or `(a, b, ...)` (i.e., expr seq cannot have length 1)
+    /// `()`, `(a,)`, or `(a, b, ...)` (a single element requires a trailing comma)
     Tuple(Vec<Expr>),
 
+    /// `[]` or `[a, b, ...]`
+    List(Vec<Expr>),
+
+    /// `a..b`, `a..`, `..b`, or `..`; the flag is true for inclusive ranges like `a..=b`
+    Range(Option<Expr>, Option<Expr>, bool),
+
+    /// `|a, b| expr`
+    Closure(Vec<LocalVariableDecl>, Expr),
+
     /// `if condition { block } [else { block }]`
     If(Expr, Expr, Option<Expr>),
 
     /// `atomic { block }`
     Atomic(Expr),
 
-    /// `loop { block }`
-    Loop(Expr),
+    /// `['label:] loop { block }`
+    Loop(Option<Word>, Expr),
+
+    /// `['label:] while condition { block }`
+    While(Option<Word>, Expr, Expr),
 
-    /// `while condition { block }`
-    While(Expr, Expr),
+    /// `['label:] for x in iterable { block }`
+    For(Option<Word>, LocalVariableDecl, Expr, Expr),
+
+    /// `match value { pattern => expr, ... }`
+    Match(Expr, Vec<MatchArm>),
 
     // `{ ... }`, but only as part of a control-flow construct
-    Seq(Vec<Expr>),
+    //
+    // The statements, whose values are discarded, and the tail expression
+    // whose value is the value of the block. There is no tail if the block
+    // is empty or if its last expression is followed by a `;`.
+    Seq(Vec<Expr>, Option<Expr>),
 
     /// `a + b`
     Op(Expr, Op, Expr),
@@ -168,23 +355,82 @@ pub enum ExprData {
     /// return
     Return(Option<Expr>),
 
+    /// `yield [expr]`
+    Yield(Option<Expr>),
+
+    /// `break ['label] [expr]`
+    Break(Option<Word>, Option<Expr>),
+
+    /// `continue ['label]`
+    Continue(Option<Word>),
+
+    /// `fn name(...) { ... }` within a block. The function is only in scope
+    /// within that block, and its body is parsed on demand like any other.
+    Fn(Function),
+
     /// parse or other error
     Error,
 }
 
-impl DebugWithDb<InIrDb<'_, Tree>> for ExprData {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &InIrDb<'_, Tree>) -> std::fmt::Result {
+impl ExprData {
+    /// The name of this variant, like `"Id"` or `"Op"`.
+    pub fn kind_str(&self) -> &'static str {
+        match self {
+            ExprData::Id(_) => "Id",
+            ExprData::BooleanLiteral(_) => "BooleanLiteral",
+            ExprData::IntegerLiteral(..) => "IntegerLiteral",
+            ExprData::FloatLiteral(_) => "FloatLiteral",
+            ExprData::CharLiteral(_) => "CharLiteral",
+            ExprData::StringLiteral(_) => "StringLiteral",
+            ExprData::Concatenate(_) => "Concatenate",
+            ExprData::Dot(..) => "Dot",
+            ExprData::Index(..) => "Index",
+            ExprData::Await(_) => "Await",
+            ExprData::Call(..) => "Call",
+            ExprData::ClassLiteral(..) => "ClassLiteral",
+            ExprData::Share(_) => "Share",
+            ExprData::Lease(_) => "Lease",
+            ExprData::Shlease(_) => "Shlease",
+            ExprData::Give(_) => "Give",
+            ExprData::Var(..) => "Var",
+            ExprData::VarElse(..) => "VarElse",
+            ExprData::Parenthesized(_) => "Parenthesized",
+            ExprData::Tuple(_) => "Tuple",
+            ExprData::List(_) => "List",
+            ExprData::Range(..) => "Range",
+            ExprData::Closure(..) => "Closure",
+            ExprData::If(..) => "If",
+            ExprData::Atomic(_) => "Atomic",
+            ExprData::Loop(..) => "Loop",
+            ExprData::While(..) => "While",
+            ExprData::For(..) => "For",
+            ExprData::Match(..) => "Match",
+            ExprData::Seq(..) => "Seq",
+            ExprData::Op(..) => "Op",
+            ExprData::OpEq(..) => "OpEq",
+            ExprData::Unary(..) => "Unary",
+            ExprData::Assign(..) => "Assign",
+            ExprData::Return(_) => "Return",
+            ExprData::Yield(_) => "Yield",
+            ExprData::Break(..) => "Break",
+            ExprData::Continue(_) => "Continue",
+            ExprData::Fn(_) => "Fn",
+            ExprData::Error => "Error",
+        }
+    }
+}
+
+impl DebugWithDb<DebugCx<'_>> for ExprData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
         match self {
             ExprData::Id(w) => f.debug_tuple("Id").field(&w.debug(db.db())).finish(),
             ExprData::BooleanLiteral(v) => f.debug_tuple("Boolean").field(&v).finish(),
-            ExprData::IntegerLiteral(v, _) => {
-                f.debug_tuple("Integer").field(&v.debug(db.db())).finish()
-            }
-            ExprData::FloatLiteral(v, d) => f
-                .debug_tuple("Float")
-                .field(&v.debug(db.db()))
-                .field(&d.debug(db.db()))
+            ExprData::IntegerLiteral(v) => f
+                .debug_tuple("Integer")
+                .field(&v.text.debug(db.db()))
                 .finish(),
+            ExprData::FloatLiteral(v) => f.debug_tuple("Float").field(&v.debug(db.db())).finish(),
+            ExprData::CharLiteral(v) => f.debug_tuple("Char").field(&v.debug(db.db())).finish(),
             ExprData::StringLiteral(v) => f.debug_tuple("String").field(&v.debug(db.db())).finish(),
             ExprData::Concatenate(exprs) => f
                 .debug_tuple("Concatenate")
@@ -195,12 +441,22 @@ impl DebugWithDb<InIrDb<'_, Tree>> for ExprData {
                 .field(&lhs.debug(db))
                 .field(&rhs.debug(db.db()))
                 .finish(),
+            ExprData::Index(base, index) => f
+                .debug_tuple("Index")
+                .field(&base.debug(db))
+                .field(&index.debug(db))
+                .finish(),
             ExprData::Await(e) => f.debug_tuple("Await").field(&e.debug(db)).finish(),
             ExprData::Call(func, args) => f
                 .debug_tuple("Call")
                 .field(&func.debug(db))
                 .field(&args.debug(db))
                 .finish(),
+            ExprData::ClassLiteral(name, fields) => f
+                .debug_tuple("ClassLiteral")
+                .field(&name.debug(db.db()))
+                .field(&fields.debug(db))
+                .finish(),
             ExprData::Share(e) => f.debug_tuple("Share").field(&e.debug(db)).finish(),
             ExprData::Lease(e) => f.debug_tuple("Lease").field(&e.debug(db)).finish(),
             ExprData::Shlease(e) => f.debug_tuple("Shlease").field(&e.debug(db)).finish(),
@@ -210,10 +466,28 @@ impl DebugWithDb<InIrDb<'_, Tree>> for ExprData {
                 .field(&v.debug(db))
                 .field(&e.debug(db))
                 .finish(),
+            ExprData::VarElse(v, e, else_e) => f
+                .debug_tuple("VarElse")
+                .field(&v.debug(db))
+                .field(&e.debug(db))
+                .field(&else_e.debug(db))
+                .finish(),
             ExprData::Parenthesized(e) => {
                 f.debug_tuple("Parenthesized").field(&e.debug(db)).finish()
             }
             ExprData::Tuple(e) => f.debug_tuple("Tuple").field(&e.debug(db)).finish(),
+            ExprData::List(e) => f.debug_tuple("List").field(&e.debug(db)).finish(),
+            ExprData::Range(s, e, i) => f
+                .debug_tuple("Range")
+                .field(&s.debug(db))
+                .field(&e.debug(db))
+                .field(i)
+                .finish(),
+            ExprData::Closure(p, e) => f
+                .debug_tuple("Closure")
+                .field(&p.debug(db))
+                .field(&e.debug(db))
+                .finish(),
             ExprData::If(c, t, e) => f
                 .debug_tuple("If")
                 .field(&c.debug(db))
@@ -221,13 +495,34 @@ impl DebugWithDb<InIrDb<'_, Tree>> for ExprData {
                 .field(&e.debug(db))
                 .finish(),
             ExprData::Atomic(e) => f.debug_tuple("Atomic").field(&e.debug(db)).finish(),
-            ExprData::Loop(e) => f.debug_tuple("Loop").field(&e.debug(db)).finish(),
-            ExprData::While(c, e) => f
+            ExprData::Loop(l, e) => f
+                .debug_tuple("Loop")
+                .field(&l.debug(db.db()))
+                .field(&e.debug(db))
+                .finish(),
+            ExprData::While(l, c, e) => f
                 .debug_tuple("While")
+                .field(&l.debug(db.db()))
                 .field(&c.debug(db))
                 .field(&e.debug(db))
                 .finish(),
-            ExprData::Seq(e) => f.debug_tuple("Seq").field(&e.debug(db)).finish(),
+            ExprData::For(l, v, i, e) => f
+                .debug_tuple("For")
+                .field(&l.debug(db.db()))
+                .field(&v.debug(db))
+                .field(&i.debug(db))
+                .field(&e.debug(db))
+                .finish(),
+            ExprData::Match(v, arms) => f
+                .debug_tuple("Match")
+                .field(&v.debug(db))
+                .field(&arms.debug(db))
+                .finish(),
+            ExprData::Seq(s, t) => f
+                .debug_tuple("Seq")
+                .field(&s.debug(db))
+                .field(&t.debug(db))
+                .finish(),
             ExprData::Op(l, o, r) => f
                 .debug_tuple("Op")
                 .field(&l.debug(db))
@@ -246,7 +541,15 @@ impl DebugWithDb<InIrDb<'_, Tree>> for ExprData {
                 .field(&r.debug(db))
                 .finish(),
             ExprData::Error => f.debug_tuple("Error").finish(),
+            ExprData::Fn(function) => f.debug_tuple("Fn").field(&function.debug(db.db())).finish(),
             ExprData::Return(e) => f.debug_tuple("Return").field(&e.debug(db)).finish(),
+            ExprData::Yield(e) => f.debug_tuple("Yield").field(&e.debug(db)).finish(),
+            ExprData::Break(l, e) => f
+                .debug_tuple("Break")
+                .field(&l.debug(db.db()))
+                .field(&e.debug(db))
+                .finish(),
+            ExprData::Continue(l) => f.debug_tuple("Continue").field(&l.debug(db.db())).finish(),
             ExprData::Unary(o, e) => f
                 .debug_tuple("Unary")
                 .field(&o)
@@ -258,25 +561,29 @@ impl DebugWithDb<InIrDb<'_, Tree>> for ExprData {
 
 id!(pub struct LocalVariableDecl);
 
-impl DebugWithDb<InIrDb<'_, Tree>> for LocalVariableDecl {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &InIrDb<'_, Tree>) -> std::fmt::Result {
+impl DebugWithDb<DebugCx<'_>> for LocalVariableDecl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
         DebugWithDb::fmt(self.data(db.tables()), f, db)
     }
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Debug)]
 pub struct LocalVariableDeclData {
+    pub mutable: Mutable,
     pub specifier: SpannedSpecifier,
     pub atomic: Atomic,
+    pub weak: Weak,
     pub name: Word,
     pub ty: Option<crate::ty::Ty>,
 }
 
-impl DebugWithDb<InIrDb<'_, Tree>> for LocalVariableDeclData {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &InIrDb<'_, Tree>) -> std::fmt::Result {
+impl DebugWithDb<DebugCx<'_>> for LocalVariableDeclData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
         f.debug_struct("LocalVariableDeclData")
+            .field("mutable", &self.mutable)
             .field("specifier", &self.specifier.specifier(db.db()))
             .field("atomic", &self.atomic)
+            .field("weak", &self.weak)
             .field("name", &self.name.debug(db.db()))
             .field("ty", &self.ty.debug(db.db()))
             .finish()
@@ -287,12 +594,15 @@ impl DebugWithDb<InIrDb<'_, Tree>> for LocalVariableDeclData {
 pub struct LocalVariableDeclSpan {
     pub atomic_span: Span,
     pub name_span: Span,
+
+    /// The span of the declared type, if any.
+    pub ty_span: Option<Span>,
 }
 
 id!(pub struct NamedExpr);
 
-impl DebugWithDb<InIrDb<'_, Tree>> for NamedExpr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &InIrDb<'_, Tree>) -> std::fmt::Result {
+impl DebugWithDb<DebugCx<'_>> for NamedExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
         DebugWithDb::fmt(self.data(db.tables()), f, db)
     }
 }
@@ -303,12 +613,69 @@ pub struct NamedExprData {
     pub expr: Expr,
 }
 
-impl DebugWithDb<InIrDb<'_, Tree>> for NamedExprData {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &InIrDb<'_, Tree>) -> std::fmt::Result {
+impl DebugWithDb<DebugCx<'_>> for NamedExprData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
         f.debug_tuple(&format!("{:?}", self.name.word(db.db()).debug(db.db())))
             .field(&self.expr.debug(db))
             .finish()
     }
 }
 
+id!(pub struct MatchArm);
+
+impl DebugWithDb<DebugCx<'_>> for MatchArm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
+        DebugWithDb::fmt(self.data(db.tables()), f, db)
+    }
+}
+
+/// `pattern => body`
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Debug)]
+pub struct MatchArmData {
+    pub pattern: Pattern,
+    pub body: Expr,
+}
+
+impl DebugWithDb<DebugCx<'_>> for MatchArmData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
+        f.debug_tuple("MatchArm")
+            .field(&self.pattern.debug(db))
+            .field(&self.body.debug(db))
+            .finish()
+    }
+}
+
+id!(pub struct Pattern);
+
+impl DebugWithDb<DebugCx<'_>> for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
+        DebugWithDb::fmt(self.data(db.tables()), f, db)
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Debug)]
+pub enum PatternData {
+    /// `x`: matches anything, binding it to a fresh local variable
+    Binding(LocalVariableDecl),
+
+    /// `22`, `"foo"`, `true`: matches values equal to the literal
+    Literal(Expr),
+}
+
+impl DebugWithDb<DebugCx<'_>> for PatternData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &DebugCx<'_>) -> std::fmt::Result {
+        match self {
+            PatternData::Binding(v) => f.debug_tuple("Binding").field(&v.debug(db)).finish(),
+            PatternData::Literal(e) => f.debug_tuple("Literal").field(&e.debug(db)).finish(),
+        }
+    }
+}
+
+pub mod diff;
+pub mod integer;
 pub mod op;
+#[cfg(feature = "json")]
+mod to_json;
+mod structural_eq;
+pub mod to_source;
+pub mod visit;
@@ -0,0 +1,104 @@
+//! Finds the expressions that differ between two versions of a syntax tree.
+//! This is meant for debugging incremental reuse, e.g. to see why a small
+//! edit caused more to be recomputed than expected.
+
+use crate::code::syntax::structural_eq::Comparer;
+use crate::code::syntax::visit::{walk_expr, Visitor};
+use crate::code::syntax::{Expr, Spans, Tables, Tree};
+use crate::span::Span;
+
+/// An expression that differs between the old and new versions of a tree.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TreeChange {
+    pub old_expr: Expr,
+    pub new_expr: Expr,
+
+    /// The span of `new_expr` in the new tree.
+    pub span: Span,
+}
+
+/// Compares `old` and `new` by structure (like [`Tree::structurally_eq`])
+/// and returns the outermost expressions that differ, in source order.
+///
+/// Where an expression differs only in its children, the children are
+/// compared instead, so that changing one literal reports just that literal.
+/// If the parameters differ, the root expression is reported as changed.
+pub fn diff_trees(db: &dyn crate::Db, old: Tree, new: Tree) -> Vec<TreeChange> {
+    let (old_data, new_data) = (old.data(db), new.data(db));
+    let mut differ = Differ {
+        comparer: Comparer {
+            db,
+            tables_a: &old_data.tables,
+            tables_b: &new_data.tables,
+            shallow: true,
+            modulo_formatting: false,
+        },
+        new_spans: new.spans(db),
+        changes: vec![],
+    };
+    if differ
+        .comparer
+        .decls(&old_data.parameter_decls, &new_data.parameter_decls)
+    {
+        differ.expr(old_data.root_expr, new_data.root_expr);
+    } else {
+        differ.change(old_data.root_expr, new_data.root_expr);
+    }
+    differ.changes
+}
+
+struct Differ<'me> {
+    /// Compares nodes shallowly; the differ recurses into the children.
+    comparer: Comparer<'me>,
+    new_spans: &'me Spans,
+    changes: Vec<TreeChange>,
+}
+
+impl Differ<'_> {
+    fn expr(&mut self, old_expr: Expr, new_expr: Expr) {
+        if !self.comparer.node(old_expr, new_expr) {
+            self.change(old_expr, new_expr);
+            return;
+        }
+
+        let old_children = children(self.comparer.tables_a, old_expr);
+        let new_children = children(self.comparer.tables_b, new_expr);
+        for (old_child, new_child) in old_children.into_iter().zip(new_children) {
+            self.expr(old_child, new_child);
+        }
+    }
+
+    fn change(&mut self, old_expr: Expr, new_expr: Expr) {
+        self.changes.push(TreeChange {
+            old_expr,
+            new_expr,
+            span: self.new_spans[new_expr],
+        });
+    }
+}
+
+/// The expressions nested directly within `expr`, in source order
+/// (including those within its named arguments and match arms).
+fn children(tables: &Tables, expr: Expr) -> Vec<Expr> {
+    struct Children<'t> {
+        tables: &'t Tables,
+        exprs: Vec<Expr>,
+    }
+
+    impl<'t> Visitor<'t> for Children<'t> {
+        fn tables(&self) -> &'t Tables {
+            self.tables
+        }
+
+        fn visit_expr(&mut self, expr: Expr) {
+            self.exprs.push(expr);
+        }
+    }
+
+    let mut children = Children {
+        tables,
+        exprs: vec![],
+    };
+    walk_expr(&mut children, expr);
+    children.exprs
+}
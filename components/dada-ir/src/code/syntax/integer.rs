@@ -0,0 +1,100 @@
+//! Integer literals, like `22`, `0xFF`, or `1_000_u`.
+
+use crate::word::Word;
+
+/// The base of an integer literal, given by its prefix.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum IntegerBase {
+    /// `0b1010`
+    Binary,
+
+    /// `0o17`
+    Octal,
+
+    /// `22`
+    Decimal,
+
+    /// `0xFF`
+    Hexadecimal,
+}
+
+impl IntegerBase {
+    pub fn radix(self) -> u32 {
+        match self {
+            IntegerBase::Binary => 2,
+            IntegerBase::Octal => 8,
+            IntegerBase::Decimal => 10,
+            IntegerBase::Hexadecimal => 16,
+        }
+    }
+
+    /// The prefix that introduces literals in this base (empty for decimal).
+    pub fn prefix(self) -> &'static str {
+        match self {
+            IntegerBase::Binary => "0b",
+            IntegerBase::Octal => "0o",
+            IntegerBase::Decimal => "",
+            IntegerBase::Hexadecimal => "0x",
+        }
+    }
+
+    /// The name of the base, for use in error messages.
+    pub fn str(self) -> &'static str {
+        match self {
+            IntegerBase::Binary => "binary",
+            IntegerBase::Octal => "octal",
+            IntegerBase::Decimal => "decimal",
+            IntegerBase::Hexadecimal => "hexadecimal",
+        }
+    }
+
+    /// Splits the text of an integer literal into its base and its digits.
+    pub fn split_prefix(text: &str) -> (IntegerBase, &str) {
+        [
+            IntegerBase::Binary,
+            IntegerBase::Octal,
+            IntegerBase::Hexadecimal,
+        ]
+        .into_iter()
+        .find_map(|base| Some((base, text.strip_prefix(base.prefix())?)))
+        .unwrap_or((IntegerBase::Decimal, text))
+    }
+}
+
+/// An integer literal: its text, along with the base and value that the
+/// parser found in it, and its suffix (if any).
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct IntegerLiteral {
+    /// The text of the literal, including any base prefix and underscores
+    /// (but not the suffix).
+    pub text: Word,
+
+    pub base: IntegerBase,
+
+    /// The value of the digits, or `None` if they are not valid digits
+    /// in `base` or do not fit in a `u64`.
+    pub value: Option<u64>,
+
+    /// `u` or `i`, in `22u` or `22_i`
+    pub suffix: Option<Word>,
+}
+
+impl IntegerLiteral {
+    pub fn new(db: &dyn crate::Db, text: Word, suffix: Option<Word>) -> Self {
+        let (base, _) = IntegerBase::split_prefix(text.as_str(db));
+        let mut literal = IntegerLiteral {
+            text,
+            base,
+            value: None,
+            suffix,
+        };
+        literal.value = u64::from_str_radix(&literal.digits(db), base.radix()).ok();
+        literal
+    }
+
+    /// The digits of the literal, without the base prefix or underscores.
+    pub fn digits(self, db: &dyn crate::Db) -> String {
+        let (_, digits) = IntegerBase::split_prefix(self.text.as_str(db));
+        digits.chars().filter(|&c| c != '_').collect()
+    }
+}
@@ -22,7 +22,8 @@ macro_rules! define_operators {
                 Self::ALL.iter().copied()
             }
 
-            pub fn str(self) -> &'static str {
+            /// The spelling of the operator, like `+=` for `PlusEqual`.
+            pub fn as_str(self) -> &'static str {
                 Self::STRS[self as usize]
             }
         }
@@ -31,27 +32,45 @@ macro_rules! define_operators {
 
 impl std::fmt::Display for Op {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.str())
+        write!(f, "{}", self.as_str())
     }
 }
 
 define_operators! {
-    // 2-character ops (must come first!)
+    // 3-character ops (must come first!)
+    DotDotEqual => "..=",
+    ShiftLeftEqual => "<<=",
+    ShiftRightEqual => ">>=",
+
+    // 2-character ops
+    DotDot => "..",
     PlusEqual => "+=",
     MinusEqual => "-=",
     TimesEqual => "*=",
     DividedByEqual => "/=",
+    BitAndEqual => "&=",
+    BitOrEqual => "|=",
+    BitXorEqual => "^=",
     ColonEqual => ":=",
     EqualEqual => "==",
+    NotEqual => "!=",
     GreaterEqual => ">=",
     LessEqual => "<=",
     RightArrow => "->",
+    FatArrow => "=>",
+    AndAnd => "&&",
+    OrOr => "||",
+    ShiftLeft => "<<",
+    ShiftRight => ">>",
 
     // 1-character ops
     Plus => "+",
     Minus => "-",
     Times => "*",
     DividedBy => "/",
+    BitAnd => "&",
+    BitOr => "|",
+    BitXor => "^",
     Colon => ":",
     SemiColon => ";",
     Equal => "=",
@@ -60,6 +79,20 @@ define_operators! {
     LeftAngle => "<",
     RightAngle => ">",
     Dot => ".",
+    Pipe => "|",
+    Not => "!",
+}
+
+/// The error from parsing a string that is not the spelling of any [`Op`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnknownOp;
+
+impl std::str::FromStr for Op {
+    type Err = UnknownOp;
+
+    fn from_str(s: &str) -> Result<Op, UnknownOp> {
+        Op::from_str(s).ok_or(UnknownOp)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -71,32 +104,89 @@ pub struct BinaryOp {
 impl Op {
     /// Operators that can be adjacent to other operators. e.g. we can have `<<`
     /// and parse that as two left angle brackets. Note that we *cannot* parse
-    /// that as two "less than" operators.
-    pub const ACCEPT_ADJACENT: &'static [Op] = &[Op::LeftAngle, Op::RightAngle];
+    /// that as two "less than" operators. Similarly, `..-1` is a range
+    /// operator followed by a negative number, `||` is a closure with
+    /// no parameters, and `!!x` is a double negation.
+    pub const ACCEPT_ADJACENT: &'static [Op] = &[
+        Op::LeftAngle,
+        Op::RightAngle,
+        Op::DotDot,
+        Op::DotDotEqual,
+        Op::Pipe,
+        Op::Not,
+    ];
+
+    /// The operator spelled `s`, the inverse of [`Op::as_str`]. A few operators
+    /// share a spelling (`<` is both `LessThan` and `LeftAngle`, `>` is both
+    /// `GreaterThan` and `RightAngle`, and `|` is both `BitOr` and `Pipe`);
+    /// for those, this returns the one that is declared first.
+    ///
+    /// `Op` also implements [`std::str::FromStr`] in terms of this, for `parse`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Op> {
+        Op::all().find(|op| op.as_str() == s)
+    }
+
+    /// The compound assignment form of this operator, like `+=` for `+`,
+    /// if it has one.
+    pub fn assign_op(self) -> Option<Op> {
+        match self {
+            Op::Plus => Some(Op::PlusEqual),
+            Op::Minus => Some(Op::MinusEqual),
+            Op::Times => Some(Op::TimesEqual),
+            Op::DividedBy => Some(Op::DividedByEqual),
+            Op::BitAnd => Some(Op::BitAndEqual),
+            Op::BitOr => Some(Op::BitOrEqual),
+            Op::BitXor => Some(Op::BitXorEqual),
+            Op::ShiftLeft => Some(Op::ShiftLeftEqual),
+            Op::ShiftRight => Some(Op::ShiftRightEqual),
+            _ => None,
+        }
+    }
+
+    /// For a compound assignment like `+=`, the operator that it applies, like `+`.
+    pub fn binary_op(self) -> Option<Op> {
+        Op::all().find(|op| op.assign_op() == Some(self))
+    }
+
+    /// The name of the method that a class defines to support this operator
+    /// on its instances, like `add` for `+`, if the operator can be overloaded.
+    pub fn method_name(self) -> Option<&'static str> {
+        match self {
+            Op::Plus => Some("add"),
+            Op::Minus => Some("sub"),
+            Op::Times => Some("mul"),
+            Op::DividedBy => Some("div"),
+            _ => None,
+        }
+    }
+
+    /// True for the comparison operators like `<` and `==`.
+    pub fn is_comparison(self) -> bool {
+        matches!(
+            self,
+            Op::EqualEqual
+                | Op::NotEqual
+                | Op::LessThan
+                | Op::GreaterThan
+                | Op::GreaterEqual
+                | Op::LessEqual
+        )
+    }
 }
 
 /// Returns a table mapping binary operators like `+` to their `+=` form.
 #[salsa::memoized(in crate::Jar ref)]
 #[allow(clippy::needless_lifetimes)]
 pub fn binary_ops(_db: &dyn crate::Db) -> Vec<BinaryOp> {
-    vec![
-        BinaryOp {
-            binary_op: Op::Plus,
-            assign_op: Op::PlusEqual,
-        },
-        BinaryOp {
-            binary_op: Op::Minus,
-            assign_op: Op::MinusEqual,
-        },
-        BinaryOp {
-            binary_op: Op::Times,
-            assign_op: Op::TimesEqual,
-        },
-        BinaryOp {
-            binary_op: Op::DividedBy,
-            assign_op: Op::DividedByEqual,
-        },
-    ]
+    Op::all()
+        .filter_map(|binary_op| {
+            Some(BinaryOp {
+                binary_op,
+                assign_op: binary_op.assign_op()?,
+            })
+        })
+        .collect()
 }
 
 /// Check that the operator strings have the longest strings first.
@@ -111,3 +201,64 @@ const fn check(i: usize, j: usize) {
 
 /// Check that the operator strings have the longest strings first.
 const _: () = check(0, 1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_op_round_trips_through_its_spelling() {
+        for op in Op::all() {
+            // These share their spelling with an operator declared earlier.
+            let expected = match op {
+                Op::LeftAngle => Op::LessThan,
+                Op::RightAngle => Op::GreaterThan,
+                Op::Pipe => Op::BitOr,
+                _ => op,
+            };
+            assert_eq!(Op::from_str(op.as_str()), Some(expected), "{:?}", op);
+        }
+    }
+
+    #[test]
+    fn spellings() {
+        let spellings = [
+            (Op::Plus, "+"),
+            (Op::PlusEqual, "+="),
+            (Op::EqualEqual, "=="),
+            (Op::NotEqual, "!="),
+            (Op::LessEqual, "<="),
+            (Op::AndAnd, "&&"),
+            (Op::BitXor, "^"),
+            (Op::ShiftLeftEqual, "<<="),
+            (Op::DotDotEqual, "..="),
+            (Op::FatArrow, "=>"),
+            (Op::Not, "!"),
+        ];
+        for (op, spelling) in spellings {
+            assert_eq!(op.as_str(), spelling);
+            assert_eq!(Op::from_str(spelling), Some(op));
+            assert_eq!(spelling.parse::<Op>(), Ok(op));
+        }
+    }
+
+    #[test]
+    fn unknown_spellings_are_not_ops() {
+        assert_eq!(Op::from_str(""), None);
+        assert_eq!(Op::from_str("+++"), None);
+        assert_eq!("+++".parse::<Op>(), Err(UnknownOp));
+    }
+
+    #[test]
+    fn compound_assignments_derive_from_binary_ops() {
+        for op in Op::all() {
+            if let Some(assign_op) = op.assign_op() {
+                assert_eq!(assign_op.as_str(), format!("{}=", op.as_str()));
+                assert_eq!(assign_op.binary_op(), Some(op));
+            }
+        }
+        assert_eq!(Op::LessThan.assign_op(), None);
+        assert_eq!(Op::LessEqual.binary_op(), None);
+        assert_eq!(Op::ColonEqual.binary_op(), None);
+    }
+}
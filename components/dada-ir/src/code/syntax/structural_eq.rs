@@ -0,0 +1,260 @@
+//! Compares syntax trees by their structure, ignoring spans.
+//!
+//! Deriving `PartialEq` is not enough for this: trees parsed from the same
+//! text at different places compare unequal, because specifiers and argument
+//! names are entities that carry their spans, and each nested function is
+//! an entity of its own.
+
+use crate::code::syntax::{
+    Expr, ExprData, LocalVariableDecl, MatchArm, NamedExpr, PatternData, Tables, Tree,
+};
+
+impl Tree {
+    /// True if `self` and `other` have the same parameters and body, ignoring
+    /// spans (and the ids that expressions happen to be allocated with).
+    /// Nested functions are compared by their source text.
+    pub fn structurally_eq(self, other: Tree, db: &dyn crate::Db) -> bool {
+        let (data_a, data_b) = (self.data(db), other.data(db));
+        let comparer = Comparer {
+            db,
+            tables_a: &data_a.tables,
+            tables_b: &data_b.tables,
+            shallow: false,
+            modulo_formatting: false,
+        };
+        comparer.decls(&data_a.parameter_decls, &data_b.parameter_decls)
+            && comparer.expr(data_a.root_expr, data_b.root_expr)
+    }
+
+    /// Like [`Self::structurally_eq`], but also ignoring the differences that
+    /// formatting introduces: parentheses, and the indentation of nested
+    /// functions. Formatting a tree and parsing the result should yield a
+    /// tree that is equal to the original in this sense.
+    pub fn eq_modulo_formatting(self, other: Tree, db: &dyn crate::Db) -> bool {
+        let (data_a, data_b) = (self.data(db), other.data(db));
+        let comparer = Comparer {
+            db,
+            tables_a: &data_a.tables,
+            tables_b: &data_b.tables,
+            shallow: false,
+            modulo_formatting: true,
+        };
+        comparer.decls(&data_a.parameter_decls, &data_b.parameter_decls)
+            && comparer.expr(data_a.root_expr, data_b.root_expr)
+    }
+}
+
+pub(super) struct Comparer<'me> {
+    pub(super) db: &'me dyn crate::Db,
+    pub(super) tables_a: &'me Tables,
+    pub(super) tables_b: &'me Tables,
+
+    /// If true, the expressions nested in the ones being compared are
+    /// assumed to be equal, so only the nodes themselves are compared
+    /// (their kind, operators, names, declarations, and number of children).
+    pub(super) shallow: bool,
+
+    /// If true, parentheses are looked through and the text of nested
+    /// functions is compared ignoring whitespace, since the formatter
+    /// drops redundant parentheses and re-indents nested functions.
+    pub(super) modulo_formatting: bool,
+}
+
+impl Comparer<'_> {
+    pub(super) fn expr(&self, a: Expr, b: Expr) -> bool {
+        self.shallow || self.node(a, b)
+    }
+
+    /// Compares `a` and `b` themselves, comparing their nested expressions
+    /// with [`Self::expr`].
+    pub(super) fn node(&self, a: Expr, b: Expr) -> bool {
+        let db = self.db;
+        let (a, b) = if self.modulo_formatting {
+            (
+                strip_parens(self.tables_a, a),
+                strip_parens(self.tables_b, b),
+            )
+        } else {
+            (a, b)
+        };
+        match (&self.tables_a[a], &self.tables_b[b]) {
+            (ExprData::Id(a), ExprData::Id(b))
+            | (ExprData::FloatLiteral(a), ExprData::FloatLiteral(b))
+            | (ExprData::CharLiteral(a), ExprData::CharLiteral(b))
+            | (ExprData::StringLiteral(a), ExprData::StringLiteral(b)) => a == b,
+
+            (ExprData::BooleanLiteral(a), ExprData::BooleanLiteral(b)) => a == b,
+
+            (ExprData::IntegerLiteral(a), ExprData::IntegerLiteral(b)) => a == b,
+
+            (ExprData::Concatenate(a), ExprData::Concatenate(b))
+            | (ExprData::Tuple(a), ExprData::Tuple(b))
+            | (ExprData::List(a), ExprData::List(b)) => self.exprs(a, b),
+
+            (ExprData::Dot(a, field_a), ExprData::Dot(b, field_b)) => {
+                field_a == field_b && self.expr(*a, *b)
+            }
+
+            (ExprData::Index(a0, a1), ExprData::Index(b0, b1))
+            | (ExprData::Assign(a0, a1), ExprData::Assign(b0, b1)) => {
+                self.expr(*a0, *b0) && self.expr(*a1, *b1)
+            }
+
+            (ExprData::Await(a), ExprData::Await(b))
+            | (ExprData::Share(a), ExprData::Share(b))
+            | (ExprData::Lease(a), ExprData::Lease(b))
+            | (ExprData::Shlease(a), ExprData::Shlease(b))
+            | (ExprData::Give(a), ExprData::Give(b))
+            | (ExprData::Parenthesized(a), ExprData::Parenthesized(b))
+            | (ExprData::Atomic(a), ExprData::Atomic(b)) => self.expr(*a, *b),
+
+            (ExprData::Call(a, args_a), ExprData::Call(b, args_b)) => {
+                self.expr(*a, *b) && self.named_exprs(args_a, args_b)
+            }
+
+            (ExprData::ClassLiteral(a, fields_a), ExprData::ClassLiteral(b, fields_b)) => {
+                a == b && self.named_exprs(fields_a, fields_b)
+            }
+
+            (ExprData::Var(decl_a, a), ExprData::Var(decl_b, b)) => {
+                self.decl(*decl_a, *decl_b) && self.expr(*a, *b)
+            }
+
+            (ExprData::VarElse(decl_a, a, else_a), ExprData::VarElse(decl_b, b, else_b)) => {
+                self.decl(*decl_a, *decl_b) && self.expr(*a, *b) && self.expr(*else_a, *else_b)
+            }
+
+            (ExprData::Range(start_a, end_a, a), ExprData::Range(start_b, end_b, b)) => {
+                a == b && self.opt_expr(*start_a, *start_b) && self.opt_expr(*end_a, *end_b)
+            }
+
+            (ExprData::Closure(decls_a, a), ExprData::Closure(decls_b, b)) => {
+                self.decls(decls_a, decls_b) && self.expr(*a, *b)
+            }
+
+            (ExprData::If(cond_a, then_a, else_a), ExprData::If(cond_b, then_b, else_b)) => {
+                self.expr(*cond_a, *cond_b)
+                    && self.expr(*then_a, *then_b)
+                    && self.opt_expr(*else_a, *else_b)
+            }
+
+            (ExprData::Loop(label_a, a), ExprData::Loop(label_b, b)) => {
+                label_a == label_b && self.expr(*a, *b)
+            }
+
+            (ExprData::While(label_a, cond_a, a), ExprData::While(label_b, cond_b, b)) => {
+                label_a == label_b && self.expr(*cond_a, *cond_b) && self.expr(*a, *b)
+            }
+
+            (
+                ExprData::For(label_a, decl_a, iter_a, a),
+                ExprData::For(label_b, decl_b, iter_b, b),
+            ) => {
+                label_a == label_b
+                    && self.decl(*decl_a, *decl_b)
+                    && self.expr(*iter_a, *iter_b)
+                    && self.expr(*a, *b)
+            }
+
+            (ExprData::Match(a, arms_a), ExprData::Match(b, arms_b)) => {
+                self.expr(*a, *b)
+                    && arms_a.len() == arms_b.len()
+                    && arms_a
+                        .iter()
+                        .zip(arms_b)
+                        .all(|(&arm_a, &arm_b)| self.match_arm(arm_a, arm_b))
+            }
+
+            (ExprData::Seq(statements_a, tail_a), ExprData::Seq(statements_b, tail_b)) => {
+                self.exprs(statements_a, statements_b) && self.opt_expr(*tail_a, *tail_b)
+            }
+
+            (ExprData::Op(lhs_a, op_a, rhs_a), ExprData::Op(lhs_b, op_b, rhs_b))
+            | (ExprData::OpEq(lhs_a, op_a, rhs_a), ExprData::OpEq(lhs_b, op_b, rhs_b)) => {
+                op_a == op_b && self.expr(*lhs_a, *lhs_b) && self.expr(*rhs_a, *rhs_b)
+            }
+
+            (ExprData::Unary(op_a, a), ExprData::Unary(op_b, b)) => {
+                op_a == op_b && self.expr(*a, *b)
+            }
+
+            (ExprData::Return(a), ExprData::Return(b))
+            | (ExprData::Yield(a), ExprData::Yield(b)) => self.opt_expr(*a, *b),
+
+            (ExprData::Break(label_a, a), ExprData::Break(label_b, b)) => {
+                label_a == label_b && self.opt_expr(*a, *b)
+            }
+
+            (ExprData::Continue(label_a), ExprData::Continue(label_b)) => label_a == label_b,
+
+            (ExprData::Fn(a), ExprData::Fn(b)) => {
+                let (a, b) = (a.span(db).snippet(db), b.span(db).snippet(db));
+                if self.modulo_formatting {
+                    a.split_whitespace().eq(b.split_whitespace())
+                } else {
+                    a == b
+                }
+            }
+
+            (ExprData::Error, ExprData::Error) => true,
+
+            _ => false,
+        }
+    }
+
+    fn opt_expr(&self, a: Option<Expr>, b: Option<Expr>) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) => self.expr(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn exprs(&self, a: &[Expr], b: &[Expr]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(&a, &b)| self.expr(a, b))
+    }
+
+    fn named_exprs(&self, a: &[NamedExpr], b: &[NamedExpr]) -> bool {
+        let db = self.db;
+        a.len() == b.len()
+            && a.iter().zip(b).all(|(&a, &b)| {
+                let (a, b) = (&self.tables_a[a], &self.tables_b[b]);
+                a.name.word(db) == b.name.word(db) && self.expr(a.expr, b.expr)
+            })
+    }
+
+    fn decl(&self, a: LocalVariableDecl, b: LocalVariableDecl) -> bool {
+        let db = self.db;
+        let (a, b) = (&self.tables_a[a], &self.tables_b[b]);
+        a.mutable == b.mutable
+            && a.specifier.specifier(db) == b.specifier.specifier(db)
+            && a.specifier.defaulted(db) == b.specifier.defaulted(db)
+            && a.atomic == b.atomic
+            && a.weak == b.weak
+            && a.name == b.name
+            && a.ty == b.ty
+    }
+
+    pub(super) fn decls(&self, a: &[LocalVariableDecl], b: &[LocalVariableDecl]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(&a, &b)| self.decl(a, b))
+    }
+
+    fn match_arm(&self, a: MatchArm, b: MatchArm) -> bool {
+        let (a, b) = (&self.tables_a[a], &self.tables_b[b]);
+        let patterns_eq = match (&self.tables_a[a.pattern], &self.tables_b[b.pattern]) {
+            (PatternData::Binding(decl_a), PatternData::Binding(decl_b)) => {
+                self.decl(*decl_a, *decl_b)
+            }
+            (PatternData::Literal(a), PatternData::Literal(b)) => self.expr(*a, *b),
+            _ => false,
+        };
+        patterns_eq && self.expr(a.body, b.body)
+    }
+}
+
+fn strip_parens(tables: &Tables, mut expr: Expr) -> Expr {
+    while let ExprData::Parenthesized(inner) = tables[expr] {
+        expr = inner;
+    }
+    expr
+}
@@ -0,0 +1,239 @@
+//! Serializes a syntax tree to JSON for use by external tools.
+//!
+//! The output is self-contained: words are written out as strings and each
+//! expression is nested inside its parent instead of being referred to by
+//! its index. Every expression is an object whose `kind` is the name of its
+//! [`ExprData`] variant; the other fields depend on the kind. Spans are not
+//! included.
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::code::syntax::{
+    Expr, ExprData, LocalVariableDecl, MatchArm, NamedExpr, Pattern, PatternData, Tables, Tree,
+};
+use crate::storage::{Atomic, Mutable, Weak};
+
+impl Tree {
+    /// Serializes this tree to a JSON object with the `parameters` of the
+    /// function and its body (`root`).
+    pub fn to_json(self, db: &dyn crate::Db) -> String {
+        let data = self.data(db);
+        let cx = &JsonCx {
+            db,
+            tables: &data.tables,
+        };
+        let tree = TreeJson {
+            parameters: cx.wrap(&data.parameter_decls[..]),
+            root: cx.wrap(data.root_expr),
+        };
+        serde_json::to_string(&tree).unwrap()
+    }
+}
+
+#[derive(serde::Serialize)]
+struct TreeJson<'me> {
+    parameters: InTree<'me, &'me [LocalVariableDecl]>,
+    root: InTree<'me, Expr>,
+}
+
+struct JsonCx<'me> {
+    db: &'me dyn crate::Db,
+    tables: &'me Tables,
+}
+
+impl<'me> JsonCx<'me> {
+    fn wrap<T>(&'me self, value: T) -> InTree<'me, T> {
+        InTree { cx: self, value }
+    }
+}
+
+/// A part of the tree, which needs the tables and the database to be serialized.
+struct InTree<'me, T> {
+    cx: &'me JsonCx<'me>,
+    value: T,
+}
+
+impl<'me, T: Copy> Serialize for InTree<'me, &'me [T]>
+where
+    InTree<'me, T>: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.value.iter().map(|&value| self.cx.wrap(value)))
+    }
+}
+
+impl Serialize for InTree<'_, Expr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (cx, db) = (self.cx, self.cx.db);
+        let wrap_opt = |expr: &Option<Expr>| expr.map(|expr| cx.wrap(expr));
+        let word_opt = |word: &Option<crate::word::Word>| word.map(|word| word.as_str(db));
+
+        let mut map = serializer.serialize_map(None)?;
+        let data = &cx.tables[self.value];
+        map.serialize_entry("kind", data.kind_str())?;
+        match data {
+            ExprData::Id(name) => map.serialize_entry("name", name.as_str(db))?,
+            ExprData::BooleanLiteral(value) => map.serialize_entry("value", value)?,
+            ExprData::IntegerLiteral(literal) => {
+                map.serialize_entry("digits", literal.text.as_str(db))?;
+                map.serialize_entry("base", literal.base.str())?;
+                map.serialize_entry("value", &literal.value)?;
+                map.serialize_entry("suffix", &word_opt(&literal.suffix))?;
+            }
+            ExprData::FloatLiteral(text)
+            | ExprData::CharLiteral(text)
+            | ExprData::StringLiteral(text) => map.serialize_entry("text", text.as_str(db))?,
+            ExprData::Concatenate(exprs) => map.serialize_entry("parts", &cx.wrap(&exprs[..]))?,
+            ExprData::Dot(owner, field) => {
+                map.serialize_entry("owner", &cx.wrap(*owner))?;
+                map.serialize_entry("field", field.as_str(db))?;
+            }
+            ExprData::Index(owner, index) => {
+                map.serialize_entry("owner", &cx.wrap(*owner))?;
+                map.serialize_entry("index", &cx.wrap(*index))?;
+            }
+            ExprData::Await(expr)
+            | ExprData::Share(expr)
+            | ExprData::Lease(expr)
+            | ExprData::Shlease(expr)
+            | ExprData::Give(expr)
+            | ExprData::Parenthesized(expr) => map.serialize_entry("expr", &cx.wrap(*expr))?,
+            ExprData::Call(callee, arguments) => {
+                map.serialize_entry("callee", &cx.wrap(*callee))?;
+                map.serialize_entry("arguments", &cx.wrap(&arguments[..]))?;
+            }
+            ExprData::ClassLiteral(class, fields) => {
+                map.serialize_entry("class", class.as_str(db))?;
+                map.serialize_entry("fields", &cx.wrap(&fields[..]))?;
+            }
+            ExprData::Var(decl, initializer) => {
+                map.serialize_entry("decl", &cx.wrap(*decl))?;
+                map.serialize_entry("initializer", &cx.wrap(*initializer))?;
+            }
+            ExprData::VarElse(decl, initializer, else_expr) => {
+                map.serialize_entry("decl", &cx.wrap(*decl))?;
+                map.serialize_entry("initializer", &cx.wrap(*initializer))?;
+                map.serialize_entry("else", &cx.wrap(*else_expr))?;
+            }
+            ExprData::Tuple(exprs) | ExprData::List(exprs) => {
+                map.serialize_entry("elements", &cx.wrap(&exprs[..]))?
+            }
+            ExprData::Range(start, end, inclusive) => {
+                map.serialize_entry("start", &wrap_opt(start))?;
+                map.serialize_entry("end", &wrap_opt(end))?;
+                map.serialize_entry("inclusive", inclusive)?;
+            }
+            ExprData::Closure(parameters, body) => {
+                map.serialize_entry("parameters", &cx.wrap(&parameters[..]))?;
+                map.serialize_entry("body", &cx.wrap(*body))?;
+            }
+            ExprData::If(condition, then_expr, else_expr) => {
+                map.serialize_entry("condition", &cx.wrap(*condition))?;
+                map.serialize_entry("then", &cx.wrap(*then_expr))?;
+                map.serialize_entry("else", &wrap_opt(else_expr))?;
+            }
+            ExprData::Atomic(body) => map.serialize_entry("body", &cx.wrap(*body))?,
+            ExprData::Loop(label, body) => {
+                map.serialize_entry("label", &word_opt(label))?;
+                map.serialize_entry("body", &cx.wrap(*body))?;
+            }
+            ExprData::While(label, condition, body) => {
+                map.serialize_entry("label", &word_opt(label))?;
+                map.serialize_entry("condition", &cx.wrap(*condition))?;
+                map.serialize_entry("body", &cx.wrap(*body))?;
+            }
+            ExprData::For(label, decl, iterable, body) => {
+                map.serialize_entry("label", &word_opt(label))?;
+                map.serialize_entry("decl", &cx.wrap(*decl))?;
+                map.serialize_entry("iterable", &cx.wrap(*iterable))?;
+                map.serialize_entry("body", &cx.wrap(*body))?;
+            }
+            ExprData::Match(value, arms) => {
+                map.serialize_entry("value", &cx.wrap(*value))?;
+                map.serialize_entry("arms", &cx.wrap(&arms[..]))?;
+            }
+            ExprData::Seq(statements, tail) => {
+                map.serialize_entry("statements", &cx.wrap(&statements[..]))?;
+                map.serialize_entry("tail", &wrap_opt(tail))?;
+            }
+            ExprData::Op(lhs, op, rhs) | ExprData::OpEq(lhs, op, rhs) => {
+                map.serialize_entry("lhs", &cx.wrap(*lhs))?;
+                map.serialize_entry("op", op.as_str())?;
+                map.serialize_entry("rhs", &cx.wrap(*rhs))?;
+            }
+            ExprData::Unary(op, rhs) => {
+                map.serialize_entry("op", op.as_str())?;
+                map.serialize_entry("rhs", &cx.wrap(*rhs))?;
+            }
+            ExprData::Assign(lhs, rhs) => {
+                map.serialize_entry("lhs", &cx.wrap(*lhs))?;
+                map.serialize_entry("rhs", &cx.wrap(*rhs))?;
+            }
+            ExprData::Return(value) | ExprData::Yield(value) => {
+                map.serialize_entry("value", &wrap_opt(value))?
+            }
+            ExprData::Break(label, value) => {
+                map.serialize_entry("label", &word_opt(label))?;
+                map.serialize_entry("value", &wrap_opt(value))?;
+            }
+            ExprData::Continue(label) => map.serialize_entry("label", &word_opt(label))?,
+            ExprData::Fn(function) => map.serialize_entry("name", function.name(db).as_str(db))?,
+            ExprData::Error => {}
+        }
+        map.end()
+    }
+}
+
+impl Serialize for InTree<'_, NamedExpr> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (cx, db) = (self.cx, self.cx.db);
+        let data = &cx.tables[self.value];
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("name", &data.name.word(db).map(|word| word.as_str(db)))?;
+        map.serialize_entry("expr", &cx.wrap(data.expr))?;
+        map.end()
+    }
+}
+
+impl Serialize for InTree<'_, LocalVariableDecl> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (cx, db) = (self.cx, self.cx.db);
+        let data = &cx.tables[self.value];
+        let mut map = serializer.serialize_map(Some(5))?;
+        map.serialize_entry("name", data.name.as_str(db))?;
+        map.serialize_entry("mutable", &(data.mutable == Mutable::Yes))?;
+        map.serialize_entry("specifier", &data.specifier.specifier(db).to_string())?;
+        map.serialize_entry("atomic", &(data.atomic == Atomic::Yes))?;
+        map.serialize_entry("weak", &(data.weak == Weak::Yes))?;
+        map.end()
+    }
+}
+
+impl Serialize for InTree<'_, MatchArm> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let cx = self.cx;
+        let data = &cx.tables[self.value];
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("pattern", &cx.wrap(data.pattern))?;
+        map.serialize_entry("body", &cx.wrap(data.body))?;
+        map.end()
+    }
+}
+
+impl Serialize for InTree<'_, Pattern> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let cx = self.cx;
+        let mut map = serializer.serialize_map(Some(2))?;
+        match cx.tables[self.value] {
+            PatternData::Binding(decl) => {
+                map.serialize_entry("kind", "Binding")?;
+                map.serialize_entry("decl", &cx.wrap(decl))?;
+            }
+            PatternData::Literal(expr) => {
+                map.serialize_entry("kind", "Literal")?;
+                map.serialize_entry("expr", &cx.wrap(expr))?;
+            }
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,669 @@
+//! Renders a syntax tree back into Dada source with canonical formatting.
+//! The comments between the statements of a block are kept, but the
+//! original layout (and any comments within an expression) is not, and
+//! parentheses are only kept where the precedence of the operators requires them.
+
+use crate::{
+    code::syntax::{
+        op::Op, Comment, Expr, ExprData, LocalVariableDecl, MatchArm, NamedExpr, PatternData,
+        Spans, Tables, Tree,
+    },
+    kw::Keyword,
+    storage::{Atomic, Mutable, Weak},
+    word::Word,
+};
+
+/// Controls the layout chosen by [`Tree::to_source_with`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Number of spaces per level of indentation. When indenting with tabs,
+    /// this is the width that a tab counts for.
+    pub indent_width: usize,
+
+    /// Indent with tabs rather than spaces.
+    pub use_tabs: bool,
+
+    /// Calls that would not fit in this many columns are written with each
+    /// argument on its own line.
+    pub max_width: usize,
+
+    /// Text that starts every line after the first, such as the indentation
+    /// of a method whose body is formatted on its own.
+    pub base_indent: String,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            use_tabs: false,
+            max_width: 100,
+            base_indent: String::new(),
+        }
+    }
+}
+
+impl Tree {
+    /// Renders the body of this tree with the default [`FormatOptions`].
+    pub fn to_source(self, db: &dyn crate::Db) -> String {
+        self.to_source_with(db, &FormatOptions::default())
+    }
+
+    /// Renders the body of this tree as a `{ ... }` block, with one expression
+    /// per line. Parsing the result yields an equal tree (modulo spans), except
+    /// when the tree contains [`ExprData::Error`], which has no source form.
+    pub fn to_source_with(self, db: &dyn crate::Db, options: &FormatOptions) -> String {
+        let data = self.data(db);
+        let mut printer = SourcePrinter {
+            db,
+            tables: &data.tables,
+            spans: self.spans(db),
+            options,
+            output: String::new(),
+            indent: 0,
+            in_condition: false,
+            in_format_string: false,
+        };
+        printer.print_expr(data.root_expr);
+        printer.output
+    }
+}
+
+struct SourcePrinter<'me> {
+    db: &'me dyn crate::Db,
+    tables: &'me Tables,
+    spans: &'me Spans,
+    options: &'me FormatOptions,
+    output: String,
+    indent: usize,
+
+    /// True while printing the condition of an `if` (etc.) outside of any
+    /// delimiters, where a class literal would be taken for the body.
+    in_condition: bool,
+
+    /// True while printing an expression embedded in a format string, which
+    /// must stay on one line.
+    in_format_string: bool,
+}
+
+impl SourcePrinter<'_> {
+    fn push(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    fn push_keyword(&mut self, keyword: Keyword) {
+        self.push(keyword.str());
+    }
+
+    fn newline(&mut self) {
+        let indent = if self.options.use_tabs {
+            "\t".to_string()
+        } else {
+            " ".repeat(self.options.indent_width)
+        };
+        self.output.push('\n');
+        self.output.push_str(&self.options.base_indent);
+        self.output.push_str(&indent.repeat(self.indent));
+    }
+
+    /// True if any line of the output from the one containing `start` onwards
+    /// is wider than the maximum width.
+    fn overflows_since(&self, start: usize) -> bool {
+        let line_start = self.output[..start].rfind('\n').map_or(0, |i| i + 1);
+        self.output[line_start..]
+            .lines()
+            .any(|line| self.width(line) > self.options.max_width)
+    }
+
+    /// The number of columns that `line` takes up, counting tabs as a full indent.
+    fn width(&self, line: &str) -> usize {
+        line.chars()
+            .map(|c| match c {
+                '\t' => self.options.indent_width,
+                _ => 1,
+            })
+            .sum()
+    }
+
+    fn print_expr(&mut self, expr: Expr) {
+        self.print_operand(expr, Precedence::Open);
+    }
+
+    /// Prints `expr` in a position that only accepts expressions binding at
+    /// least as tightly as `min`. Parentheses from the source are dropped and
+    /// re-inserted only where `expr` would otherwise parse differently.
+    fn print_operand(&mut self, expr: Expr, min: Precedence) {
+        let expr = self.strip_parens(expr);
+        let class_literal_in_condition =
+            self.in_condition && matches!(self.tables[expr], ExprData::ClassLiteral(..));
+        if self.precedence(expr) < min || class_literal_in_condition {
+            self.push("(");
+            self.with_in_condition(false, |this| this.print_unparenthesized(expr));
+            self.push(")");
+        } else {
+            self.print_unparenthesized(expr);
+        }
+    }
+
+    /// Prints the condition of an `if`, `while`, `for`, or `match`.
+    fn print_condition(&mut self, expr: Expr) {
+        let start = self.output.len();
+        self.with_in_condition(true, |this| this.print_expr(expr));
+        // A condition cannot start with a block, as it would be taken for the body.
+        if self.output[start..].starts_with('{') {
+            self.output.insert(start, '(');
+            self.push(")");
+        }
+    }
+
+    fn with_in_condition(&mut self, in_condition: bool, op: impl FnOnce(&mut Self)) {
+        let old_in_condition = std::mem::replace(&mut self.in_condition, in_condition);
+        op(self);
+        self.in_condition = old_in_condition;
+    }
+
+    fn strip_parens(&self, mut expr: Expr) -> Expr {
+        while let ExprData::Parenthesized(inner) = self.tables[expr] {
+            expr = inner;
+        }
+        expr
+    }
+
+    fn precedence(&self, expr: Expr) -> Precedence {
+        match &self.tables[expr] {
+            ExprData::Var(..)
+            | ExprData::VarElse(..)
+            | ExprData::Closure(..)
+            | ExprData::Return(_)
+            | ExprData::Yield(_)
+            | ExprData::Break(..)
+            | ExprData::Continue(_) => Precedence::Open,
+            ExprData::Assign(..) | ExprData::OpEq(..) => Precedence::Assign,
+            ExprData::Range(..) => Precedence::Range,
+            ExprData::Op(_, op, _) => Precedence::of_binary_op(*op),
+            ExprData::Unary(..) => Precedence::Prefix,
+            ExprData::Dot(..)
+            | ExprData::Index(..)
+            | ExprData::Call(..)
+            | ExprData::Await(_)
+            | ExprData::Share(_)
+            | ExprData::Lease(_)
+            | ExprData::Shlease(_)
+            | ExprData::Give(_) => Precedence::Postfix,
+            _ => Precedence::Atom,
+        }
+    }
+
+    /// Prints `expr` itself, without parentheses around it.
+    fn print_unparenthesized(&mut self, expr: Expr) {
+        let (db, tables) = (self.db, self.tables);
+        match &tables[expr] {
+            ExprData::Id(word) => self.push(word.as_str(db)),
+            ExprData::BooleanLiteral(true) => self.push_keyword(Keyword::True),
+            ExprData::BooleanLiteral(false) => self.push_keyword(Keyword::False),
+            ExprData::IntegerLiteral(literal) => {
+                self.push(literal.text.as_str(db));
+                if let Some(suffix) = literal.suffix {
+                    self.push(suffix.as_str(db));
+                }
+            }
+            ExprData::FloatLiteral(word) => self.push(word.as_str(db)),
+            ExprData::CharLiteral(word) => {
+                self.push("'");
+                self.push(word.as_str(db));
+                self.push("'");
+            }
+            ExprData::StringLiteral(word) => self.push(word.as_str(db)),
+            ExprData::Concatenate(exprs) => {
+                self.push("\"");
+                for &expr in exprs {
+                    if let ExprData::StringLiteral(_) = tables[expr] {
+                        self.print_expr(expr);
+                    } else {
+                        self.push("{");
+                        let in_format_string = std::mem::replace(&mut self.in_format_string, true);
+                        self.with_in_condition(false, |this| this.print_expr(expr));
+                        self.in_format_string = in_format_string;
+                        self.push("}");
+                    }
+                }
+                self.push("\"");
+            }
+            ExprData::Dot(owner, field) => {
+                self.print_operand(*owner, Precedence::Postfix);
+                self.push(".");
+                self.push(field.as_str(db));
+            }
+            ExprData::Index(base, index) => {
+                self.print_operand(*base, Precedence::Postfix);
+                self.push("[");
+                self.with_in_condition(false, |this| this.print_expr(*index));
+                self.push("]");
+            }
+            ExprData::Await(expr) => self.print_dot_keyword(*expr, Keyword::Await),
+            ExprData::Share(expr) => self.print_dot_keyword(*expr, Keyword::Share),
+            ExprData::Lease(expr) => self.print_dot_keyword(*expr, Keyword::Lease),
+            ExprData::Shlease(expr) => self.print_dot_keyword(*expr, Keyword::Shlease),
+            ExprData::Give(expr) => self.print_dot_keyword(*expr, Keyword::Give),
+            ExprData::Call(func, named_exprs) => {
+                self.print_operand(*func, Precedence::Postfix);
+                self.print_arguments(named_exprs);
+            }
+            ExprData::ClassLiteral(name, named_exprs) => {
+                self.push(name.as_str(db));
+                if named_exprs.is_empty() {
+                    self.push(" {}");
+                } else {
+                    self.push(" { ");
+                    self.print_named_exprs(named_exprs);
+                    self.push(" }");
+                }
+            }
+            ExprData::Var(decl, initializer) => {
+                self.print_local_variable_decl(*decl);
+                self.push(" = ");
+                self.print_expr(*initializer);
+            }
+            ExprData::VarElse(decl, initializer, else_expr) => {
+                self.print_local_variable_decl(*decl);
+                self.push(" = ");
+                self.print_expr(*initializer);
+                self.push(" ");
+                self.push_keyword(Keyword::Else);
+                self.push(" ");
+                self.print_expr(*else_expr);
+            }
+            ExprData::Parenthesized(expr) => self.print_unparenthesized(*expr),
+            ExprData::Tuple(exprs) => {
+                self.push("(");
+                self.print_comma_separated(exprs, |this, expr| this.print_expr(expr));
+                if exprs.len() == 1 {
+                    // `(a,)` is a tuple, but `(a)` is just `a`
+                    self.push(",");
+                }
+                self.push(")");
+            }
+            ExprData::List(exprs) => {
+                self.push("[");
+                self.print_comma_separated(exprs, |this, expr| this.print_expr(expr));
+                self.push("]");
+            }
+            ExprData::Range(start, end, inclusive) => {
+                if let Some(start) = start {
+                    self.print_operand(*start, Precedence::Or);
+                }
+                if *inclusive {
+                    self.push(Op::DotDotEqual.as_str());
+                } else {
+                    self.push(Op::DotDot.as_str());
+                }
+                if let Some(end) = end {
+                    self.print_operand(*end, Precedence::Or);
+                }
+            }
+            ExprData::Closure(parameters, body) => {
+                self.push(Op::Pipe.as_str());
+                self.print_comma_separated(parameters, |this, decl| {
+                    this.print_local_variable_decl(decl)
+                });
+                self.push(Op::Pipe.as_str());
+                self.push(" ");
+                self.print_expr(*body);
+            }
+            ExprData::If(condition, then_expr, else_expr) => {
+                self.push_keyword(Keyword::If);
+                self.push(" ");
+                self.print_condition(*condition);
+                self.push(" ");
+                self.print_expr(*then_expr);
+                if let Some(else_expr) = else_expr {
+                    self.push(" ");
+                    self.push_keyword(Keyword::Else);
+                    self.push(" ");
+                    self.print_expr(*else_expr);
+                }
+            }
+            ExprData::Atomic(body) => {
+                self.push_keyword(Keyword::Atomic);
+                self.push(" ");
+                self.print_expr(*body);
+            }
+            ExprData::Loop(label, body) => {
+                self.print_loop_label(*label);
+                self.push_keyword(Keyword::Loop);
+                self.push(" ");
+                self.print_expr(*body);
+            }
+            ExprData::While(label, condition, body) => {
+                self.print_loop_label(*label);
+                self.push_keyword(Keyword::While);
+                self.push(" ");
+                self.print_condition(*condition);
+                self.push(" ");
+                self.print_expr(*body);
+            }
+            ExprData::For(label, decl, iterable, body) => {
+                self.print_loop_label(*label);
+                self.push_keyword(Keyword::For);
+                self.push(" ");
+                self.print_local_variable_decl(*decl);
+                self.push(" ");
+                self.push_keyword(Keyword::In);
+                self.push(" ");
+                self.print_condition(*iterable);
+                self.push(" ");
+                self.print_expr(*body);
+            }
+            ExprData::Match(scrutinee, arms) => {
+                self.push_keyword(Keyword::Match);
+                self.push(" ");
+                self.print_condition(*scrutinee);
+                self.push(" ");
+                self.print_block(arms, &[], Self::print_match_arm);
+            }
+            ExprData::Seq(statements, tail) => {
+                // Without a tail, the last statement needs a `;` so that it is
+                // not parsed as the tail.
+                let semicolon_after = statements.last().filter(|_| tail.is_none());
+                let exprs: Vec<Expr> = statements.iter().copied().chain(*tail).collect();
+                let spans = self.spans;
+                let mut previous_end = None;
+                self.print_block(&exprs, spans.dangling_comments(expr), |this, expr| {
+                    for comment in spans.leading_comments(expr) {
+                        this.push(comment.text.as_str(db));
+                        this.newline();
+                    }
+                    let start = this.output.len();
+                    this.print_expr(expr);
+                    if let Some(previous_end) = previous_end {
+                        if continues_previous_statement(&this.output[start..]) {
+                            this.output.insert(previous_end, ';');
+                        }
+                    }
+                    if semicolon_after == Some(&expr) {
+                        this.push(Op::SemiColon.as_str());
+                    }
+                    previous_end = Some(this.output.len());
+                    if let Some(comment) = spans.trailing_comment(expr) {
+                        this.push(" ");
+                        this.push(comment.text.as_str(db));
+                    }
+                });
+            }
+            ExprData::Op(lhs, op, rhs) => {
+                // All binary operators are left-associative.
+                let precedence = Precedence::of_binary_op(*op);
+                self.print_operand(*lhs, precedence);
+                self.push(" ");
+                self.push(op.as_str());
+                self.push(" ");
+                self.print_operand(*rhs, precedence.tighter());
+            }
+            ExprData::OpEq(lhs, op, rhs) => self.print_assignment(*lhs, *op, *rhs),
+            ExprData::Unary(op, rhs) => {
+                self.push(op.as_str());
+                // `- -x` and `-!x` would otherwise lex as a single operator
+                // (only `!!x` is accepted).
+                if let ExprData::Unary(rhs_op, _) = tables[self.strip_parens(*rhs)] {
+                    if !(*op == Op::Not && rhs_op == Op::Not) {
+                        self.push(" ");
+                    }
+                }
+                self.print_operand(*rhs, Precedence::Prefix);
+            }
+            ExprData::Assign(lhs, rhs) => self.print_assignment(*lhs, Op::ColonEqual, *rhs),
+            ExprData::Return(with_value) => {
+                self.push_keyword(Keyword::Return);
+                if let Some(with_value) = with_value {
+                    self.push(" ");
+                    self.print_expr(*with_value);
+                }
+            }
+            ExprData::Yield(with_value) => {
+                self.push_keyword(Keyword::Yield);
+                if let Some(with_value) = with_value {
+                    self.push(" ");
+                    self.print_expr(*with_value);
+                }
+            }
+            ExprData::Break(label, with_value) => {
+                self.push_keyword(Keyword::Break);
+                if let Some(label) = label {
+                    self.push(" '");
+                    self.push(label.as_str(db));
+                }
+                if let Some(with_value) = with_value {
+                    self.push(" ");
+                    self.print_expr(*with_value);
+                }
+            }
+            ExprData::Continue(label) => {
+                self.push_keyword(Keyword::Continue);
+                if let Some(label) = label {
+                    self.push(" '");
+                    self.push(label.as_str(db));
+                }
+            }
+            ExprData::Fn(function) => self.push(function.span(db).snippet(db)),
+            ExprData::Error => self.push("<error>"),
+        }
+    }
+
+    /// Prints the `'label: ` in front of a loop, if it has one.
+    fn print_loop_label(&mut self, label: Option<Word>) {
+        if let Some(label) = label {
+            let db = self.db;
+            self.push("'");
+            self.push(label.as_str(db));
+            self.push(": ");
+        }
+    }
+
+    /// Prints `lhs op rhs` for `:=` and the compound assignments like `+=`,
+    /// which are right-associative.
+    fn print_assignment(&mut self, lhs: Expr, op: Op, rhs: Expr) {
+        self.print_operand(lhs, Precedence::Range);
+        self.push(" ");
+        self.push(op.as_str());
+        self.push(" ");
+        self.print_operand(rhs, Precedence::Assign);
+    }
+
+    /// Prints `expr.keyword`, e.g. `foo.await`.
+    fn print_dot_keyword(&mut self, expr: Expr, keyword: Keyword) {
+        self.print_operand(expr, Precedence::Postfix);
+        self.push(".");
+        self.push_keyword(keyword);
+    }
+
+    /// Prints `{`, then each item on its own (indented) line, followed by
+    /// the `dangling_comments` that come after the last item, then `}`.
+    fn print_block<T: Copy>(
+        &mut self,
+        items: &[T],
+        dangling_comments: &[Comment],
+        mut print_item: impl FnMut(&mut Self, T),
+    ) {
+        if items.is_empty() && dangling_comments.is_empty() {
+            self.push("{}");
+            return;
+        }
+
+        self.push("{");
+        self.indent += 1;
+        let in_condition = std::mem::replace(&mut self.in_condition, false);
+        for &item in items {
+            self.newline();
+            print_item(self, item);
+        }
+        for comment in dangling_comments {
+            self.newline();
+            self.push(comment.text.as_str(self.db));
+        }
+        self.in_condition = in_condition;
+        self.indent -= 1;
+        self.newline();
+        self.push("}");
+    }
+
+    fn print_comma_separated<T: Copy>(
+        &mut self,
+        items: &[T],
+        mut print_item: impl FnMut(&mut Self, T),
+    ) {
+        self.with_in_condition(false, |this| {
+            for (index, &item) in items.iter().enumerate() {
+                if index > 0 {
+                    this.push(", ");
+                }
+                print_item(this, item);
+            }
+        });
+    }
+
+    /// Prints the `(...)` arguments of a call on one line if they fit, and
+    /// otherwise with each argument on its own line, followed by a comma.
+    fn print_arguments(&mut self, named_exprs: &[NamedExpr]) {
+        let start = self.output.len();
+        self.push("(");
+        self.print_named_exprs(named_exprs);
+        self.push(")");
+        if named_exprs.is_empty() || self.in_format_string || !self.overflows_since(start) {
+            return;
+        }
+
+        self.output.truncate(start);
+        self.push("(");
+        self.indent += 1;
+        self.with_in_condition(false, |this| {
+            for &named_expr in named_exprs {
+                this.newline();
+                this.print_named_expr(named_expr);
+                this.push(",");
+            }
+        });
+        self.indent -= 1;
+        self.newline();
+        self.push(")");
+    }
+
+    fn print_named_exprs(&mut self, named_exprs: &[NamedExpr]) {
+        self.print_comma_separated(named_exprs, Self::print_named_expr);
+    }
+
+    fn print_named_expr(&mut self, named_expr: NamedExpr) {
+        let tables = self.tables;
+        let data = &tables[named_expr];
+        if let Some(name) = data.name.as_str(self.db) {
+            self.push(name);
+            self.push(": ");
+        }
+        self.print_expr(data.expr);
+    }
+
+    fn print_match_arm(&mut self, arm: MatchArm) {
+        let tables = self.tables;
+        let data = &tables[arm];
+        match &tables[data.pattern] {
+            PatternData::Binding(decl) => self.print_local_variable_decl(*decl),
+            PatternData::Literal(expr) => self.print_expr(*expr),
+        }
+        self.push(" ");
+        self.push(Op::FatArrow.as_str());
+        self.push(" ");
+        self.print_expr(data.body);
+    }
+
+    /// Prints `[shared] [specifier] [atomic] [weak] name`, omitting the specifier if it
+    /// was defaulted. A `var` keyword is dropped, as it is the default.
+    fn print_local_variable_decl(&mut self, decl: LocalVariableDecl) {
+        let (db, tables) = (self.db, self.tables);
+        let data = &tables[decl];
+        if let Mutable::No = data.mutable {
+            self.push_keyword(Keyword::Shared);
+            self.push(" ");
+        }
+        if !data.specifier.defaulted(db) {
+            self.push(&data.specifier.specifier(db).to_string());
+            self.push(" ");
+        }
+        if let Atomic::Yes = data.atomic {
+            self.push_keyword(Keyword::Atomic);
+            self.push(" ");
+        }
+        if let Weak::Yes = data.weak {
+            self.push_keyword(Keyword::Weak);
+            self.push(" ");
+        }
+        self.push(data.name.as_str(db));
+        // FIXME: print the type once local variables can be declared with one
+    }
+}
+
+/// True if a statement starting with `text` would continue the statement on
+/// the line before it, e.g. `a` followed by `(b)` parses as `a(b)`, and `a`
+/// followed by `-b` as `a - b`. Such statements must be separated by a `;`.
+fn continues_previous_statement(text: &str) -> bool {
+    text.starts_with(|c: char| matches!(c, '(' | '-' | '.' | '|'))
+}
+
+/// How tightly an expression binds, from loosest to tightest. This follows the
+/// precedence ladder of the parser (see `parse_expr` in `dada-parse`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    /// Expressions that can only appear where a full expression is expected,
+    /// like `x = 1`, or that extend as far to the right as they can, like
+    /// `return x` and `|x| x + 1`.
+    Open,
+    Assign,
+    Range,
+    Or,
+    And,
+    Compare,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shift,
+    Sum,
+    Product,
+    Prefix,
+    Postfix,
+    Atom,
+}
+
+impl Precedence {
+    fn of_binary_op(op: Op) -> Precedence {
+        match op {
+            Op::OrOr => Precedence::Or,
+            Op::AndAnd => Precedence::And,
+            _ if op.is_comparison() => Precedence::Compare,
+            Op::BitOr => Precedence::BitOr,
+            Op::BitXor => Precedence::BitXor,
+            Op::BitAnd => Precedence::BitAnd,
+            Op::ShiftLeft | Op::ShiftRight => Precedence::Shift,
+            Op::Plus | Op::Minus => Precedence::Sum,
+            Op::Times | Op::DividedBy => Precedence::Product,
+            _ => panic!("not a binary operator: {op:?}"),
+        }
+    }
+
+    /// The next level that binds more tightly than this one.
+    fn tighter(self) -> Precedence {
+        match self {
+            Precedence::Open => Precedence::Assign,
+            Precedence::Assign => Precedence::Range,
+            Precedence::Range => Precedence::Or,
+            Precedence::Or => Precedence::And,
+            Precedence::And => Precedence::Compare,
+            Precedence::Compare => Precedence::BitOr,
+            Precedence::BitOr => Precedence::BitXor,
+            Precedence::BitXor => Precedence::BitAnd,
+            Precedence::BitAnd => Precedence::Shift,
+            Precedence::Shift => Precedence::Sum,
+            Precedence::Sum => Precedence::Product,
+            Precedence::Product => Precedence::Prefix,
+            Precedence::Prefix => Precedence::Postfix,
+            Precedence::Postfix | Precedence::Atom => Precedence::Atom,
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Traversal of syntax trees.
+//!
+//! Implement [`Visitor`] and override the `visit_*` methods for the nodes you
+//! are interested in; call the corresponding `walk_*` function from an
+//! override to continue into that node's children. [`VisitorMut`] is the same,
+//! but has mutable access to the tables so that it can rewrite expressions.
+
+use crate::code::syntax::{
+    Expr, ExprData, LocalVariableDecl, MatchArm, NamedExpr, Pattern, PatternData, Tables,
+};
+
+pub trait Visitor<'t> {
+    fn tables(&self) -> &'t Tables;
+
+    fn visit_expr(&mut self, expr: Expr) {
+        walk_expr(self, expr)
+    }
+
+    fn visit_named_expr(&mut self, named_expr: NamedExpr) {
+        walk_named_expr(self, named_expr)
+    }
+
+    fn visit_match_arm(&mut self, match_arm: MatchArm) {
+        walk_match_arm(self, match_arm)
+    }
+
+    fn visit_pattern(&mut self, pattern: Pattern) {
+        walk_pattern(self, pattern)
+    }
+
+    fn visit_local_variable_decl(&mut self, _decl: LocalVariableDecl) {}
+}
+
+/// Visits each of the children of `expr`, in source order.
+pub fn walk_expr<'t, V: Visitor<'t> + ?Sized>(visitor: &mut V, expr: Expr) {
+    let tables = visitor.tables();
+    for_each_child(&tables[expr], |child| match child {
+        Child::Expr(expr) => visitor.visit_expr(expr),
+        Child::NamedExpr(named_expr) => visitor.visit_named_expr(named_expr),
+        Child::MatchArm(match_arm) => visitor.visit_match_arm(match_arm),
+        Child::LocalVariableDecl(decl) => visitor.visit_local_variable_decl(decl),
+    })
+}
+
+pub fn walk_named_expr<'t, V: Visitor<'t> + ?Sized>(visitor: &mut V, named_expr: NamedExpr) {
+    let expr = visitor.tables()[named_expr].expr;
+    visitor.visit_expr(expr)
+}
+
+pub fn walk_match_arm<'t, V: Visitor<'t> + ?Sized>(visitor: &mut V, match_arm: MatchArm) {
+    let data = &visitor.tables()[match_arm];
+    visitor.visit_pattern(data.pattern);
+    visitor.visit_expr(data.body);
+}
+
+pub fn walk_pattern<'t, V: Visitor<'t> + ?Sized>(visitor: &mut V, pattern: Pattern) {
+    match visitor.tables()[pattern] {
+        PatternData::Binding(decl) => visitor.visit_local_variable_decl(decl),
+        PatternData::Literal(expr) => visitor.visit_expr(expr),
+    }
+}
+
+pub trait VisitorMut {
+    fn tables_mut(&mut self) -> &mut Tables;
+
+    fn visit_expr_mut(&mut self, expr: Expr) {
+        walk_expr_mut(self, expr)
+    }
+
+    fn visit_named_expr_mut(&mut self, named_expr: NamedExpr) {
+        walk_named_expr_mut(self, named_expr)
+    }
+
+    fn visit_match_arm_mut(&mut self, match_arm: MatchArm) {
+        walk_match_arm_mut(self, match_arm)
+    }
+
+    fn visit_pattern_mut(&mut self, pattern: Pattern) {
+        walk_pattern_mut(self, pattern)
+    }
+
+    fn visit_local_variable_decl_mut(&mut self, _decl: LocalVariableDecl) {}
+}
+
+/// Visits each of the children of `expr`, in source order. The children are
+/// those `expr` had when the walk started, even if a visitor rewrites it.
+pub fn walk_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expr: Expr) {
+    let data = visitor.tables_mut()[expr].clone();
+    for_each_child(&data, |child| match child {
+        Child::Expr(expr) => visitor.visit_expr_mut(expr),
+        Child::NamedExpr(named_expr) => visitor.visit_named_expr_mut(named_expr),
+        Child::MatchArm(match_arm) => visitor.visit_match_arm_mut(match_arm),
+        Child::LocalVariableDecl(decl) => visitor.visit_local_variable_decl_mut(decl),
+    })
+}
+
+pub fn walk_named_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, named_expr: NamedExpr) {
+    let expr = visitor.tables_mut()[named_expr].expr;
+    visitor.visit_expr_mut(expr)
+}
+
+pub fn walk_match_arm_mut<V: VisitorMut + ?Sized>(visitor: &mut V, match_arm: MatchArm) {
+    let data = visitor.tables_mut()[match_arm].clone();
+    visitor.visit_pattern_mut(data.pattern);
+    visitor.visit_expr_mut(data.body);
+}
+
+pub fn walk_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, pattern: Pattern) {
+    let data = visitor.tables_mut()[pattern].clone();
+    match data {
+        PatternData::Binding(decl) => visitor.visit_local_variable_decl_mut(decl),
+        PatternData::Literal(expr) => visitor.visit_expr_mut(expr),
+    }
+}
+
+enum Child {
+    Expr(Expr),
+    NamedExpr(NamedExpr),
+    MatchArm(MatchArm),
+    LocalVariableDecl(LocalVariableDecl),
+}
+
+/// Invokes `op` on each of the children of an expression, in source order.
+/// This is the only place that needs to change when a new kind of expression is added.
+fn for_each_child(data: &ExprData, mut op: impl FnMut(Child)) {
+    fn op_exprs(op: &mut impl FnMut(Child), exprs: &[Expr]) {
+        for &expr in exprs {
+            op(Child::Expr(expr));
+        }
+    }
+
+    match data {
+        ExprData::Id(_)
+        | ExprData::BooleanLiteral(_)
+        | ExprData::IntegerLiteral(..)
+        | ExprData::FloatLiteral(_)
+        | ExprData::CharLiteral(_)
+        | ExprData::StringLiteral(_)
+        | ExprData::Continue(_)
+        | ExprData::Fn(_)
+        | ExprData::Error => {}
+
+        ExprData::Concatenate(exprs)
+        | ExprData::Tuple(exprs)
+        | ExprData::List(exprs) => op_exprs(&mut op, exprs),
+
+        ExprData::Seq(statements, tail) => {
+            op_exprs(&mut op, statements);
+            op_exprs(&mut op, tail.as_slice());
+        }
+
+        ExprData::Dot(expr, _)
+        | ExprData::Await(expr)
+        | ExprData::Share(expr)
+        | ExprData::Lease(expr)
+        | ExprData::Shlease(expr)
+        | ExprData::Give(expr)
+        | ExprData::Parenthesized(expr)
+        | ExprData::Atomic(expr)
+        | ExprData::Loop(_, expr)
+        | ExprData::Unary(_, expr) => op_exprs(&mut op, &[*expr]),
+
+        ExprData::Index(lhs, rhs)
+        | ExprData::Op(lhs, _, rhs)
+        | ExprData::OpEq(lhs, _, rhs)
+        | ExprData::Assign(lhs, rhs)
+        | ExprData::While(_, lhs, rhs) => op_exprs(&mut op, &[*lhs, *rhs]),
+
+        ExprData::Range(start, end, _) => {
+            op_exprs(&mut op, start.as_slice());
+            op_exprs(&mut op, end.as_slice());
+        }
+        ExprData::If(condition, then_expr, else_expr) => {
+            op_exprs(&mut op, &[*condition, *then_expr]);
+            op_exprs(&mut op, else_expr.as_slice());
+        }
+        ExprData::Return(with_value)
+        | ExprData::Yield(with_value)
+        | ExprData::Break(_, with_value) => {
+            op_exprs(&mut op, with_value.as_slice())
+        }
+
+        ExprData::Call(func, named_exprs) => {
+            op_exprs(&mut op, &[*func]);
+            for &named_expr in named_exprs {
+                op(Child::NamedExpr(named_expr));
+            }
+        }
+        ExprData::ClassLiteral(_, named_exprs) => {
+            for &named_expr in named_exprs {
+                op(Child::NamedExpr(named_expr));
+            }
+        }
+        ExprData::Match(scrutinee, arms) => {
+            op_exprs(&mut op, &[*scrutinee]);
+            for &arm in arms {
+                op(Child::MatchArm(arm));
+            }
+        }
+
+        ExprData::Var(decl, initializer) => {
+            op(Child::LocalVariableDecl(*decl));
+            op_exprs(&mut op, &[*initializer]);
+        }
+        ExprData::VarElse(decl, initializer, else_expr) => {
+            op(Child::LocalVariableDecl(*decl));
+            op_exprs(&mut op, &[*initializer, *else_expr]);
+        }
+        ExprData::For(_, decl, iterable, body) => {
+            op(Child::LocalVariableDecl(*decl));
+            op_exprs(&mut op, &[*iterable, *body]);
+        }
+        ExprData::Closure(parameters, body) => {
+            for &decl in parameters {
+                op(Child::LocalVariableDecl(decl));
+            }
+            op_exprs(&mut op, &[*body]);
+        }
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
     in_ir_db::InIrDb,
     intrinsic::Intrinsic,
     prelude::InIrDbExt,
-    storage::{Atomic, SpannedSpecifier},
+    storage::{Atomic, Mutable, SpannedSpecifier},
     word::{SpannedOptionalWord, Word},
 };
 use dada_id::{id, prelude::*, tables};
@@ -104,7 +104,7 @@ origin_table! {
         expr_spans: Expr => ExprOrigin,
         place_spans: Place => ExprOrigin,
         target_place_spans: TargetPlace => ExprOrigin,
-        named_exprs: NamedExpr => syntax::NamedExpr,
+        named_exprs: NamedExpr => NamedExprOrigin,
         local_variables: LocalVariable => LocalVariableOrigin,
     }
 }
@@ -134,6 +134,17 @@ impl ExprOrigin {
     }
 }
 
+/// Where a validated [`NamedExpr`] came from.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum NamedExprOrigin {
+    /// An argument (or field) written in the source, like the `x: 22` in `f(x: 22)`.
+    Real(syntax::NamedExpr),
+
+    /// An argument of a call synthesized during validation, like an operand
+    /// of an operator that is validated as a method call.
+    Synthesized(syntax::Expr),
+}
+
 impl std::fmt::Debug for ExprOrigin {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let ExprOrigin {
@@ -196,6 +207,10 @@ pub struct LocalVariableData {
     pub specifier: Option<SpannedSpecifier>,
 
     pub atomic: Atomic,
+
+    /// Whether the variable can be assigned after it is declared.
+    /// Temporaries are always mutable.
+    pub mutable: Mutable,
 }
 
 #[derive(PartialEq, Eq, Copy, Clone, Hash, Debug)]
@@ -234,6 +249,9 @@ pub enum ExprData {
     /// `2.2`
     FloatLiteral(eq_float::F64),
 
+    /// `'a'`
+    CharLiteral(char),
+
     /// `"foo"` with no format strings
     StringLiteral(Word),
 
@@ -261,9 +279,19 @@ pub enum ExprData {
     /// `expr.give`
     Give(Place),
 
-    /// `()` or `(a, b, ...)` (i.e., expr seq cannot have length 1)
+    /// `()`, `(a,)`, or `(a, b, ...)`
     Tuple(Vec<Expr>),
 
+    /// `[a, b, ...]`
+    List(Vec<Expr>),
+
+    /// `a..b`, `a..=b`, `a..`, `..b`, or `..` (the flag is true for `..=`)
+    Range(Option<Expr>, Option<Expr>, bool),
+
+    /// `|a, b| expr`: the parameters, the places of the variables that
+    /// the closure captures from the enclosing function, and the body.
+    Closure(Vec<LocalVariable>, Vec<Place>, Expr),
+
     /// `if condition { block } [else { block }]`
     If(Expr, Expr, Expr),
 
@@ -335,6 +363,7 @@ impl ExprData {
             ExprData::UnsignedIntegerLiteral(v) => write!(f, "{}", v),
             ExprData::SignedIntegerLiteral(v) => write!(f, "{}", v),
             ExprData::FloatLiteral(v) => write!(f, "{}", v),
+            ExprData::CharLiteral(v) => std::fmt::Debug::fmt(v, f),
             ExprData::StringLiteral(v) => std::fmt::Debug::fmt(&v.as_str(db.db()), f),
             ExprData::Await(expr) => f.debug_tuple("Await").field(&expr.debug(db)).finish(),
             ExprData::Call(expr, args) => f
@@ -354,6 +383,25 @@ impl ExprData {
                 }
                 f.finish()
             }
+            ExprData::List(exprs) => {
+                let mut f = f.debug_tuple("List");
+                for expr in exprs {
+                    f.field(&expr.debug(db));
+                }
+                f.finish()
+            }
+            ExprData::Range(start, end, inclusive) => f
+                .debug_tuple("Range")
+                .field(&start.debug(db))
+                .field(&end.debug(db))
+                .field(inclusive)
+                .finish(),
+            ExprData::Closure(parameters, captures, body) => f
+                .debug_tuple("Closure")
+                .field(&parameters.debug(db))
+                .field(&captures.debug(db))
+                .field(&body.debug(db))
+                .finish(),
             ExprData::Concatenate(exprs) => {
                 let mut f = f.debug_tuple("Concatenate");
                 for expr in exprs {
@@ -437,6 +485,10 @@ pub enum PlaceData {
     Intrinsic(Intrinsic),
     Class(Class),
     Dot(Place, Word),
+
+    /// `base[index]`, where the index has already been evaluated
+    /// into a temporary.
+    Index(Place, Place),
 }
 
 impl DebugWithDb<InIrDb<'_, Tree>> for PlaceData {
@@ -451,6 +503,11 @@ impl DebugWithDb<InIrDb<'_, Tree>> for PlaceData {
                 .field(&place.debug(db))
                 .field(&field.debug(db.db()))
                 .finish(),
+            PlaceData::Index(place, index) => f
+                .debug_tuple("Index")
+                .field(&place.debug(db))
+                .field(&index.debug(db))
+                .finish(),
         }
     }
 }
@@ -37,12 +37,19 @@ impl std::fmt::Display for Op {
 
 define_operators! {
     EqualEqual => "==",
+    NotEqual => "!=",
     GreaterEqual => ">=",
     LessEqual => "<=",
     Plus => "+",
     Minus => "-",
     Times => "*",
     DividedBy => "/",
+    BitAnd => "&",
+    BitOr => "|",
+    BitXor => "^",
+    ShiftLeft => "<<",
+    ShiftRight => ">>",
     LessThan => "<",
     GreaterThan => ">",
+    Not => "!",
 }
@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+
 use salsa::DebugWithDb;
 
 use crate::{
@@ -17,6 +19,7 @@ pub struct Diagnostic {
     pub span: FileSpan,
     pub message: String,
     pub labels: Vec<Label>,
+    pub suggestions: Vec<Suggestion>,
     pub children: Vec<Diagnostic>,
 }
 
@@ -35,9 +38,46 @@ pub struct Label {
     pub message: String,
 }
 
+/// A suggested fix for a diagnostic: replace the text at `span`
+/// with `replacement`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Suggestion {
+    pub span: FileSpan,
+    pub replacement: String,
+}
+
 #[salsa::accumulator(in crate::Jar)]
 pub struct Diagnostics(Diagnostic);
 
+thread_local! {
+    /// One list per active call to [`capture`], innermost last.
+    static CAPTURED: RefCell<Vec<Vec<Diagnostic>>> = RefCell::new(vec![]);
+}
+
+/// Runs `op` and returns the diagnostics it emits, instead of pushing them
+/// onto the [`Diagnostics`] accumulator. This lets a query report them
+/// itself (e.g., at adjusted spans) or drop them if they are reported
+/// elsewhere. `op` must not execute queries that emit diagnostics, as
+/// those would be missing when the query's result is reused.
+pub fn capture<R>(op: impl FnOnce() -> R) -> (R, Vec<Diagnostic>) {
+    /// Stops capturing even if `op` panics (e.g., because salsa cancelled it).
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            CAPTURED.with(|captured| captured.borrow_mut().pop());
+        }
+    }
+
+    CAPTURED.with(|captured| captured.borrow_mut().push(vec![]));
+    let guard = Guard;
+    let result = op();
+    let diagnostics =
+        CAPTURED.with(|captured| std::mem::take(captured.borrow_mut().last_mut().unwrap()));
+    drop(guard);
+    (result, diagnostics)
+}
+
 /// Convenience macro for avoiding `format!`
 #[macro_export]
 macro_rules! diag {
@@ -90,9 +130,49 @@ impl Diagnostic {
     /// dependencies) by invoking `query::accumulated::<Diagnostics>(..)`.
     pub fn emit(self, db: &dyn crate::Db) -> ErrorReported {
         tracing::debug!("emitting error {self:#?} at {:?}", self.span.debug(db));
-        Diagnostics::push(db, self);
+        let uncaptured = CAPTURED.with(|captured| match captured.borrow_mut().last_mut() {
+            Some(diagnostics) => {
+                diagnostics.push(self);
+                None
+            }
+            None => Some(self),
+        });
+        if let Some(diagnostic) = uncaptured {
+            Diagnostics::push(db, diagnostic);
+        }
         ErrorReported
     }
+
+    /// Returns this diagnostic with all of its spans moved `amount` bytes
+    /// later in their file. See [`FileSpan::shifted`].
+    pub fn shifted(self, amount: u32) -> Diagnostic {
+        Diagnostic {
+            severity: self.severity,
+            span: self.span.shifted(amount),
+            message: self.message,
+            labels: self
+                .labels
+                .into_iter()
+                .map(|label| Label {
+                    span: label.span.shifted(amount),
+                    message: label.message,
+                })
+                .collect(),
+            suggestions: self
+                .suggestions
+                .into_iter()
+                .map(|suggestion| Suggestion {
+                    span: suggestion.span.shifted(amount),
+                    replacement: suggestion.replacement,
+                })
+                .collect(),
+            children: self
+                .children
+                .into_iter()
+                .map(|child| child.shifted(amount))
+                .collect(),
+        }
+    }
 }
 
 impl Label {
@@ -105,6 +185,16 @@ impl Label {
     }
 }
 
+impl Suggestion {
+    pub fn span(&self) -> FileSpan {
+        self.span
+    }
+
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
 #[must_use]
 pub struct DiagnosticBuilder {
     severity: Severity,
@@ -113,6 +203,7 @@ pub struct DiagnosticBuilder {
 
     /// All labels added by user so far (primary or secondary).
     labels: Vec<Label>,
+    suggestions: Vec<Suggestion>,
     children: Vec<Diagnostic>,
 
     /// Initially true. Indicates if we should add a default primary
@@ -128,6 +219,7 @@ impl DiagnosticBuilder {
             span,
             message: message.to_string(),
             labels: vec![],
+            suggestions: vec![],
             children: vec![],
             add_primary_label: true,
         }
@@ -166,6 +258,19 @@ impl DiagnosticBuilder {
         self
     }
 
+    /// Suggest replacing the text at `span` with `replacement` to fix
+    /// the problem (if you supply a [`Span`] and not a [`FileSpan`], the
+    /// [`Span`] is assumed to be in the same file as the primary location).
+    #[must_use = "you have not emitted the diagnostic"]
+    pub fn suggestion(mut self, span: impl IntoFileSpan, replacement: impl ToString) -> Self {
+        let span = span.maybe_in_file(self.span.filename);
+        self.suggestions.push(Suggestion {
+            span,
+            replacement: replacement.to_string(),
+        });
+        self
+    }
+
     /// Add a child diagnostic. Our severity is raised to at least
     /// the child's level.
     #[must_use = "you have not emitted the diagnostic"]
@@ -190,6 +295,7 @@ impl DiagnosticBuilder {
             span: self.span,
             message: self.message,
             labels: self.labels,
+            suggestions: self.suggestions,
             children: self.children,
         }
     }
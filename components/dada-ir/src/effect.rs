@@ -3,6 +3,10 @@
 /// Ordering: a "lesser" effect permits fewer things.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Effect {
+    /// Free of side effects (`const fn`). Does not permit await statements,
+    /// atomic statements, or assignments to state outside the function.
+    Const,
+
     /// Executes atomically. Permits atomic statements, but they are no-ops.
     Atomic,
 
@@ -11,6 +15,10 @@ pub enum Effect {
 
     /// May contain "await" statements, permits atomic statements.
     Async,
+
+    /// May contain "yield" expressions (`gen fn`). Like an async function,
+    /// permits await and atomic statements.
+    Generator,
 }
 
 impl Effect {
@@ -18,11 +26,19 @@ impl Effect {
         self >= Effect::Async
     }
 
+    pub fn permits_yield(self) -> bool {
+        self == Effect::Generator
+    }
+
+    pub fn is_const(self) -> bool {
+        self == Effect::Const
+    }
+
     pub fn permits_atomic(self) -> bool {
         self >= Effect::Atomic
     }
 
     pub fn is_atomic(self) -> bool {
-        self <= Effect::Atomic
+        self == Effect::Atomic
     }
 }
@@ -1,9 +1,12 @@
 use crate::{
+    attribute::Attribute,
+    class::Class,
     code::UnparsedCode,
     effect::Effect,
     filename::Filename,
     return_type::ReturnType,
-    span::FileSpan,
+    span::{FileSpan, Span},
+    visibility::Visibility,
     word::{SpannedWord, Word},
 };
 
@@ -11,11 +14,24 @@ salsa::entity2! {
     entity Function in crate::Jar {
         #[id] name: SpannedWord,
 
+        /// Generic type parameters, e.g. `T` and `U` in `fn f[T, U]()`.
+        #[value ref] generics: Vec<SpannedWord>,
+
+        /// The lines of the doc comment above the function (`## ...`), without
+        /// the leading `##`. The span of each line is that of the whole comment.
+        #[value ref] docs: Vec<SpannedWord>,
+
+        /// Attributes written before the function, like `@test`.
+        #[value ref] attributes: Vec<Attribute>,
+
+        /// `Pub` if the function was declared with `pub fn`.
+        visibility: Visibility,
+
         /// Declared effect for the function body -- e.g., `async fn` would have
-        /// this be `async`. This can affect validation and code generation.
+        /// this be `async`, and `const fn` would have this be `const`. This can affect validation and code generation.
         effect: Effect,
 
-        /// If this func has a declared effect, this is the span of that keyword (e.g., `async`)
+        /// If this func has a declared effect, this is the span of that keyword (e.g., `async` or `const`)
         /// Otherwise, it is the span of the `fn` keyword.
         effect_span: FileSpan,
 
@@ -34,6 +50,10 @@ salsa::entity2! {
 
         /// Overall span of the function (including the code)
         span: FileSpan,
+
+        /// If this function is a method, the class that declares it.
+        /// Methods have an implicit `self` parameter.
+        class: Option<Class>,
     }
 }
 
@@ -48,6 +68,45 @@ impl Function {
     pub fn filename(self, db: &dyn crate::Db) -> Filename {
         self.span(db).filename
     }
+
+    /// The attribute named `name` (without the `@`), if the function has one.
+    pub fn attribute(self, db: &dyn crate::Db, name: &str) -> Option<&Attribute> {
+        self.attributes(db)
+            .iter()
+            .find(|attribute| attribute.name.as_str(db) == name)
+    }
+
+    /// The text of the doc comment, with one line per comment line,
+    /// or `None` if the function is not documented.
+    pub fn doc_text(self, db: &dyn crate::Db) -> Option<String> {
+        let docs = self.docs(db);
+        if docs.is_empty() {
+            return None;
+        }
+        let lines: Vec<&str> = docs.iter().map(|line| line.as_str(db)).collect();
+        Some(lines.join("\n"))
+    }
+
+    /// The span of the parameter list, including the parentheses.
+    /// Functions without unparsed code, like the `main` function synthesized
+    /// from top-level expressions, have no parameter list, so this is the
+    /// span of the whole function.
+    pub fn params_span(self, db: &dyn crate::Db) -> FileSpan {
+        let Some(unparsed_code) = self.unparsed_code(db) else {
+            return self.span(db);
+        };
+
+        // The parameter tokens exclude the `(` and the `)`, which is missing
+        // if the list was never closed.
+        let filename = self.filename(db);
+        let tokens_span = unparsed_code.parameter_tokens.span(db);
+        let start = usize::from(tokens_span.start) - 1;
+        let mut end = usize::from(tokens_span.end);
+        if crate::manifest::source_text(db, filename)[end..].starts_with(')') {
+            end += 1;
+        }
+        Span::from(start, end).in_file(filename)
+    }
 }
 
 salsa::entity2! {
@@ -0,0 +1,21 @@
+use std::path::Path;
+
+use crate::{filename::Filename, word::SpannedWord};
+
+/// A `use module.name` declaration at the top level of a file. It brings the
+/// `pub` item `name` of the module into scope, where the module is the file
+/// `module.dada` in the same directory as the importing file.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Import {
+    pub module: SpannedWord,
+    pub name: SpannedWord,
+}
+
+impl Import {
+    /// The file that `importing_file` refers to with this import's module name.
+    pub fn module_filename(self, db: &dyn crate::Db, importing_file: Filename) -> Filename {
+        let module = self.module.as_str(db);
+        let path = Path::new(importing_file.as_str(db)).with_file_name(format!("{module}.dada"));
+        Filename::from(db, &path)
+    }
+}
@@ -30,4 +30,16 @@ macro_rules! intrinsic {
 
 intrinsic! {
     Print => "print",
+    Len => "len",
+}
+
+impl Intrinsic {
+    /// True if calling the intrinsic has effects outside the program,
+    /// such as printing.
+    pub fn performs_io(self) -> bool {
+        match self {
+            Intrinsic::Print => true,
+            Intrinsic::Len => false,
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{class::Class, function::Function, span::FileSpan, word::Word};
+use crate::{class::Class, function::Function, span::FileSpan, visibility::Visibility, word::Word};
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 pub enum Item {
@@ -28,6 +28,13 @@ impl Item {
         }
     }
 
+    pub fn visibility(self, db: &dyn crate::Db) -> Visibility {
+        match self {
+            Item::Function(f) => f.visibility(db),
+            Item::Class(c) => c.visibility(db),
+        }
+    }
+
     pub fn kind_str(self) -> &'static str {
         match self {
             Item::Function(_) => "function",
@@ -47,15 +47,22 @@ define_keywords! {
     Async => "async",
     Atomic => "atomic",
     Await => "await",
+    Break => "break",
     Class => "class",
+    Const => "const",
+    Continue => "continue",
     Else => "else",
     False => "false",
     Fn => "fn",
+    For => "for",
+    Gen => "gen",
     Give => "give",
     If => "if",
+    In => "in",
     Lease => "lease",
     Leased => "leased",
     Loop => "loop",
+    Match => "match",
     My => "my",
     Return => "return",
     Share => "share",
@@ -63,8 +70,13 @@ define_keywords! {
     Shlease => "shlease",
     Shleased => "shleased",
     True => "true",
+    Use => "use",
+    Var => "var",
     Our => "our",
+    Pub => "pub",
+    Weak => "weak",
     While => "while",
+    Yield => "yield",
 }
 
 #[salsa::memoized(in crate::Jar ref)]
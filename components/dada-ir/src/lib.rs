@@ -1,6 +1,7 @@
 #[macro_use]
 pub mod origin_table;
 
+pub mod attribute;
 pub mod class;
 pub mod code;
 pub mod diagnostic;
@@ -8,6 +9,7 @@ pub mod effect;
 pub mod filename;
 pub mod format_string;
 pub mod function;
+pub mod import;
 pub mod in_ir_db;
 pub mod intrinsic;
 pub mod item;
@@ -23,6 +25,7 @@ pub mod storage;
 pub mod token;
 pub mod token_tree;
 pub mod ty;
+pub mod visibility;
 pub mod word;
 
 #[salsa::jar(Db)]
@@ -40,6 +43,7 @@ pub struct Jar(
     kw::keywords,
     lines::line_table,
     manifest::source_text,
+    manifest::has_source_text,
     parameter::Parameter,
     source_file::SourceFile,
     storage::SpannedSpecifier,
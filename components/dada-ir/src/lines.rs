@@ -14,6 +14,8 @@ pub struct LineTable {
 struct LineInfo {
     /// Offset of line start
     start: Offset,
+    /// Offset of the `\n` that ends the line (or the end of the file, for the last line)
+    end: Offset,
     /// Spans of chars with utf8 length > 1
     wide_chars: Vec<Span>,
 }
@@ -23,14 +25,17 @@ impl LineTable {
         let mut table = LineTable {
             lines: vec![LineInfo {
                 start: Offset::from(0u32),
+                end: Offset::from(source_text.len()),
                 wide_chars: Vec::new(),
             }],
             end_offset: Offset::from(source_text.len()),
         };
         for (i, c) in source_text.char_indices() {
             if c == '\n' {
+                table.lines.last_mut().unwrap().end = Offset::from(i);
                 table.lines.push(LineInfo {
                     start: Offset::from(i + 1),
+                    end: Offset::from(source_text.len()),
                     wide_chars: Vec::new(),
                 })
             } else if c.len_utf8() > 1 {
@@ -58,7 +63,8 @@ impl LineTable {
                 offset += wc.len() - 1;
             }
         }
-        Offset::from(offset).min(self.end_offset)
+        // A column past the end of the line stays on that line.
+        Offset::from(offset).min(line.end)
     }
 
     fn line_column(&self, position: Offset) -> LineColumn {
@@ -82,6 +88,55 @@ impl LineTable {
             }
         }
     }
+
+    /// Like [`Self::line_column`], but a tab advances the column to the next
+    /// multiple of `tab_width`.
+    fn line_column_with_tab_width(
+        &self,
+        source_text: &str,
+        position: Offset,
+        tab_width: u32,
+    ) -> LineColumn {
+        let line0 = self.line_column(position).line0_usize();
+        let line = &self.lines[line0];
+        let text = &source_text[usize::from(line.start)..usize::from(position)];
+        let column0 = text
+            .chars()
+            .fold(0, |column0, c| advance_column(column0, c, tab_width));
+        LineColumn::new0(line0, column0)
+    }
+
+    /// Like [`Self::offset`], but a tab advances the column to the next
+    /// multiple of `tab_width`. A column in the middle of a tab maps to the tab.
+    fn offset_with_tab_width(
+        &self,
+        source_text: &str,
+        position: LineColumn,
+        tab_width: u32,
+    ) -> Offset {
+        if position.line0_usize() >= self.num_lines() {
+            return self.end_offset;
+        }
+        let line = &self.lines[position.line0_usize()];
+        let text = &source_text[usize::from(line.start)..usize::from(line.end)];
+        let mut column0 = 0;
+        for (i, c) in text.char_indices() {
+            column0 = advance_column(column0, c, tab_width);
+            if column0 > position.column0() {
+                return line.start + i;
+            }
+        }
+        line.end
+    }
+}
+
+/// The column after `c`, if `c` starts at `column0`.
+fn advance_column(column0: u32, c: char, tab_width: u32) -> u32 {
+    if c == '\t' && tab_width > 0 {
+        (column0 / tab_width + 1) * tab_width
+    } else {
+        column0 + 1
+    }
 }
 
 /// Converts a character index `position` into a line and column tuple.
@@ -96,6 +151,31 @@ pub fn offset(db: &dyn crate::Db, filename: Filename, position: LineColumn) -> O
     table.offset(position)
 }
 
+/// Like [`line_column`], but expands tabs to the next multiple of `tab_width` columns,
+/// as an editor displaying the file would.
+pub fn line_column_with_tab_width(
+    db: &dyn crate::Db,
+    filename: Filename,
+    position: Offset,
+    tab_width: u32,
+) -> LineColumn {
+    let source_text = crate::manifest::source_text(db, filename);
+    let table = line_table(db, filename);
+    table.line_column_with_tab_width(source_text, position, tab_width)
+}
+
+/// The inverse of [`line_column_with_tab_width`].
+pub fn offset_with_tab_width(
+    db: &dyn crate::Db,
+    filename: Filename,
+    position: LineColumn,
+    tab_width: u32,
+) -> Offset {
+    let source_text = crate::manifest::source_text(db, filename);
+    let table = line_table(db, filename);
+    table.offset_with_tab_width(source_text, position, tab_width)
+}
+
 #[salsa::memoized(in crate::Jar ref)]
 fn line_table(db: &dyn crate::Db, filename: Filename) -> LineTable {
     let source_text = crate::manifest::source_text(db, filename);
@@ -135,6 +215,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn trailing_newline() {
+        let source_text = "foo\nbar\n";
+        check_line_column(source_text);
+
+        // The end of the file is the start of an empty last line.
+        let line_table = LineTable::new(source_text);
+        let end = Offset::from(source_text.len());
+        assert_eq!(line_table.line_column(end), LineColumn::new0(2u32, 0u32));
+        assert_eq!(line_table.offset(LineColumn::new0(2u32, 0u32)), end);
+        assert_eq!(line_table.offset(LineColumn::new0(5u32, 0u32)), end);
+    }
+
+    #[test]
+    fn column_past_end_of_line() {
+        let line_table = LineTable::new("foo\r\nbar\n");
+        assert_eq!(
+            line_table.offset(LineColumn::new0(0u32, 10u32)),
+            Offset::from(4u32)
+        );
+        assert_eq!(
+            line_table.offset(LineColumn::new0(1u32, 10u32)),
+            Offset::from(8u32)
+        );
+    }
+
+    #[test]
+    fn tabs() {
+        let source_text = "a\tb\n\t\tc\r\nd";
+        let line_table = LineTable::new(source_text);
+        let check = |offset: u32, line0: u32, column0: u32| {
+            let position = LineColumn::new0(line0, column0);
+            let offset = Offset::from(offset);
+            assert_eq!(
+                line_table.line_column_with_tab_width(source_text, offset, 4),
+                position
+            );
+            assert_eq!(
+                line_table.offset_with_tab_width(source_text, position, 4),
+                offset
+            );
+        };
+        check(0, 0, 0); // a
+        check(1, 0, 1); // tab
+        check(2, 0, 4); // b
+        check(4, 1, 0); // first tab
+        check(5, 1, 4); // second tab
+        check(6, 1, 8); // c
+        check(7, 1, 9); // \r
+        check(9, 2, 0); // d
+        check(10, 2, 1); // end of file
+
+        // A column within a tab maps to the tab.
+        assert_eq!(
+            line_table.offset_with_tab_width(source_text, LineColumn::new0(1u32, 6u32), 4),
+            Offset::from(5u32)
+        );
+    }
+
     #[test]
     fn crlf_line_endings() {
         check_line_column("foo\r\nb🙂ar\r\nbaz")
@@ -5,3 +5,10 @@ use crate::filename::Filename;
 pub fn source_text(_db: &dyn crate::Db, _filename: Filename) -> String {
     panic!("input")
 }
+
+/// True if the source text of `filename` has been set, which makes it
+/// available to be imported by other files. Set along with the source text.
+#[salsa::memoized(in crate::Jar)]
+pub fn has_source_text(_db: &dyn crate::Db, _filename: Filename) -> bool {
+    false
+}
@@ -2,14 +2,25 @@
 /// something in the IR. The meaning of origin depends on the IR:
 /// for a syntax tree, we map directly into the input. For other IRs,
 /// we typically map back to the previous IR.
+///
+/// Each `field: Key => Origin` has an origin for every key. Fields listed
+/// after `..` are ordinary fields, for origins that only some keys have;
+/// the table must provide its own methods to access them.
 macro_rules! origin_table {
-    ($(#[$attr:meta])* $pub:vis struct $table:ident { $($(#[$field_attr:meta])* $field:ident : $key:ty => $origins:ty,)* }) => {
+    ($(#[$attr:meta])* $pub:vis struct $table:ident {
+        $($(#[$field_attr:meta])* $field:ident : $key:ty => $origins:ty,)*
+        $(.. $($(#[$extra_field_attr:meta])* $extra_field:ident : $extra_ty:ty,)*)?
+    }) => {
         $(#[$attr])*
         $pub struct $table {
             $(
                 $(#[$field_attr])*
                 $field: dada_collections::IndexVec<$key, $origins>,
             )*
+            $($(
+                $(#[$extra_field_attr])*
+                $extra_field: $extra_ty,
+            )*)?
         }
 
         impl<K> std::ops::Index<K> for $table
@@ -37,6 +48,13 @@ macro_rules! origin_table {
             {
                 K::push_origin_in(k, self, s)
             }
+
+            /// Removes all origins, keeping the allocations where possible,
+            /// so that the table can be reused for another tree.
+            $pub fn clear(&mut self) {
+                $(self.$field.clear();)*
+                $($(self.$extra_field = Default::default();)*)?
+            }
         }
 
         $(
@@ -6,5 +6,8 @@ salsa::entity2! {
         #[id] name: Word,
         decl: crate::code::syntax::LocalVariableDeclData,
         decl_span: crate::code::syntax::LocalVariableDeclSpan,
+
+        /// The default value (`x = 22`), if any, parsed as its own syntax tree.
+        default: Option<crate::code::syntax::Tree>,
     }
 }
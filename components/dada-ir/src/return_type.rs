@@ -1,31 +1,61 @@
 use crate::span::FileSpan;
+use crate::ty::Ty;
 
 salsa::entity2! {
     /// Represents the return type of a function.
     ///
-    /// If `kind` is [ReturnTypeKind::Value] `span` is the span of `->`.
+    /// If the function has `->`, `span` is the span of `->`
+    /// (extended over the declared type, if any).
     ///
     /// If `kind` is [ReturnTypeKind::Unit] `span` is the span between parameters and body.
     entity ReturnType in crate::Jar {
         kind: ReturnTypeKind,
         span: FileSpan,
+
+        /// If the declared type is a tuple like `(Int, Int)`, the number of elements.
+        tuple_arity: Option<usize>,
+
+        /// If the declared type is a name like `Int`, that type.
+        ty: Option<Ty>,
     }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ReturnTypeKind {
-    Value,
+    /// `-> Int` or `-> (Int, Int)`: the function returns a value of the declared type.
+    Declared,
+
+    /// `->` without a type: the function returns a value, and its type is
+    /// inferred from the body.
+    Inferred,
+
+    /// `-> ()`: the function returns unit, as written explicitly.
+    ExplicitUnit,
+
+    /// No `->`: the function returns unit.
     Unit,
 }
 
+impl ReturnTypeKind {
+    /// True if the function returns a value (rather than unit).
+    pub fn returns_value(self) -> bool {
+        match self {
+            ReturnTypeKind::Declared | ReturnTypeKind::Inferred => true,
+            ReturnTypeKind::ExplicitUnit | ReturnTypeKind::Unit => false,
+        }
+    }
+}
+
 impl<Db: ?Sized + crate::Db> salsa::DebugWithDb<Db> for ReturnType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &Db) -> std::fmt::Result {
         let db = db.as_dyn_ir_db();
         write!(
             f,
-            "ReturnType({:?}, {:?})",
+            "ReturnType({:?}, {:?}, {:?}, {:?})",
             self.kind(db),
-            self.span(db).into_debug(db)
+            self.span(db).into_debug(db),
+            self.tuple_arity(db),
+            self.ty(db).into_debug(db),
         )
     }
 }
@@ -1,4 +1,5 @@
 use crate::function::Function;
+use crate::import::Import;
 use crate::{filename::Filename, item::Item};
 
 salsa::entity2! {
@@ -7,6 +8,9 @@ salsa::entity2! {
     entity SourceFile in crate::Jar {
         #[id] filename: Filename,
 
+        /// The `use` declarations in the file.
+        #[value ref] imports: Vec<Import>,
+
         /// The items found in the file.
         #[value ref] items: Vec<Item>,
 
@@ -14,8 +14,22 @@ impl FileSpan {
     }
 
     /// True if the given character falls within this span.
+    /// See [`Span::contains`].
     pub fn contains(&self, offset: Offset) -> bool {
-        self.start <= offset && offset < self.end
+        Span::from(*self).contains(offset)
+    }
+
+    /// True if the two spans are in the same file and have some character
+    /// in common. See [`Span::overlaps`].
+    pub fn overlaps(&self, other: FileSpan) -> bool {
+        self.filename == other.filename && Span::from(*self).overlaps(Span::from(other))
+    }
+
+    /// Returns this span moved `amount` bytes later in the same file.
+    /// See [`Span::shifted`].
+    #[must_use]
+    pub fn shifted(self, amount: u32) -> FileSpan {
+        Span::from(self).shifted(amount).in_file(self.filename)
     }
 }
 
@@ -36,6 +50,7 @@ impl<Db: ?Sized + crate::Db> salsa::DebugWithDb<Db> for FileSpan {
     }
 }
 
+/// A range of a file, from `start` up to but not including `end`.
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Span {
     pub start: Offset,
@@ -154,6 +169,30 @@ impl Span {
         self.len() == 0
     }
 
+    /// True if the character at `offset` falls within this span. The end is
+    /// exclusive, so an empty span contains nothing.
+    pub fn contains(self, offset: Offset) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
+    /// True if the two spans have some character in common. Spans that are
+    /// merely adjacent (one ends where the other starts) do not overlap, and
+    /// neither does an empty span.
+    pub fn overlaps(self, other: Span) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+
+    /// Returns the smallest span that covers both `self` and `other` (and
+    /// anything between them). Unlike [`Span::to`], the spans may come in any
+    /// order.
+    #[must_use]
+    pub fn merge(self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
     #[must_use]
     pub fn to(self, other: Span) -> Span {
         assert!(self.start <= other.start && other.end >= self.end);
@@ -162,6 +201,16 @@ impl Span {
             end: other.end,
         }
     }
+
+    /// Returns this span moved `amount` bytes later, e.g. to turn a span
+    /// relative to the start of an item into one relative to the file.
+    #[must_use]
+    pub fn shifted(self, amount: u32) -> Span {
+        Span {
+            start: self.start + amount,
+            end: self.end + amount,
+        }
+    }
 }
 
 impl std::ops::Add<u32> for Offset {
@@ -256,3 +305,44 @@ impl U32OrUsize for usize {
         n
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_is_half_open() {
+        let span = Span::from(2u32, 5u32);
+        assert!(!span.contains(Offset::from(1u32)));
+        assert!(span.contains(Offset::from(2u32)));
+        assert!(span.contains(Offset::from(4u32)));
+        assert!(!span.contains(Offset::from(5u32)));
+    }
+
+    #[test]
+    fn empty_span_contains_nothing() {
+        let span = Span::from(3u32, 3u32);
+        assert!(!span.contains(Offset::from(3u32)));
+        assert!(!span.overlaps(Span::from(0u32, 10u32)));
+        assert!(!Span::from(0u32, 10u32).overlaps(span));
+    }
+
+    #[test]
+    fn adjacent_spans_do_not_overlap() {
+        let left = Span::from(2u32, 5u32);
+        let right = Span::from(5u32, 8u32);
+        assert!(!left.overlaps(right));
+        assert!(!right.overlaps(left));
+        assert!(left.overlaps(Span::from(4u32, 8u32)));
+        assert!(left.overlaps(Span::from(3u32, 4u32)));
+    }
+
+    #[test]
+    fn merge_covers_both_spans() {
+        let left = Span::from(2u32, 5u32);
+        let right = Span::from(7u32, 8u32);
+        assert_eq!(left.merge(right), Span::from(2u32, 8u32));
+        assert_eq!(right.merge(left), Span::from(2u32, 8u32));
+        assert_eq!(left.merge(Span::from(3u32, 3u32)), left);
+    }
+}
@@ -93,6 +93,24 @@ impl std::ops::BitOrAssign for Atomic {
     }
 }
 
+/// Whether a local variable was declared `weak`, like `weak x = y.share`.
+/// A weak variable is meant for cache-like references that do not keep
+/// their value alive.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Weak {
+    No,
+    Yes,
+}
+
+/// Whether a local variable can be assigned after it is declared. A variable
+/// declared `shared`, like `shared x = 22`, cannot; one declared `var`, or
+/// with neither keyword, can.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Mutable {
+    No,
+    Yes,
+}
+
 /// NB: Ordering is significant. As we traverse a path, we take the
 /// max of the joint properties for the various storage modes,
 /// and we want that to be atomic if any step was joint.
@@ -1,11 +1,114 @@
 use salsa::DebugWithDb;
 
+use crate::word::Word;
+
+/// The names of the types that are built into the language, which can be
+/// used without being declared.
+pub const BUILTIN_TYPE_NAMES: &[&str] = &[
+    "Bool", "Char", "Float", "Int", "Int32", "String", "UInt", "UInt32",
+];
+
 #[salsa::interned(Ty in super::Jar)]
 #[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
-pub enum TyData {}
+pub enum TyData {
+    /// A type written as a name, like `Int` or `Point`.
+    Named(Word),
+}
 
 impl DebugWithDb<dyn crate::Db + '_> for Ty {
-    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>, _db: &dyn crate::Db) -> std::fmt::Result {
-        unreachable!()
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>, db: &dyn crate::Db) -> std::fmt::Result {
+        match self.data(db) {
+            TyData::Named(name) => std::fmt::Debug::fmt(name.as_str(db), f),
+        }
+    }
+}
+
+/// The kinds of numbers that are told apart when inferring the types of
+/// literals and local variables.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum NumericTy {
+    /// An integer, like `22`, `22i`, or `22u`. Whether it is signed depends
+    /// on the suffix and is checked when operations execute. Integers are
+    /// 64 bits wide unless declared otherwise, see [`IntegerWidth`].
+    Integer,
+
+    /// A 64-bit float, like `2.2`.
+    Float,
+}
+
+impl NumericTy {
+    /// The numeric type of the built-in type named `name`, if it is numeric.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Int" | "UInt" | "Int32" | "UInt32" => Some(NumericTy::Integer),
+            "Float" => Some(NumericTy::Float),
+            _ => None,
+        }
+    }
+
+    /// Describes the type for error messages, like "an integer".
+    pub fn description(self) -> &'static str {
+        match self {
+            NumericTy::Integer => "an integer",
+            NumericTy::Float => "a float",
+        }
+    }
+}
+
+/// The values that a built-in integer type, like `Int32`, can hold.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct IntegerWidth {
+    /// The number of bits.
+    pub bits: u32,
+
+    /// True if the type holds negative values too.
+    pub signed: bool,
+}
+
+impl IntegerWidth {
+    /// The width of the built-in integer type named `name`, if it is one.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let (bits, signed) = match name {
+            "Int" => (64, true),
+            "UInt" => (64, false),
+            "Int32" => (32, true),
+            "UInt32" => (32, false),
+            _ => return None,
+        };
+        Some(IntegerWidth { bits, signed })
+    }
+
+    /// Describes the width for error messages, like "a 32-bit signed integer".
+    pub fn description(self) -> String {
+        let signedness = if self.signed { "signed" } else { "unsigned" };
+        format!("a {}-bit {} integer", self.bits, signedness)
+    }
+
+    /// True if `value` can be held by an integer of this width.
+    pub fn contains(self, value: i128) -> bool {
+        if self.signed {
+            let max = (1_i128 << (self.bits - 1)) - 1;
+            (-max - 1..=max).contains(&value)
+        } else {
+            (0..1_i128 << self.bits).contains(&value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_width_bounds() {
+        let int32 = IntegerWidth::from_name("Int32").unwrap();
+        assert!(int32.contains(i128::from(i32::MAX)));
+        assert!(int32.contains(i128::from(i32::MIN)));
+        assert!(!int32.contains(i128::from(i32::MAX) + 1));
+
+        let uint = IntegerWidth::from_name("UInt").unwrap();
+        assert!(uint.contains(i128::from(u64::MAX)));
+        assert!(!uint.contains(-1));
+        assert_eq!(IntegerWidth::from_name("Float"), None);
     }
 }
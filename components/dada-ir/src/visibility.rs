@@ -0,0 +1,15 @@
+/// Whether an item can be used outside of the file that declares it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Visibility {
+    /// The default: the item can only be used within its own file.
+    Private,
+
+    /// Declared with `pub`: the item can be used from other files.
+    Pub,
+}
+
+impl Visibility {
+    pub fn is_pub(self) -> bool {
+        self == Visibility::Pub
+    }
+}
@@ -19,6 +19,18 @@ pub struct Options {
     /// Log the BIR
     #[structopt(long)]
     log_bir: bool,
+
+    /// Report functions that call themselves
+    #[structopt(long)]
+    debug_recursion: bool,
+
+    /// Warn about functions that are never used
+    #[structopt(long)]
+    warn_dead_functions: bool,
+
+    /// Warn about variables that shadow a variable from an enclosing scope
+    #[structopt(long)]
+    warn_shadowing: bool,
 }
 
 impl Options {
@@ -30,6 +42,10 @@ impl Options {
                 .with_context(|| format!("reading `{}`", path.display()))?;
             let filename = dada_ir::filename::Filename::from(&db, path);
             db.update_file(filename, contents);
+            db.load_imports(filename, |path| std::fs::read_to_string(path).ok());
+            db.set_debug_recursion(filename, self.debug_recursion);
+            db.set_warn_dead_functions(filename, self.warn_dead_functions);
+            db.set_warn_shadowing(filename, self.warn_shadowing);
             all_diagnostics.extend(db.diagnostics(filename));
 
             if self.log_syntax_tree {
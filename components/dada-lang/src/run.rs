@@ -16,6 +16,10 @@ pub struct Options {
     #[structopt(long)]
     bir: Option<Regex>,
 
+    /// Instead of executing, print the syntax tree (with spans) for items whose names match the given regex
+    #[structopt(long)]
+    syntax: Option<Regex>,
+
     /// Instead of executing, print validated tree for items whose names match the given regex
     #[structopt(long)]
     validated: Option<Regex>,
@@ -29,6 +33,7 @@ impl Options {
             .with_context(|| format!("reading `{}`", self.path.display()))?;
         let filename = dada_ir::filename::Filename::from(&db, &self.path);
         db.update_file(filename, contents);
+        db.load_imports(filename, |path| std::fs::read_to_string(path).ok());
 
         for diagnostic in db.diagnostics(filename) {
             dada_error_format::print_diagnostic(&db, &diagnostic)?;
@@ -36,6 +41,18 @@ impl Options {
 
         let mut should_execute = true;
 
+        if let Some(name_regex) = &self.syntax {
+            for item in db.items(filename) {
+                let name = item.name(&db).as_str(&db);
+                if name_regex.is_match(name) {
+                    if let Some(tree) = db.debug_syntax_tree_with_spans(item) {
+                        tracing::info!("Syntax tree for {:?} is {:#?}", item.debug(&db), tree);
+                    }
+                }
+            }
+            should_execute = false;
+        }
+
         if let Some(name_regex) = &self.validated {
             for item in db.items(filename) {
                 let name = item.name(&db).as_str(&db);
@@ -5,7 +5,8 @@ use std::{env, fs};
 
 use dada_execute::kernel::BufferKernel;
 use dada_execute::machine::ProgramCounter;
-use dada_ir::{filename::Filename, item::Item};
+use dada_ir::{diagnostic::Severity, filename::Filename, function::Function, item::Item};
+use dada_parse::prelude::*;
 use eyre::Context;
 use lsp_types::Diagnostic;
 use regex::Regex;
@@ -165,6 +166,10 @@ impl Options {
             .with_context(|| format!("reading `{}`", &source_path.display()))?;
         let filename = dada_ir::filename::Filename::from(&db, &source_path);
         db.update_file(filename, contents);
+        db.load_imports(filename, |path| std::fs::read_to_string(path).ok());
+        db.set_debug_recursion(filename, true);
+        db.set_warn_dead_functions(filename, expected_diagnostics.warn_dead_functions);
+        db.set_warn_shadowing(filename, expected_diagnostics.warn_shadowing);
         let diagnostics = db.diagnostics(filename);
 
         let mut errors = Errors::default();
@@ -210,6 +215,7 @@ impl Options {
             &mut errors,
         )
         .await?;
+        self.check_formatted_round_trip(&mut db, path, filename, &diagnostics, &mut errors)?;
 
         for (query, query_index) in expected_queries.iter().zip(0..) {
             self.perform_query_on_db(&mut db, path, filename, query, query_index, &mut errors)
@@ -219,6 +225,68 @@ impl Options {
         errors.into_result()
     }
 
+    /// Formats the file and checks that parsing the result yields the same
+    /// syntax trees (ignoring spans and parentheses), and that formatting it
+    /// again changes nothing. Files with errors are skipped, as erroneous code
+    /// has no source form.
+    fn check_formatted_round_trip(
+        &self,
+        db: &mut dada_db::Db,
+        path: &Path,
+        filename: Filename,
+        diagnostics: &[dada_ir::diagnostic::Diagnostic],
+        errors: &mut Errors,
+    ) -> eyre::Result<()> {
+        if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+            return Ok(());
+        }
+
+        let formatted = db.formatted_source(filename);
+        let formatted_path = path.join("formatted.dada");
+        let formatted_filename = Filename::from(db, &formatted_path);
+        db.update_file(formatted_filename, formatted.clone());
+        let db = &*db;
+
+        let functions = |filename: Filename| -> Vec<Function> {
+            filename
+                .items(db)
+                .iter()
+                .filter_map(|item| match *item {
+                    Item::Function(function) => Some(function),
+                    _ => None,
+                })
+                .collect()
+        };
+        let original_functions = functions(filename);
+        let formatted_functions = functions(formatted_filename);
+        let changed_functions: Vec<String> = original_functions
+            .iter()
+            .zip(&formatted_functions)
+            .filter(|(original, formatted)| {
+                let original_tree = original.syntax_tree(db);
+                !original_tree.eq_modulo_formatting(formatted.syntax_tree(db), db)
+            })
+            .map(|(original, _)| original.name(db).as_str(db).to_string())
+            .collect();
+        if original_functions.len() != formatted_functions.len() || !changed_functions.is_empty() {
+            errors.push(FormattingChangedSyntaxTree {
+                formatted_path: formatted_path.clone(),
+                changed_functions,
+                formatted: formatted.clone(),
+            });
+        }
+
+        let reformatted = db.formatted_source(formatted_filename);
+        if formatted != reformatted {
+            errors.push(RefOutputDoesNotMatch {
+                ref_path: formatted_path,
+                expected: formatted,
+                actual: reformatted,
+            });
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip(self, lsp_client))]
     fn test_dada_file_in_ide(
         &self,
@@ -229,11 +297,22 @@ impl Options {
         lsp_client.send_open(&path.with_extension("dada"))?;
         let diagnostics = lsp_client.receive_errors()?;
 
+        // The language server does not set debug flags, so it reports no notes.
+        // Nor does it enable `#! OPTION` warnings, so in files that use them
+        // only errors are compared.
+        let expected_compile: Vec<_> = expected_diagnostics
+            .compile
+            .iter()
+            .filter(|d| d.severity != "NOTE")
+            .filter(|d| !(expected_diagnostics.sets_warning_option() && d.severity == "WARNING"))
+            .cloned()
+            .collect();
+
         let mut errors = Errors::default();
         self.match_diagnostics_against_expectations(
             &(),
             &diagnostics,
-            &expected_diagnostics.compile,
+            &expected_compile,
             &mut errors,
         )?;
         self.bless_debug_file(format!("{:#?}", diagnostics), &path.join("lsp.debug"))?;
@@ -624,6 +703,23 @@ fn display_diff(
     )
 }
 
+/// Parsing the formatted source of a file yields different syntax trees
+/// than the original source did.
+#[derive(Debug)]
+struct FormattingChangedSyntaxTree {
+    formatted_path: PathBuf,
+    changed_functions: Vec<String>,
+    formatted: String,
+}
+
+impl std::error::Error for FormattingChangedSyntaxTree {}
+
+impl std::fmt::Display for FormattingChangedSyntaxTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:#?}")
+    }
+}
+
 #[derive(Debug)]
 struct RefOutputDoesNotMatch {
     ref_path: PathBuf,
@@ -712,6 +808,19 @@ struct ExpectedDiagnostics {
 
     // Any `#! FIXME` annotations found
     fixmes: Vec<String>,
+
+    // True if the file has a `#! OPTION warn_dead_functions` line
+    warn_dead_functions: bool,
+
+    // True if the file has a `#! OPTION warn_shadowing` line
+    warn_shadowing: bool,
+}
+
+impl ExpectedDiagnostics {
+    /// True if the file enables warnings with an `#! OPTION` line.
+    fn sets_warning_option(&self) -> bool {
+        self.warn_dead_functions || self.warn_shadowing
+    }
 }
 
 /// Returns the diagnostics that we expect to see in the file, sorted by line number.
@@ -719,7 +828,7 @@ fn expected_diagnostics(path: &Path) -> eyre::Result<ExpectedDiagnostics> {
     let file_contents = std::fs::read_to_string(path)?;
 
     let diagnostic_marker = regex::Regex::new(
-        r"^(?P<prefix>[^#]*)#!\s*(?P<highlight>\^+)?\s*(?P<type>RUN)?\s*(?P<severity>ERROR|WARNING|INFO)\s*(?P<msg>.*)",
+        r"^(?P<prefix>[^#]*)#!\s*(?P<highlight>\^+)?\s*(?P<type>RUN)?\s*(?P<severity>ERROR|WARNING|NOTE|INFO)\s*(?P<msg>.*)",
     )
     .unwrap();
 
@@ -732,6 +841,8 @@ fn expected_diagnostics(path: &Path) -> eyre::Result<ExpectedDiagnostics> {
 
     let any_output_marker = regex::Regex::new(r"^(?P<prefix>[^#]*)#!\s*OUTPUT ANY").unwrap();
 
+    let option_marker = regex::Regex::new(r"^\s*#!\s*OPTION\s+(?P<option>\S+)\s*$").unwrap();
+
     let any_marker = regex::Regex::new(r"^[^#]*#!").unwrap();
 
     fn compute_line_number(
@@ -770,6 +881,8 @@ fn expected_diagnostics(path: &Path) -> eyre::Result<ExpectedDiagnostics> {
     let mut output = vec![];
     let mut fixmes = vec![];
     let mut any_output_marker_seen = None;
+    let mut warn_dead_functions = false;
+    let mut warn_shadowing = false;
     for (line, line_number) in file_contents.lines().zip(1..) {
         if let Some(c) = diagnostic_marker.captures(line) {
             let start_line = compute_line_number(&c["prefix"], None, last_code_line, line_number);
@@ -800,6 +913,12 @@ fn expected_diagnostics(path: &Path) -> eyre::Result<ExpectedDiagnostics> {
             }
         } else if any_output_marker.is_match(line) {
             any_output_marker_seen = Some(line_number);
+        } else if let Some(c) = option_marker.captures(line) {
+            match &c["option"] {
+                "warn_dead_functions" => warn_dead_functions = true,
+                "warn_shadowing" => warn_shadowing = true,
+                option => eyre::bail!("unknown option `{}` on line {}", option, line_number),
+            }
         } else if let Some(c) = output_marker.captures(line) {
             let line1 =
                 compute_line_number(&c["prefix"], c.name("adjust"), last_code_line, line_number);
@@ -838,6 +957,8 @@ fn expected_diagnostics(path: &Path) -> eyre::Result<ExpectedDiagnostics> {
             Some(output)
         },
         fixmes,
+        warn_dead_functions,
+        warn_shadowing,
     })
 }
 
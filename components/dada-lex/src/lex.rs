@@ -2,6 +2,7 @@ use dada_ir::filename::Filename;
 use dada_ir::format_string::{
     FormatString, FormatStringData, FormatStringSection, FormatStringSectionData,
 };
+use dada_ir::kw::Keyword;
 use dada_ir::span::{FileSpan, Offset, Span};
 use dada_ir::token::Token;
 use dada_ir::token_tree::TokenTree;
@@ -20,7 +21,10 @@ pub(crate) fn lex_filespan(db: &dyn crate::Db, span: FileSpan) -> TokenTree {
     lex_text(db, span.filename, &source_text[start..end], start)
 }
 
-fn lex_text(
+/// Lexes `source_text`, which starts `start_offset` bytes into `filename`.
+/// Only `source_text` is read, so text lexed with a `start_offset` of `0`
+/// yields spans relative to its own start.
+pub fn lex_text(
     db: &dyn crate::Db,
     filename: Filename,
     source_text: &str,
@@ -35,6 +39,8 @@ fn lex_text(
         db,
         filename,
         chars,
+        source_text,
+        start_offset,
         file_len: start_offset + source_text.len(),
     };
     lexer.lex_tokens(None)
@@ -50,9 +56,12 @@ pub fn closing_delimiter(ch: char) -> char {
     }
 }
 
+/// Opens and closes a multi-line string like `"""foo"""`.
+const TRIPLE_QUOTES: &str = "\"\"\"";
+
 macro_rules! op {
     () => {
-        '+' | '-' | '/' | '*' | '>' | '<' | '&' | '|' | '.' | ':' | ';' | '='
+        '+' | '-' | '/' | '*' | '>' | '<' | '&' | '|' | '^' | '.' | ':' | ';' | '=' | '!'
     };
 }
 
@@ -63,6 +72,11 @@ where
     db: &'me dyn crate::Db,
     filename: Filename,
     chars: &'me mut Peekable<I>,
+
+    /// The text being lexed, which starts at `start_offset` within the file.
+    source_text: &'me str,
+    start_offset: usize,
+
     file_len: usize,
 }
 
@@ -87,6 +101,13 @@ where
                 break;
             }
 
+            // If a `{` was never closed, don't swallow the rest of the file:
+            // an item at the start of a line ends the block, and the parser
+            // reports the missing `}`.
+            if end_ch == Some('}') && self.item_starts_line_at(pos) {
+                break;
+            }
+
             self.chars.next();
 
             match ch {
@@ -115,7 +136,15 @@ where
                         .map(|&(_, ch)| matches!(ch, '"' | '\''))
                         .unwrap_or(false);
 
-                    if is_prefix {
+                    let is_raw_string =
+                        text.as_str(self.db) == "r" && matches!(self.chars.peek(), Some(&(_, '"')));
+
+                    if is_raw_string {
+                        self.chars.next();
+                        push_token(Token::FormatString(
+                            self.raw_string_literal(Offset::from(pos)),
+                        ));
+                    } else if is_prefix {
                         push_token(Token::Prefix(text));
                     } else {
                         push_token(Token::Alphabetic(text));
@@ -130,14 +159,59 @@ where
                     push_token(Token::Comma);
                 }
                 '0'..='9' => {
-                    let text = self.accumulate(ch, |c| matches!(c, '0'..='9' | '_'));
+                    // `0x`, `0o`, and `0b` begin hexadecimal, octal, and binary literals.
+                    // The digits are checked against the base by the validator.
+                    let base_ch = match self.chars.peek() {
+                        Some(&(_, base_ch @ ('x' | 'o' | 'b'))) if ch == '0' => Some(base_ch),
+                        _ => None,
+                    };
+                    let text = match base_ch {
+                        Some(base_ch) => {
+                            self.chars.next();
+                            let digits = self.accumulate_string(base_ch, |c| {
+                                c.is_ascii_hexdigit() || c == '_'
+                            });
+                            Word::from(self.db, format!("{ch}{digits}"))
+                        }
+                        None => self.accumulate(ch, |c| matches!(c, '0'..='9' | '_')),
+                    };
                     push_token(Token::Number(text));
                 }
                 op!() => {
                     push_token(Token::Op(ch));
                 }
                 '"' => {
-                    push_token(Token::FormatString(self.string_literal(Offset::from(pos))));
+                    let triple_quoted = self.starts_with_at(pos, TRIPLE_QUOTES);
+                    if triple_quoted {
+                        self.chars.next();
+                        self.chars.next();
+                    }
+                    push_token(Token::FormatString(
+                        self.string_literal(Offset::from(pos), triple_quoted),
+                    ));
+                }
+                '\'' => {
+                    // `'a'` is a character literal, but `'a` followed by anything
+                    // other than a `'` is a label, like the `'outer` in `break 'outer`.
+                    let identifier_follows =
+                        matches!(self.chars.peek(), Some(&(_, 'a'..='z' | 'A'..='Z' | '_')));
+                    if identifier_follows {
+                        let (_, ch1) = self.chars.next().unwrap();
+                        let text = self.accumulate_string(
+                            ch1,
+                            |c| matches!(c, 'a'..='z' | 'A'..='Z' | '_' | '0'..='9'),
+                        );
+                        if let Some(&(_, '\'')) = self.chars.peek() {
+                            self.chars.next();
+                            let text = Word::from(self.db, format!("'{text}'"));
+                            push_token(Token::CharLiteral(text));
+                        } else {
+                            push_token(Token::Unknown(ch));
+                            push_token(Token::Alphabetic(Word::from(self.db, text)));
+                        }
+                    } else {
+                        push_token(Token::CharLiteral(self.char_literal(ch)));
+                    }
                 }
                 _ => {
                     if !ch.is_whitespace() {
@@ -163,6 +237,34 @@ where
         )
     }
 
+    /// True if an item (like `fn` or `class`, possibly with `pub`) starts
+    /// at `pos`, at the very beginning of a line. Indented items, like
+    /// methods and nested functions, belong to the enclosing block.
+    fn item_starts_line_at(&self, pos: usize) -> bool {
+        let pos = pos - self.start_offset;
+        if pos > 0 && !self.source_text[..pos].ends_with('\n') {
+            return false;
+        }
+        if self.source_text[pos..].starts_with(char::is_whitespace) {
+            return false;
+        }
+
+        let mut words = self.source_text[pos..].split_whitespace().peekable();
+        words.next_if_eq(&Keyword::Pub.str());
+        match words.next() {
+            Some(word) if word == Keyword::Class.str() || word == Keyword::Fn.str() => true,
+            Some(word) if word == Keyword::Async.str() || word == Keyword::Const.str() => {
+                words.next() == Some(Keyword::Fn.str())
+            }
+            _ => false,
+        }
+    }
+
+    /// True if the source text at `pos` starts with `text`.
+    fn starts_with_at(&self, pos: usize, text: &str) -> bool {
+        self.source_text[pos - self.start_offset..].starts_with(text)
+    }
+
     /// Returns the offset of the next character within the file.
     fn peek_offset(&mut self) -> usize {
         match self.chars.peek() {
@@ -193,18 +295,65 @@ where
         Word::from(self.db, string)
     }
 
-    /// Invoked after consuming a `"`
-    fn string_literal(&mut self, start: Offset) -> FormatString {
-        let mut buffer = StringFormatBuffer::new(self.db);
+    /// Invoked after consuming a `'`. Accumulates the text of the character
+    /// literal, including both quotes, stopping early at a newline if the
+    /// literal is unterminated. Checking the contents is left to the parser.
+    fn char_literal(&mut self, ch0: char) -> Word {
+        let mut string = String::new();
+        string.push(ch0);
         let mut is_backslash_previous = false;
-        while let Some((ch_offset, ch)) = self.chars.next() {
-            let ch_offset = Offset::from(ch_offset);
+        while let Some(&(_, ch)) = self.chars.peek() {
+            if ch == '\n' {
+                break;
+            }
+
+            self.chars.next();
+            string.push(ch);
 
-            if ch == '"' && !is_backslash_previous {
+            if ch == '\'' && !is_backslash_previous {
                 break;
             }
 
-            if ch == '{' && !is_backslash_previous {
+            is_backslash_previous = ch == '\\' && !is_backslash_previous;
+        }
+        Word::from(self.db, string)
+    }
+
+    /// Invoked after consuming a `"`, or all three quotes of a `"""`.
+    ///
+    /// Within a triple-quoted string, a `"` only ends the string if it begins a
+    /// `"""`; other quotes are recorded as if they had been escaped, so that
+    /// the string is equivalent to an ordinary one.
+    fn string_literal(&mut self, start: Offset, triple_quoted: bool) -> FormatString {
+        let mut buffer = StringFormatBuffer::new(self.db);
+        let mut terminated = false;
+        while let Some((ch_offset, ch)) = self.chars.next() {
+            if ch == '"' {
+                if !triple_quoted {
+                    terminated = true;
+                    break;
+                }
+
+                if self.starts_with_at(ch_offset, TRIPLE_QUOTES) {
+                    self.chars.next();
+                    self.chars.next();
+                    terminated = true;
+                    break;
+                }
+
+                buffer.push_char('\\');
+                buffer.push_char(ch);
+                continue;
+            }
+
+            if ch == '\\' {
+                self.string_escape(ch_offset, &mut buffer);
+                continue;
+            }
+
+            let ch_offset = Offset::from(ch_offset);
+
+            if ch == '{' {
                 // Format string! Grab a token tree.
                 let tree = self.lex_tokens(Some('}'));
                 buffer.push_tree(tree);
@@ -232,17 +381,123 @@ where
                 continue;
             }
 
-            if ch == '\\' {
-                is_backslash_previous = !is_backslash_previous;
-            } else {
-                is_backslash_previous = false;
+            buffer.push_char(ch);
+        }
+
+        if triple_quoted && !terminated {
+            self.report_unterminated_string(start, 3, TRIPLE_QUOTES);
+        }
+
+        buffer.flush_text();
+        self.format_string(start, buffer)
+    }
+
+    /// Invoked after consuming the `\\` at `backslash_offset` within a string
+    /// literal. Copies the escape into `buffer` as is, since escapes are
+    /// interpreted during validation, but reports it here if it is not one
+    /// we recognize, where we still know its exact span.
+    fn string_escape(&mut self, backslash_offset: usize, buffer: &mut StringFormatBuffer<'_>) {
+        buffer.push_char('\\');
+        let Some((_, ch)) = self.chars.next() else {
+            return;
+        };
+        buffer.push_char(ch);
+
+        match ch {
+            'n' | 'r' | 't' | '"' | '\\' | '{' | '}' => {}
+            'u' => self.unicode_escape(backslash_offset, buffer),
+            _ => {
+                dada_ir::error!(
+                    Span::from(backslash_offset, self.peek_offset()).in_file(self.filename),
+                    "unrecognized escape `\\{ch}`"
+                )
+                .emit(self.db);
+            }
+        }
+    }
+
+    /// Invoked after consuming the `\\u` of an escape like `\\u{1F600}`, which
+    /// must have between 1 and 6 hex digits giving a valid character.
+    /// Consumes the braces (so that they are not taken as a `{...}` expression)
+    /// and copies them into `buffer`.
+    fn unicode_escape(&mut self, backslash_offset: usize, buffer: &mut StringFormatBuffer<'_>) {
+        let mut text = String::new();
+        if let Some(&(_, '{')) = self.chars.peek() {
+            while let Some(&(_, ch)) = self.chars.peek() {
+                if matches!(ch, '"' | '\n') {
+                    break;
+                }
+
+                self.chars.next();
+                text.push(ch);
+                if ch == '}' {
+                    break;
+                }
             }
+        }
+        buffer.push_str(&text);
+
+        let is_valid = text
+            .strip_prefix('{')
+            .and_then(|text| text.strip_suffix('}'))
+            .filter(|digits| (1..=6).contains(&digits.len()))
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .and_then(char::from_u32)
+            .is_some();
+        if !is_valid {
+            dada_ir::error!(
+                Span::from(backslash_offset, self.peek_offset()).in_file(self.filename),
+                "malformed unicode escape"
+            )
+            .primary_label("expected 1 to 6 hex digits in braces, like `\\u{1F600}`")
+            .emit(self.db);
+        }
+    }
 
+    /// Invoked after consuming the `r"` of a raw string like `r"C:\path"`.
+    /// Raw strings contain neither escapes nor `{...}` expressions. Escapes are
+    /// interpreted later (during validation), so we escape the characters that
+    /// would otherwise be interpreted, making the string equivalent to an
+    /// ordinary one.
+    fn raw_string_literal(&mut self, start: Offset) -> FormatString {
+        let mut buffer = StringFormatBuffer::new(self.db);
+        let mut terminated = false;
+        for (_, ch) in &mut *self.chars {
+            if ch == '"' {
+                terminated = true;
+                break;
+            }
+
+            if matches!(ch, '\\' | '{' | '}') {
+                buffer.push_char('\\');
+            }
             buffer.push_char(ch);
         }
 
+        if !terminated {
+            self.report_unterminated_string(start, 2, "\"");
+        }
+
         buffer.flush_text();
+        self.format_string(start, buffer)
+    }
 
+    /// Reports a string literal starting at `start` that was never closed,
+    /// highlighting its opening quotes (the first `opening_len` bytes).
+    fn report_unterminated_string(&self, start: Offset, opening_len: u32, closing_quotes: &str) {
+        dada_ir::error!(
+            Span {
+                start,
+                end: start + opening_len,
+            }
+            .in_file(self.filename),
+            "unterminated string literal"
+        )
+        .primary_label(format!("expected a closing `{closing_quotes}`"))
+        .emit(self.db);
+    }
+
+    fn format_string(&mut self, start: Offset, buffer: StringFormatBuffer<'_>) -> FormatString {
         let end = Offset::from(self.peek_offset());
 
         FormatStringData {
@@ -272,6 +527,10 @@ impl<'me> StringFormatBuffer<'me> {
         self.text.push(ch);
     }
 
+    fn push_str(&mut self, s: &str) {
+        self.text.push_str(s);
+    }
+
     fn push_tree(&mut self, token_tree: TokenTree) {
         self.flush_text();
         self.sections
@@ -21,3 +21,4 @@ where
 
 pub use lex::closing_delimiter;
 pub use lex::lex_file;
+pub use lex::lex_text;
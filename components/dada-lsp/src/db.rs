@@ -8,6 +8,13 @@ use lsp_types::{
 };
 use salsa::ParallelDatabase;
 
+/// Reads the file at `uri` from disk. Used for the modules that an open file
+/// imports, which the editor may not have opened.
+fn read_uri(uri: &str) -> Option<String> {
+    let path = Url::parse(uri).ok()?.to_file_path().ok()?;
+    std::fs::read_to_string(path).ok()
+}
+
 pub struct LspServerDatabase {
     db: dada_db::Db,
     threads: threadpool::ThreadPool,
@@ -32,6 +39,7 @@ impl LspServerDatabase {
         let filename = self.filename_from_uri(&params.text_document.uri);
         let source_text = params.text_document.text;
         self.db.update_file(filename, source_text);
+        self.db.load_imports(filename, read_uri);
         self.spawn_check(
             params.text_document.uri,
             params.text_document.version,
@@ -45,6 +53,7 @@ impl LspServerDatabase {
         let change = params.content_changes.into_iter().next().unwrap();
         let source_text = change.text;
         self.db.update_file(filename, source_text);
+        self.db.load_imports(filename, read_uri);
         self.spawn_check(
             params.text_document.uri,
             params.text_document.version,
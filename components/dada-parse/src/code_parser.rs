@@ -1,17 +1,187 @@
-use crate::{parser::Parser, prelude::*};
+use crate::{parameter_parser::self_parameter, parser::Parser, prelude::*};
 
-use dada_ir::{code::syntax::Tree, function::Function};
+use dada_id::prelude::*;
+use dada_ir::{
+    code::syntax::{self, Spans, Tree, TreeData},
+    diagnostic::{self, Diagnostic},
+    filename::Filename,
+    function::Function,
+    storage::SpannedSpecifier,
+    token::Token,
+    token_tree::TokenTree,
+    visibility::Visibility,
+    word::{SpannedOptionalWord, SpannedWord},
+};
+use dada_lex::prelude::*;
 use salsa::DebugWithDb;
 
+/// The text of a function, from its first keyword to the end of its body.
+/// Bodies are parsed from this text alone, so a function whose text is
+/// unchanged is not reparsed when an edit elsewhere in the file moves it.
+#[salsa::interned(FunctionText in crate::Jar)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionTextData {
+    filename: Filename,
+
+    /// True for methods, which have an implicit `self` parameter.
+    is_method: bool,
+
+    text: String,
+}
+
+/// Returns the syntax tree for the body of `function`. The body is parsed by
+/// [`parse_function_body`] from the function's own text; this only moves the
+/// spans of the result, and of the errors found while parsing, to where the
+/// function is in its file.
 #[salsa::component(in crate::Jar)]
-pub fn parse_function_body(db: &dyn crate::Db, function: Function) -> Tree {
-    if let Some(unparsed_code) = function.unparsed_code(db) {
-        let body = unparsed_code.body_tokens;
-        Parser::new(db, body).parse_code_body(function.parameters(db))
-    } else {
+pub fn function_syntax_tree(db: &dyn crate::Db, function: Function) -> Tree {
+    if function.unparsed_code(db).is_none() {
         panic!(
             "cannot parse function `{:?}` which did not have unparsed code",
             function.debug(db)
         );
     }
+
+    let (tree, diagnostics) = parse_function_body(db, function_text(db, function));
+
+    let start = u32::from(function.span(db).start);
+    for diagnostic in diagnostics {
+        diagnostic.clone().shifted(start).emit(db);
+    }
+    shift_tree(db, *tree, start)
+}
+
+/// Returns the text of `function`, which its body is parsed from.
+pub(crate) fn function_text(db: &dyn crate::Db, function: Function) -> FunctionText {
+    let span = function.span(db);
+    FunctionTextData {
+        filename: span.filename,
+        is_method: function.class(db).is_some(),
+        text: span.snippet(db).to_string(),
+    }
+    .intern(db)
+}
+
+/// Parses the body of the function whose text is `text`. The spans in the
+/// tree, and in the errors found while parsing, are relative to the start
+/// of `text`. The errors are returned rather than reported, so that
+/// [`function_syntax_tree`] can report them where the function is now.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub(crate) fn parse_function_body(
+    db: &dyn crate::Db,
+    text: FunctionText,
+) -> (Tree, Vec<Diagnostic>) {
+    let data = text.data(db);
+
+    // Errors in the tokens and in the parameters are reported when the file
+    // is lexed and the parameters are parsed, so they are dropped here.
+    let (tokens, _) = diagnostic::capture(|| dada_lex::lex_text(db, data.filename, &data.text, 0));
+    let (parameter_tokens, body_tokens) = parameter_and_body_tokens(db, tokens);
+    let (parameters, _) = diagnostic::capture(|| {
+        let mut parameters =
+            Parser::with_source_text(db, parameter_tokens, &data.text).parse_only_parameters();
+        if data.is_method {
+            parameters.insert(0, self_parameter(db, parameter_tokens));
+        }
+        parameters
+    });
+
+    diagnostic::capture(|| {
+        Parser::with_source_text(db, body_tokens, &data.text).parse_code_body(&parameters)
+    })
+}
+
+/// Returns the tokens of the parameter list and of the body among the
+/// `tokens` of a function's text.
+fn parameter_and_body_tokens(db: &dyn crate::Db, tokens: TokenTree) -> (TokenTree, TokenTree) {
+    let mut delimiter = None;
+    let mut parameter_tokens = None;
+    let mut body_tokens = None;
+    for &token in tokens.tokens(db) {
+        match token {
+            Token::Delimiter(ch) => delimiter = Some(ch),
+            // The lexer always produces a tree after an opening delimiter.
+            // The parameters are the first `(...)`, the body the last `{...}`.
+            Token::Tree(tree) => match delimiter {
+                Some('(') if parameter_tokens.is_none() => parameter_tokens = Some(tree),
+                Some('{') => body_tokens = Some(tree),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    (
+        parameter_tokens.expect("function text without parameters"),
+        body_tokens.expect("function text without a body"),
+    )
+}
+
+/// Returns a copy of `tree` with its spans, and those of the entities
+/// created while parsing it, moved `amount` bytes later.
+fn shift_tree(db: &dyn crate::Db, tree: Tree, amount: u32) -> Tree {
+    let TreeData {
+        tables,
+        parameter_decls,
+        root_expr,
+    } = tree.data(db);
+    let mut tables = tables.clone();
+
+    for expr in syntax::Expr::max_key(&tables).iter() {
+        if let syntax::ExprData::Fn(function) = tables[expr] {
+            tables[expr] = syntax::ExprData::Fn(shift_function(db, function, amount));
+        }
+    }
+
+    for named_expr in syntax::NamedExpr::max_key(&tables).iter() {
+        let name = tables[named_expr].name;
+        tables[named_expr].name =
+            SpannedOptionalWord::new(db, name.word(db), name.span(db).shifted(amount));
+    }
+
+    for decl in syntax::LocalVariableDecl::max_key(&tables).iter() {
+        let specifier = tables[decl].specifier;
+        tables[decl].specifier = SpannedSpecifier::new(
+            db,
+            specifier.specifier(db),
+            specifier.defaulted(db),
+            specifier.span(db).shifted(amount),
+        );
+    }
+
+    let tree_data = TreeData {
+        tables,
+        parameter_decls: parameter_decls.clone(),
+        root_expr: *root_expr,
+    };
+    Tree::new(db, tree_data, tree.spans(db).shifted(amount))
+}
+
+/// Returns the function declared in a body, which was parsed with relative
+/// spans, as it is declared `amount` bytes later. Its header is parsed again
+/// from there; its errors were already found when parsing the body.
+fn shift_function(db: &dyn crate::Db, function: Function, amount: u32) -> Function {
+    let docs = function
+        .docs(db)
+        .iter()
+        .map(|doc| SpannedWord::new(db, doc.word(db), doc.span(db).shifted(amount)))
+        .collect();
+    let (shifted_function, _) = diagnostic::capture(|| {
+        let tokens = function.span(db).shifted(amount).tokens(db);
+        Parser::new(db, tokens).parse_function(docs, vec![], Visibility::Private, None)
+    });
+
+    // The text is the same as when the body was parsed, so this only
+    // falls back to the unshifted function if lexing it on its own differs.
+    shifted_function.unwrap_or(function)
+}
+
+/// Returns the spans for the body of `function`. Code that only needs spans
+/// (e.g., to report a diagnostic) should use this rather than reading them
+/// from the tree, so that it is reused when a reparse leaves the spans
+/// unchanged. Parsing never depends on this query.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub fn function_spans(db: &dyn crate::Db, function: Function) -> Spans {
+    function.syntax_tree(db).spans(db).clone()
 }
@@ -0,0 +1,41 @@
+//! Formats a single function, so that editors can reformat one function
+//! without touching the rest of the file.
+
+use dada_ir::code::syntax::to_source::FormatOptions;
+use dada_ir::function::Function;
+use dada_ir::span::FileSpan;
+
+use crate::prelude::*;
+
+/// Returns the edit that formats the body of `function`: the span from its
+/// opening `{` to its closing `}` (the end of the function's span), and the
+/// text to replace it with. Returns `None` if the function has no body.
+///
+/// The body is indented to match the line where the function starts, so that
+/// methods and nested functions stay aligned with their surroundings.
+pub fn format_function(db: &dyn crate::Db, function: Function) -> Option<(FileSpan, String)> {
+    let unparsed_code = function.unparsed_code(db)?;
+    let function_span = function.span(db);
+
+    // The body tokens exclude the `{`.
+    let body_start = usize::from(unparsed_code.body_tokens.span(db).start) - 1;
+    let span = FileSpan {
+        filename: function_span.filename,
+        start: body_start.into(),
+        end: function_span.end,
+    };
+
+    let source = dada_ir::manifest::source_text(db, function_span.filename);
+    let function_start = usize::from(function_span.start);
+    let line_start = source[..function_start].rfind('\n').map_or(0, |i| i + 1);
+    let options = FormatOptions {
+        base_indent: source[line_start..function_start]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect(),
+        ..FormatOptions::default()
+    };
+
+    let text = function.syntax_tree(db).to_source_with(db, &options);
+    Some((span, text))
+}
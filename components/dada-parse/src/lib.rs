@@ -5,20 +5,31 @@
 
 mod code_parser;
 mod file_parser;
+pub mod format;
+mod method_parser;
 mod parameter_parser;
 mod parser;
+pub mod semantic_tokens;
 mod token_test;
 mod tokens;
+pub mod tokenize;
 
 #[salsa::jar(Db)]
 pub struct Jar(
+    code_parser::FunctionText,
+    code_parser::function_syntax_tree,
     code_parser::parse_function_body,
+    code_parser::function_spans,
     file_parser::parse_file,
+    method_parser::parse_class_methods,
     parameter_parser::parse_function_parameters,
     parameter_parser::parse_class_parameters,
+    semantic_tokens::semantic_tokens,
 );
 
 pub trait Db: salsa::DbWithJar<Jar> + dada_lex::Db + dada_ir::Db {}
 impl<T> Db for T where T: salsa::DbWithJar<Jar> + dada_lex::Db + dada_ir::Db {}
 
 pub mod prelude;
+
+pub use code_parser::FunctionText;
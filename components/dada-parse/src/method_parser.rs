@@ -0,0 +1,13 @@
+use crate::parser::Parser;
+
+use dada_ir::{class::Class, function::Function};
+
+/// Parses the methods declared in the body of `class`, if it has one.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub fn parse_class_methods(db: &dyn crate::Db, class: Class) -> Vec<Function> {
+    match class.body_tokens(db) {
+        Some(body_tokens) => Parser::new(db, body_tokens).parse_only_methods(class),
+        None => vec![],
+    }
+}
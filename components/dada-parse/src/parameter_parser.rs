@@ -1,13 +1,27 @@
 use crate::parser::Parser;
 
-use dada_ir::{class::Class, function::Function, parameter::Parameter, token_tree::TokenTree};
+use dada_ir::{
+    class::{Class, SELF_PARAMETER},
+    code::syntax::{LocalVariableDeclData, LocalVariableDeclSpan},
+    function::Function,
+    parameter::Parameter,
+    span::Span,
+    storage::{Atomic, Mutable, SpannedSpecifier, Weak},
+    token_tree::TokenTree,
+    word::Word,
+};
 use salsa::DebugWithDb;
 
 #[salsa::component(in crate::Jar ref)]
 #[allow(clippy::needless_lifetimes)]
 pub fn parse_function_parameters(db: &dyn crate::Db, function: Function) -> Vec<Parameter> {
     if let Some(unparsed_code) = function.unparsed_code(db) {
-        parse_parameters(db, unparsed_code.parameter_tokens)
+        let parameter_tokens = unparsed_code.parameter_tokens;
+        let mut parameters = parse_parameters(db, parameter_tokens);
+        if function.class(db).is_some() {
+            parameters.insert(0, self_parameter(db, parameter_tokens));
+        }
+        parameters
     } else {
         panic!(
             "cannot parse parameters of function `{:?}` which did not have unparsed code",
@@ -19,9 +33,61 @@ pub fn parse_function_parameters(db: &dyn crate::Db, function: Function) -> Vec<
 #[salsa::memoized(in crate::Jar ref)]
 #[allow(clippy::needless_lifetimes)]
 pub fn parse_class_parameters(db: &dyn crate::Db, class: Class) -> Vec<Parameter> {
-    parse_parameters(db, class.field_tokens(db))
+    let field_tokens = class.field_tokens(db);
+    let filename = field_tokens.filename(db);
+    let fields = parse_parameters(db, field_tokens);
+    for (index, &field) in fields.iter().enumerate() {
+        let name = field.name(db);
+        let previous_field = fields[..index].iter().find(|f| f.name(db) == name);
+        if let Some(previous_field) = previous_field {
+            dada_ir::error!(
+                field.decl_span(db).name_span.in_file(filename),
+                "field `{}` is declared more than once",
+                name.as_str(db),
+            )
+            .primary_label("duplicate field")
+            .secondary_label(
+                previous_field.decl_span(db).name_span.in_file(filename),
+                "first declared here",
+            )
+            .emit(db);
+        }
+
+        if let Some(default) = field.default(db) {
+            let span = default.spans(db)[default.data(db).root_expr];
+            dada_ir::error!(
+                span.in_file(filename),
+                "class fields cannot have default values"
+            )
+            .emit(db);
+        }
+    }
+    fields
 }
 
 fn parse_parameters(db: &dyn crate::Db, token_tree: TokenTree) -> Vec<Parameter> {
     Parser::new(db, token_tree).parse_only_parameters()
 }
+
+/// Creates the implicit `self` parameter of a method whose parameters are
+/// `parameter_tokens`. It is not written in the source, so its span is the
+/// empty span at the start of the parameter list.
+pub(crate) fn self_parameter(db: &dyn crate::Db, parameter_tokens: TokenTree) -> Parameter {
+    let name = Word::from(db, SELF_PARAMETER);
+    let start = parameter_tokens.span(db).start;
+    let span = Span { start, end: start };
+    let decl = LocalVariableDeclData {
+        specifier: SpannedSpecifier::new_defaulted(db, span.in_file(parameter_tokens.filename(db))),
+        mutable: Mutable::Yes,
+        atomic: Atomic::No,
+        weak: Weak::No,
+        name,
+        ty: None,
+    };
+    let decl_span = LocalVariableDeclSpan {
+        atomic_span: span,
+        name_span: span,
+        ty_span: None,
+    };
+    Parameter::new(db, name, decl, decl_span, None)
+}
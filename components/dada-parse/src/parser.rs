@@ -16,19 +16,45 @@ pub(crate) struct Parser<'me> {
     db: &'me dyn crate::Db,
     filename: Filename,
     tokens: Tokens<'me>,
+
+    /// The text that the spans of `tokens` index into.
+    source_text: &'me str,
 }
 
 impl<'me> Parser<'me> {
     pub(crate) fn new(db: &'me dyn crate::Db, token_tree: TokenTree) -> Self {
+        let source_text = dada_ir::manifest::source_text(db, token_tree.filename(db));
+        Self::with_source_text(db, token_tree, source_text)
+    }
+
+    /// Creates a parser for `token_tree` whose spans index into `source_text`
+    /// rather than into the whole file, e.g. because `token_tree` was lexed
+    /// from the text of a single item.
+    pub(crate) fn with_source_text(
+        db: &'me dyn crate::Db,
+        token_tree: TokenTree,
+        source_text: &'me str,
+    ) -> Self {
         let tokens = Tokens::new(db, token_tree);
         let filename = token_tree.filename(db);
         Self {
             db,
             tokens,
             filename,
+            source_text,
         }
     }
 
+    /// Creates a parser for `token_tree`, a tree nested within our tokens.
+    fn sub_parser(&self, token_tree: TokenTree) -> Parser<'me> {
+        Parser::with_source_text(self.db, token_tree, self.source_text)
+    }
+
+    /// Returns the source text of `span`.
+    fn snippet(&self, span: Span) -> &'me str {
+        &self.source_text[usize::from(span.start)..usize::from(span.end)]
+    }
+
     /// Returns `Some` if the next pending token matches `is`, along
     /// with the narrowed view of the next token.
     fn peek<TT: TokenTest>(&mut self, test: TT) -> Option<TT::Narrow> {
@@ -75,7 +101,7 @@ impl<'me> Parser<'me> {
         let mut tokens = self.tokens;
         let span0 = tokens.peek_span();
 
-        let mut chars = op.str().chars();
+        let mut chars = op.as_str().chars();
 
         let ch0 = chars.next().unwrap();
         match tokens.consume() {
@@ -141,8 +167,11 @@ impl<'me> Parser<'me> {
 
         // Consume closing delimiter (if present)
         let closing_delimiter = dada_lex::closing_delimiter(delimiter);
-        self.eat(Token::Delimiter(closing_delimiter))
-            .or_report_error(self, || format!("expected `{closing_delimiter}`"));
+        if self.eat(Token::Delimiter(closing_delimiter)).is_none() {
+            self.error(open_span, format!("unclosed `{delimiter}`"))
+                .primary_label(format!("this `{delimiter}` is never closed"))
+                .emit(self.db);
+        }
 
         let span = open_span.to(self.tokens.last_span());
         Some((span, token_tree))
@@ -155,8 +184,8 @@ impl<'me> Parser<'me> {
     }
 
     fn tighten_span(&self, mut span: Span) -> Span {
-        let strip_from_start = span
-            .snippet(self.db, self.filename)
+        let strip_from_start = self
+            .snippet(span)
             .char_indices()
             .take_while(|(_, ch)| ch.is_whitespace())
             .map(|(offset, _)| offset)
@@ -164,8 +193,8 @@ impl<'me> Parser<'me> {
             .unwrap_or(0);
         span.start = span.start + strip_from_start;
 
-        if let Some(new_len) = span
-            .snippet(self.db, self.filename)
+        if let Some(new_len) = self
+            .snippet(span)
             .char_indices()
             .rev()
             .take_while(|(_, ch)| ch.is_whitespace())
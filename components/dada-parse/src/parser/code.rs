@@ -1,15 +1,16 @@
 use crate::{
     parser::Parser,
-    token_test::{Alphabetic, FormatStringLiteral, Identifier, Number},
+    token_test::{Alphabetic, CharLiteral, FormatStringLiteral, Identifier, Number},
 };
 
 use dada_id::InternValue;
 use dada_ir::{
     code::{
-        syntax::{op::Op, LocalVariableDecl},
+        syntax::{integer::IntegerLiteral, op::Op, LocalVariableDecl},
         syntax::{
-            Expr, ExprData, LocalVariableDeclData, LocalVariableDeclSpan, NamedExpr, NamedExprData,
-            Spans, Tables, Tree, TreeData,
+            Comment, Expr, ExprData, LocalVariableDeclData, LocalVariableDeclSpan, MatchArm,
+            MatchArmData, NamedExpr, NamedExprData, Pattern, PatternData, Spans, Tables, Tree,
+            TreeData,
         },
     },
     format_string::FormatStringSectionData,
@@ -17,10 +18,11 @@ use dada_ir::{
     origin_table::PushOriginIn,
     parameter::Parameter,
     span::Span,
-    storage::Atomic,
+    storage::{Atomic, Mutable, SpannedSpecifier, Weak},
     token::Token,
     token_tree::TokenTree,
-    word::SpannedOptionalWord,
+    visibility::Visibility,
+    word::{SpannedOptionalWord, Word},
 };
 use salsa::AsId;
 
@@ -36,6 +38,7 @@ impl Parser<'_> {
             parser: self,
             tables: &mut tables,
             spans: &mut spans,
+            in_condition: false,
         };
 
         let parameter_decls = parameters
@@ -44,8 +47,8 @@ impl Parser<'_> {
             .collect::<Vec<_>>();
 
         let start = code_parser.tokens.last_span();
-        let exprs = code_parser.parse_only_expr_seq();
-        self.create_syntax_tree(start, parameter_decls, tables, spans, exprs)
+        let contents = code_parser.parse_only_block_contents();
+        self.create_syntax_tree(start, parameter_decls, tables, spans, contents)
     }
 
     pub(crate) fn parse_top_level_expr(
@@ -57,17 +60,39 @@ impl Parser<'_> {
             parser: self,
             tables,
             spans,
+            in_condition: false,
         };
         code_parser.parse_expr()
     }
 
+    /// Parses the default value of a parameter (the `22` in `x = 22`) into a
+    /// syntax tree of its own, whose root is that expression.
+    pub(crate) fn parse_default_value(&mut self) -> Option<Tree> {
+        let mut tables = Tables::default();
+        let mut spans = Spans::default();
+        let root_expr = CodeParser {
+            parser: self,
+            tables: &mut tables,
+            spans: &mut spans,
+            in_condition: false,
+        }
+        .parse_expr()?;
+
+        let tree_data = TreeData {
+            tables,
+            parameter_decls: vec![],
+            root_expr,
+        };
+        Some(Tree::new(self.db, tree_data, spans))
+    }
+
     pub(crate) fn create_syntax_tree(
         &mut self,
         start: Span,
         parameter_decls: Vec<LocalVariableDecl>,
         mut tables: Tables,
         mut spans: Spans,
-        exprs: Vec<Expr>,
+        contents: BlockContents,
     ) -> Tree {
         let span = self.span_consumed_since(start);
 
@@ -76,8 +101,9 @@ impl Parser<'_> {
                 parser: self,
                 tables: &mut tables,
                 spans: &mut spans,
+                in_condition: false,
             };
-            code_parser.add(ExprData::Seq(exprs), span)
+            code_parser.add_block(contents, span)
         };
 
         let tree_data = TreeData {
@@ -89,10 +115,24 @@ impl Parser<'_> {
     }
 }
 
+/// The statements of a `{ ... }` block or of a function body.
+pub(crate) struct BlockContents {
+    pub(crate) statements: Vec<Expr>,
+    pub(crate) tail: Option<Expr>,
+
+    /// The comments after the last statement.
+    pub(crate) dangling_comments: Vec<Comment>,
+}
+
 struct CodeParser<'me, 'db> {
     parser: &'me mut Parser<'db>,
     tables: &'me mut Tables,
     spans: &'me mut Spans,
+
+    /// True while parsing an if/while/for/match condition, where
+    /// `x { ... }` is the expression `x` followed by a block and
+    /// not a class literal.
+    in_condition: bool,
 }
 
 impl<'db> std::ops::Deref for CodeParser<'_, 'db> {
@@ -110,16 +150,137 @@ impl<'db> std::ops::DerefMut for CodeParser<'_, 'db> {
 }
 
 impl CodeParser<'_, '_> {
-    /// Parses a series of expressions; expects to consume all available tokens (and errors if there are extra).
+    /// Parses a series of expressions; expects to consume all available tokens.
+    /// If a statement cannot be parsed, reports an error, replaces the rest of
+    /// the statement with [`ExprData::Error`], and continues with the next one.
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn parse_only_expr_seq(&mut self) -> Vec<Expr> {
         tracing::debug!("parse_only_expr_seq");
-        let exprs = self.parse_list(true, CodeParser::parse_expr);
+        let mut exprs = vec![];
+        while self.tokens.peek().is_some() {
+            let Some(expr) = self.parse_statement() else {
+                exprs.push(self.recover_statement("expected expression"));
+                continue;
+            };
+            exprs.push(expr);
+
+            // Statements are separated by a newline or a comma.
+            if !self.skipped_newline() && !self.eat_comma() && self.tokens.peek().is_some() {
+                exprs.push(self.recover_statement("extra tokens after end of expression"));
+            }
+        }
         tracing::debug!("exprs = {:?}", exprs);
-        self.emit_error_if_more_tokens("extra tokens after end of expression");
+        exprs.shrink_to_fit();
         exprs
     }
 
+    /// Parses the contents of a block: statements separated by a newline, a comma,
+    /// or a `;`. Returns the statements along with the tail expression, which is
+    /// the last expression unless it is followed by a `;`.
+    ///
+    /// The comments between the statements are recorded in the spans: each
+    /// comment belongs to the statement that follows it, except for one at
+    /// the end of a statement's line, which belongs to that statement.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) fn parse_only_block_contents(&mut self) -> BlockContents {
+        tracing::debug!("parse_only_block_contents");
+        let mut statements = vec![];
+        let mut tail = None;
+        while self.tokens.peek().is_some() {
+            statements.extend(tail.take());
+            let leading_comments = self.take_comments(statements.last().copied());
+
+            let Some(expr) = self.parse_statement() else {
+                let expr = self.recover_statement("expected expression");
+                self.spans.push_leading_comments(expr, leading_comments);
+                statements.push(expr);
+                continue;
+            };
+            self.spans.push_leading_comments(expr, leading_comments);
+
+            if self.eat_op(Op::SemiColon).is_some() {
+                statements.push(expr);
+                continue;
+            }
+            tail = Some(expr);
+
+            if !self.skipped_newline() && !self.eat_comma() && self.tokens.peek().is_some() {
+                statements.extend(tail.take());
+                statements.push(self.recover_statement("extra tokens after end of expression"));
+            }
+        }
+        tracing::debug!("statements = {:?}, tail = {:?}", statements, tail);
+        let dangling_comments = self.take_comments(tail.or_else(|| statements.last().copied()));
+        statements.shrink_to_fit();
+        BlockContents {
+            statements,
+            tail,
+            dangling_comments,
+        }
+    }
+
+    /// Takes the comments skipped before the next token. A comment at the end
+    /// of the line of the `previous` statement is recorded as its trailing
+    /// comment; the others are returned.
+    fn take_comments(&mut self, previous: Option<Expr>) -> Vec<Comment> {
+        let (db, source_text) = (self.db, self.source_text);
+        let (trailing, others) = self.tokens.skipped_comments();
+        let comment = |span: Span| Comment {
+            span,
+            text: Word::from(
+                db,
+                source_text[usize::from(span.start)..usize::from(span.end)].trim_end(),
+            ),
+        };
+        let mut comments = vec![];
+        match (previous, trailing) {
+            (Some(previous), Some(span)) => {
+                let trailing = comment(span);
+                self.spans.push_trailing_comment(previous, trailing);
+            }
+            (None, Some(span)) => comments.push(comment(span)),
+            (_, None) => {}
+        }
+        comments.extend(others.into_iter().map(comment));
+        comments
+    }
+
+    /// Adds the `Seq` expression for a block with the given contents.
+    fn add_block(&mut self, contents: BlockContents, span: Span) -> Expr {
+        let expr = self.add(ExprData::Seq(contents.statements, contents.tail), span);
+        self.spans
+            .push_dangling_comments(expr, contents.dangling_comments);
+        expr
+    }
+
+    /// Parses a statement in a block: a nested function or an expression.
+    fn parse_statement(&mut self) -> Option<Expr> {
+        if self.is_function_next() {
+            let start = self.tokens.peek_span();
+            let docs = self.parse_doc_comment();
+            let function = self.parse_function(docs, vec![], Visibility::Private, None)?;
+            let span = self.span_consumed_since(start);
+            return Some(self.add(ExprData::Fn(function), span));
+        }
+
+        self.parse_expr()
+    }
+
+    /// Reports `message` at the current token, then skips tokens up to the end
+    /// of the statement: a `;`, a newline, or the end of the enclosing block.
+    /// Returns an [`ExprData::Error`] covering the skipped tokens.
+    fn recover_statement(&mut self, message: &str) -> Expr {
+        let start = self.tokens.peek_span();
+        self.error_at_current_token(message).emit(self.db);
+        while let Some(token) = self.tokens.consume() {
+            if token == Token::Op(';') || self.skipped_newline() {
+                break;
+            }
+        }
+        let span = self.span_consumed_since(start);
+        self.add(ExprData::Error, span)
+    }
+
     /// Parses a series of expressions; expects to consume all available tokens (and errors if there are extra).
     fn parse_only_expr(&mut self) -> Option<Expr> {
         let expr = self.parse_expr()?;
@@ -127,6 +288,30 @@ impl CodeParser<'_, '_> {
         Some(expr)
     }
 
+    /// Parses the contents of `(...)`; expects to consume all available tokens (and errors if there are extra).
+    /// Returns the expressions along with a flag that indicates whether they were followed by
+    /// a trailing comma, which distinguishes the tuple `(a,)` from the parenthesized expression `(a)`.
+    fn parse_only_parenthesized_contents(&mut self) -> (Vec<Expr>, bool) {
+        let exprs = self.parse_list(true, CodeParser::parse_expr);
+
+        if exprs.is_empty() && self.peek(Token::Comma).is_some() {
+            // `(,)`
+            self.error_at_current_token("expected an expression before `,`")
+                .emit(self.db);
+            return (exprs, false);
+        }
+
+        // `parse_list` consumes the trailing comma, if any, so it will be
+        // the last token we consumed.
+        let trailing_comma = match exprs.last() {
+            Some(&last_expr) => self.tokens.last_span().end > self.spans[last_expr].end,
+            None => false,
+        };
+
+        self.emit_error_if_more_tokens("extra tokens after end of expression");
+        (exprs, trailing_comma)
+    }
+
     /// Parses a series of named expressions (`id: expr`); expects to consume all available tokens (and errors if there are extra).
     pub(crate) fn parse_only_named_exprs(&mut self) -> Vec<NamedExpr> {
         let exprs = self.parse_list(true, CodeParser::parse_named_expr);
@@ -148,11 +333,16 @@ impl CodeParser<'_, '_> {
     }
 
     /// Parses an if/while condition -- this can be any sort of expression but a block.
+    /// Class literals are not accepted at the top level of a condition (they can
+    /// still be written inside parentheses).
     pub(crate) fn parse_condition(&mut self) -> Option<Expr> {
         if self.peek(Token::Delimiter('{')).is_some() {
             None
         } else {
-            self.parse_expr()
+            let in_condition = std::mem::replace(&mut self.in_condition, true);
+            let expr = self.parse_expr();
+            self.in_condition = in_condition;
+            expr
         }
     }
 
@@ -197,15 +387,44 @@ impl CodeParser<'_, '_> {
     ///       | `if` Expr Block [`else` Block]
     ///       | `while` Expr Block
     ///       | `loop` Block
-    ///       | `continue`
-    ///       | `break` [Expr]
+    ///       | `for` Id `in` Expr Block
+    ///       | `match` Expr { Pattern => Expr, ... }
+    ///       | `continue` ['label]
+    ///       | `break` ['label] [Expr]
     ///       | `return` [Expr]
+    ///       | `|` Id, ... `|` Expr
     ///       | Block
     ///       | Expr . Ident
     ///       | Expr BinaryOp Expr
+    ///       | [Expr] `..` [Expr]
+    ///       | Expr `..=` Expr
     ///       | Expr ( args )
+    ///       | Expr [ Expr ]
+    ///       | Id { Id: Expr, ... }
     ///       | SharingMode? Id = Expr
+    ///       | SharingMode? Id = Expr `else` Block
     /// ```
+    ///
+    /// Binary operators are parsed by a ladder of functions, one per precedence
+    /// level, from tightest to loosest:
+    ///
+    /// | Level          | Operators                      | Associativity |
+    /// | -------------- | ------------------------------ | ------------- |
+    /// | `parse_expr_1` | `.` `(...)` `[...]`            | left          |
+    /// | `parse_expr_2` | unary `-` `!`                  | prefix        |
+    /// | `parse_expr_3` | `*` `/`                        | left          |
+    /// | `parse_expr_4` | `+` `-`                        | left          |
+    /// | shift          | `<<` `>>`                      | left          |
+    /// | bit and        | `&`                            | left          |
+    /// | bit xor        | `^`                            | left          |
+    /// | bit or         | `\|`                           | left          |
+    /// | `parse_expr_5` | `==` `!=` `<` `>` `>=` `<=`    | left          |
+    /// | and            | `&&`                           | left          |
+    /// | or             | `\|\|`                         | left          |
+    /// | range          | `..` `..=`                     | none          |
+    /// | `parse_expr_6` | `:=` and compound assignments  | right         |
+    ///
+    /// So `a := b + c * d` is `a := (b + (c * d))`.
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn parse_expr(&mut self) -> Option<Expr> {
         tracing::debug!("parse_expr");
@@ -214,7 +433,13 @@ impl CodeParser<'_, '_> {
         }
 
         if let Some((return_span, _)) = self.eat(Keyword::Return) {
-            match self.parse_expr() {
+            // The value (if any) must begin on the same line as the `return`.
+            let value = if self.tokens.skipped_newline() {
+                None
+            } else {
+                self.parse_expr()
+            };
+            match value {
                 Some(expr) => {
                     let span = self.span_consumed_since(return_span);
                     return Some(self.add(ExprData::Return(Some(expr)), span));
@@ -225,48 +450,186 @@ impl CodeParser<'_, '_> {
             }
         }
 
+        if let Some((yield_span, _)) = self.eat(Keyword::Yield) {
+            // As with `return`, the value (if any) must begin on the same line.
+            let value = if self.tokens.skipped_newline() {
+                None
+            } else {
+                self.parse_expr()
+            };
+            let span = self.span_consumed_since(yield_span);
+            return Some(self.add(ExprData::Yield(value), span));
+        }
+
+        if let Some((break_span, _)) = self.eat(Keyword::Break) {
+            let label = self.parse_label_reference();
+            // The value (if any) must begin on the same line as the `break`.
+            let value = if self.tokens.skipped_newline() {
+                None
+            } else {
+                self.parse_expr()
+            };
+            let span = self.span_consumed_since(break_span);
+            return Some(self.add(ExprData::Break(label, value), span));
+        }
+
+        if let Some((continue_span, _)) = self.eat(Keyword::Continue) {
+            let label = self.parse_label_reference();
+            let span = self.span_consumed_since(continue_span);
+            return Some(self.add(ExprData::Continue(label), span));
+        }
+
         self.parse_expr_6()
     }
 
-    pub(crate) fn parse_expr_6(&mut self) -> Option<Expr> {
-        let mut expr = self.parse_expr_5()?;
+    /// Parses the `'label` that may follow `break` or `continue`.
+    /// The label must appear on the same line.
+    fn parse_label_reference(&mut self) -> Option<Word> {
+        if self.tokens.skipped_newline() {
+            return None;
+        }
 
-        loop {
-            if let Some(expr1) = self.parse_binop(
-                expr,
-                &[
-                    Op::PlusEqual,
-                    Op::MinusEqual,
-                    Op::DividedByEqual,
-                    Op::TimesEqual,
-                    Op::ColonEqual,
-                ],
-                Self::parse_expr_5,
-            ) {
-                expr = expr1;
-                continue;
+        self.lookahead(|this| {
+            this.eat(Token::Unknown('\''))?;
+            if this.tokens.skipped_any() {
+                return None;
             }
+            let (_, label) = this.eat(Identifier)?;
+            Some(label)
+        })
+    }
 
-            break;
+    /// Eats the keyword of a loop (`loop`, `while`, or `for`), along with
+    /// the `'label:` in front of it, if any. Returns the span where the
+    /// loop starts (at the label, if there is one) and the label.
+    fn eat_loop_keyword(&mut self, keyword: Keyword) -> Option<(Span, Option<Word>)> {
+        self.lookahead(|this| {
+            let label = this.lookahead(|this| {
+                let (quote_span, _) = this.eat(Token::Unknown('\''))?;
+                if this.tokens.skipped_any() {
+                    return None;
+                }
+                let (_, label) = this.eat(Identifier)?;
+                this.eat_op(Op::Colon)?;
+                Some((quote_span, label))
+            });
+            let (keyword_span, _) = this.eat(keyword)?;
+            Some(match label {
+                Some((quote_span, label)) => (quote_span, Some(label)),
+                None => (keyword_span, None),
+            })
+        })
+    }
+
+    pub(crate) fn parse_expr_6(&mut self) -> Option<Expr> {
+        let expr = self.parse_range_expr()?;
+
+        // Assignments are right-associative, so `a := b := c` is `a := (b := c)`.
+        if let Some(expr1) = self.parse_binop(
+            expr,
+            &[
+                Op::PlusEqual,
+                Op::MinusEqual,
+                Op::DividedByEqual,
+                Op::TimesEqual,
+                Op::BitAndEqual,
+                Op::BitOrEqual,
+                Op::BitXorEqual,
+                Op::ShiftLeftEqual,
+                Op::ShiftRightEqual,
+                Op::ColonEqual,
+            ],
+            Self::parse_expr_6,
+        ) {
+            return Some(expr1);
+        }
+
+        Some(expr)
+    }
+
+    /// Parses a range like `a..b` or `a..=b`, where either bound may be omitted.
+    /// Ranges bind more loosely than comparisons, so `a + 1 .. b` is `(a + 1)..b`.
+    pub(crate) fn parse_range_expr(&mut self) -> Option<Expr> {
+        let start = if self.test_range_op() {
+            None
+        } else {
+            Some(self.parse_or_expr()?)
+        };
+
+        let start_span = self.tokens.peek_span();
+        let inclusive = match self.eat_range_op() {
+            Some(inclusive) => inclusive,
+            None => return start,
+        };
+
+        // The upper bound is omitted if the range ends the line or
+        // is followed by a block (e.g., `for x in a.. { ... }`).
+        let end = if self.tokens.skipped_newline() || self.peek(Token::Delimiter('{')).is_some() {
+            None
+        } else {
+            self.parse_or_expr()
+        };
+
+        let span = match start {
+            Some(start) => self.span_consumed_since(self.spans[start]),
+            None => self.span_consumed_since(start_span),
+        };
+        Some(self.add(ExprData::Range(start, end, inclusive), span))
+    }
+
+    /// Parses `a || b`; `||` binds more loosely than `&&`.
+    pub(crate) fn parse_or_expr(&mut self) -> Option<Expr> {
+        let mut expr = self.parse_and_expr()?;
+
+        while let Some(expr1) = self.parse_binop(expr, &[Op::OrOr], Self::parse_and_expr) {
+            expr = expr1;
         }
 
         Some(expr)
     }
 
+    /// Parses `a && b`; `&&` binds more loosely than comparisons.
+    pub(crate) fn parse_and_expr(&mut self) -> Option<Expr> {
+        let mut expr = self.parse_expr_5()?;
+
+        while let Some(expr1) = self.parse_binop(expr, &[Op::AndAnd], Self::parse_expr_5) {
+            expr = expr1;
+        }
+
+        Some(expr)
+    }
+
+    /// True if the next token is `..` or `..=`.
+    fn test_range_op(&self) -> bool {
+        self.test_op(Op::DotDotEqual).is_some() || self.test_op(Op::DotDot).is_some()
+    }
+
+    /// Consumes `..` or `..=`, returning true if the range is inclusive.
+    fn eat_range_op(&mut self) -> Option<bool> {
+        if self.eat_op(Op::DotDotEqual).is_some() {
+            Some(true)
+        } else if self.eat_op(Op::DotDot).is_some() {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn parse_expr_5(&mut self) -> Option<Expr> {
-        let mut expr = self.parse_expr_4()?;
+        let mut expr = self.parse_bit_or_expr()?;
 
         loop {
             if let Some(expr1) = self.parse_binop(
                 expr,
                 &[
                     Op::EqualEqual,
+                    Op::NotEqual,
                     Op::LessThan,
                     Op::GreaterThan,
                     Op::GreaterEqual,
                     Op::LessEqual,
                 ],
-                Self::parse_expr_4,
+                Self::parse_bit_or_expr,
             ) {
                 expr = expr1;
                 continue;
@@ -278,6 +641,52 @@ impl CodeParser<'_, '_> {
         Some(expr)
     }
 
+    /// Parses `a | b`; `|` binds more loosely than `^`.
+    pub(crate) fn parse_bit_or_expr(&mut self) -> Option<Expr> {
+        let mut expr = self.parse_bit_xor_expr()?;
+
+        while let Some(expr1) = self.parse_binop(expr, &[Op::BitOr], Self::parse_bit_xor_expr) {
+            expr = expr1;
+        }
+
+        Some(expr)
+    }
+
+    /// Parses `a ^ b`; `^` binds more loosely than `&`.
+    pub(crate) fn parse_bit_xor_expr(&mut self) -> Option<Expr> {
+        let mut expr = self.parse_bit_and_expr()?;
+
+        while let Some(expr1) = self.parse_binop(expr, &[Op::BitXor], Self::parse_bit_and_expr) {
+            expr = expr1;
+        }
+
+        Some(expr)
+    }
+
+    /// Parses `a & b`; `&` binds more loosely than shifts.
+    pub(crate) fn parse_bit_and_expr(&mut self) -> Option<Expr> {
+        let mut expr = self.parse_shift_expr()?;
+
+        while let Some(expr1) = self.parse_binop(expr, &[Op::BitAnd], Self::parse_shift_expr) {
+            expr = expr1;
+        }
+
+        Some(expr)
+    }
+
+    /// Parses `a << b` and `a >> b`; shifts bind more loosely than `+` and `-`.
+    pub(crate) fn parse_shift_expr(&mut self) -> Option<Expr> {
+        let mut expr = self.parse_expr_4()?;
+
+        while let Some(expr1) =
+            self.parse_binop(expr, &[Op::ShiftLeft, Op::ShiftRight], Self::parse_expr_4)
+        {
+            expr = expr1;
+        }
+
+        Some(expr)
+    }
+
     pub(crate) fn parse_expr_4(&mut self) -> Option<Expr> {
         let mut expr = self.parse_expr_3()?;
 
@@ -312,7 +721,7 @@ impl CodeParser<'_, '_> {
     }
 
     pub(crate) fn parse_expr_2(&mut self) -> Option<Expr> {
-        if let Some(expr) = self.parse_unary(&[Op::Minus], Self::parse_expr_2) {
+        if let Some(expr) = self.parse_unary(&[Op::Minus, Op::Not], Self::parse_expr_2) {
             return Some(expr);
         }
         self.parse_expr_1()
@@ -364,60 +773,180 @@ impl CodeParser<'_, '_> {
                 continue;
             }
 
+            // `base[index]` -- but a `[` that starts a new line begins a list literal
+            if !self.tokens.skipped_newline() {
+                if let Some((index_span, token_tree)) = self.delimited('[') {
+                    let index_expr = self.parse_required_sub_expr(token_tree);
+                    let span = self.spans[expr].to(index_span);
+                    expr = self.add(ExprData::Index(expr, index_expr), span);
+                    continue;
+                }
+            }
+
             break;
         }
 
         Some(expr)
     }
 
-    pub(crate) fn parse_expr_0(&mut self) -> Option<Expr> {
-        tracing::debug!("parse_expr_0: peek = {:?}", self.tokens.peek());
-        if let Some((true_span, _)) = self.eat(Keyword::True) {
-            Some(self.add(ExprData::BooleanLiteral(true), true_span))
-        } else if let Some((false_span, _)) = self.eat(Keyword::False) {
-            Some(self.add(ExprData::BooleanLiteral(false), false_span))
-        } else if let Some((id_span, id)) = self.eat(Identifier) {
-            tracing::debug!("identifier");
-            Some(self.add(ExprData::Id(id), id_span))
-        } else if let Some((word_span, word)) = self.eat(Number) {
-            let whitespace_after_number = self.tokens.skipped_any();
+    /// Parses the remainder of an integer or float literal, given the
+    /// leading `Number` token that was just consumed:
+    ///
+    /// ```text
+    /// Integer := Number [Suffix]
+    /// Float := Number `.` Number [Exponent]
+    ///        | Number Exponent
+    /// Exponent := `e` [`+` | `-`] Number
+    /// ```
+    ///
+    /// The lexer has no notion of floats, so `2.5e-3` arrives as the tokens
+    /// `2`, `.`, `5`, `e`, `-`, `3`, which must not be separated by whitespace.
+    /// Note that `1.foo` is a field access on the integer `1`.
+    fn parse_number_literal(&mut self, word_span: Span, word: Word) -> Expr {
+        let whitespace_after_number = self.tokens.skipped_any();
+
+        let dot_name_follows = self
+            .testahead(|this| this.eat_op(Op::Dot).is_some() && this.peek(Alphabetic).is_some());
+        if dot_name_follows {
+            return self.integer_literal(word, None, word_span);
+        }
 
-            match self.eat_op(Op::Dot) {
-                None => {
-                    if whitespace_after_number {
-                        return Some(self.add(ExprData::IntegerLiteral(word, None), word_span));
-                    }
-                    match self.eat(Alphabetic) {
-                        Some((_, alphabetic)) => {
-                            let span = self.span_consumed_since(word_span);
-                            Some(self.add(ExprData::IntegerLiteral(word, Some(alphabetic)), span))
-                        }
-                        None => Some(self.add(ExprData::IntegerLiteral(word, None), word_span)),
+        let mut text = word.as_str(self.db).to_string();
+        match self.eat_op(Op::Dot) {
+            None => {
+                if whitespace_after_number {
+                    return self.integer_literal(word, None, word_span);
+                }
+                if let Some(exponent) = self.parse_float_exponent() {
+                    text.push_str(&exponent);
+                    let span = self.span_consumed_since(word_span);
+                    return self.add(ExprData::FloatLiteral(Word::from(self.db, text)), span);
+                }
+                match self.eat(Alphabetic) {
+                    Some((_, alphabetic)) => {
+                        let span = self.span_consumed_since(word_span);
+                        self.integer_literal(word, Some(alphabetic), span)
                     }
+                    None => self.integer_literal(word, None, word_span),
                 }
-                Some(dot_span) => {
-                    let whitespace_after_dot = self.tokens.skipped_any();
-                    if let Some((_, dec_word)) = self.eat(Number) {
+            }
+            Some(dot_span) => {
+                let whitespace_after_dot = self.tokens.skipped_any();
+                if let Some((_, dec_word)) = self.eat(Number) {
+                    text.push('.');
+                    text.push_str(dec_word.as_str(self.db));
+
+                    if whitespace_after_number || whitespace_after_dot {
                         let span = self.span_consumed_since(word_span);
+                        self.parser
+                            .error(span, "whitespace is not allowed in float literals")
+                            .emit(self.db);
+                    } else if let Some(exponent) = self.parse_float_exponent() {
+                        text.push_str(&exponent);
+                    }
 
-                        if whitespace_after_number || whitespace_after_dot {
-                            self.parser
-                                .error(span, "whitespace is not allowed in float literals")
-                                .emit(self.db);
-                        }
+                    // Something like `1.2.3`: consume the extra components so that
+                    // we report one error for the whole literal.
+                    let mut malformed = false;
+                    while !self.tokens.skipped_any()
+                        && self.testahead(|this| {
+                            this.eat_op(Op::Dot).is_some()
+                                && !this.tokens.skipped_any()
+                                && this.eat(Number).is_some()
+                        })
+                    {
+                        self.eat_op(Op::Dot);
+                        let (_, extra_word) = self.eat(Number).unwrap();
+                        text.push('.');
+                        text.push_str(extra_word.as_str(self.db));
+                        malformed = true;
+                    }
 
-                        Some(self.add(ExprData::FloatLiteral(word, dec_word), span))
-                    } else {
+                    let span = self.span_consumed_since(word_span);
+                    if malformed {
                         self.parser
-                            .error(dot_span, "expected digits after `.`")
+                            .error(span, format!("`{}` is not a valid float literal", text))
+                            .primary_label("a float literal can only contain one `.`")
                             .emit(self.db);
-                        let span = self.span_consumed_since(word_span);
-                        Some(self.add(ExprData::Error, span))
+                        return self.add(ExprData::Error, span);
                     }
+
+                    self.add(ExprData::FloatLiteral(Word::from(self.db, text)), span)
+                } else {
+                    self.parser
+                        .error(dot_span, "expected digits after `.`")
+                        .emit(self.db);
+                    let span = self.span_consumed_since(word_span);
+                    self.add(ExprData::Error, span)
                 }
             }
+        }
+    }
+
+    /// Adds an integer literal with the text `word`, finding its base and value.
+    fn integer_literal(&mut self, word: Word, suffix: Option<Word>, span: Span) -> Expr {
+        let literal = IntegerLiteral::new(self.db, word, suffix);
+        self.add(ExprData::IntegerLiteral(literal), span)
+    }
+
+    /// Parses the exponent of a float literal that directly follows its digits,
+    /// returning its text (e.g., `e10`, `E-3`). Returns `None` without
+    /// consuming anything if there is no exponent.
+    fn parse_float_exponent(&mut self) -> Option<String> {
+        self.lookahead(|this| {
+            let (_, alphabetic) = this.eat(Alphabetic)?;
+            let alphabetic_str = alphabetic.as_str(this.db);
+            let (e, digits) = alphabetic_str.split_at(1);
+            if e != "e" && e != "E" {
+                return None;
+            }
+
+            // `1e10` lexes as `1` followed by `e10`
+            if !digits.is_empty() {
+                let is_exponent = digits.chars().all(|c| matches!(c, '0'..='9' | '_'))
+                    && digits.chars().any(|c| c != '_');
+                return is_exponent.then(|| alphabetic_str.to_string());
+            }
+
+            // `1e-10` lexes as `1`, `e`, `-`, `10`
+            if this.tokens.skipped_any() {
+                return None;
+            }
+            let sign = if this.eat_op(Op::Minus).is_some() {
+                "-"
+            } else if this.eat_op(Op::Plus).is_some() {
+                "+"
+            } else {
+                return None;
+            };
+            if this.tokens.skipped_any() {
+                return None;
+            }
+            let (_, digits) = this.eat(Number)?;
+            Some(format!("{}{}{}", e, sign, digits.as_str(this.db)))
+        })
+    }
+
+    pub(crate) fn parse_expr_0(&mut self) -> Option<Expr> {
+        tracing::debug!("parse_expr_0: peek = {:?}", self.tokens.peek());
+        if let Some((true_span, _)) = self.eat(Keyword::True) {
+            Some(self.add(ExprData::BooleanLiteral(true), true_span))
+        } else if let Some((false_span, _)) = self.eat(Keyword::False) {
+            Some(self.add(ExprData::BooleanLiteral(false), false_span))
+        } else if let Some((id_span, id)) = self.eat(Identifier) {
+            tracing::debug!("identifier");
+            if let Some(expr) = self.parse_class_literal(id_span, id) {
+                // Class { field: expr, ... }
+                Some(expr)
+            } else {
+                Some(self.add(ExprData::Id(id), id_span))
+            }
+        } else if let Some((word_span, word)) = self.eat(Number) {
+            Some(self.parse_number_literal(word_span, word))
         } else if let Some(expr) = self.parse_format_string() {
             Some(expr)
+        } else if let Some(expr) = self.parse_char_literal() {
+            Some(expr)
         } else if let Some(expr) = self.parse_block_expr() {
             // { ... }
             Some(expr)
@@ -427,38 +956,75 @@ impl CodeParser<'_, '_> {
             tracing::debug!("atomic");
             Some(self.add(ExprData::Atomic(body_expr), span))
         } else if let Some((if_span, _)) = self.eat(Keyword::If) {
-            if let Some(condition) = self.parse_condition() {
-                let then_expr = self.parse_required_block_expr(Keyword::If);
-                let else_expr = self
-                    .eat(Keyword::Else)
-                    .map(|_| self.parse_required_block_expr(Keyword::Else));
-                let span = self.span_consumed_since(if_span);
-                Some(self.add(ExprData::If(condition, then_expr, else_expr), span))
-            } else {
-                self.error_at_current_token("expected `if` condition")
-                    .emit(self.db);
-                None
-            }
-        } else if let Some((loop_span, _)) = self.eat(Keyword::Loop) {
+            self.parse_if(if_span)
+        } else if let Some((loop_span, label)) = self.eat_loop_keyword(Keyword::Loop) {
             let body = self.parse_required_block_expr(Keyword::Loop);
             let span = self.span_consumed_since(loop_span);
-            Some(self.add(ExprData::Loop(body), span))
-        } else if let Some((while_span, _)) = self.eat(Keyword::While) {
+            Some(self.add(ExprData::Loop(label, body), span))
+        } else if let Some((while_span, label)) = self.eat_loop_keyword(Keyword::While) {
             if let Some(condition) = self.parse_condition() {
                 let body = self.parse_required_block_expr(Keyword::While);
                 let span = self.span_consumed_since(while_span);
-                Some(self.add(ExprData::While(condition, body), span))
+                Some(self.add(ExprData::While(label, condition, body), span))
             } else {
                 self.error_at_current_token("expected `while` condition")
                     .emit(self.db);
                 None
             }
-        } else if let Some((span, token_tree)) = self.delimited('(') {
-            let expr =
+        } else if let Some((for_span, label)) = self.eat_loop_keyword(Keyword::For) {
+            if let Some(decl) = self.parse_binding() {
+                self.eat(Keyword::In)
+                    .or_report_error(self, || "expected `in` after `for` loop variable");
+                let iterable = self
+                    .parse_condition()
+                    .or_report_error(self, || "expected expression to iterate over")
+                    .or_dummy_expr(self);
+                let body = self.parse_required_block_expr(Keyword::For);
+                let span = self.span_consumed_since(for_span);
+                Some(self.add(ExprData::For(label, decl, iterable, body), span))
+            } else {
+                self.error_at_current_token("expected loop variable after `for`")
+                    .emit(self.db);
+                None
+            }
+        } else if let Some((match_span, _)) = self.eat(Keyword::Match) {
+            if let Some(scrutinee) = self.parse_condition() {
+                let arms = match self.delimited('{') {
+                    Some((_, token_tree)) => self.with_sub_parser(token_tree, |sub_parser| {
+                        sub_parser.parse_only_match_arms()
+                    }),
+                    None => {
+                        self.error_at_current_token("expected `{` after `match` value")
+                            .emit(self.db);
+                        vec![]
+                    }
+                };
+                let span = self.span_consumed_since(match_span);
+                Some(self.add(ExprData::Match(scrutinee, arms), span))
+            } else {
+                self.error_at_current_token("expected value to match on")
+                    .emit(self.db);
+                None
+            }
+        } else if let Some(closure_span) = self.eat_op(Op::Pipe) {
+            let parameters = self.parse_closure_parameters();
+            let body = self
+                .parse_expr()
+                .or_report_error(self, || "expected closure body")
+                .or_dummy_expr(self);
+            let span = self.span_consumed_since(closure_span);
+            Some(self.add(ExprData::Closure(parameters, body), span))
+        } else if let Some((span, token_tree)) = self.delimited('[') {
+            let exprs =
                 self.with_sub_parser(token_tree, |subparser| subparser.parse_only_expr_seq());
+            Some(self.add(ExprData::List(exprs), span))
+        } else if let Some((span, token_tree)) = self.delimited('(') {
+            let (expr, trailing_comma) = self.with_sub_parser(token_tree, |subparser| {
+                subparser.parse_only_parenthesized_contents()
+            });
 
             Some(self.add(
-                if expr.len() == 1 {
+                if expr.len() == 1 && !trailing_comma {
                     ExprData::Parenthesized(expr[0])
                 } else {
                     ExprData::Tuple(expr)
@@ -470,34 +1036,158 @@ impl CodeParser<'_, '_> {
         }
     }
 
-    /// Parses `[permission-mode] [atomic] x = expr`
+    /// Parses the `x, y|` that follows the opening `|` of a closure.
+    fn parse_closure_parameters(&mut self) -> Vec<LocalVariableDecl> {
+        let mut parameters = vec![];
+        loop {
+            if self.eat_op(Op::Pipe).is_some() {
+                return parameters;
+            }
+
+            match self.parse_binding() {
+                Some(decl) => parameters.push(decl),
+                None => {
+                    self.error_at_current_token("expected closure parameter or `|`")
+                        .emit(self.db);
+                    return parameters;
+                }
+            }
+
+            if self.eat(Token::Comma).is_none() {
+                self.eat_op(Op::Pipe)
+                    .or_report_error(self, || "expected `,` or `|` after closure parameter");
+                return parameters;
+            }
+        }
+    }
+
+    /// Parses a series of match arms (`pattern => expr`); expects to consume all available tokens (and errors if there are extra).
+    fn parse_only_match_arms(&mut self) -> Vec<MatchArm> {
+        let arms = self.parse_list(true, CodeParser::parse_match_arm);
+        self.emit_error_if_more_tokens("extra tokens after end of match arms");
+        arms
+    }
+
+    /// Parses `pattern => expr`
+    fn parse_match_arm(&mut self) -> Option<MatchArm> {
+        let pattern = self.parse_pattern()?;
+        self.eat_op(Op::FatArrow)
+            .or_report_error(self, || "expected `=>` after pattern");
+        let body = self
+            .parse_expr()
+            .or_report_error(self, || "expected expression after `=>`")
+            .or_dummy_expr(self);
+        let span = self.span_consumed_since(self.spans[pattern]);
+        Some(self.add(MatchArmData { pattern, body }, span))
+    }
+
+    /// ```text
+    /// Pattern := Id
+    ///          | Literal
+    /// ```
+    fn parse_pattern(&mut self) -> Option<Pattern> {
+        if let Some(decl) = self.parse_binding() {
+            let span = self.spans[decl].name_span;
+            return Some(self.add(PatternData::Binding(decl), span));
+        }
+
+        let literal = self.parse_literal_pattern()?;
+        let span = self.spans[literal];
+        Some(self.add(PatternData::Literal(literal), span))
+    }
+
+    /// ```text
+    /// Literal := [`-`] Number
+    ///          | `true` | `false`
+    ///          | String | Char
+    /// ```
+    ///
+    /// The pattern is parsed like an expression, for the sake of error
+    /// recovery, but it is an error unless it is a literal: `-x` and `1 + 2`
+    /// are not patterns, and neither are strings with `{...}` expressions.
+    fn parse_literal_pattern(&mut self) -> Option<Expr> {
+        let expr = self.parse_expr()?;
+        if self.is_literal(expr) {
+            return Some(expr);
+        }
+
+        let span = self.spans[expr];
+        self.parser
+            .error(span, "match patterns must be a name or a literal")
+            .primary_label("this is not a literal")
+            .emit(self.db);
+        Some(self.add(ExprData::Error, span))
+    }
+
+    fn is_literal(&self, expr: Expr) -> bool {
+        match &self.tables[expr] {
+            ExprData::BooleanLiteral(_)
+            | ExprData::IntegerLiteral(_)
+            | ExprData::FloatLiteral(_)
+            | ExprData::CharLiteral(_) => true,
+            ExprData::Concatenate(exprs) => exprs
+                .iter()
+                .all(|&expr| matches!(self.tables[expr], ExprData::StringLiteral(_))),
+            ExprData::Unary(Op::Minus, operand) => matches!(
+                self.tables[*operand],
+                ExprData::IntegerLiteral(_) | ExprData::FloatLiteral(_)
+            ),
+            _ => false,
+        }
+    }
+
+    /// Parses `[shared|var] [permission-mode] [atomic] [weak] x = expr`
     #[tracing::instrument(level = "debug", skip_all)]
     fn parse_local_variable_decl(&mut self) -> Option<Expr> {
         // Look for `[mode] x = `. If we see that, we are committed to this
         // being a local variable declaration. Otherwise, we roll fully back.
-        let (specifier, atomic_span, atomic, name_span, name) = self.lookahead(|this| {
-            let specifier = this.parse_permission_specifier();
+        let (mutable, specifier, atomic_span, atomic, weak, name_span, name) =
+            self.lookahead(|this| {
+                let mutable = match this.eat(Keyword::Shared) {
+                    Some(_) => Mutable::No,
+                    None => {
+                        this.eat(Keyword::Var);
+                        Mutable::Yes
+                    }
+                };
 
-            // A storage mode like `shared` or `var` *could* be a variable declaration,
-            // but if we see `atomic` it might not be, so check for the `x = ` next.
-            let (atomic_span, atomic) = if let Some(span) = this.parse_atomic() {
-                (span, Atomic::Yes)
-            } else {
-                (this.tokens.peek_span(), Atomic::No)
-            };
+                let specifier = this.parse_permission_specifier();
 
-            let (name_span, name) = this.eat(Identifier)?;
+                // A storage mode like `shared` or `var` *could* be a variable declaration,
+                // but if we see `atomic` it might not be, so check for the `x = ` next.
+                let (atomic_span, atomic) = if let Some(span) = this.parse_atomic() {
+                    (span, Atomic::Yes)
+                } else {
+                    (this.tokens.peek_span(), Atomic::No)
+                };
+
+                let weak = match this.eat(Keyword::Weak) {
+                    Some(_) => Weak::Yes,
+                    None => Weak::No,
+                };
 
-            this.eat_op(Op::Equal)?;
+                let (name_span, name) = this.eat(Identifier)?;
 
-            Some((specifier, atomic_span, atomic, name_span, name))
-        })?;
+                this.eat_op(Op::Equal)?;
+
+                Some((
+                    mutable,
+                    specifier,
+                    atomic_span,
+                    atomic,
+                    weak,
+                    name_span,
+                    name,
+                ))
+            })?;
 
         let specifier = specifier.or_defaulted(self, name_span);
 
         let local_variable_decl = self.add(
             LocalVariableDeclData {
+                mutable,
                 atomic,
+                weak,
                 specifier,
                 name,
                 ty: None, // FIXME-- should permit `ty: Ty = ...`
@@ -505,6 +1195,7 @@ impl CodeParser<'_, '_> {
             LocalVariableDeclSpan {
                 atomic_span,
                 name_span,
+                ty_span: None,
             },
         );
 
@@ -513,12 +1204,66 @@ impl CodeParser<'_, '_> {
             .or_report_error(self, || "expected value for local variable".to_string())
             .or_dummy_expr(self);
 
+        if let Some((else_span, _)) = self.eat(Keyword::Else) {
+            // `x = value else { ... }`
+            let else_expr = self.parse_required_block_expr(Keyword::Else);
+            let expr = self.add(
+                ExprData::VarElse(local_variable_decl, value, else_expr),
+                self.span_consumed_since(atomic_span),
+            );
+            self.spans.push_else_span(expr, else_span);
+            return Some(expr);
+        }
+
         Some(self.add(
             ExprData::Var(local_variable_decl, value),
             self.span_consumed_since(atomic_span),
         ))
     }
 
+    /// Parses a variable name that introduces a fresh local variable with the
+    /// default specifier, like the `x` in `for x in iterable`, a binding
+    /// pattern in a match arm, or a closure parameter.
+    fn parse_binding(&mut self) -> Option<LocalVariableDecl> {
+        let (name_span, name) = self.eat(Identifier)?;
+        let specifier = SpannedSpecifier::new_defaulted(self.db, name_span.in_file(self.filename));
+        Some(self.add(
+            LocalVariableDeclData {
+                mutable: Mutable::Yes,
+                atomic: Atomic::No,
+                weak: Weak::No,
+                specifier,
+                name,
+                ty: None,
+            },
+            LocalVariableDeclSpan {
+                atomic_span: name_span,
+                name_span,
+                ty_span: None,
+            },
+        ))
+    }
+
+    /// Parses the rest of an `if` expression, after the `if` keyword (at `if_span`).
+    /// An `else if` is parsed directly as the `else` branch, without a block around it.
+    fn parse_if(&mut self, if_span: Span) -> Option<Expr> {
+        let Some(condition) = self.parse_condition() else {
+            self.error_at_current_token("expected `if` condition")
+                .emit(self.db);
+            return None;
+        };
+        let then_expr = self.parse_required_block_expr(Keyword::If);
+        let else_expr = if self.eat(Keyword::Else).is_none() {
+            None
+        } else if let Some((else_if_span, _)) = self.eat(Keyword::If) {
+            Some(self.parse_if(else_if_span).or_dummy_expr(self))
+        } else {
+            Some(self.parse_required_block_expr(Keyword::Else))
+        };
+        let span = self.span_consumed_since(if_span);
+        Some(self.add(ExprData::If(condition, then_expr, else_expr), span))
+    }
+
     fn parse_required_block_expr(&mut self, after: impl std::fmt::Display) -> Expr {
         self.parse_block_expr()
             .or_report_error(self, || format!("expected block after {after}"))
@@ -527,9 +1272,10 @@ impl CodeParser<'_, '_> {
 
     fn parse_block_expr(&mut self) -> Option<Expr> {
         let (span, token_tree) = self.delimited('{')?;
-        let block = self.with_sub_parser(token_tree, |sub_parser| sub_parser.parse_only_expr_seq());
-        let expr = self.add(ExprData::Seq(block), span);
-        Some(expr)
+        let contents = self.with_sub_parser(token_tree, |sub_parser| {
+            sub_parser.parse_only_block_contents()
+        });
+        Some(self.add_block(contents, span))
     }
 
     fn parse_required_sub_expr(&mut self, token_tree: TokenTree) -> Expr {
@@ -559,6 +1305,58 @@ impl CodeParser<'_, '_> {
         Some(self.add(ExprData::Concatenate(exprs), span))
     }
 
+    /// Parses the fields of a class literal like `Point { x: 1, y: 2 }`, given the
+    /// already consumed class name. Returns `None` (consuming nothing) if the name
+    /// is not followed by a `{` on the same line, or if we are in a condition.
+    fn parse_class_literal(&mut self, id_span: Span, id: Word) -> Option<Expr> {
+        if self.in_condition || self.tokens.skipped_newline() {
+            return None;
+        }
+
+        let (fields_span, token_tree) = self.delimited('{')?;
+        let fields =
+            self.with_sub_parser(token_tree, |sub_parser| sub_parser.parse_only_named_exprs());
+        Some(self.add(ExprData::ClassLiteral(id, fields), id_span.to(fields_span)))
+    }
+
+    /// Parses a character literal like `'a'`, `'\n'`, or `'\u{1F600}'`. Escapes
+    /// are interpreted during validation; here we only check that the literal
+    /// is terminated and contains exactly one (possibly escaped) character.
+    fn parse_char_literal(&mut self) -> Option<Expr> {
+        let (span, word) = self.eat(CharLiteral)?;
+        let text = word.as_str(self.db);
+
+        let contents = text[1..].strip_suffix('\'');
+        let len = contents.and_then(char_literal_len);
+        match (contents, len) {
+            (Some(contents), Some(1)) => {
+                let contents = Word::from(self.db, contents);
+                Some(self.add(ExprData::CharLiteral(contents), span))
+            }
+            (_, None) => {
+                self.parser
+                    .error(span, "unterminated character literal")
+                    .primary_label("expected a closing `'`")
+                    .emit(self.db);
+                Some(self.add(ExprData::Error, span))
+            }
+            (_, Some(0)) => {
+                self.parser
+                    .error(span, "empty character literal")
+                    .primary_label("expected a character between the quotes")
+                    .emit(self.db);
+                Some(self.add(ExprData::Error, span))
+            }
+            (_, Some(_)) => {
+                self.parser
+                    .error(span, "character literal may only contain one character")
+                    .primary_label("use `\"` for a string")
+                    .emit(self.db);
+                Some(self.add(ExprData::Error, span))
+            }
+        }
+    }
+
     fn parse_binop(
         &mut self,
         base: Expr,
@@ -566,18 +1364,26 @@ impl CodeParser<'_, '_> {
         mut parse_rhs: impl FnMut(&mut Self) -> Option<Expr>,
     ) -> Option<Expr> {
         for &op in ops {
-            if self.eat_op(op).is_some() {
+            if let Some(op_span) = self.eat_op(op) {
                 let rhs = parse_rhs(self)
                     .or_report_error(self, || format!("expected expression after {op}"))
                     .or_dummy_expr(self);
                 let span = self.spans[base].to(self.spans[rhs]);
-                match op {
-                    Op::ColonEqual => return Some(self.add(ExprData::Assign(base, rhs), span)),
-                    Op::PlusEqual | Op::MinusEqual | Op::DividedByEqual | Op::TimesEqual => {
-                        return Some(self.add(ExprData::OpEq(base, op, rhs), span))
-                    }
-                    _ => return Some(self.add(ExprData::Op(base, op, rhs), span)),
-                }
+                let expr = match op {
+                    Op::ColonEqual => self.add(ExprData::Assign(base, rhs), span),
+                    Op::PlusEqual
+                    | Op::MinusEqual
+                    | Op::DividedByEqual
+                    | Op::TimesEqual
+                    | Op::BitAndEqual
+                    | Op::BitOrEqual
+                    | Op::BitXorEqual
+                    | Op::ShiftLeftEqual
+                    | Op::ShiftRightEqual => self.add(ExprData::OpEq(base, op, rhs), span),
+                    _ => self.add(ExprData::Op(base, op, rhs), span),
+                };
+                self.spans.push_op_span(expr, op_span);
+                return Some(expr);
             }
         }
         None
@@ -605,11 +1411,12 @@ impl CodeParser<'_, '_> {
         token_tree: TokenTree,
         op: impl FnOnce(&mut CodeParser<'_, '_>) -> R,
     ) -> R {
-        let mut parser = Parser::new(self.db, token_tree);
+        let mut parser = self.sub_parser(token_tree);
         let mut sub_parser = CodeParser {
             parser: &mut parser,
             tables: self.tables,
             spans: self.spans,
+            in_condition: false,
         };
         op(&mut sub_parser)
     }
@@ -650,6 +1457,29 @@ impl TightenSpan for LocalVariableDeclSpan {
         LocalVariableDeclSpan {
             atomic_span: self.atomic_span.tighten_span(parser),
             name_span: self.name_span.tighten_span(parser),
+            ty_span: self.ty_span.map(|span| span.tighten_span(parser)),
+        }
+    }
+}
+
+/// Counts the characters in the contents of a character literal, treating an
+/// escape like `\n` or `\u{1F600}` as a single character. Returns `None` if
+/// the contents end in a lone `\`, which means the closing quote was escaped.
+fn char_literal_len(contents: &str) -> Option<usize> {
+    let mut chars = contents.chars().peekable();
+    let mut len = 0;
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            let escaped = chars.next()?;
+            if escaped == 'u' && chars.peek() == Some(&'{') {
+                for ch in chars.by_ref() {
+                    if ch == '}' {
+                        break;
+                    }
+                }
+            }
         }
+        len += 1;
     }
+    Some(len)
 }
@@ -1,6 +1,10 @@
-use crate::{parser::Parser, token_test::SpannedIdentifier};
+use crate::{
+    parser::Parser,
+    token_test::{Alphabetic, Number, SpannedIdentifier},
+};
 
 use dada_ir::{
+    attribute::Attribute,
     class::Class,
     code::{
         syntax::{op::Op, Spans, Tables},
@@ -8,24 +12,30 @@ use dada_ir::{
     },
     effect::Effect,
     function::Function,
+    import::Import,
     item::Item,
     kw::Keyword,
     return_type::{ReturnType, ReturnTypeKind},
     source_file::{self, SourceFile},
     span::Span,
+    token::Token,
+    visibility::Visibility,
     word::{SpannedWord, Word},
 };
 
-use super::OrReportError;
+use super::{code::BlockContents, OrReportError, ParseList};
 
 impl<'db> Parser<'db> {
     pub(crate) fn parse_source_file(&mut self) -> SourceFile {
+        let mut imports = vec![];
         let mut items = vec![];
         let mut exprs = vec![];
         let mut tables = Tables::default();
         let mut spans = Spans::default();
         while self.tokens.peek().is_some() {
-            if let Some(item) = self.parse_item() {
+            if let Some(import) = self.parse_import() {
+                imports.push(import);
+            } else if let Some(item) = self.parse_item() {
                 items.push(item);
             } else if let Some(expr) = self.parse_top_level_expr(&mut tables, &mut spans) {
                 exprs.push(expr);
@@ -48,20 +58,32 @@ impl<'db> Parser<'db> {
             // Create the `main` function entity -- its code is already parsed, so use `None` for `unparsed_code`
             let main_name = Word::from(self.db, source_file::TOP_LEVEL_FN);
             let main_name = SpannedWord::new(self.db, main_name, main_span);
-            let return_type = ReturnType::new(self.db, ReturnTypeKind::Unit, main_span);
+            let return_type = ReturnType::new(self.db, ReturnTypeKind::Unit, main_span, None, None);
             let function = Function::new(
                 self.db,
                 main_name,
+                vec![],
+                vec![],
+                vec![],
+                Visibility::Private,
                 Effect::Async,
                 main_span,
                 return_type,
                 None,
                 main_span,
+                None,
             );
 
             // Set the syntax-tree and parameters for the main function.
-            let syntax_tree = self.create_syntax_tree(start_span, vec![], tables, spans, exprs);
-            crate::code_parser::parse_function_body::set(self.db, function, syntax_tree);
+            // The value of the last expression is the tail, as in a block.
+            let tail = exprs.pop();
+            let contents = BlockContents {
+                statements: exprs,
+                tail,
+                dangling_comments: vec![],
+            };
+            let syntax_tree = self.create_syntax_tree(start_span, vec![], tables, spans, contents);
+            crate::code_parser::function_syntax_tree::set(self.db, function, syntax_tree);
             crate::parameter_parser::parse_function_parameters::set(self.db, function, vec![]);
 
             items.push(Item::Function(function));
@@ -70,20 +92,97 @@ impl<'db> Parser<'db> {
             None
         };
 
-        SourceFile::new(self.db, self.filename, items, main_fn)
+        SourceFile::new(self.db, self.filename, imports, items, main_fn)
+    }
+
+    /// Parses a `use module.name` declaration.
+    fn parse_import(&mut self) -> Option<Import> {
+        self.eat(Keyword::Use)?;
+        let (_, module) = self
+            .eat(SpannedIdentifier)
+            .or_report_error(self, || "expected a module name after `use`")?;
+        self.eat_op(Op::Dot)
+            .or_report_error(self, || "expected `.` after the module name")?;
+        let (_, name) = self
+            .eat(SpannedIdentifier)
+            .or_report_error(self, || "expected the name of an item to import")?;
+        Some(Import { module, name })
     }
 
     fn parse_item(&mut self) -> Option<Item> {
-        if let Some(class) = self.parse_class() {
+        let (docs, attributes) = self.parse_docs_and_attributes();
+        let pub_span = self.eat(Keyword::Pub).map(|(span, _)| span);
+        let visibility = match pub_span {
+            Some(_) => Visibility::Pub,
+            None => Visibility::Private,
+        };
+        if let Some(class) = self.parse_class(visibility) {
+            self.report_misplaced_attributes(
+                &attributes,
+                "attributes are only allowed on functions",
+            );
             Some(Item::Class(class))
-        } else if let Some(func) = self.parse_function() {
-            Some(Item::Function(func))
+        } else if self.is_function_next() {
+            Some(Item::Function(
+                self.parse_function(docs, attributes, visibility, None)?,
+            ))
         } else {
+            self.report_misplaced_attributes(
+                &attributes,
+                "expected a function after this attribute",
+            );
+            if let Some(pub_span) = pub_span {
+                self.error(pub_span, "expected a function or class after `pub`")
+                    .emit(self.db);
+            }
             None
         }
     }
 
-    fn parse_class(&mut self) -> Option<Class> {
+    /// Parses the doc comment and the attributes before an item, like
+    /// `@test` or `@export(name)`. The lines of the doc comment may come
+    /// before, between or after the attributes.
+    fn parse_docs_and_attributes(&mut self) -> (Vec<SpannedWord>, Vec<Attribute>) {
+        let mut docs = self.parse_doc_comment();
+        let mut attributes = vec![];
+        while let Some((at_span, _)) = self.eat(Token::Unknown('@')) {
+            attributes.extend(self.parse_attribute(at_span));
+            docs.extend(self.parse_doc_comment());
+        }
+        (docs, attributes)
+    }
+
+    /// Parses the rest of the attribute whose `@` is at `at_span`.
+    fn parse_attribute(&mut self, at_span: Span) -> Option<Attribute> {
+        let (_, name) = self
+            .eat(SpannedIdentifier)
+            .or_report_error(self, || "expected an attribute name")?;
+        let args = match self.delimited('(') {
+            Some((_, arg_tokens)) => {
+                let mut parser = self.sub_parser(arg_tokens);
+                let args = parser.parse_list(true, |parser| {
+                    let (_, arg) = parser.eat(Alphabetic).or_else(|| parser.eat(Number))?;
+                    Some(arg)
+                });
+                parser.emit_error_if_more_tokens("extra tokens after attribute arguments");
+                args
+            }
+            None => vec![],
+        };
+        Some(Attribute {
+            name,
+            args,
+            span: self.span_consumed_since(at_span).in_file(self.filename),
+        })
+    }
+
+    fn report_misplaced_attributes(&self, attributes: &[Attribute], message: &str) {
+        for attribute in attributes {
+            dada_ir::error!(attribute.span, "{}", message).emit(self.db);
+        }
+    }
+
+    fn parse_class(&mut self, visibility: Visibility) -> Option<Class> {
         let (class_span, _) = self.eat(Keyword::Class)?;
         let (_, class_name) = self
             .eat(SpannedIdentifier)
@@ -91,28 +190,68 @@ impl<'db> Parser<'db> {
         let (_, field_tokens) = self
             .delimited('(')
             .or_report_error(self, || "expected class parameters")?;
+        let body_tokens = self.delimited('{').map(|(_, body_tokens)| body_tokens);
         Some(Class::new(
             self.db,
             class_name,
+            visibility,
             field_tokens,
+            body_tokens,
             self.span_consumed_since(class_span).in_file(self.filename),
         ))
     }
 
-    fn parse_function(&mut self) -> Option<Function> {
-        // Look ahead to see if this is a function. It can look like
-        //
-        //     async? fn
-        let is_fn = self.testahead(|parser| {
-            let _ = parser.eat(Keyword::Async); // optional async keyword
-            parser.eat(Keyword::Fn).is_some()
-        });
-        if !is_fn {
-            return None;
+    /// Parses the methods in the body of `class`.
+    pub(crate) fn parse_only_methods(&mut self, class: Class) -> Vec<Function> {
+        let mut methods = vec![];
+        while self.tokens.peek().is_some() {
+            let (docs, attributes) = self.parse_docs_and_attributes();
+            let visibility = match self.eat(Keyword::Pub) {
+                Some(_) => Visibility::Pub,
+                None => Visibility::Private,
+            };
+            if self.is_function_next() {
+                methods.extend(self.parse_function(docs, attributes, visibility, Some(class)));
+            } else {
+                self.report_misplaced_attributes(
+                    &attributes,
+                    "expected a method after this attribute",
+                );
+                self.error_at_current_token("expected a method")
+                    .primary_label("only methods can be declared in a class body")
+                    .emit(self.db);
+                self.tokens.consume();
+            }
         }
+        methods
+    }
+
+    /// Looks ahead to see if a function comes next. It can look like
+    ///
+    ///     (async|const|gen)? fn
+    pub(crate) fn is_function_next(&mut self) -> bool {
+        self.testahead(|parser| {
+            // optional effect keyword
+            if parser.eat(Keyword::Async).is_none() && parser.eat(Keyword::Const).is_none() {
+                let _ = parser.eat(Keyword::Gen);
+            }
+            parser.eat(Keyword::Fn).is_some()
+        })
+    }
 
+    pub(crate) fn parse_function(
+        &mut self,
+        docs: Vec<SpannedWord>,
+        attributes: Vec<Attribute>,
+        visibility: Visibility,
+        class: Option<Class>,
+    ) -> Option<Function> {
         let (effect_span, effect) = if let Some((span, _)) = self.eat(Keyword::Async) {
             (Some(span), Effect::Async)
+        } else if let Some((span, _)) = self.eat(Keyword::Const) {
+            (Some(span), Effect::Const)
+        } else if let Some((span, _)) = self.eat(Keyword::Gen) {
+            (Some(span), Effect::Generator)
         } else {
             (None, Effect::Default)
         };
@@ -120,27 +259,34 @@ impl<'db> Parser<'db> {
         let (_, func_name) = self
             .eat(SpannedIdentifier)
             .or_report_error(self, || "expected function name".to_string())?;
+        let generics = self.parse_generics();
         let (_, parameter_tokens) = self
             .delimited('(')
             .or_report_error(self, || "expected function parameters".to_string())?;
         let return_type = {
             let right_arrow = self.eat_op(Op::RightArrow);
+            let tuple_arity = right_arrow.and_then(|_| self.parse_tuple_return_type());
+            let ty = match (right_arrow, tuple_arity) {
+                (Some(_), None) => self.parse_ty().map(|(_, ty)| ty),
+                _ => None,
+            };
+            let (kind, tuple_arity) = match (right_arrow, tuple_arity, ty) {
+                (None, ..) => (ReturnTypeKind::Unit, None),
+                (Some(_), Some(0), _) => (ReturnTypeKind::ExplicitUnit, None),
+                (Some(_), Some(_), _) | (Some(_), None, Some(_)) => {
+                    (ReturnTypeKind::Declared, tuple_arity)
+                }
+                (Some(_), None, None) => (ReturnTypeKind::Inferred, None),
+            };
             let span = right_arrow
+                .map(|right_arrow| self.span_consumed_since(right_arrow))
                 .unwrap_or_else(|| Span {
                     // span between last non skipped token and next non skippable token
                     start: self.tokens.last_span().end,
                     end: self.tokens.peek_span().start,
                 })
                 .in_file(self.filename);
-            ReturnType::new(
-                self.db,
-                if right_arrow.is_some() {
-                    ReturnTypeKind::Value
-                } else {
-                    ReturnTypeKind::Unit
-                },
-                span,
-            )
+            ReturnType::new(self.db, kind, span, tuple_arity, ty)
         };
         let (_, body_tokens) = self
             .delimited('{')
@@ -150,11 +296,55 @@ impl<'db> Parser<'db> {
         Some(Function::new(
             self.db,
             func_name,
+            generics,
+            docs,
+            attributes,
+            visibility,
             effect,
             effect_span.unwrap_or(fn_span).in_file(self.filename),
             return_type,
             Some(code),
             self.span_consumed_since(start_span).in_file(self.filename),
+            class,
         ))
     }
+
+    /// Returns the lines of the doc comment before the next token. Doc
+    /// comments start with `##`; other comments are ignored.
+    pub(crate) fn parse_doc_comment(&self) -> Vec<SpannedWord> {
+        self.tokens
+            .leading_comments()
+            .into_iter()
+            .filter_map(|span| {
+                let text = self.snippet(span).strip_prefix("##")?;
+                let text = text.strip_prefix(' ').unwrap_or(text).trim_end();
+                let word = Word::from(self.db, text);
+                Some(SpannedWord::new(self.db, word, span.in_file(self.filename)))
+            })
+            .collect()
+    }
+
+    /// Parses the (optional) generic parameters of a function, like `[T, U]`.
+    fn parse_generics(&mut self) -> Vec<SpannedWord> {
+        let Some((_, generic_tokens)) = self.delimited('[') else {
+            return vec![];
+        };
+        let mut parser = self.sub_parser(generic_tokens);
+        let generics = parser.parse_list(true, |parser| {
+            let (_, name) = parser.eat(SpannedIdentifier)?;
+            Some(name)
+        });
+        parser.emit_error_if_more_tokens("extra tokens after generic parameters");
+        generics
+    }
+
+    /// Parses a tuple return type like `(Int, Int)` and returns its number of elements.
+    fn parse_tuple_return_type(&mut self) -> Option<usize> {
+        let (_, element_tokens) = self.delimited('(')?;
+        let mut parser = self.sub_parser(element_tokens);
+        // FIXME: types are not resolved yet, so each element is just a name
+        let elements = parser.parse_list(true, |parser| parser.eat(SpannedIdentifier));
+        parser.emit_error_if_more_tokens("extra tokens after tuple element types");
+        Some(elements.len())
+    }
 }
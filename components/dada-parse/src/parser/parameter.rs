@@ -6,10 +6,10 @@ use dada_ir::{
     kw::Keyword,
     parameter::Parameter,
     span::Span,
-    storage::{Atomic, SpannedSpecifier, Specifier},
+    storage::{Atomic, Mutable, SpannedSpecifier, Specifier, Weak},
 };
 
-use super::ParseList;
+use super::{OrReportError, ParseList};
 
 impl<'db> Parser<'db> {
     pub(crate) fn parse_only_parameters(&mut self) -> Vec<Parameter> {
@@ -23,7 +23,13 @@ impl<'db> Parser<'db> {
         let opt_storage_mode = self.parse_atomic();
         if let Some((name_span, name)) = self.eat(Identifier) {
             let opt_ty = if let Some(colon_span) = self.eat_op(Op::Colon) {
-                let opt_ty = self.parse_ty();
+                // The type must be on the same line as the `:`, so that a
+                // missing type doesn't swallow the parameter on the next line.
+                let opt_ty = if self.skipped_newline() {
+                    None
+                } else {
+                    self.parse_ty()
+                };
 
                 if opt_ty.is_none() {
                     self.error_at_current_token(&"expected type after `:`".to_string())
@@ -36,6 +42,13 @@ impl<'db> Parser<'db> {
                 None
             };
 
+            let default = if self.eat_op(Op::Equal).is_some() {
+                self.parse_default_value()
+                    .or_report_error(self, || "expected a default value after `=`")
+            } else {
+                None
+            };
+
             let (atomic_span, atomic) = match opt_storage_mode {
                 Some(span) => (span, Atomic::Yes),
                 None => (name_span, Atomic::No),
@@ -44,18 +57,21 @@ impl<'db> Parser<'db> {
             let specifier = opt_specifier.or_defaulted(self, name_span);
 
             let decl = LocalVariableDeclData {
+                mutable: Mutable::Yes,
                 atomic,
+                weak: Weak::No,
                 specifier,
                 name,
-                ty: opt_ty,
+                ty: opt_ty.map(|(_, ty)| ty),
             };
 
             let decl_span = LocalVariableDeclSpan {
                 atomic_span,
                 name_span,
+                ty_span: opt_ty.map(|(ty_span, _)| ty_span),
             };
 
-            Some(Parameter::new(self.db, name, decl, decl_span))
+            Some(Parameter::new(self.db, name, decl, decl_span, default))
         } else {
             // No identifier == no parameter; if there's a storage mode,
             // that's an error.
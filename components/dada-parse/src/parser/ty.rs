@@ -1,9 +1,14 @@
-use crate::parser::Parser;
+use crate::{parser::Parser, token_test::Identifier};
 
-use dada_ir::ty::Ty;
+use dada_ir::{
+    span::Span,
+    ty::{Ty, TyData},
+};
 
 impl<'db> Parser<'db> {
-    pub(crate) fn parse_ty(&mut self) -> Option<Ty> {
-        None
+    /// Parses a type, like the `Int` in `x: Int`.
+    pub(crate) fn parse_ty(&mut self) -> Option<(Span, Ty)> {
+        let (name_span, name) = self.eat(Identifier)?;
+        Some((name_span, TyData::Named(name).intern(self.db)))
     }
 }
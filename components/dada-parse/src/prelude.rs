@@ -1,8 +1,11 @@
 use dada_ir::{
-    class::Class, code::syntax, filename::Filename, function::Function, item::Item,
-    parameter::Parameter, source_file::SourceFile,
+    class::Class, code::syntax, filename::Filename, function::Function, import::Import, item::Item,
+    parameter::Parameter, source_file::SourceFile, span::FileSpan,
 };
 
+use crate::code_parser::FunctionText;
+use crate::semantic_tokens::SemanticTokenKind;
+
 #[extension_trait::extension_trait]
 pub impl DadaParseItemExt for Item {
     fn syntax_tree(self, db: &dyn crate::Db) -> Option<syntax::Tree> {
@@ -17,12 +20,30 @@ pub impl DadaParseItemExt for Item {
 pub impl DadaParseFunctionExt for Function {
     /// Returns the Ast for a function.
     fn syntax_tree(self, db: &dyn crate::Db) -> syntax::Tree {
-        crate::code_parser::parse_function_body(db, self)
+        crate::code_parser::function_syntax_tree(db, self)
+    }
+
+    /// Returns the spans for the function's Ast. These are kept separate
+    /// from the Ast to avoid reducing incremental reuse.
+    fn spans(self, db: &dyn crate::Db) -> &syntax::Spans {
+        crate::code_parser::function_spans(db, self)
+    }
+
+    /// The text of the function, which its body is parsed from. Parsing is
+    /// keyed on the text, so moving the function does not reparse it.
+    fn text(self, db: &dyn crate::Db) -> FunctionText {
+        crate::code_parser::function_text(db, self)
     }
 
     fn parameters(self, db: &dyn crate::Db) -> &[Parameter] {
         crate::parameter_parser::parse_function_parameters(db, self)
     }
+
+    /// The tokens of the function body classified for semantic highlighting,
+    /// sorted by where they start.
+    fn semantic_tokens(self, db: &dyn crate::Db) -> &[(FileSpan, SemanticTokenKind)] {
+        crate::semantic_tokens::semantic_tokens(db, self)
+    }
 }
 
 #[extension_trait::extension_trait]
@@ -30,6 +51,11 @@ pub impl DadaParseClassExt for Class {
     fn fields(self, db: &dyn crate::Db) -> &Vec<Parameter> {
         crate::parameter_parser::parse_class_parameters(db, self)
     }
+
+    /// The methods declared in the body of the class.
+    fn methods(self, db: &dyn crate::Db) -> &Vec<Function> {
+        crate::method_parser::parse_class_methods(db, self)
+    }
 }
 
 #[extension_trait::extension_trait]
@@ -41,4 +67,8 @@ pub impl DadaParseFilenameExt for Filename {
     fn items(self, db: &dyn crate::Db) -> &Vec<Item> {
         self.source_file(db).items(db)
     }
+
+    fn imports(self, db: &dyn crate::Db) -> &Vec<Import> {
+        self.source_file(db).imports(db)
+    }
 }
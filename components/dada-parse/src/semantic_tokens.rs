@@ -0,0 +1,288 @@
+//! Classifies the tokens of a function body for semantic highlighting.
+//!
+//! Tokens are located using the span tables of the syntax tree (narrowed
+//! to the keyword or operator where necessary), so every token maps to a
+//! real range of the source. Tokens that the syntax tree does not record,
+//! like punctuation or the text of a format string with `{...}` sections,
+//! are not classified.
+
+use dada_ir::code::syntax::{
+    self,
+    visit::{self, Visitor},
+    Expr, ExprData, LocalVariableDecl, NamedExpr,
+};
+use dada_ir::filename::Filename;
+use dada_ir::function::Function;
+use dada_ir::kw::Keyword;
+use dada_ir::span::{FileSpan, Offset};
+use dada_ir::storage::Atomic;
+use dada_lex::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SemanticTokenKind {
+    /// The name of a variable, function, class, or field.
+    Identifier,
+
+    /// A keyword like `if`, `while`, `loop`, or `atomic`.
+    Keyword,
+
+    /// A literal like `22`, `'a'`, `"hello"`, or `true`.
+    Literal,
+
+    /// An operator like `+`, `-`, or `:=`.
+    Operator,
+
+    /// A storage mode like `my` or `leased`, or a permission operation like `.give`.
+    StorageMode,
+}
+
+/// Returns the classified tokens of `function`, sorted by where they start.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub(crate) fn semantic_tokens(
+    db: &dyn crate::Db,
+    function: Function,
+) -> Vec<(FileSpan, SemanticTokenKind)> {
+    let tree_data = function.syntax_tree(db).data(db);
+    let mut classifier = Classifier {
+        db,
+        filename: function.filename(db),
+        tables: &tree_data.tables,
+        spans: function.spans(db),
+        tokens: vec![],
+    };
+    for &decl in &tree_data.parameter_decls {
+        classifier.visit_local_variable_decl(decl);
+    }
+    classifier.visit_expr(tree_data.root_expr);
+
+    let mut tokens = classifier.tokens;
+    tokens.sort_by_key(|(span, _)| (span.start, span.end));
+    tokens.dedup();
+    tokens
+}
+
+struct Classifier<'me> {
+    db: &'me dyn crate::Db,
+    filename: Filename,
+    tables: &'me syntax::Tables,
+    spans: &'me syntax::Spans,
+    tokens: Vec<(FileSpan, SemanticTokenKind)>,
+}
+
+impl<'me> Visitor<'me> for Classifier<'me> {
+    fn tables(&self) -> &'me syntax::Tables {
+        self.tables
+    }
+
+    fn visit_expr(&mut self, expr: Expr) {
+        let span = self.span(expr);
+        let tables = self.tables;
+        match &tables[expr] {
+            ExprData::Id(_) => self.push(span, SemanticTokenKind::Identifier),
+
+            ExprData::BooleanLiteral(_)
+            | ExprData::IntegerLiteral(..)
+            | ExprData::FloatLiteral(_)
+            | ExprData::CharLiteral(_)
+            | ExprData::StringLiteral(_) => self.push(span, SemanticTokenKind::Literal),
+
+            ExprData::Concatenate(exprs) => {
+                // Each text section has the span of the entire string, so only
+                // strings without `{...}` sections can be classified.
+                let code_exprs: Vec<Expr> = exprs
+                    .iter()
+                    .copied()
+                    .filter(|&expr| !matches!(tables[expr], ExprData::StringLiteral(_)))
+                    .collect();
+                if code_exprs.is_empty() {
+                    self.push(span, SemanticTokenKind::Literal);
+                }
+                for expr in code_exprs {
+                    self.visit_expr(expr);
+                }
+                return;
+            }
+
+            ExprData::Dot(_, field) => {
+                let field = field.as_str(self.db);
+                if let Some(start) = usize::from(span.end).checked_sub(field.len()) {
+                    let field_span = FileSpan {
+                        start: Offset::from(start),
+                        ..span
+                    };
+                    self.push_if_text(field_span, field, SemanticTokenKind::Identifier);
+                }
+            }
+
+            ExprData::ClassLiteral(class, _) => {
+                let class = class.as_str(self.db);
+                let class_span = FileSpan {
+                    end: span.start + class.len(),
+                    ..span
+                };
+                self.push_if_text(class_span, class, SemanticTokenKind::Identifier);
+            }
+
+            ExprData::Await(_) => self.trailing_keyword(span, Keyword::Await),
+            ExprData::Share(_) => self.trailing_storage_mode(span, Keyword::Share),
+            ExprData::Lease(_) => self.trailing_storage_mode(span, Keyword::Lease),
+            ExprData::Shlease(_) => self.trailing_storage_mode(span, Keyword::Shlease),
+            ExprData::Give(_) => self.trailing_storage_mode(span, Keyword::Give),
+
+            ExprData::VarElse(_, initializer_expr, else_expr) => {
+                let gap = self.gap(self.span(*initializer_expr), self.span(*else_expr));
+                self.leading_keyword(gap, Keyword::Else);
+            }
+
+            ExprData::If(_, then_expr, else_expr) => {
+                self.leading_keyword(span, Keyword::If);
+                if let Some(else_expr) = else_expr {
+                    let gap = self.gap(self.span(*then_expr), self.span(*else_expr));
+                    self.leading_keyword(gap, Keyword::Else);
+                }
+            }
+
+            ExprData::Atomic(_) => self.leading_keyword(span, Keyword::Atomic),
+            ExprData::Loop(..) => self.leading_keyword(span, Keyword::Loop),
+            ExprData::While(..) => self.leading_keyword(span, Keyword::While),
+            ExprData::Match(..) => self.leading_keyword(span, Keyword::Match),
+            ExprData::Return(_) => self.leading_keyword(span, Keyword::Return),
+            ExprData::Yield(_) => self.leading_keyword(span, Keyword::Yield),
+            ExprData::Break(..) => self.leading_keyword(span, Keyword::Break),
+            ExprData::Continue(_) => self.leading_keyword(span, Keyword::Continue),
+            ExprData::Fn(function) => {
+                self.leading_keyword(span, Keyword::Fn);
+                let name_span = function.name(self.db).span(self.db);
+                self.push(name_span, SemanticTokenKind::Identifier);
+            }
+
+            ExprData::For(_, decl, iterable_expr, _) => {
+                self.leading_keyword(span, Keyword::For);
+                let decl_span = self.spans[*decl].name_span.in_file(self.filename);
+                let gap = self.gap(decl_span, self.span(*iterable_expr));
+                self.leading_keyword(gap, Keyword::In);
+            }
+
+            ExprData::Op(lhs_expr, _, rhs_expr)
+            | ExprData::OpEq(lhs_expr, _, rhs_expr)
+            | ExprData::Assign(lhs_expr, rhs_expr) => {
+                let gap = self.gap(self.span(*lhs_expr), self.span(*rhs_expr));
+                self.operator(gap);
+            }
+
+            ExprData::Unary(_, rhs_expr) => {
+                let gap = FileSpan {
+                    end: self.span(*rhs_expr).start,
+                    ..span
+                };
+                self.operator(gap);
+            }
+
+            ExprData::Range(start_expr, end_expr, _) => {
+                let start = start_expr.map_or(span.start, |expr| self.span(expr).end);
+                let end = end_expr.map_or(span.end, |expr| self.span(expr).start);
+                if start <= end {
+                    self.operator(FileSpan { start, end, ..span });
+                }
+            }
+
+            ExprData::Call(..)
+            | ExprData::Index(..)
+            | ExprData::Var(..)
+            | ExprData::Parenthesized(_)
+            | ExprData::Tuple(_)
+            | ExprData::List(_)
+            | ExprData::Closure(..)
+            | ExprData::Seq(..)
+            | ExprData::Error => {}
+        }
+
+        visit::walk_expr(self, expr)
+    }
+
+    fn visit_named_expr(&mut self, named_expr: NamedExpr) {
+        let name = self.tables[named_expr].name;
+        if name.word(self.db).is_some() {
+            self.push(name.span(self.db), SemanticTokenKind::Identifier);
+        }
+        visit::walk_named_expr(self, named_expr)
+    }
+
+    fn visit_local_variable_decl(&mut self, decl: LocalVariableDecl) {
+        let (tables, spans) = (self.tables, self.spans);
+        let data = &tables[decl];
+        let decl_span = &spans[decl];
+        if data.atomic == Atomic::Yes {
+            self.push(
+                decl_span.atomic_span.in_file(self.filename),
+                SemanticTokenKind::Keyword,
+            );
+        }
+        if !data.specifier.defaulted(self.db) {
+            self.push(data.specifier.span(self.db), SemanticTokenKind::StorageMode);
+        }
+        self.push(
+            decl_span.name_span.in_file(self.filename),
+            SemanticTokenKind::Identifier,
+        );
+    }
+}
+
+impl Classifier<'_> {
+    fn span(&self, expr: Expr) -> FileSpan {
+        self.spans[expr].in_file(self.filename)
+    }
+
+    fn push(&mut self, span: FileSpan, kind: SemanticTokenKind) {
+        self.tokens.push((span, kind));
+    }
+
+    /// Pushes `span` only if it covers exactly `text`, so that an
+    /// unexpected span never produces a bogus token.
+    fn push_if_text(&mut self, span: FileSpan, text: &str, kind: SemanticTokenKind) {
+        if span.start <= span.end && span.snippet(self.db) == text {
+            self.push(span, kind);
+        }
+    }
+
+    /// The span between the end of `before` and the start of `after`.
+    fn gap(&self, before: FileSpan, after: FileSpan) -> FileSpan {
+        FileSpan {
+            filename: self.filename,
+            start: before.end,
+            end: after.start.max(before.end),
+        }
+    }
+
+    fn leading_keyword(&mut self, within: FileSpan, keyword: Keyword) {
+        let span = within.leading_keyword(self.db, keyword);
+        self.push_if_text(span, keyword.str(), SemanticTokenKind::Keyword);
+    }
+
+    fn trailing_keyword(&mut self, within: FileSpan, keyword: Keyword) {
+        let span = within.trailing_keyword(self.db, keyword);
+        self.push_if_text(span, keyword.str(), SemanticTokenKind::Keyword);
+    }
+
+    fn trailing_storage_mode(&mut self, within: FileSpan, keyword: Keyword) {
+        let span = within.trailing_keyword(self.db, keyword);
+        self.push_if_text(span, keyword.str(), SemanticTokenKind::StorageMode);
+    }
+
+    /// Pushes the operator in `gap`, which is the text between two operands.
+    fn operator(&mut self, gap: FileSpan) {
+        let text = gap.snippet(self.db);
+        let start = gap.start + (text.len() - text.trim_start().len());
+        let span = FileSpan {
+            start,
+            end: start + text.trim().len(),
+            ..gap
+        };
+        if span.start < span.end {
+            self.push(span, SemanticTokenKind::Operator);
+        }
+    }
+}
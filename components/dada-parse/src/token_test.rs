@@ -115,6 +115,20 @@ impl TokenTest for FormatStringLiteral {
     }
 }
 
+/// A character literal like `'a'`, including its quotes.
+#[derive(Debug)]
+pub(crate) struct CharLiteral;
+impl TokenTest for CharLiteral {
+    type Narrow = Word;
+
+    fn test(self, _db: &dyn crate::Db, token: Token, _span: FileSpan) -> Option<Word> {
+        match token {
+            Token::CharLiteral(w) => Some(w),
+            _ => None,
+        }
+    }
+}
+
 impl TokenTest for Token {
     type Narrow = Token;
 
@@ -0,0 +1,86 @@
+//! Exposes the tokens of a file without parsing it, for tools like syntax
+//! highlighters.
+
+use dada_ir::filename::Filename;
+use dada_ir::span::Span;
+use dada_ir::token::Token;
+use dada_ir::token_tree::TokenTree;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A name like `foo` that is not a keyword.
+    Identifier,
+
+    /// A keyword like `fn` or `if`.
+    Keyword,
+
+    /// A number like `22` or `0xff`. The `.` of a float is a separate [`TokenKind::Op`].
+    Number,
+
+    /// A single character of an operator; `+=` is two tokens.
+    Op,
+
+    /// A `,`.
+    Comma,
+
+    /// `(`, `)`, `[`, `]`, `{`, or `}`.
+    Delimiter,
+
+    /// A word written right before a string or character literal, like the `b` in `b"foo"`.
+    Prefix,
+
+    /// A string literal like `"foo {bar}"`, including its quotes.
+    StringLiteral,
+
+    /// A character literal like `'a'`, including its quotes.
+    CharLiteral,
+
+    /// A single whitespace character, including newlines.
+    Whitespace,
+
+    /// A `# ...` comment, not including the newline that ends it.
+    Comment,
+
+    /// A character that cannot begin any other kind of token.
+    Unknown,
+}
+
+/// Returns the tokens of `filename`, in order, as produced by the same lexer
+/// that the parser uses. Unlike the parser, this includes whitespace and comments,
+/// so the spans cover the entire source text.
+pub fn tokenize(db: &dyn crate::Db, filename: Filename) -> Vec<(TokenKind, Span)> {
+    let mut tokens = vec![];
+    push_tokens(db, dada_lex::lex_file(db, filename), &mut tokens);
+    tokens
+}
+
+/// Pushes the tokens of `token_tree` onto `output`, flattening the trees
+/// that hold the contents of delimiters.
+fn push_tokens(db: &dyn crate::Db, token_tree: TokenTree, output: &mut Vec<(TokenKind, Span)>) {
+    for (span, token) in token_tree.spanned_tokens(db) {
+        let kind = match token {
+            Token::Tree(tree) => {
+                push_tokens(db, tree, output);
+                continue;
+            }
+            Token::Alphabetic(word) => {
+                if dada_ir::kw::keywords(db).contains_key(&word) {
+                    TokenKind::Keyword
+                } else {
+                    TokenKind::Identifier
+                }
+            }
+            Token::Number(_) => TokenKind::Number,
+            Token::Comma => TokenKind::Comma,
+            Token::Op(_) => TokenKind::Op,
+            Token::Delimiter(_) => TokenKind::Delimiter,
+            Token::Prefix(_) => TokenKind::Prefix,
+            Token::FormatString(_) => TokenKind::StringLiteral,
+            Token::CharLiteral(_) => TokenKind::CharLiteral,
+            Token::Whitespace(_) => TokenKind::Whitespace,
+            Token::Unknown(_) => TokenKind::Unknown,
+            Token::Comment(_) => TokenKind::Comment,
+        };
+        output.push((kind, span));
+    }
+}
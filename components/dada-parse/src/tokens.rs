@@ -1,4 +1,8 @@
-use dada_ir::{span::Span, token::Token, token_tree::TokenTree};
+use dada_ir::{
+    span::{Offset, Span},
+    token::Token,
+    token_tree::TokenTree,
+};
 
 #[derive(Copy, Clone)]
 pub(crate) struct Tokens<'me> {
@@ -11,6 +15,14 @@ pub(crate) struct Tokens<'me> {
     last_not_skipped_span: Span,
 
     skipped: Skipped,
+
+    /// The tokens skipped before the next pending token, which start at `skipped_start`.
+    skipped_tokens: &'me [Token],
+    skipped_start: Offset,
+
+    /// True once any token has been consumed.
+    consumed_any: bool,
+
     tokens: &'me [Token],
 }
 
@@ -31,6 +43,9 @@ impl<'me> Tokens<'me> {
             last_not_skipped_span: start_span,
             tokens,
             skipped: Skipped::None,
+            skipped_tokens: &[],
+            skipped_start: start_span.end,
+            consumed_any: false,
         };
         this.skip_tokens();
         this
@@ -79,6 +94,8 @@ impl<'me> Tokens<'me> {
     /// such as whitespace.
     fn skip_tokens(&mut self) {
         self.skipped = Skipped::None;
+        self.skipped_start = self.last_span.end;
+        let tokens = self.tokens;
         while let Some(t) = self.peek() {
             if let Some(skipped) = self.should_skip_token(t) {
                 self.skipped = self.skipped.max(skipped);
@@ -87,11 +104,67 @@ impl<'me> Tokens<'me> {
                 break;
             }
         }
+        self.skipped_tokens = &tokens[..tokens.len() - self.tokens.len()];
+    }
+
+    /// The spans of the comments right before the next pending token, like
+    /// the doc comment of an item. These are the comments on the lines just
+    /// above the token: a blank line ends the run, and a comment at the end
+    /// of the line of the previous token belongs to that token instead.
+    pub(crate) fn leading_comments(&self) -> Vec<Span> {
+        let mut comments = vec![];
+        let mut newlines = 0;
+        let mut first = true;
+        let mut start = self.skipped_start;
+        for &token in self.skipped_tokens {
+            let span = Span::from(start, start + token.span_len(self.db));
+            start = span.end;
+            match token {
+                Token::Comment(_) => {
+                    if newlines > 1 {
+                        comments.clear();
+                    }
+                    if !(first && newlines == 0 && self.consumed_any) {
+                        comments.push(span);
+                    }
+                    first = false;
+                    newlines = 0;
+                }
+                Token::Whitespace('\n') => newlines += 1,
+                _ => {}
+            }
+        }
+        if newlines > 1 {
+            comments.clear();
+        }
+        comments
+    }
+
+    /// The spans of all comments skipped before the next pending token. The
+    /// first is the comment at the end of the line of the previous token, if
+    /// there is one; the second are the others, which are on lines of their own.
+    pub(crate) fn skipped_comments(&self) -> (Option<Span>, Vec<Span>) {
+        let mut trailing = None;
+        let mut others = vec![];
+        let mut newline = false;
+        let mut start = self.skipped_start;
+        for &token in self.skipped_tokens {
+            let span = Span::from(start, start + token.span_len(self.db));
+            start = span.end;
+            match token {
+                Token::Comment(_) if !newline && self.consumed_any => trailing = Some(span),
+                Token::Comment(_) => others.push(span),
+                Token::Whitespace('\n') => newline = true,
+                _ => {}
+            }
+        }
+        (trailing, others)
     }
 
     /// Advance by one token and return the span + token just consumed (if any).
     pub(crate) fn consume(&mut self) -> Option<Token> {
         let token = self.next_token(false)?;
+        self.consumed_any = true;
 
         self.skip_tokens();
 
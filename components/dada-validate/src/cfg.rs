@@ -0,0 +1,329 @@
+//! Builds a control-flow graph from a function's validated tree, so that
+//! dataflow analyses (definite assignment, dead code, moves) can share one
+//! traversal instead of each re-deriving the control flow of `if`, `loop`,
+//! `break`, `continue`, and `return`.
+//!
+//! The graph only contains reachable code: expressions that follow a
+//! `break`, `continue`, or `return` are left out, and no block is created
+//! unless something jumps to it.
+
+use dada_id::{id, prelude::*, tables};
+use dada_ir::{code::validated, function::Function};
+
+use crate::prelude::*;
+
+/// Builds the control-flow graph for `function`.
+pub fn build_cfg(db: &dyn crate::Db, function: Function) -> Cfg {
+    let tree = function.validated_tree(db);
+    let tree_data = tree.data(db);
+
+    let mut builder = Builder {
+        validated_tables: &tree_data.tables,
+        tables: Tables::default(),
+        current_block: None,
+        loops: vec![],
+    };
+    let start_block = builder.new_block(tree_data.root_expr);
+    builder.current_block = Some(start_block);
+    builder.build_expr(tree_data.root_expr);
+    builder.terminate(tree_data.root_expr, Terminator::Return, None);
+
+    Cfg {
+        tables: builder.tables,
+        start_block,
+    }
+}
+
+/// The control-flow graph of a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cfg {
+    pub tables: Tables,
+
+    /// The block where the function starts executing.
+    pub start_block: BasicBlock,
+}
+
+impl Cfg {
+    pub fn num_basic_blocks(&self) -> usize {
+        usize::from(BasicBlock::max_key(&self.tables))
+    }
+
+    pub fn all_basic_blocks(&self) -> impl Iterator<Item = BasicBlock> {
+        BasicBlock::max_key(&self.tables).iter()
+    }
+
+    /// The blocks that control may go to when `block` finishes.
+    pub fn successors(&self, block: BasicBlock) -> Vec<BasicBlock> {
+        match self.tables[block].terminator {
+            Terminator::Goto(target) => vec![target],
+            Terminator::If(then_block, else_block) => vec![then_block, else_block],
+            Terminator::Return => vec![],
+        }
+    }
+
+    /// The number of edges between blocks.
+    pub fn num_edges(&self) -> usize {
+        self.all_basic_blocks()
+            .map(|block| self.successors(block).len())
+            .sum()
+    }
+}
+
+tables! {
+    /// Tables that store the data for the blocks of a [`Cfg`].
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Tables {
+        basic_blocks: alloc BasicBlock => BasicBlockData,
+    }
+}
+
+id!(pub struct BasicBlock);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BasicBlockData {
+    /// The expressions evaluated in this block, in evaluation order
+    /// (each one after its operands). Control-flow expressions like `if`
+    /// and `break` appear as the `terminator_expr` instead.
+    pub exprs: Vec<validated::Expr>,
+
+    /// The expression that ends the block, like the `if` that branches
+    /// or the `return`. For the final block of the function, this is the
+    /// root expression.
+    pub terminator_expr: validated::Expr,
+
+    pub terminator: Terminator,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Terminator {
+    /// Continue executing the given block.
+    Goto(BasicBlock),
+
+    /// Branch on the value of the condition, which is the last expression
+    /// in the block: `If(then_block, else_block)`.
+    If(BasicBlock, BasicBlock),
+
+    /// Exit the function.
+    Return,
+}
+
+struct Builder<'me> {
+    validated_tables: &'me validated::Tables,
+    tables: Tables,
+
+    /// The block that we are currently appending to.
+    ///
+    /// If `None`, we are in a section of dead code.
+    current_block: Option<BasicBlock>,
+
+    /// The loops that enclose the current expression, innermost last.
+    loops: Vec<LoopContext>,
+}
+
+struct LoopContext {
+    loop_expr: validated::Expr,
+
+    /// The start of the loop body, where `continue` goes.
+    head_block: BasicBlock,
+
+    /// Where `break` goes; created when we find the first `break`,
+    /// so that a loop that is never broken out of has no exit.
+    break_block: Option<BasicBlock>,
+}
+
+impl Builder<'_> {
+    fn new_block(&mut self, origin: validated::Expr) -> BasicBlock {
+        // The terminator is a placeholder until the block is terminated.
+        self.tables.add(BasicBlockData {
+            exprs: vec![],
+            terminator_expr: origin,
+            terminator: Terminator::Return,
+        })
+    }
+
+    fn push_expr(&mut self, expr: validated::Expr) {
+        if let Some(block) = self.current_block {
+            self.tables[block].exprs.push(expr);
+        }
+    }
+
+    /// Ends the current block (if any) with `terminator`, continuing in `next_block`.
+    fn terminate(
+        &mut self,
+        terminator_expr: validated::Expr,
+        terminator: Terminator,
+        next_block: Option<BasicBlock>,
+    ) {
+        if let Some(block) = self.current_block {
+            let data = &mut self.tables[block];
+            data.terminator_expr = terminator_expr;
+            data.terminator = terminator;
+            self.current_block = next_block;
+        }
+    }
+
+    fn goto(&mut self, origin: validated::Expr, target: BasicBlock) {
+        self.terminate(origin, Terminator::Goto(target), Some(target));
+    }
+
+    fn loop_context(&mut self, loop_expr: validated::Expr) -> &mut LoopContext {
+        self.loops
+            .iter_mut()
+            .rev()
+            .find(|context| context.loop_expr == loop_expr)
+            .expect("`break` or `continue` outside of its loop")
+    }
+
+    fn build_expr(&mut self, expr: validated::Expr) {
+        // Nothing after a `break`, `continue`, or `return` can execute.
+        if self.current_block.is_none() {
+            return;
+        }
+
+        match self.validated_tables[expr].clone() {
+            validated::ExprData::BooleanLiteral(_)
+            | validated::ExprData::SignedIntegerLiteral(_)
+            | validated::ExprData::UnsignedIntegerLiteral(_)
+            | validated::ExprData::IntegerLiteral(_)
+            | validated::ExprData::FloatLiteral(_)
+            | validated::ExprData::CharLiteral(_)
+            | validated::ExprData::StringLiteral(_)
+            | validated::ExprData::Reserve(_)
+            | validated::ExprData::Lease(_)
+            | validated::ExprData::Shlease(_)
+            | validated::ExprData::Give(_)
+            | validated::ExprData::AssignFromPlace(..)
+            | validated::ExprData::Error => self.push_expr(expr),
+
+            // The body of a closure runs when the closure is called, so it is
+            // not part of this graph.
+            validated::ExprData::Closure(..) => self.push_expr(expr),
+
+            validated::ExprData::Concatenate(exprs)
+            | validated::ExprData::Tuple(exprs)
+            | validated::ExprData::List(exprs) => {
+                for element_expr in exprs {
+                    self.build_expr(element_expr);
+                }
+                self.push_expr(expr);
+            }
+
+            validated::ExprData::Seq(exprs) => {
+                for statement_expr in exprs {
+                    self.build_expr(statement_expr);
+                }
+            }
+
+            validated::ExprData::Await(operand_expr)
+            | validated::ExprData::Share(operand_expr)
+            | validated::ExprData::Atomic(operand_expr)
+            | validated::ExprData::Unary(_, operand_expr)
+            | validated::ExprData::AssignTemporary(_, operand_expr)
+            | validated::ExprData::Declare(_, operand_expr) => {
+                self.build_expr(operand_expr);
+                self.push_expr(expr);
+            }
+
+            validated::ExprData::Call(callee_expr, named_exprs) => {
+                self.build_expr(callee_expr);
+                for named_expr in named_exprs {
+                    let argument_expr = self.validated_tables[named_expr].expr;
+                    self.build_expr(argument_expr);
+                }
+                self.push_expr(expr);
+            }
+
+            validated::ExprData::Range(start_expr, end_expr, _) => {
+                for operand_expr in start_expr.into_iter().chain(end_expr) {
+                    self.build_expr(operand_expr);
+                }
+                self.push_expr(expr);
+            }
+
+            validated::ExprData::Op(lhs_expr, _, rhs_expr) => {
+                self.build_expr(lhs_expr);
+                self.build_expr(rhs_expr);
+                self.push_expr(expr);
+            }
+
+            validated::ExprData::If(condition_expr, then_expr, else_expr) => {
+                self.build_expr(condition_expr);
+                if self.current_block.is_none() {
+                    return;
+                }
+
+                let then_block = self.new_block(then_expr);
+                let else_block = self.new_block(else_expr);
+                self.terminate(expr, Terminator::If(then_block, else_block), None);
+
+                let mut end_blocks = vec![];
+                for (block, branch_expr) in [(then_block, then_expr), (else_block, else_expr)] {
+                    self.current_block = Some(block);
+                    self.build_expr(branch_expr);
+                    end_blocks.extend(self.current_block);
+                }
+
+                // If only one branch completes, keep appending to it.
+                self.current_block = match end_blocks[..] {
+                    [] => None,
+                    [end_block] => Some(end_block),
+                    _ => {
+                        let join_block = self.new_block(expr);
+                        for &end_block in &end_blocks {
+                            self.current_block = Some(end_block);
+                            self.goto(expr, join_block);
+                        }
+                        Some(join_block)
+                    }
+                };
+            }
+
+            validated::ExprData::Loop(body_expr) => {
+                let head_block = self.new_block(body_expr);
+                self.goto(expr, head_block);
+
+                self.loops.push(LoopContext {
+                    loop_expr: expr,
+                    head_block,
+                    break_block: None,
+                });
+                self.build_expr(body_expr);
+                self.terminate(expr, Terminator::Goto(head_block), None);
+                let context = self.loops.pop().unwrap();
+
+                self.current_block = context.break_block;
+            }
+
+            validated::ExprData::Break {
+                from_expr,
+                with_value,
+            } => {
+                self.build_expr(with_value);
+                if self.current_block.is_none() {
+                    return;
+                }
+
+                let break_block = self.loop_context(from_expr).break_block;
+                let break_block = match break_block {
+                    Some(break_block) => break_block,
+                    None => {
+                        let break_block = self.new_block(from_expr);
+                        self.loop_context(from_expr).break_block = Some(break_block);
+                        break_block
+                    }
+                };
+                self.terminate(expr, Terminator::Goto(break_block), None);
+            }
+
+            validated::ExprData::Continue(loop_expr) => {
+                let head_block = self.loop_context(loop_expr).head_block;
+                self.terminate(expr, Terminator::Goto(head_block), None);
+            }
+
+            validated::ExprData::Return(with_value) => {
+                self.build_expr(with_value);
+                self.terminate(expr, Terminator::Return, None);
+            }
+        }
+    }
+}
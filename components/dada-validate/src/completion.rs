@@ -0,0 +1,174 @@
+//! Lists the names that are in scope at the cursor, for code completion.
+//!
+//! We walk the syntax tree in evaluation order, opening a scope wherever
+//! the validator opens one (the branches of an `if`, the body of a loop,
+//! and so on), and stop when we reach the cursor.
+
+use dada_ir::code::syntax::{
+    self,
+    visit::{self, Visitor},
+    Expr, ExprData, LocalVariableDecl, MatchArm, PatternData,
+};
+use dada_ir::filename::Filename;
+use dada_ir::span::Offset;
+use dada_ir::word::Word;
+use dada_parse::prelude::*;
+
+use crate::hover::function_at;
+
+/// Returns the names in scope at `offset`, sorted alphabetically and without
+/// duplicates. These are the local variables visible at that point (declared
+/// before it in an enclosing block) and the root definitions of the file
+/// (functions, classes, and intrinsics).
+pub fn completions(db: &dyn crate::Db, filename: Filename, offset: Offset) -> Vec<Word> {
+    let mut names: Vec<Word> = crate::validate::root_definitions(db, filename)
+        .names()
+        .collect();
+
+    if let Some(function) = function_at(db, filename, offset) {
+        let tree_data = function.syntax_tree(db).data(db);
+        let mut walker = ScopeWalker {
+            db,
+            tables: &tree_data.tables,
+            spans: function.spans(db),
+            offset,
+            scopes: vec![tree_data
+                .parameter_decls
+                .iter()
+                .map(|&decl| tree_data.tables[decl].name)
+                .collect()],
+            in_scope: None,
+        };
+        walker.visit_expr(tree_data.root_expr);
+        names.extend(walker.in_scope.take().unwrap_or_else(|| walker.flatten()));
+    }
+
+    names.sort_by_key(|name| name.as_str(db));
+    names.dedup();
+    names
+}
+
+struct ScopeWalker<'me> {
+    db: &'me dyn crate::Db,
+    tables: &'me syntax::Tables,
+    spans: &'me syntax::Spans,
+    offset: Offset,
+
+    /// The local variables declared so far, innermost scope last.
+    scopes: Vec<Vec<Word>>,
+
+    /// Once we reach the cursor, the local variables that are in scope there.
+    in_scope: Option<Vec<Word>>,
+}
+
+impl<'me> Visitor<'me> for ScopeWalker<'me> {
+    fn tables(&self) -> &'me syntax::Tables {
+        self.tables
+    }
+
+    fn visit_expr(&mut self, expr: Expr) {
+        if self.in_scope.is_some() {
+            return;
+        }
+
+        // Everything from here on comes after the cursor.
+        if self.spans[expr].start > self.offset {
+            self.in_scope = Some(self.flatten());
+            return;
+        }
+
+        let tables = self.tables;
+        match &tables[expr] {
+            ExprData::If(condition_expr, then_expr, else_expr) => {
+                self.visit_expr(*condition_expr);
+                self.visit_subscope(*then_expr, vec![]);
+                if let Some(else_expr) = else_expr {
+                    self.visit_subscope(*else_expr, vec![]);
+                }
+            }
+
+            ExprData::Atomic(body_expr) | ExprData::Loop(_, body_expr) => {
+                self.visit_subscope(*body_expr, vec![])
+            }
+
+            ExprData::While(_, condition_expr, body_expr) => {
+                self.visit_expr(*condition_expr);
+                self.visit_subscope(*body_expr, vec![]);
+            }
+
+            ExprData::For(_, decl, iterable_expr, body_expr) => {
+                self.visit_expr(*iterable_expr);
+                self.visit_subscope(*body_expr, vec![tables[*decl].name]);
+            }
+
+            ExprData::Closure(parameter_decls, body_expr) => {
+                let names = parameter_decls
+                    .iter()
+                    .map(|&decl| tables[decl].name)
+                    .collect();
+                self.visit_subscope(*body_expr, names);
+            }
+
+            ExprData::Var(decl, initializer_expr) => {
+                self.visit_expr(*initializer_expr);
+                self.declare(*decl);
+            }
+
+            ExprData::Fn(function) => {
+                if self.in_scope.is_none() {
+                    let name = function.name(self.db).word(self.db);
+                    self.scopes.last_mut().unwrap().push(name);
+                }
+            }
+
+            ExprData::VarElse(decl, initializer_expr, else_expr) => {
+                self.visit_expr(*initializer_expr);
+                self.visit_subscope(*else_expr, vec![]);
+                self.declare(*decl);
+            }
+
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+
+    fn visit_match_arm(&mut self, match_arm: MatchArm) {
+        let tables = self.tables;
+        let data = &tables[match_arm];
+        let names = match tables[data.pattern] {
+            PatternData::Binding(decl) => vec![tables[decl].name],
+            PatternData::Literal(expr) => {
+                self.visit_expr(expr);
+                vec![]
+            }
+        };
+        self.visit_subscope(data.body, names);
+    }
+}
+
+impl ScopeWalker<'_> {
+    /// Visits `expr` in a new scope that starts out with `names`.
+    fn visit_subscope(&mut self, expr: Expr, names: Vec<Word>) {
+        self.scopes.push(names);
+        self.visit_expr(expr);
+
+        // The cursor may be inside `expr` but after its last child,
+        // e.g. at the end of a block.
+        let span = self.spans[expr];
+        if self.in_scope.is_none() && span.contains(self.offset) {
+            self.in_scope = Some(self.flatten());
+        }
+        self.scopes.pop();
+    }
+
+    /// Adds `decl` to the innermost scope, unless we have already reached the cursor.
+    fn declare(&mut self, decl: LocalVariableDecl) {
+        if self.in_scope.is_none() {
+            let name = self.tables[decl].name;
+            self.scopes.last_mut().unwrap().push(name);
+        }
+    }
+
+    fn flatten(&self) -> Vec<Word> {
+        self.scopes.iter().flatten().copied().collect()
+    }
+}
@@ -0,0 +1,19 @@
+//! Finds where the local variable under the cursor was declared, for
+//! editors that support "go to definition".
+
+use dada_ir::filename::Filename;
+use dada_ir::span::{FileSpan, Offset};
+
+use crate::hover::{hover, Binding};
+
+/// If `offset` is on the name of a local variable or parameter, returns the
+/// span of the name in the declaration that introduced it. Shadowing is
+/// resolved the same way as during validation, so this is the nearest
+/// enclosing declaration. Returns `None` for anything else, including names
+/// of functions, classes, and intrinsics.
+pub fn definition(db: &dyn crate::Db, filename: Filename, offset: Offset) -> Option<FileSpan> {
+    match hover(db, filename, offset)?.binding? {
+        Binding::LocalVariable(_, name_span) | Binding::Parameter(_, name_span) => Some(name_span),
+        Binding::Function(_) | Binding::Class(_) | Binding::Intrinsic(_) => None,
+    }
+}
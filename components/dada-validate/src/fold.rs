@@ -0,0 +1,323 @@
+//! Folds constant expressions in a validated tree.
+//!
+//! The pass is conservative: it only folds operators whose operands are
+//! already literals (which have no side effects), and `if`s whose condition
+//! is a literal. Operations that would fail at runtime (overflow, division
+//! by zero, mismatched operands) are left alone so that the error is still
+//! reported when the program runs.
+//!
+//! An integer operation overflows if its result does not fit in the width of
+//! its literals at runtime, or in the width of its declared type (like
+//! `Int32`). [`folded_tree`] warns about each such operation.
+
+use dada_ir::code::validated::{self, op::Op, ExprData};
+use dada_ir::function::Function;
+use dada_ir::ty::IntegerWidth;
+use dada_parse::prelude::*;
+
+use crate::prelude::*;
+use crate::NumericTypes;
+
+/// The width of unsuffixed and unsigned integers at runtime.
+const UNSIGNED: IntegerWidth = IntegerWidth {
+    bits: 64,
+    signed: false,
+};
+
+/// The width of signed integers at runtime.
+const SIGNED: IntegerWidth = IntegerWidth {
+    bits: 64,
+    signed: true,
+};
+
+/// The validated tree of `function` with its constant expressions folded.
+/// Warns about each operation that is left unfolded because it overflows.
+#[salsa::memoized(in crate::Jar)]
+pub(crate) fn folded_tree(db: &dyn crate::Db, function: Function) -> validated::Tree {
+    let validated_tree = function.validated_tree(db);
+    let (tree, _, overflows) = fold(db, validated_tree);
+
+    let origins = validated_tree.origins(db);
+    let spans = function.spans(db);
+    for (expr, width) in overflows {
+        let syntax_expr = origins[expr].syntax_expr;
+        let span = spans
+            .op_span(syntax_expr)
+            .unwrap_or(spans[syntax_expr])
+            .in_file(function.filename(db));
+        dada_ir::warning!(span, "integer overflow in constant expression")
+            .primary_label(format!(
+                "the result does not fit in {}",
+                width.description()
+            ))
+            .emit(db);
+    }
+    tree
+}
+
+/// Returns a copy of `tree` with its constant expressions folded.
+pub fn fold_constants(db: &dyn crate::Db, tree: validated::Tree) -> validated::Tree {
+    fold_constants_counted(db, tree).0
+}
+
+/// Like [`fold_constants`], but also returns the number of expressions that
+/// were folded.
+pub fn fold_constants_counted(
+    db: &dyn crate::Db,
+    tree: validated::Tree,
+) -> (validated::Tree, usize) {
+    let (tree, folded, _) = fold(db, tree);
+    (tree, folded)
+}
+
+/// Folds `tree`, returning the folded tree, the number of folded expressions,
+/// and the operations left unfolded because they overflow.
+fn fold(
+    db: &dyn crate::Db,
+    tree: validated::Tree,
+) -> (validated::Tree, usize, Vec<(validated::Expr, IntegerWidth)>) {
+    let mut data = tree.data(db).clone();
+    let mut folder = Folder {
+        tables: &mut data.tables,
+        numeric_types: tree.origin(db).numeric_types(db),
+        folded: 0,
+        overflows: vec![],
+    };
+    folder.fold_expr(data.root_expr);
+    let Folder {
+        folded, overflows, ..
+    } = folder;
+
+    let origins = tree.origins(db).clone();
+    (
+        validated::Tree::new(db, tree.origin(db), data, origins),
+        folded,
+        overflows,
+    )
+}
+
+struct Folder<'me> {
+    tables: &'me mut validated::Tables,
+    numeric_types: &'me NumericTypes,
+    folded: usize,
+
+    /// Operations whose result does not fit in the given width.
+    overflows: Vec<(validated::Expr, IntegerWidth)>,
+}
+
+/// The result of evaluating an operator at compile time.
+pub(crate) enum Folded {
+    /// The operation evaluates to this literal.
+    Literal(ExprData),
+
+    /// The result of the operation does not fit in this width.
+    Overflow(IntegerWidth),
+}
+
+impl Folder<'_> {
+    fn fold_expr(&mut self, expr: validated::Expr) {
+        match self.tables[expr].clone() {
+            ExprData::BooleanLiteral(_)
+            | ExprData::SignedIntegerLiteral(_)
+            | ExprData::UnsignedIntegerLiteral(_)
+            | ExprData::IntegerLiteral(_)
+            | ExprData::FloatLiteral(_)
+            | ExprData::CharLiteral(_)
+            | ExprData::StringLiteral(_)
+            | ExprData::Reserve(_)
+            | ExprData::Lease(_)
+            | ExprData::Shlease(_)
+            | ExprData::Give(_)
+            | ExprData::Continue(_)
+            | ExprData::AssignFromPlace(..)
+            | ExprData::Error => {}
+
+            ExprData::Concatenate(exprs)
+            | ExprData::Tuple(exprs)
+            | ExprData::List(exprs)
+            | ExprData::Seq(exprs) => exprs.into_iter().for_each(|e| self.fold_expr(e)),
+
+            ExprData::Call(callee, arguments) => {
+                self.fold_expr(callee);
+                for argument in arguments {
+                    let argument_expr = self.tables[argument].expr;
+                    self.fold_expr(argument_expr);
+                }
+            }
+
+            ExprData::Range(start, end, _) => {
+                start.into_iter().chain(end).for_each(|e| self.fold_expr(e))
+            }
+
+            ExprData::Await(e)
+            | ExprData::Share(e)
+            | ExprData::Closure(_, _, e)
+            | ExprData::Atomic(e)
+            | ExprData::Loop(e)
+            | ExprData::Break { with_value: e, .. }
+            | ExprData::Return(e)
+            | ExprData::AssignTemporary(_, e)
+            | ExprData::Declare(_, e) => self.fold_expr(e),
+
+            ExprData::If(condition, then_expr, else_expr) => {
+                self.fold_expr(condition);
+                self.fold_expr(then_expr);
+                self.fold_expr(else_expr);
+
+                // The branch is wrapped in a sequence, rather than copied into
+                // `expr`, because `break` and `continue` refer to loops by key.
+                if let ExprData::BooleanLiteral(value) = self.tables[condition] {
+                    let branch = if value { then_expr } else { else_expr };
+                    self.replace(expr, ExprData::Seq(vec![branch]));
+                }
+            }
+
+            ExprData::Op(lhs, op, rhs) => {
+                self.fold_expr(lhs);
+                self.fold_expr(rhs);
+                let width = self.numeric_types.integer_width(expr);
+                match fold_op(&self.tables[lhs], op, &self.tables[rhs], width) {
+                    Some(Folded::Literal(data)) => self.replace(expr, data),
+                    Some(Folded::Overflow(width)) => self.overflows.push((expr, width)),
+                    None => {}
+                }
+            }
+
+            ExprData::Unary(op, rhs) => {
+                self.fold_expr(rhs);
+                if let Some(data) = fold_unary(op, &self.tables[rhs]) {
+                    self.replace(expr, data);
+                }
+            }
+        }
+    }
+
+    fn replace(&mut self, expr: validated::Expr, data: ExprData) {
+        self.tables[expr] = data;
+        self.folded += 1;
+    }
+}
+
+/// Evaluates `lhs op rhs`, following the rules of the interpreter. The
+/// result must also fit in `declared_width`, if the operation has one.
+pub(crate) fn fold_op(
+    lhs: &ExprData,
+    op: Op,
+    rhs: &ExprData,
+    declared_width: Option<IntegerWidth>,
+) -> Option<Folded> {
+    let unsigned_literal = |value: i128| {
+        u64::try_from(value)
+            .ok()
+            .map(ExprData::UnsignedIntegerLiteral)
+    };
+    let integer_literal = |value: i128| u64::try_from(value).ok().map(ExprData::IntegerLiteral);
+    let signed_literal = |value: i128| {
+        i64::try_from(value)
+            .ok()
+            .map(ExprData::SignedIntegerLiteral)
+    };
+    match (lhs, rhs) {
+        (&ExprData::BooleanLiteral(lhs), &ExprData::BooleanLiteral(rhs)) => match op {
+            Op::EqualEqual => Some(Folded::Literal(ExprData::BooleanLiteral(lhs == rhs))),
+            Op::NotEqual => Some(Folded::Literal(ExprData::BooleanLiteral(lhs != rhs))),
+            Op::GreaterEqual => Some(Folded::Literal(ExprData::BooleanLiteral(lhs >= rhs))),
+            Op::LessEqual => Some(Folded::Literal(ExprData::BooleanLiteral(lhs <= rhs))),
+            _ => None,
+        },
+
+        (&ExprData::UnsignedIntegerLiteral(lhs), &ExprData::UnsignedIntegerLiteral(rhs))
+        | (&ExprData::UnsignedIntegerLiteral(lhs), &ExprData::IntegerLiteral(rhs))
+        | (&ExprData::IntegerLiteral(lhs), &ExprData::UnsignedIntegerLiteral(rhs)) => {
+            let widths = [declared_width, Some(UNSIGNED)];
+            fold_integer(lhs.into(), op, rhs.into(), widths, unsigned_literal)
+        }
+        (&ExprData::IntegerLiteral(lhs), &ExprData::IntegerLiteral(rhs)) => {
+            let widths = [declared_width, Some(UNSIGNED)];
+            fold_integer(lhs.into(), op, rhs.into(), widths, integer_literal)
+        }
+
+        (&ExprData::SignedIntegerLiteral(lhs), &ExprData::SignedIntegerLiteral(rhs)) => {
+            let widths = [declared_width, Some(SIGNED)];
+            fold_integer(lhs.into(), op, rhs.into(), widths, signed_literal)
+        }
+
+        // Unsuffixed integers are converted to signed ones, which overflows
+        // if they are too large.
+        (&ExprData::IntegerLiteral(lhs), &ExprData::SignedIntegerLiteral(rhs)) => {
+            let Ok(lhs) = i64::try_from(lhs) else {
+                return Some(Folded::Overflow(SIGNED));
+            };
+            let widths = [declared_width, Some(SIGNED)];
+            fold_integer(lhs.into(), op, rhs.into(), widths, signed_literal)
+        }
+        (&ExprData::SignedIntegerLiteral(lhs), &ExprData::IntegerLiteral(rhs)) => {
+            let Ok(rhs) = i64::try_from(rhs) else {
+                return Some(Folded::Overflow(SIGNED));
+            };
+            let widths = [declared_width, Some(SIGNED)];
+            fold_integer(lhs.into(), op, rhs.into(), widths, signed_literal)
+        }
+
+        _ => None,
+    }
+}
+
+/// Evaluates `lhs op rhs` on integers, whose result must fit in each of
+/// `widths`. Returns `None` if the operation fails for another reason, like
+/// division by zero.
+fn fold_integer(
+    lhs: i128,
+    op: Op,
+    rhs: i128,
+    widths: [Option<IntegerWidth>; 2],
+    literal: impl Fn(i128) -> Option<ExprData>,
+) -> Option<Folded> {
+    // `None` if the result does not even fit in an `i128`.
+    let value = match op {
+        Op::EqualEqual => return Some(Folded::Literal(ExprData::BooleanLiteral(lhs == rhs))),
+        Op::NotEqual => return Some(Folded::Literal(ExprData::BooleanLiteral(lhs != rhs))),
+        Op::GreaterEqual => return Some(Folded::Literal(ExprData::BooleanLiteral(lhs >= rhs))),
+        Op::LessEqual => return Some(Folded::Literal(ExprData::BooleanLiteral(lhs <= rhs))),
+        Op::LessThan => return Some(Folded::Literal(ExprData::BooleanLiteral(lhs < rhs))),
+        Op::GreaterThan => return Some(Folded::Literal(ExprData::BooleanLiteral(lhs > rhs))),
+        Op::Plus => lhs.checked_add(rhs),
+        Op::Minus => lhs.checked_sub(rhs),
+        Op::Times => lhs.checked_mul(rhs),
+        Op::DividedBy if rhs == 0 => return None,
+        Op::DividedBy => Some(lhs / rhs),
+        Op::BitAnd => Some(lhs & rhs),
+        Op::BitOr => Some(lhs | rhs),
+        Op::BitXor => Some(lhs ^ rhs),
+
+        // Like the interpreter, shifting by 64 bits or more overflows.
+        Op::ShiftLeft | Op::ShiftRight if !(0..64).contains(&rhs) => None,
+        Op::ShiftLeft => lhs.checked_mul(1 << rhs),
+        Op::ShiftRight => Some(lhs >> rhs),
+        Op::Not => return None,
+    };
+
+    let overflowed = widths.into_iter().flatten().find(|width| match value {
+        Some(value) => !width.contains(value),
+        None => true,
+    });
+    match (overflowed, value) {
+        (Some(width), _) => Some(Folded::Overflow(width)),
+        (None, Some(value)) => literal(value).map(Folded::Literal),
+        (None, None) => None,
+    }
+}
+
+/// Evaluates `op rhs`, following the rules of the interpreter.
+pub(crate) fn fold_unary(op: Op, rhs: &ExprData) -> Option<ExprData> {
+    match (op, rhs) {
+        (Op::Not, &ExprData::BooleanLiteral(rhs)) => Some(ExprData::BooleanLiteral(!rhs)),
+        (Op::Minus, &ExprData::SignedIntegerLiteral(rhs)) => {
+            Some(ExprData::SignedIntegerLiteral(rhs.checked_neg()?))
+        }
+        (Op::Minus, &ExprData::IntegerLiteral(rhs)) => {
+            Some(ExprData::SignedIntegerLiteral(-i64::try_from(rhs).ok()?))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,160 @@
+//! Describes the expression under the cursor, for editors that show
+//! information when hovering over the code.
+
+use dada_id::prelude::*;
+use dada_ir::class::Class;
+use dada_ir::code::{syntax, validated};
+use dada_ir::filename::Filename;
+use dada_ir::function::Function;
+use dada_ir::intrinsic::Intrinsic;
+use dada_ir::item::Item;
+use dada_ir::span::{FileSpan, Offset};
+use dada_ir::word::Word;
+use dada_parse::prelude::*;
+
+use crate::prelude::*;
+
+/// The innermost expression that contains some offset.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Hover {
+    pub function: Function,
+    pub expr: syntax::Expr,
+    pub span: FileSpan,
+
+    /// The name of the [`syntax::ExprData`] variant of `expr`.
+    pub kind: &'static str,
+
+    /// If `expr` is an identifier, what it refers to.
+    pub binding: Option<Binding>,
+}
+
+/// What an identifier refers to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Binding {
+    /// A local variable, along with the span of its name where it is declared.
+    LocalVariable(Word, FileSpan),
+
+    /// A parameter, along with the span of its name where it is declared.
+    Parameter(Word, FileSpan),
+
+    Function(Function),
+    Class(Class),
+    Intrinsic(Intrinsic),
+}
+
+impl Hover {
+    /// A one-line description, like "Id: parameter `a`".
+    pub fn describe(&self, db: &dyn crate::Db) -> String {
+        match self.binding {
+            Some(binding) => format!("{}: {}", self.kind, binding.describe(db)),
+            None => self.kind.to_string(),
+        }
+    }
+}
+
+impl Binding {
+    pub fn describe(&self, db: &dyn crate::Db) -> String {
+        match *self {
+            Binding::LocalVariable(name, _) => format!("local variable `{}`", name.as_str(db)),
+            Binding::Parameter(name, _) => format!("parameter `{}`", name.as_str(db)),
+            Binding::Function(function) => {
+                format!(
+                    "function `{}`",
+                    Item::Function(function).name(db).as_str(db)
+                )
+            }
+            Binding::Class(class) => format!("class `{}`", Item::Class(class).name(db).as_str(db)),
+            Binding::Intrinsic(intrinsic) => format!("intrinsic `{}`", intrinsic.as_str(db)),
+        }
+    }
+}
+
+/// Finds the innermost expression of `filename` whose span contains `offset`.
+/// Returns `None` if `offset` is not within the body of a function.
+pub fn hover(db: &dyn crate::Db, filename: Filename, offset: Offset) -> Option<Hover> {
+    let function = function_at(db, filename, offset)?;
+
+    // Children are created before their parents, so when two expressions
+    // have the same span (e.g., a block with a single statement), the first
+    // one is the innermost.
+    let syntax_tree = function.syntax_tree(db);
+    let tables = &syntax_tree.data(db).tables;
+    let spans = function.spans(db);
+    let expr = syntax::Expr::max_key(tables)
+        .iter()
+        .filter(|&expr| spans[expr].contains(offset))
+        .min_by_key(|&expr| spans[expr].len())?;
+
+    Some(Hover {
+        function,
+        expr,
+        span: spans[expr].in_file(filename),
+        kind: tables[expr].kind_str(),
+        binding: match tables[expr] {
+            syntax::ExprData::Id(_) => binding(db, function, expr),
+            _ => None,
+        },
+    })
+}
+
+/// The function whose span contains `offset`, if any.
+pub(crate) fn function_at(
+    db: &dyn crate::Db,
+    filename: Filename,
+    offset: Offset,
+) -> Option<Function> {
+    filename.items(db).iter().find_map(|item| match item {
+        Item::Function(function) if item.span(db).contains(offset) => Some(*function),
+        _ => None,
+    })
+}
+
+/// Finds what the identifier `id_expr` resolved to during validation.
+fn binding(db: &dyn crate::Db, function: Function, id_expr: syntax::Expr) -> Option<Binding> {
+    let validated_tree = function.validated_tree(db);
+    let tables = &validated_tree.data(db).tables;
+    let origins = validated_tree.origins(db);
+
+    let local_variable = validated::Place::max_key(tables)
+        .iter()
+        .filter(|&place| origins[place].syntax_expr == id_expr)
+        .find_map(|place| match tables[place] {
+            validated::PlaceData::LocalVariable(local_variable) => Some(Ok(local_variable)),
+            validated::PlaceData::Function(function) => Some(Err(Binding::Function(function))),
+            validated::PlaceData::Class(class) => Some(Err(Binding::Class(class))),
+            validated::PlaceData::Intrinsic(intrinsic) => Some(Err(Binding::Intrinsic(intrinsic))),
+            validated::PlaceData::Dot(..) | validated::PlaceData::Index(..) => None,
+        })
+        .or_else(|| {
+            // Assignments like `x := 1` use a target place instead.
+            validated::TargetPlace::max_key(tables)
+                .iter()
+                .filter(|&target_place| origins[target_place].syntax_expr == id_expr)
+                .find_map(|target_place| match tables[target_place] {
+                    validated::TargetPlaceData::LocalVariable(local_variable) => {
+                        Some(Ok(local_variable))
+                    }
+                    validated::TargetPlaceData::Dot(..) => None,
+                })
+        })?;
+
+    let local_variable = match local_variable {
+        Ok(local_variable) => local_variable,
+        Err(binding) => return Some(binding),
+    };
+    let name = tables[local_variable].name?;
+    let name_span = |decl: syntax::LocalVariableDecl| {
+        function.spans(db)[decl]
+            .name_span
+            .in_file(function.filename(db))
+    };
+    match origins[local_variable] {
+        validated::LocalVariableOrigin::LocalVariable(decl) => {
+            Some(Binding::LocalVariable(name, name_span(decl)))
+        }
+        validated::LocalVariableOrigin::Parameter(decl) => {
+            Some(Binding::Parameter(name, name_span(decl)))
+        }
+        validated::LocalVariableOrigin::Temporary(_) => None,
+    }
+}
@@ -8,10 +8,44 @@
 mod validate;
 
 #[salsa::jar(Db)]
-pub struct Jar(validate::root_definitions, validate::validate_function);
+pub struct Jar(
+    validate::root_definitions,
+    validate::validate_function,
+    validate::effect_inference::inferred_effect,
+    validate::numeric_types::numeric_types,
+    validate::field_types::check_field_types,
+    validate::imports::resolve_imports,
+    validate::call_graph::callees,
+    validate::call_graph::referenced_functions,
+    validate::call_graph::call_graph,
+    validate::const_calls::performs_io,
+    validate::const_calls::check_const_calls,
+    validate::dead_functions::warn_dead_functions,
+    validate::dead_functions::unreachable_functions,
+    validate::dead_functions::report_dead_functions,
+    validate::main_function::main_function,
+    validate::main_function::check_main_function,
+    validate::recursion::debug_recursion,
+    validate::recursion::is_recursive,
+    validate::recursion::report_recursion,
+    validate::shadowing::warn_shadowing,
+    validate::test_functions::test_functions,
+    fold::folded_tree,
+);
 
 pub trait Db: salsa::DbWithJar<Jar> + dada_ir::Db + dada_parse::Db {}
 
 impl<T> Db for T where T: salsa::DbWithJar<Jar> + dada_ir::Db + dada_parse::Db {}
 
+pub mod cfg;
+pub mod completion;
+pub mod definition;
+pub mod fold;
+pub mod hover;
 pub mod prelude;
+pub mod rename;
+
+pub use validate::dead_functions::warn_dead_functions;
+pub use validate::numeric_types::NumericTypes;
+pub use validate::recursion::debug_recursion;
+pub use validate::shadowing::warn_shadowing;
@@ -1,9 +1,35 @@
-use dada_ir::{code::validated, filename::Filename, function::Function, item::Item};
+use dada_collections::IndexMap;
+use dada_ir::{
+    code::validated, diagnostic::Diagnostic, effect::Effect, filename::Filename,
+    function::Function, item::Item, parameter::Parameter,
+};
+
+use crate::NumericTypes;
 
 #[extension_trait::extension_trait]
 pub impl DadaValidateFilenameExt for Filename {
     fn validate_root(self, db: &dyn crate::Db) {
         crate::validate::root_definitions(db, self);
+        crate::validate::dead_functions::report_dead_functions(db, self);
+        crate::validate::field_types::check_field_types(db, self);
+        crate::validate::main_function::check_main_function(db, self);
+        crate::validate::recursion::report_recursion(db, self);
+        crate::validate::test_functions::test_functions(db, self);
+    }
+
+    /// The functions called by each function defined in the file.
+    fn call_graph(self, db: &dyn crate::Db) -> &IndexMap<Function, Vec<Function>> {
+        crate::validate::call_graph::call_graph(db, self)
+    }
+
+    /// The functions marked `@test` that can be run as tests.
+    fn test_functions(self, db: &dyn crate::Db) -> &Vec<Function> {
+        crate::validate::test_functions::test_functions(db, self)
+    }
+
+    /// The function named `main`, or an error if it is missing or cannot be the entry point.
+    fn main_function(self, db: &dyn crate::Db) -> &Result<Function, Diagnostic> {
+        crate::validate::main_function::main_function(db, self)
     }
 }
 
@@ -12,6 +38,41 @@ pub impl DadaValidateFunctionExt for Function {
     fn validated_tree(self, db: &dyn crate::Db) -> validated::Tree {
         crate::validate::validate_function(db, self)
     }
+
+    /// The declared effect of the function or, if it has none, the effect inferred from its body.
+    fn inferred_effect(self, db: &dyn crate::Db) -> Effect {
+        crate::validate::effect_inference::inferred_effect(db, self)
+    }
+
+    /// Whether each literal and local variable in the function is an integer or a float.
+    fn numeric_types(self, db: &dyn crate::Db) -> &NumericTypes {
+        crate::validate::numeric_types::numeric_types(db, self)
+    }
+
+    /// The validated tree with its constant expressions folded.
+    fn folded_tree(self, db: &dyn crate::Db) -> validated::Tree {
+        crate::fold::folded_tree(db, self)
+    }
+
+    /// The functions that this function calls by name.
+    fn callees(self, db: &dyn crate::Db) -> &Vec<Function> {
+        crate::validate::call_graph::callees(db, self)
+    }
+
+    /// True if the function calls itself, directly or through other functions.
+    fn is_recursive(self, db: &dyn crate::Db) -> bool {
+        crate::validate::recursion::is_recursive(db, self)
+    }
+
+    /// True if calling the function may perform I/O, directly or through other functions.
+    fn performs_io(self, db: &dyn crate::Db) -> bool {
+        crate::validate::const_calls::performs_io(db, self)
+    }
+
+    /// If the function is declared `const`, reports its calls that perform I/O.
+    fn check_const_calls(self, db: &dyn crate::Db) {
+        crate::validate::const_calls::check_const_calls(db, self)
+    }
 }
 
 #[extension_trait::extension_trait]
@@ -23,3 +84,11 @@ pub impl DadaValidateItemExt for Item {
         }
     }
 }
+
+#[extension_trait::extension_trait]
+pub impl DadaValidateParameterExt for Parameter {
+    /// The value of the parameter's default, if it has a valid one.
+    fn default_value(self, db: &dyn crate::Db) -> Option<validated::ExprData> {
+        crate::validate::parameter_defaults::default_value(db, self)
+    }
+}
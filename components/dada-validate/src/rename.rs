@@ -0,0 +1,77 @@
+//! Finds every mention of a local variable, so that an editor can rename it.
+
+use dada_id::prelude::*;
+use dada_ir::code::{syntax, validated};
+use dada_ir::filename::Filename;
+use dada_ir::span::{FileSpan, Offset};
+use dada_parse::prelude::*;
+
+use crate::definition::definition;
+use crate::hover::function_at;
+use crate::prelude::*;
+
+/// If `offset` is on the declaration of a local variable or parameter, or
+/// on one of its uses, returns the spans of its name in the declaration and
+/// in every use, sorted by where they start. Other variables with the same
+/// name (e.g., ones that shadow it in an inner block) are not included.
+/// Returns an empty list if there is no variable at `offset`.
+pub fn rename_spans(db: &dyn crate::Db, filename: Filename, offset: Offset) -> Vec<FileSpan> {
+    let Some(function) = function_at(db, filename, offset) else {
+        return vec![];
+    };
+    let syntax_tables = &function.syntax_tree(db).data(db).tables;
+    let spans = function.spans(db);
+    let name_span = |decl: syntax::LocalVariableDecl| spans[decl].name_span.in_file(filename);
+
+    // The cursor is either on the declaration itself or on a use of it.
+    let definition_span = definition(db, filename, offset);
+    let Some(decl) = syntax::LocalVariableDecl::max_key(syntax_tables)
+        .iter()
+        .find(|&decl| name_span(decl).contains(offset) || Some(name_span(decl)) == definition_span)
+    else {
+        return vec![];
+    };
+
+    let validated_tree = function.validated_tree(db);
+    let tables = &validated_tree.data(db).tables;
+    let origins = validated_tree.origins(db);
+    let Some(local_variable) =
+        validated::LocalVariable::max_key(tables)
+            .iter()
+            .find(|&local_variable| match origins[local_variable] {
+                validated::LocalVariableOrigin::LocalVariable(d)
+                | validated::LocalVariableOrigin::Parameter(d) => d == decl,
+                validated::LocalVariableOrigin::Temporary(_) => false,
+            })
+    else {
+        return vec![];
+    };
+
+    // Only places that come from an identifier written by the user count;
+    // the declaration itself assigns through a synthesized target place.
+    let is_use = |origin: validated::ExprOrigin| {
+        !origin.synthesized && matches!(syntax_tables[origin.syntax_expr], syntax::ExprData::Id(_))
+    };
+    let place_uses = validated::Place::max_key(tables)
+        .iter()
+        .filter(|&place| tables[place] == validated::PlaceData::LocalVariable(local_variable))
+        .map(|place| origins[place]);
+    let target_place_uses = validated::TargetPlace::max_key(tables)
+        .iter()
+        .filter(|&target_place| {
+            tables[target_place] == validated::TargetPlaceData::LocalVariable(local_variable)
+        })
+        .map(|target_place| origins[target_place]);
+
+    let mut result: Vec<FileSpan> = std::iter::once(name_span(decl))
+        .chain(
+            place_uses
+                .chain(target_place_uses)
+                .filter(|&origin| is_use(origin))
+                .map(|origin| spans[origin.syntax_expr].in_file(filename)),
+        )
+        .collect();
+    result.sort_by_key(|span| span.start);
+    result.dedup();
+    result
+}
@@ -5,7 +5,25 @@ use dada_parse::prelude::*;
 
 use self::name_lookup::Scope;
 
+mod attributes;
+pub(crate) mod call_graph;
+pub(crate) mod const_calls;
+pub(crate) mod dead_functions;
+mod definite_assignment;
+pub(crate) mod effect_inference;
+pub(crate) mod field_types;
+pub(crate) mod imports;
+mod literal;
+pub(crate) mod main_function;
 mod name_lookup;
+pub(crate) mod numeric_types;
+pub(crate) mod parameter_defaults;
+pub(crate) mod recursion;
+pub(crate) mod shadowing;
+pub(crate) mod test_functions;
+mod unreachable;
+mod unused_variables;
+mod use_after_give;
 mod validator;
 
 /// Computes a validated tree for the given code (may produce errors).
@@ -13,6 +31,8 @@ mod validator;
 #[tracing::instrument(level = "debug", skip(db))]
 pub(crate) fn validate_function(db: &dyn crate::Db, function: Function) -> validated::Tree {
     let syntax_tree = function.syntax_tree(db);
+    attributes::check(db, function);
+    parameter_defaults::check(db, function);
 
     let mut tables = validated::Tables::default();
     let mut origins = validated::Origins::default();
@@ -22,6 +42,7 @@ pub(crate) fn validate_function(db: &dyn crate::Db, function: Function) -> valid
     let mut validator =
         validator::Validator::root(db, function, syntax_tree, &mut tables, &mut origins, scope);
 
+    validator.validate_generics(function.generics(db));
     for parameter in &syntax_tree.data(db).parameter_decls {
         validator.validate_parameter(*parameter);
     }
@@ -30,6 +51,9 @@ pub(crate) fn validate_function(db: &dyn crate::Db, function: Function) -> valid
     let root_expr = validator.give_validated_root_expr(syntax_tree.data(db).root_expr);
     std::mem::drop(validator);
     let data = validated::TreeData::new(tables, num_parameters, root_expr);
+    definite_assignment::check(db, function, &data, &origins);
+    use_after_give::check(db, function, &data, &origins);
+    unused_variables::check(db, function, &data, &origins);
     validated::Tree::new(db, function, data, origins)
 }
 
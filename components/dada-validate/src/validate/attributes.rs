@@ -0,0 +1,19 @@
+//! Checks the attributes of a function, like `@test`.
+
+use dada_ir::{attribute::Attribute, function::Function};
+
+/// Reports attributes whose names are not in [`Attribute::KNOWN`].
+pub(crate) fn check(db: &dyn crate::Db, function: Function) {
+    for attribute in function.attributes(db) {
+        let name = attribute.name.as_str(db);
+        if !Attribute::KNOWN.contains(&name) {
+            let known: Vec<String> = Attribute::KNOWN
+                .iter()
+                .map(|known| format!("`@{}`", known))
+                .collect();
+            dada_ir::error!(attribute.span, "unknown attribute `@{}`", name)
+                .primary_label(format!("the known attributes are {}", known.join(", ")))
+                .emit(db);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! The call graph: which functions each function calls by name.
+//!
+//! Callee names are resolved by the validator (against the root definitions
+//! of the file), so we only look for calls whose callee is a function place.
+//! The edges of each function are computed separately, so editing one
+//! function body only recomputes its own edges.
+
+use dada_collections::IndexMap;
+use dada_id::prelude::*;
+use dada_ir::code::validated;
+use dada_ir::filename::Filename;
+use dada_ir::function::Function;
+use dada_ir::item::Item;
+use dada_parse::prelude::*;
+
+/// Returns the functions that `function` calls by name, without duplicates,
+/// in the order in which they were validated.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub(crate) fn callees(db: &dyn crate::Db, function: Function) -> Vec<Function> {
+    let tree_data = super::validate_function(db, function).data(db);
+    let tables = &tree_data.tables;
+    let mut callees = vec![];
+    for expr in validated::Expr::max_key(tables).iter() {
+        let validated::ExprData::Call(func_expr, _) = &tables[expr] else {
+            continue;
+        };
+
+        // The callee is validated in "reserve" mode.
+        let validated::ExprData::Reserve(place) = tables[*func_expr] else {
+            continue;
+        };
+        if let validated::PlaceData::Function(callee) = tables[place] {
+            if !callees.contains(&callee) {
+                callees.push(callee);
+            }
+        }
+    }
+    callees
+}
+
+/// Returns the functions that `function` refers to by name, without
+/// duplicates, in the order in which they were validated. Unlike
+/// [`callees`], this includes functions that are only used as values.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub(crate) fn referenced_functions(db: &dyn crate::Db, function: Function) -> Vec<Function> {
+    let tree_data = super::validate_function(db, function).data(db);
+    let tables = &tree_data.tables;
+    let mut referenced = vec![];
+    for place in validated::Place::max_key(tables).iter() {
+        if let validated::PlaceData::Function(f) = tables[place] {
+            if !referenced.contains(&f) {
+                referenced.push(f);
+            }
+        }
+    }
+    referenced
+}
+
+/// Returns the callees of each function defined in `filename`, with the
+/// functions in the order in which they are defined.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub(crate) fn call_graph(
+    db: &dyn crate::Db,
+    filename: Filename,
+) -> IndexMap<Function, Vec<Function>> {
+    filename
+        .items(db)
+        .iter()
+        .filter_map(|&item| match item {
+            Item::Function(function) => Some((function, callees(db, function).clone())),
+            Item::Class(_) => None,
+        })
+        .collect()
+}
@@ -0,0 +1,87 @@
+//! Reports calls from `const` functions to functions that perform I/O.
+//!
+//! A function performs I/O if it calls an intrinsic like `print`, directly
+//! or through the functions it calls. Functions declared `const` are checked
+//! on their own, so calls into them are trusted to be free of I/O.
+
+use dada_collections::Set;
+use dada_id::prelude::*;
+use dada_ir::code::validated;
+use dada_ir::function::Function;
+use dada_parse::prelude::*;
+
+/// True if calling `function` may perform I/O, following its calls through
+/// the [call graph](super::call_graph).
+#[salsa::memoized(in crate::Jar)]
+#[tracing::instrument(level = "debug", skip(db))]
+pub(crate) fn performs_io(db: &dyn crate::Db, function: Function) -> bool {
+    let mut visited: Set<Function> = Set::default();
+    let mut stack = vec![function];
+    while let Some(function) = stack.pop() {
+        if function.effect(db).is_const() || !visited.insert(function) {
+            continue;
+        }
+        let tree_data = super::validate_function(db, function).data(db);
+        let tables = &tree_data.tables;
+        let calls_intrinsic = calls(tables).any(|(_, place)| match tables[place] {
+            validated::PlaceData::Intrinsic(intrinsic) => intrinsic.performs_io(),
+            _ => false,
+        });
+        if calls_intrinsic {
+            return true;
+        }
+        stack.extend(super::call_graph::callees(db, function));
+    }
+    false
+}
+
+/// If `function` is declared `const`, reports each call in its body to an
+/// intrinsic or function that performs I/O.
+#[salsa::memoized(in crate::Jar)]
+pub(crate) fn check_const_calls(db: &dyn crate::Db, function: Function) {
+    if !function.effect(db).is_const() {
+        return;
+    }
+
+    let validated_tree = super::validate_function(db, function);
+    let tables = &validated_tree.data(db).tables;
+    let origins = validated_tree.origins(db);
+    for (expr, place) in calls(tables) {
+        let callee_name = match tables[place] {
+            validated::PlaceData::Intrinsic(intrinsic) if intrinsic.performs_io() => {
+                intrinsic.as_str(db)
+            }
+            validated::PlaceData::Function(callee) if performs_io(db, callee) => {
+                callee.name(db).as_str(db)
+            }
+            _ => continue,
+        };
+
+        let call_span =
+            function.spans(db)[origins[expr].syntax_expr].in_file(function.filename(db));
+        dada_ir::error!(
+            call_span,
+            "`const` functions cannot call `{}`, which performs I/O",
+            callee_name,
+        )
+        .primary_label("call is here")
+        .secondary_label(function.effect_span(db), "fn declared `const`")
+        .emit(db);
+    }
+}
+
+/// Each call in `tables` along with the place it calls. Callees are
+/// validated in "reserve" mode, so calls through other expressions are skipped.
+fn calls(
+    tables: &validated::Tables,
+) -> impl Iterator<Item = (validated::Expr, validated::Place)> + '_ {
+    validated::Expr::max_key(tables)
+        .iter()
+        .filter_map(move |expr| match &tables[expr] {
+            validated::ExprData::Call(func_expr, _) => match tables[*func_expr] {
+                validated::ExprData::Reserve(place) => Some((expr, place)),
+                _ => None,
+            },
+            _ => None,
+        })
+}
@@ -0,0 +1,97 @@
+//! Reports functions that are never used from the entry point of a file,
+//! if [`warn_dead_functions`] is set.
+//!
+//! A function is used if it is called or referred to by name (e.g., to pass
+//! it to another function). The entry point is `main` (or the top-level
+//! expressions, if any). Files without an entry point are libraries, so
+//! nothing is reported for them. Functions marked `@test` are called by the
+//! test runner, and `pub` functions may be called from other files, so they
+//! are entry points too (but they do not make a file a program). Private
+//! functions are only visible within the file, so they are dead unless
+//! something uses them.
+
+use dada_collections::Set;
+use dada_ir::filename::Filename;
+use dada_ir::function::Function;
+use dada_ir::item::Item;
+use dada_ir::word::Word;
+use dada_parse::prelude::*;
+
+/// Salsa input: whether to warn about functions that are never used.
+///
+/// Defaults to false if not explicitly set.
+#[salsa::memoized(in crate::Jar)]
+pub fn warn_dead_functions(_db: &dyn crate::Db, _filename: Filename) -> bool {
+    false // default: off
+}
+
+/// Returns the functions in `filename` that cannot be reached from its
+/// entry point.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub(crate) fn unreachable_functions(db: &dyn crate::Db, filename: Filename) -> Vec<Function> {
+    let mut stack = entry_points(db, filename);
+    if stack.is_empty() {
+        return vec![];
+    }
+    stack.extend(
+        filename
+            .items(db)
+            .iter()
+            .filter_map(|&item| match item {
+                Item::Function(function) => Some(function),
+                Item::Class(_) => None,
+            })
+            .filter(|function| {
+                function.attribute(db, "test").is_some() || function.visibility(db).is_pub()
+            }),
+    );
+
+    let mut reachable: Set<Function> = Set::default();
+    while let Some(function) = stack.pop() {
+        if reachable.insert(function) {
+            stack.extend(super::call_graph::referenced_functions(db, function));
+        }
+    }
+
+    super::call_graph::call_graph(db, filename)
+        .keys()
+        .copied()
+        .filter(|function| !reachable.contains(function))
+        .collect()
+}
+
+/// If [`warn_dead_functions`] is set, warns about each function in
+/// `filename` that cannot be reached from its entry point.
+#[salsa::memoized(in crate::Jar)]
+pub(crate) fn report_dead_functions(db: &dyn crate::Db, filename: Filename) {
+    if !warn_dead_functions(db, filename) {
+        return;
+    }
+
+    for &function in unreachable_functions(db, filename) {
+        let name = function.name(db);
+        dada_ir::warning!(
+            name.span(db),
+            "function `{}` is never called",
+            name.as_str(db)
+        )
+        .emit(db);
+    }
+}
+
+fn entry_points(db: &dyn crate::Db, filename: Filename) -> Vec<Function> {
+    let source_file = filename.source_file(db);
+    let main = Word::from(db, "main");
+    source_file
+        .items(db)
+        .iter()
+        .filter_map(|&item| match item {
+            Item::Function(function) => Some(function),
+            Item::Class(_) => None,
+        })
+        .filter(|&function| {
+            Some(function) == source_file.main_fn(db) || function.name(db).word(db) == main
+        })
+        .collect()
+}
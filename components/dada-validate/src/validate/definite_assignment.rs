@@ -0,0 +1,226 @@
+//! A simple definite-assignment check over the validated tree.
+//!
+//! Every local variable declaration has an initializer, but the variable
+//! is in scope while that initializer executes (e.g., in `x = x + 1` where
+//! there is no earlier `x`), so a variable can still be read before it is
+//! assigned. We walk the tree in evaluation order, tracking which variables
+//! are definitely initialized, and report reads of the others.
+
+use dada_id::prelude::*;
+use dada_ir::code::validated;
+use dada_ir::function::Function;
+use dada_parse::prelude::*;
+
+pub(crate) fn check(
+    db: &dyn crate::Db,
+    function: Function,
+    tree_data: &validated::TreeData,
+    origins: &validated::Origins,
+) {
+    let num_local_variables = usize::from(tree_data.max_local_variable());
+    let mut initialized = Initialized {
+        variables: vec![false; num_local_variables],
+    };
+    for parameter in tree_data.parameters() {
+        initialized.insert(parameter);
+    }
+
+    let mut checker = Checker {
+        db,
+        function,
+        tables: &tree_data.tables,
+        origins,
+        initialized,
+    };
+    checker.check_expr(tree_data.root_expr);
+}
+
+/// The set of local variables that are definitely initialized at some
+/// point in the program. After a `return`, `break`, or `continue`, the
+/// code is unreachable and we treat everything as initialized.
+#[derive(Clone)]
+struct Initialized {
+    variables: Vec<bool>,
+}
+
+impl Initialized {
+    fn contains(&self, local_variable: validated::LocalVariable) -> bool {
+        self.variables[usize::from(local_variable)]
+    }
+
+    fn insert(&mut self, local_variable: validated::LocalVariable) {
+        self.variables[usize::from(local_variable)] = true;
+    }
+
+    fn unreachable(&mut self) {
+        self.variables.iter_mut().for_each(|v| *v = true);
+    }
+
+    /// Keeps only the variables initialized on both incoming paths.
+    fn intersect(&mut self, other: &Initialized) {
+        for (v, o) in self.variables.iter_mut().zip(&other.variables) {
+            *v &= *o;
+        }
+    }
+}
+
+struct Checker<'me> {
+    db: &'me dyn crate::Db,
+    function: Function,
+    tables: &'me validated::Tables,
+    origins: &'me validated::Origins,
+    initialized: Initialized,
+}
+
+impl Checker<'_> {
+    fn check_expr(&mut self, expr: validated::Expr) {
+        match expr.data(self.tables) {
+            validated::ExprData::BooleanLiteral(_)
+            | validated::ExprData::SignedIntegerLiteral(_)
+            | validated::ExprData::UnsignedIntegerLiteral(_)
+            | validated::ExprData::IntegerLiteral(_)
+            | validated::ExprData::FloatLiteral(_)
+            | validated::ExprData::CharLiteral(_)
+            | validated::ExprData::StringLiteral(_)
+            | validated::ExprData::Error => {}
+
+            validated::ExprData::Concatenate(exprs)
+            | validated::ExprData::Tuple(exprs)
+            | validated::ExprData::List(exprs)
+            | validated::ExprData::Seq(exprs) => {
+                for expr in exprs {
+                    self.check_expr(*expr);
+                }
+            }
+
+            validated::ExprData::Await(expr)
+            | validated::ExprData::Share(expr)
+            | validated::ExprData::Atomic(expr)
+            | validated::ExprData::Unary(_, expr)
+            | validated::ExprData::Declare(_, expr) => self.check_expr(*expr),
+
+            validated::ExprData::Call(func_expr, named_exprs) => {
+                self.check_expr(*func_expr);
+                for named_expr in named_exprs {
+                    self.check_expr(named_expr.data(self.tables).expr);
+                }
+            }
+
+            validated::ExprData::Reserve(place)
+            | validated::ExprData::Lease(place)
+            | validated::ExprData::Shlease(place)
+            | validated::ExprData::Give(place) => self.check_place(*place),
+
+            validated::ExprData::Range(start_expr, end_expr, _) => {
+                for expr in start_expr.iter().chain(end_expr) {
+                    self.check_expr(*expr);
+                }
+            }
+
+            validated::ExprData::Closure(parameters, captured_places, body_expr) => {
+                for place in captured_places {
+                    self.check_place(*place);
+                }
+
+                // The body may run any number of times (or never), so whatever
+                // it initializes doesn't count for the code that follows.
+                let initialized = self.initialized.clone();
+                for parameter in parameters {
+                    self.initialized.insert(*parameter);
+                }
+                self.check_expr(*body_expr);
+                self.initialized = initialized;
+            }
+
+            validated::ExprData::If(condition_expr, then_expr, else_expr) => {
+                self.check_expr(*condition_expr);
+                let initialized = self.initialized.clone();
+                self.check_expr(*then_expr);
+                let then_initialized = std::mem::replace(&mut self.initialized, initialized);
+                self.check_expr(*else_expr);
+                self.initialized.intersect(&then_initialized);
+            }
+
+            validated::ExprData::Loop(body_expr) => {
+                // A `break` may leave the body at any point, so conservatively
+                // assume that nothing the body initializes survives the loop.
+                let initialized = self.initialized.clone();
+                self.check_expr(*body_expr);
+                self.initialized = initialized;
+            }
+
+            validated::ExprData::Break {
+                from_expr: _,
+                with_value,
+            } => {
+                self.check_expr(*with_value);
+                self.initialized.unreachable();
+            }
+
+            validated::ExprData::Continue(_) => self.initialized.unreachable(),
+
+            validated::ExprData::Return(with_value) => {
+                self.check_expr(*with_value);
+                self.initialized.unreachable();
+            }
+
+            validated::ExprData::Op(lhs_expr, _, rhs_expr) => {
+                self.check_expr(*lhs_expr);
+                self.check_expr(*rhs_expr);
+            }
+
+            validated::ExprData::AssignTemporary(local_variable, expr) => {
+                self.check_expr(*expr);
+                self.initialized.insert(*local_variable);
+            }
+
+            validated::ExprData::AssignFromPlace(target_place, place) => {
+                self.check_place(*place);
+                match target_place.data(self.tables) {
+                    validated::TargetPlaceData::LocalVariable(local_variable) => {
+                        self.initialized.insert(*local_variable);
+                    }
+                    validated::TargetPlaceData::Dot(owner_place, _) => {
+                        self.check_place(*owner_place);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_place(&mut self, place: validated::Place) {
+        match place.data(self.tables) {
+            validated::PlaceData::LocalVariable(local_variable) => {
+                if !self.initialized.contains(*local_variable) {
+                    self.report_uninitialized(place, *local_variable);
+
+                    // Only report the first use.
+                    self.initialized.insert(*local_variable);
+                }
+            }
+            validated::PlaceData::Function(_)
+            | validated::PlaceData::Intrinsic(_)
+            | validated::PlaceData::Class(_) => {}
+            validated::PlaceData::Dot(owner_place, _) => self.check_place(*owner_place),
+            validated::PlaceData::Index(owner_place, index_place) => {
+                self.check_place(*index_place);
+                self.check_place(*owner_place);
+            }
+        }
+    }
+
+    fn report_uninitialized(
+        &self,
+        place: validated::Place,
+        local_variable: validated::LocalVariable,
+    ) {
+        let syntax_expr = self.origins[place].syntax_expr;
+        let span =
+            self.function.spans(self.db)[syntax_expr].in_file(self.function.filename(self.db));
+        let name = match local_variable.data(self.tables).name {
+            Some(name) => format!("`{}`", name.as_str(self.db)),
+            None => "a temporary".to_string(),
+        };
+        dada_ir::error!(span, "{} is used before it is initialized", name).emit(self.db);
+    }
+}
@@ -0,0 +1,116 @@
+//! Infers the effect of functions that do not declare one.
+//!
+//! The inferred effect is the least effect that permits everything in the
+//! validated tree: `async` if the function awaits, `const` if it does nothing
+//! with side effects, and the default effect otherwise. An `await` in a
+//! function that is not declared `async` is reported by the validator, which
+//! suggests declaring it with `async fn`.
+
+use dada_ir::code::validated;
+use dada_ir::effect::Effect;
+use dada_ir::function::Function;
+use dada_parse::prelude::*;
+
+/// Returns the declared effect of `function` or, if it does not declare one,
+/// the effect inferred from its body.
+#[salsa::memoized(in crate::Jar)]
+#[tracing::instrument(level = "debug", skip(db))]
+pub(crate) fn inferred_effect(db: &dyn crate::Db, function: Function) -> Effect {
+    let declared_effect = function.effect(db);
+    if declared_effect != Effect::Default {
+        return declared_effect;
+    }
+
+    let validated_tree = super::validate_function(db, function);
+    let tree_data = validated_tree.data(db);
+    let mut inference = EffectInference {
+        tables: &tree_data.tables,
+    };
+    inference.infer_expr(tree_data.root_expr)
+}
+
+struct EffectInference<'me> {
+    tables: &'me validated::Tables,
+}
+
+impl EffectInference<'_> {
+    /// Returns the least effect that permits `expr` and everything within it.
+    fn infer_expr(&mut self, expr: validated::Expr) -> Effect {
+        let tables = self.tables;
+        match &tables[expr] {
+            validated::ExprData::BooleanLiteral(_)
+            | validated::ExprData::SignedIntegerLiteral(_)
+            | validated::ExprData::UnsignedIntegerLiteral(_)
+            | validated::ExprData::IntegerLiteral(_)
+            | validated::ExprData::FloatLiteral(_)
+            | validated::ExprData::CharLiteral(_)
+            | validated::ExprData::StringLiteral(_)
+            | validated::ExprData::Reserve(_)
+            | validated::ExprData::Lease(_)
+            | validated::ExprData::Shlease(_)
+            | validated::ExprData::Give(_)
+            | validated::ExprData::Continue(_) => Effect::Const,
+
+            // The body of a closure only runs when the closure is called.
+            validated::ExprData::Closure(..) => Effect::Const,
+
+            validated::ExprData::Await(future_expr) => {
+                self.infer_expr(*future_expr).max(Effect::Async)
+            }
+
+            // FIXME: use the effect of the function being called, when it is known
+            validated::ExprData::Call(func_expr, named_exprs) => {
+                let mut effect = self.infer_expr(*func_expr).max(Effect::Default);
+                for &named_expr in named_exprs {
+                    let argument_expr = tables[named_expr].expr;
+                    effect = effect.max(self.infer_expr(argument_expr));
+                }
+                effect
+            }
+
+            validated::ExprData::Atomic(body_expr) => {
+                self.infer_expr(*body_expr).max(Effect::Default)
+            }
+
+            validated::ExprData::Share(operand)
+            | validated::ExprData::Loop(operand)
+            | validated::ExprData::Return(operand)
+            | validated::ExprData::Unary(_, operand)
+            | validated::ExprData::AssignTemporary(_, operand)
+            | validated::ExprData::Declare(_, operand) => self.infer_expr(*operand),
+
+            validated::ExprData::Concatenate(exprs)
+            | validated::ExprData::Tuple(exprs)
+            | validated::ExprData::List(exprs)
+            | validated::ExprData::Seq(exprs) => self.infer_exprs(exprs),
+
+            validated::ExprData::Range(start, end, _) => {
+                let start_effect = self.infer_exprs(start.as_slice());
+                start_effect.max(self.infer_exprs(end.as_slice()))
+            }
+            validated::ExprData::If(condition, then_expr, else_expr) => {
+                self.infer_exprs(&[*condition, *then_expr, *else_expr])
+            }
+            validated::ExprData::Break { with_value, .. } => self.infer_expr(*with_value),
+            validated::ExprData::Op(lhs, _, rhs) => self.infer_exprs(&[*lhs, *rhs]),
+
+            validated::ExprData::AssignFromPlace(target_place, _) => {
+                match tables[*target_place] {
+                    validated::TargetPlaceData::LocalVariable(_) => Effect::Const,
+
+                    // Assigning to a field may modify state outside the function.
+                    validated::TargetPlaceData::Dot(..) => Effect::Default,
+                }
+            }
+
+            validated::ExprData::Error => Effect::Default,
+        }
+    }
+
+    fn infer_exprs(&mut self, exprs: &[validated::Expr]) -> Effect {
+        exprs
+            .iter()
+            .map(|&expr| self.infer_expr(expr))
+            .fold(Effect::Const, Effect::max)
+    }
+}
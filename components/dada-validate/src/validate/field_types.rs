@@ -0,0 +1,47 @@
+//! Checks the types declared for class fields, like the `Int` in
+//! `class Point(x: Int, y: Int)`.
+
+use dada_ir::filename::Filename;
+use dada_ir::item::Item;
+use dada_ir::ty::{TyData, BUILTIN_TYPE_NAMES};
+use dada_parse::prelude::*;
+
+use super::name_lookup::Definition;
+
+/// Reports field types that name neither a builtin type nor a class
+/// declared in `filename`.
+#[salsa::memoized(in crate::Jar)]
+pub(crate) fn check_field_types(db: &dyn crate::Db, filename: Filename) {
+    let root_definitions = super::root_definitions(db, filename);
+    for item in filename.items(db) {
+        let Item::Class(class) = *item else {
+            continue;
+        };
+
+        for field in class.fields(db) {
+            let (Some(ty), Some(ty_span)) = (field.decl(db).ty, field.decl_span(db).ty_span) else {
+                continue;
+            };
+
+            match ty.data(db) {
+                TyData::Named(name) => {
+                    if BUILTIN_TYPE_NAMES.contains(&name.as_str(db)) {
+                        continue;
+                    }
+
+                    if let Some(Definition::Class(_)) = root_definitions.lookup(*name) {
+                        continue;
+                    }
+
+                    dada_ir::error!(
+                        ty_span.in_file(filename),
+                        "unknown type `{}`",
+                        name.as_str(db),
+                    )
+                    .primary_label(format!("there is no class named `{}`", name.as_str(db)))
+                    .emit(db);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Resolves `use module.name` declarations to the items of other files.
+
+use dada_ir::filename::Filename;
+use dada_ir::import::Import;
+use dada_ir::item::Item;
+use dada_ir::word::SpannedWord;
+use dada_parse::prelude::*;
+
+/// Returns the items that the imports of `filename` refer to, each with the
+/// name it is imported under, reporting imports that cannot be resolved.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub(crate) fn resolve_imports(db: &dyn crate::Db, filename: Filename) -> Vec<(SpannedWord, Item)> {
+    filename
+        .imports(db)
+        .iter()
+        .filter_map(|&import| {
+            let item = resolve_import(db, filename, import)?;
+            Some((import.name, item))
+        })
+        .collect()
+}
+
+fn resolve_import(db: &dyn crate::Db, filename: Filename, import: Import) -> Option<Item> {
+    let module = import.module;
+    let module_filename = import.module_filename(db, filename);
+    if !dada_ir::manifest::has_source_text(db, module_filename) {
+        dada_ir::error!(
+            module.span(db),
+            "cannot find module `{}`",
+            module.as_str(db),
+        )
+        .primary_label(format!("there is no file `{}`", module_filename.as_str(db)))
+        .emit(db);
+        return None;
+    }
+
+    let name = import.name;
+    let Some(item) = module_filename
+        .items(db)
+        .iter()
+        .copied()
+        .find(|item| item.name(db) == name.word(db))
+    else {
+        dada_ir::error!(
+            name.span(db),
+            "module `{}` has no item named `{}`",
+            module.as_str(db),
+            name.as_str(db),
+        )
+        .emit(db);
+        return None;
+    };
+
+    if !item.visibility(db).is_pub() {
+        dada_ir::error!(
+            name.span(db),
+            "{} `{}` is private to module `{}`",
+            item.kind_str(),
+            name.as_str(db),
+            module.as_str(db),
+        )
+        .primary_label("only items declared with `pub` can be imported")
+        .emit(db);
+        return None;
+    }
+
+    Some(item)
+}
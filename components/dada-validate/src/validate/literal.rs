@@ -0,0 +1,68 @@
+//! Converts the text of numeric literals into their values.
+
+use std::str::FromStr;
+
+use dada_ir::{
+    code::{syntax::integer::IntegerLiteral, validated},
+    word::Word,
+};
+
+/// Converts an integer literal like `22`, `0xFF`, `22_000`, or `22_i` (where `i` is the suffix).
+/// On failure, returns the error message to report.
+pub(crate) fn integer_literal(
+    db: &dyn crate::Db,
+    literal: IntegerLiteral,
+) -> Result<validated::ExprData, String> {
+    let suffix = literal.suffix.map(|suffix| suffix.as_str(db));
+    let ty = match suffix {
+        Some("i") => "i64",
+        _ => "u64",
+    };
+    let too_large = || {
+        format!(
+            "integer literal `{}` is too large for `{}`",
+            literal.text.as_str(db),
+            ty
+        )
+    };
+
+    let Some(value) = literal.value else {
+        return Err(invalid_digits(db, literal).unwrap_or_else(too_large));
+    };
+    match suffix {
+        Some("u") => Ok(validated::ExprData::UnsignedIntegerLiteral(value)),
+        Some("i") => i64::try_from(value)
+            .map(validated::ExprData::SignedIntegerLiteral)
+            .map_err(|_| too_large()),
+        Some(suffix_str) => Err(format!("`{}` is not a valid integer suffix", suffix_str)),
+        None => Ok(validated::ExprData::IntegerLiteral(value)),
+    }
+}
+
+/// If the digits of `literal` are not valid in its base, returns the error
+/// message to report.
+fn invalid_digits(db: &dyn crate::Db, literal: IntegerLiteral) -> Option<String> {
+    let text = literal.text.as_str(db);
+    let digits = literal.digits(db);
+    if digits.is_empty() {
+        return Some(format!("`{}` is not a valid integer: it has no digits", text));
+    }
+    let base = literal.base;
+    let invalid = digits.chars().find(|c| !c.is_digit(base.radix()))?;
+    Some(format!(
+        "`{}` is not a valid integer: `{}` is not a {} digit",
+        text,
+        invalid,
+        base.str()
+    ))
+}
+
+/// Converts a float literal like `22.5`. On failure, returns the error message to report.
+pub(crate) fn float_literal(db: &dyn crate::Db, word: Word) -> Result<validated::ExprData, String> {
+    let raw_str = word.as_str(db);
+    let without_underscore: String = raw_str.chars().filter(|&c| c != '_').collect();
+    match f64::from_str(&without_underscore) {
+        Ok(v) => Ok(validated::ExprData::FloatLiteral(eq_float::F64(v))),
+        Err(e) => Err(format!("`{}` is not a valid float: {}", raw_str, e)),
+    }
+}
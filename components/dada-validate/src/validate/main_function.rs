@@ -0,0 +1,82 @@
+//! Finds the `main` function, where a program starts, and checks its signature.
+//!
+//! `main` is called without arguments, and whatever it returns becomes the
+//! exit status of the program, so it must return either nothing or an integer.
+
+use dada_ir::diagnostic::Diagnostic;
+use dada_ir::filename::Filename;
+use dada_ir::function::Function;
+use dada_ir::item::Item;
+use dada_ir::return_type::ReturnTypeKind;
+use dada_ir::span::Span;
+use dada_ir::word::Word;
+use dada_parse::prelude::*;
+
+use super::name_lookup::Definition;
+
+/// Returns the function named `main` in `filename`, or an error if there
+/// is no such function or it cannot be called as the entry point.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub(crate) fn main_function(
+    db: &dyn crate::Db,
+    filename: Filename,
+) -> Result<Function, Diagnostic> {
+    let main = Word::from(db, "main");
+    let function = match super::root_definitions(db, filename).lookup(main) {
+        Some(Definition::Function(function)) => function,
+        Some(Definition::Class(class)) => {
+            return Err(dada_ir::error!(
+                Item::Class(class).name_span(db),
+                "`main` must be a function",
+            )
+            .primary_label("`main` is declared as a class here")
+            .finish());
+        }
+        _ => {
+            return Err(dada_ir::error!(
+                Span::zero().in_file(filename),
+                "no function named `main`",
+            )
+            .finish());
+        }
+    };
+
+    let parameters = function.parameters(db);
+    if let (Some(first), Some(last)) = (parameters.first(), parameters.last()) {
+        let span = first
+            .decl_span(db)
+            .name_span
+            .to(last.decl_span(db).name_span)
+            .in_file(filename);
+        return Err(dada_ir::error!(span, "`main` cannot have parameters")
+            .primary_label("`main` is called without arguments")
+            .finish());
+    }
+
+    let return_type = function.return_type(db);
+    if return_type.kind(db) == ReturnTypeKind::Declared && return_type.tuple_arity(db).is_some() {
+        return Err(dada_ir::error!(
+            return_type.span(db),
+            "`main` must return nothing or an integer",
+        )
+        .primary_label("`main` cannot return a tuple")
+        .finish());
+    }
+
+    Ok(function)
+}
+
+/// Reports an error if `filename` defines a `main` that cannot be the
+/// entry point. Files without a `main` are libraries, so that is fine here.
+#[salsa::memoized(in crate::Jar)]
+pub(crate) fn check_main_function(db: &dyn crate::Db, filename: Filename) {
+    let main = Word::from(db, "main");
+    if super::root_definitions(db, filename).lookup(main).is_none() {
+        return;
+    }
+
+    if let Err(diagnostic) = main_function(db, filename) {
+        diagnostic.clone().emit(db);
+    }
+}
@@ -1,7 +1,12 @@
 use dada_collections::Map;
 use dada_ir::{
-    class::Class, code::validated, filename::Filename, function::Function, intrinsic::Intrinsic,
-    item::Item, word::Word,
+    class::Class,
+    code::validated,
+    filename::Filename,
+    function::Function,
+    intrinsic::Intrinsic,
+    item::Item,
+    word::{SpannedWord, Word},
 };
 use dada_parse::prelude::*;
 
@@ -22,6 +27,7 @@ pub(crate) enum Definition {
     Function(Function),
     Class(Class),
     Intrinsic(Intrinsic),
+    TypeParameter(SpannedWord),
 }
 
 impl Definition {
@@ -31,6 +37,7 @@ impl Definition {
             Definition::Function(_) => "functions",
             Definition::Class(_) => "classes",
             Definition::Intrinsic(_) => "functions",
+            Definition::TypeParameter(_) => "type parameters",
         }
     }
 }
@@ -50,6 +57,7 @@ impl TryInto<Item> for Definition {
         match self {
             Definition::LocalVariable(_) => Err(()),
             Definition::Intrinsic(_) => Err(()),
+            Definition::TypeParameter(_) => Err(()),
             Definition::Function(f) => Ok(Item::Function(f)),
             Definition::Class(c) => Ok(Item::Class(c)),
         }
@@ -88,6 +96,17 @@ impl<'me> Scope<'me> {
             .insert(name, Definition::LocalVariable(local_variable))
     }
 
+    /// Inserts a function defined within a block into the scope.
+    pub(crate) fn insert_function(&mut self, name: Word, function: Function) {
+        self.names.insert(name, Definition::Function(function));
+    }
+
+    /// Inserts a generic type parameter into the scope.
+    pub(crate) fn insert_type_parameter(&mut self, name: Word, type_parameter: SpannedWord) {
+        self.names
+            .insert(name, Definition::TypeParameter(type_parameter));
+    }
+
     /// Tracks a temporary that is created; they don't affect name resolution, but they get
     /// dropped at the same time as local variables in the surrounding scope.
     #[tracing::instrument(level = "Debug", skip(self))]
@@ -100,6 +119,12 @@ impl<'me> Scope<'me> {
         self.names.get(&name).copied()
     }
 
+    /// True if `local_variable` was inserted into this scope, rather than
+    /// into an enclosing one.
+    pub(crate) fn declared_here(&self, local_variable: validated::LocalVariable) -> bool {
+        self.inserted.contains(&local_variable)
+    }
+
     /// Get the vector of inserted names from this scope (replacing it with `vec![]`);
     /// used when exiting the scope, see [`Validator::exit_subscope`].
     pub(crate) fn take_inserted(&mut self) -> Vec<validated::LocalVariable> {
@@ -135,6 +160,23 @@ impl RootDefinitions {
             }
         }
 
+        // Then add the items imported from other files
+        for &(name, item) in super::imports::resolve_imports(db, filename) {
+            if let Some(&other_definition) = names.get(&name.word(db)) {
+                let other_item: Item = other_definition.try_into().unwrap();
+                dada_ir::error!(
+                    name.span(db),
+                    "already have a {} named `{}`",
+                    other_item.kind_str(),
+                    name.as_str(db),
+                )
+                .primary_label("ignoring this import")
+                .emit(db);
+            } else {
+                names.insert(name.word(db), Definition::from(item));
+            }
+        }
+
         // Populate with intrinsics from the prelude (these can be shadowed, so don't error if
         // user generates something with the same name)
         for &intrinsic in Intrinsic::ALL {
@@ -143,4 +185,14 @@ impl RootDefinitions {
 
         RootDefinitions { names }
     }
+
+    /// Lookup the given name among the root definitions.
+    pub(crate) fn lookup(&self, name: Word) -> Option<Definition> {
+        self.names.get(&name).copied()
+    }
+
+    /// The names of all the root definitions, in no particular order.
+    pub(crate) fn names(&self) -> impl Iterator<Item = Word> + '_ {
+        self.names.keys().copied()
+    }
 }
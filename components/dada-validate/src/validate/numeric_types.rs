@@ -0,0 +1,509 @@
+//! Infers which literals and local variables are integers and which are floats.
+//!
+//! Every expression and local variable starts out with an unknown numeric
+//! type. Literals and declared types (of parameters and of the return value)
+//! fix the type; arithmetic, comparisons, and assignments require the values
+//! involved to have the same type. Values used in arithmetic whose type is
+//! never fixed default to integers (which are 64 bits wide); other values
+//! whose type is never fixed, like the result of a call, are left without a
+//! numeric type.
+//!
+//! If a function has `->` but no declared return type, its return type is
+//! that of its body, and each `return` must return a value of that type.
+//!
+//! Declared integer types (like `Int32`) also fix the width of integers;
+//! values that must have the same type share the narrowest width declared
+//! for any of them.
+
+use dada_collections::Map;
+use dada_id::prelude::*;
+use dada_ir::code::validated;
+use dada_ir::code::validated::op::Op;
+use dada_ir::function::Function;
+use dada_ir::return_type::ReturnTypeKind;
+use dada_ir::span::FileSpan;
+use dada_ir::ty::{IntegerWidth, NumericTy, Ty, TyData};
+use dada_parse::prelude::*;
+
+/// The numeric types inferred for the expressions and local variables of a function.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NumericTypes {
+    exprs: Map<validated::Expr, NumericTy>,
+    local_variables: Map<validated::LocalVariable, NumericTy>,
+    return_ty: Option<NumericTy>,
+    integer_widths: Map<validated::Expr, IntegerWidth>,
+}
+
+impl NumericTypes {
+    /// The numeric type of `expr`, if it is known to be a number.
+    pub fn expr(&self, expr: validated::Expr) -> Option<NumericTy> {
+        self.exprs.get(&expr).copied()
+    }
+
+    /// The numeric type of `local_variable`, if it is known to hold a number.
+    pub fn local_variable(&self, local_variable: validated::LocalVariable) -> Option<NumericTy> {
+        self.local_variables.get(&local_variable).copied()
+    }
+
+    /// The numeric type that the function returns, whether declared or
+    /// inferred from its body, if it returns a number.
+    pub fn return_ty(&self) -> Option<NumericTy> {
+        self.return_ty
+    }
+
+    /// The width of `expr`, if it is an integer whose width was declared.
+    pub fn integer_width(&self, expr: validated::Expr) -> Option<IntegerWidth> {
+        self.integer_widths.get(&expr).copied()
+    }
+}
+
+/// Infers the numeric types in the validated tree of `function`, reporting
+/// an error wherever a value would have to be both an integer and a float.
+#[salsa::memoized(in crate::Jar ref)]
+#[tracing::instrument(level = "debug", skip(db))]
+pub(crate) fn numeric_types(db: &dyn crate::Db, function: Function) -> NumericTypes {
+    let validated_tree = super::validate_function(db, function);
+    let tree_data = validated_tree.data(db);
+    let tables = &tree_data.tables;
+    let num_exprs = usize::from(validated::Expr::max_key(tables));
+    let num_local_variables = usize::from(validated::LocalVariable::max_key(tables));
+    let return_type = function.return_type(db);
+
+    let mut inference = NumericInference {
+        db,
+        function,
+        tables,
+        origins: validated_tree.origins(db),
+        return_ty: return_type
+            .ty(db)
+            .and_then(|ty| numeric_ty(db, ty))
+            .map(|ty| (ty, return_type.span(db))),
+        return_width: return_type.ty(db).and_then(|ty| integer_width(db, ty)),
+        infers_return_ty: return_type.kind(db) == ReturnTypeKind::Inferred,
+        inferred_returns: vec![],
+        num_exprs,
+        parents: (0..num_exprs + num_local_variables).collect(),
+        known: vec![None; num_exprs + num_local_variables],
+        numeric: vec![false; num_exprs + num_local_variables],
+        widths: vec![None; num_exprs + num_local_variables],
+    };
+
+    let syntax_tables = &function.syntax_tree(db).data(db).tables;
+    for local_variable in validated::LocalVariable::max_key(tables).iter() {
+        let validated::LocalVariableOrigin::Parameter(decl) = inference.origins[local_variable] else {
+            continue;
+        };
+        let Some(declared_ty) = syntax_tables[decl].ty else {
+            continue;
+        };
+        let Some(ty) = numeric_ty(db, declared_ty) else {
+            continue;
+        };
+        let decl_span = function.spans(db)[decl];
+        let ty_span = decl_span.ty_span.unwrap_or(decl_span.name_span);
+        let var = Var::LocalVariable(local_variable);
+        inference.require(var, ty, ty_span.in_file(function.filename(db)));
+        if let Some(width) = integer_width(db, declared_ty) {
+            inference.require_width(var, width);
+        }
+    }
+
+    if let Some((ty, span)) = inference.return_ty {
+        inference.require(Var::Expr(tree_data.root_expr), ty, span);
+    }
+    if let Some(width) = inference.return_width {
+        inference.require_width(Var::Expr(tree_data.root_expr), width);
+    }
+    inference.infer_expr(tree_data.root_expr);
+    for value_expr in std::mem::take(&mut inference.inferred_returns) {
+        inference.check_inferred_return(tree_data.root_expr, value_expr);
+    }
+
+    let mut numeric_types = NumericTypes::default();
+    for expr in validated::Expr::max_key(tables).iter() {
+        if let Some(ty) = inference.ty(Var::Expr(expr)) {
+            numeric_types.exprs.insert(expr, ty);
+        }
+        if let Some(width) = inference.width(Var::Expr(expr)) {
+            numeric_types.integer_widths.insert(expr, width);
+        }
+    }
+    for local_variable in validated::LocalVariable::max_key(tables).iter() {
+        if let Some(ty) = inference.ty(Var::LocalVariable(local_variable)) {
+            numeric_types.local_variables.insert(local_variable, ty);
+        }
+    }
+    if return_type.kind(db).returns_value() {
+        numeric_types.return_ty = inference.ty(Var::Expr(tree_data.root_expr));
+    }
+    numeric_types
+}
+
+fn numeric_ty(db: &dyn crate::Db, ty: Ty) -> Option<NumericTy> {
+    match ty.data(db) {
+        TyData::Named(name) => NumericTy::from_name(name.as_str(db)),
+    }
+}
+
+fn integer_width(db: &dyn crate::Db, ty: Ty) -> Option<IntegerWidth> {
+    match ty.data(db) {
+        TyData::Named(name) => IntegerWidth::from_name(name.as_str(db)),
+    }
+}
+
+/// Something that has a numeric type.
+#[derive(Copy, Clone, Debug)]
+enum Var {
+    Expr(validated::Expr),
+    LocalVariable(validated::LocalVariable),
+}
+
+/// What is known about the type of a set of vars that must have the same type.
+#[derive(Copy, Clone, Debug)]
+enum Known {
+    /// The vars have type `NumericTy`, as required by the code at `FileSpan`.
+    Ty(NumericTy, FileSpan),
+
+    /// The vars were required to have different types; this has been reported.
+    Conflict,
+}
+
+struct NumericInference<'me> {
+    db: &'me dyn crate::Db,
+    function: Function,
+    tables: &'me validated::Tables,
+    origins: &'me validated::Origins,
+
+    /// The declared return type (if numeric) and its span.
+    return_ty: Option<(NumericTy, FileSpan)>,
+
+    /// The width of the declared return type, if it is an integer type.
+    return_width: Option<IntegerWidth>,
+
+    /// True if the function has `->` but does not declare its return type.
+    infers_return_ty: bool,
+
+    /// The values of the `return` expressions, if the return type is inferred.
+    /// They are checked once the type of the body is known.
+    inferred_returns: Vec<validated::Expr>,
+
+    num_exprs: usize,
+
+    /// Union-find forest over the vars: the exprs, followed by the local variables.
+    parents: Vec<usize>,
+
+    /// What is known about each set of vars, stored at its root.
+    known: Vec<Option<Known>>,
+
+    /// Whether each set of vars is used in arithmetic, stored at its root.
+    numeric: Vec<bool>,
+
+    /// The narrowest integer width declared for each set of vars, stored at its root.
+    widths: Vec<Option<IntegerWidth>>,
+}
+
+impl NumericInference<'_> {
+    fn index(&self, var: Var) -> usize {
+        match var {
+            Var::Expr(expr) => usize::from(expr),
+            Var::LocalVariable(local_variable) => self.num_exprs + usize::from(local_variable),
+        }
+    }
+
+    fn find(&mut self, var: Var) -> usize {
+        let mut index = self.index(var);
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    fn ty(&mut self, var: Var) -> Option<NumericTy> {
+        let root = self.find(var);
+        match self.known[root] {
+            Some(Known::Ty(ty, _)) => Some(ty),
+            Some(Known::Conflict) => None,
+            None if self.numeric[root] => Some(NumericTy::Integer),
+            None => None,
+        }
+    }
+
+    /// Records that `var` is used in arithmetic, so it is a number even
+    /// if nothing fixes its type.
+    fn require_numeric(&mut self, var: Var) {
+        let root = self.find(var);
+        self.numeric[root] = true;
+    }
+
+    fn width(&mut self, var: Var) -> Option<IntegerWidth> {
+        let root = self.find(var);
+        self.widths[root]
+    }
+
+    /// Requires `var` to fit in an integer of the given `width`.
+    fn require_width(&mut self, var: Var, width: IntegerWidth) {
+        let root = self.find(var);
+        self.widths[root] = narrowest(self.widths[root], Some(width));
+    }
+
+    /// Requires `var` to have type `ty`, because of the code at `span`.
+    fn require(&mut self, var: Var, ty: NumericTy, span: FileSpan) {
+        let root = self.find(var);
+        let known = self.merge(Some(Known::Ty(ty, span)), self.known[root]);
+        self.known[root] = known;
+    }
+
+    /// Requires `expected` and `found` to have the same type. If they cannot,
+    /// the error is reported at whatever fixed the type of `found`.
+    fn unify(&mut self, expected: Var, found: Var) {
+        let expected_root = self.find(expected);
+        let found_root = self.find(found);
+        if expected_root == found_root {
+            return;
+        }
+        self.parents[found_root] = expected_root;
+        let known = self.merge(self.known[expected_root], self.known[found_root]);
+        self.known[expected_root] = known;
+        self.numeric[expected_root] |= self.numeric[found_root];
+        self.widths[expected_root] = narrowest(self.widths[expected_root], self.widths[found_root]);
+    }
+
+    fn merge(&self, expected: Option<Known>, found: Option<Known>) -> Option<Known> {
+        match (expected, found) {
+            (None, known) | (known, None) => known,
+            (Some(Known::Conflict), _) | (_, Some(Known::Conflict)) => Some(Known::Conflict),
+            (
+                Some(Known::Ty(expected_ty, expected_span)),
+                Some(Known::Ty(found_ty, found_span)),
+            ) => {
+                if expected_ty == found_ty {
+                    return expected;
+                }
+                dada_ir::error!(
+                    found_span,
+                    "expected {}, found {}",
+                    expected_ty.description(),
+                    found_ty.description(),
+                )
+                .primary_label(format!("this is {}", found_ty.description()))
+                .secondary_label(
+                    expected_span,
+                    format!("this is {}", expected_ty.description()),
+                )
+                .emit(self.db);
+                Some(Known::Conflict)
+            }
+        }
+    }
+
+    /// Requires the value returned by a `return` to have the type of the function
+    /// body, which is the inferred return type. Unlike [`Self::unify`], a mismatch
+    /// leaves the return type as it was inferred from the body.
+    fn check_inferred_return(&mut self, root_expr: validated::Expr, value_expr: validated::Expr) {
+        let body_root = self.find(Var::Expr(root_expr));
+        let value_root = self.find(Var::Expr(value_expr));
+        if let (Some(Known::Ty(body_ty, body_span)), Some(Known::Ty(value_ty, _))) =
+            (self.known[body_root], self.known[value_root])
+        {
+            if body_ty != value_ty {
+                let db = self.db;
+                dada_ir::error!(
+                    self.span(value_expr),
+                    "returned value does not match the inferred return type",
+                )
+                .primary_label(format!("this returns {}", value_ty.description()))
+                .secondary_label(
+                    body_span,
+                    format!(
+                        "the return type is inferred to be {} from this",
+                        body_ty.description()
+                    ),
+                )
+                .secondary_label(
+                    self.function.effect_span(db),
+                    format!(
+                        "`{}` does not declare its return type",
+                        self.function.name(db).as_str(db)
+                    ),
+                )
+                .emit(db);
+                return;
+            }
+        }
+        self.unify(Var::Expr(root_expr), Var::Expr(value_expr));
+    }
+
+    fn span(&self, expr: validated::Expr) -> FileSpan {
+        let syntax_expr = self.origins[expr].syntax_expr;
+        self.function.spans(self.db)[syntax_expr].in_file(self.function.filename(self.db))
+    }
+
+    /// Unifies `expr` with the local variable in `place`, if that is what it is.
+    fn unify_with_place(&mut self, expr: validated::Expr, place: validated::Place) {
+        if let validated::PlaceData::LocalVariable(local_variable) = self.tables[place] {
+            self.unify(Var::LocalVariable(local_variable), Var::Expr(expr));
+        }
+    }
+
+    fn infer_expr(&mut self, expr: validated::Expr) {
+        let tables = self.tables;
+        match &tables[expr] {
+            validated::ExprData::SignedIntegerLiteral(_)
+            | validated::ExprData::UnsignedIntegerLiteral(_)
+            | validated::ExprData::IntegerLiteral(_) => {
+                self.require(Var::Expr(expr), NumericTy::Integer, self.span(expr))
+            }
+
+            validated::ExprData::FloatLiteral(_) => {
+                self.require(Var::Expr(expr), NumericTy::Float, self.span(expr))
+            }
+
+            validated::ExprData::BooleanLiteral(_)
+            | validated::ExprData::CharLiteral(_)
+            | validated::ExprData::StringLiteral(_)
+            | validated::ExprData::Continue(_)
+            | validated::ExprData::Error => {}
+
+            // The body of a closure has its own return type, so it is not
+            // inferred along with the enclosing function.
+            validated::ExprData::Closure(..) => {}
+
+            validated::ExprData::Reserve(place)
+            | validated::ExprData::Lease(place)
+            | validated::ExprData::Shlease(place)
+            | validated::ExprData::Give(place) => self.unify_with_place(expr, *place),
+
+            validated::ExprData::Share(operand)
+            | validated::ExprData::Atomic(operand)
+            | validated::ExprData::Declare(_, operand) => {
+                self.infer_expr(*operand);
+                self.unify(Var::Expr(expr), Var::Expr(*operand));
+            }
+
+            validated::ExprData::Await(operand)
+            | validated::ExprData::Loop(operand)
+            | validated::ExprData::Break {
+                with_value: operand,
+                ..
+            } => self.infer_expr(*operand),
+
+            validated::ExprData::Return(with_value) => {
+                self.infer_expr(*with_value);
+                if let Some((ty, span)) = self.return_ty {
+                    self.require(Var::Expr(*with_value), ty, span);
+                    if let Some(width) = self.return_width {
+                        self.require_width(Var::Expr(*with_value), width);
+                    }
+                } else if self.infers_return_ty {
+                    self.inferred_returns.push(*with_value);
+                }
+            }
+
+            validated::ExprData::Call(func_expr, named_exprs) => {
+                self.infer_expr(*func_expr);
+                for &named_expr in named_exprs {
+                    self.infer_expr(tables[named_expr].expr);
+                }
+            }
+
+            validated::ExprData::Concatenate(exprs)
+            | validated::ExprData::Tuple(exprs)
+            | validated::ExprData::List(exprs) => {
+                for &element_expr in exprs {
+                    self.infer_expr(element_expr);
+                }
+            }
+
+            validated::ExprData::Range(start, end, _) => {
+                for &operand in start.iter().chain(end) {
+                    self.infer_expr(operand);
+                }
+            }
+
+            validated::ExprData::Seq(exprs) => {
+                for &statement_expr in exprs {
+                    self.infer_expr(statement_expr);
+                }
+                if let Some(&last_expr) = exprs.last() {
+                    self.unify(Var::Expr(expr), Var::Expr(last_expr));
+                }
+            }
+
+            validated::ExprData::If(condition, then_expr, else_expr) => {
+                for &operand in [condition, then_expr, else_expr] {
+                    self.infer_expr(operand);
+                }
+                self.unify(Var::Expr(expr), Var::Expr(*then_expr));
+                self.unify(Var::Expr(expr), Var::Expr(*else_expr));
+            }
+
+            validated::ExprData::Op(lhs, op, rhs) => {
+                self.infer_expr(*lhs);
+                self.infer_expr(*rhs);
+                match op {
+                    Op::Plus
+                    | Op::Minus
+                    | Op::Times
+                    | Op::DividedBy
+                    | Op::BitAnd
+                    | Op::BitOr
+                    | Op::BitXor => {
+                        self.unify(Var::Expr(*lhs), Var::Expr(*rhs));
+                        self.unify(Var::Expr(expr), Var::Expr(*lhs));
+                        self.require_numeric(Var::Expr(expr));
+                    }
+
+                    // The shift amount need not have the type of the value shifted.
+                    Op::ShiftLeft | Op::ShiftRight => {
+                        self.unify(Var::Expr(expr), Var::Expr(*lhs));
+                        self.require_numeric(Var::Expr(expr));
+                        self.require_numeric(Var::Expr(*rhs));
+                    }
+
+                    Op::EqualEqual
+                    | Op::NotEqual
+                    | Op::GreaterEqual
+                    | Op::LessEqual
+                    | Op::LessThan
+                    | Op::GreaterThan => {
+                        self.unify(Var::Expr(*lhs), Var::Expr(*rhs));
+                    }
+
+                    Op::Not => {}
+                }
+            }
+
+            validated::ExprData::Unary(op, operand) => {
+                self.infer_expr(*operand);
+                if *op == Op::Minus {
+                    self.unify(Var::Expr(expr), Var::Expr(*operand));
+                    self.require_numeric(Var::Expr(expr));
+                }
+            }
+
+            validated::ExprData::AssignTemporary(local_variable, initializer) => {
+                self.infer_expr(*initializer);
+                self.unify(Var::LocalVariable(*local_variable), Var::Expr(*initializer));
+            }
+
+            validated::ExprData::AssignFromPlace(target_place, place) => {
+                if let (
+                    validated::TargetPlaceData::LocalVariable(target),
+                    validated::PlaceData::LocalVariable(source),
+                ) = (&tables[*target_place], &tables[*place])
+                {
+                    self.unify(Var::LocalVariable(*target), Var::LocalVariable(*source));
+                }
+            }
+        }
+    }
+}
+
+/// The narrower of two (optional) integer widths.
+fn narrowest(a: Option<IntegerWidth>, b: Option<IntegerWidth>) -> Option<IntegerWidth> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, None) | (None, a) => a,
+    }
+}
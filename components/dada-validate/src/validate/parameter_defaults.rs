@@ -0,0 +1,128 @@
+//! Default values for function parameters, like the `22` in `fn f(x = 22)`.
+//!
+//! A call may omit any trailing arguments whose parameters have a default;
+//! the interpreter then passes the default value instead. For now, defaults
+//! must be constant expressions, like `-1` or `60 * 60`, so they can be
+//! computed without running any code.
+
+use dada_ir::{
+    code::{syntax, validated},
+    function::Function,
+    parameter::Parameter,
+};
+use dada_parse::prelude::*;
+
+use crate::fold::{self, Folded};
+
+use super::{literal, validator::Validator};
+
+/// Reports defaults that are not valid constants, as well as parameters
+/// without a default that come after a parameter with one.
+pub(crate) fn check(db: &dyn crate::Db, function: Function) {
+    let filename = function.filename(db);
+    let mut first_defaulted: Option<Parameter> = None;
+    for &parameter in function.parameters(db) {
+        match parameter.default(db) {
+            Some(default) => {
+                if let Err((expr, message)) = default_constant(db, default) {
+                    let span = default.spans(db)[expr];
+                    dada_ir::error!(span.in_file(filename), "{}", message).emit(db);
+                }
+                first_defaulted.get_or_insert(parameter);
+            }
+
+            None => {
+                if let Some(defaulted) = first_defaulted {
+                    dada_ir::error!(
+                        parameter.decl_span(db).name_span.in_file(filename),
+                        "parameter `{}` needs a default value",
+                        parameter.name(db).as_str(db),
+                    )
+                    .primary_label("parameters with a default value must come last")
+                    .secondary_label(
+                        defaulted.decl_span(db).name_span.in_file(filename),
+                        format!("`{}` has a default value", defaulted.name(db).as_str(db)),
+                    )
+                    .emit(db);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the value of the default for `parameter`, if it has one.
+/// Returns `None` if the default is invalid (an error is reported by [`check`]).
+pub(crate) fn default_value(
+    db: &dyn crate::Db,
+    parameter: Parameter,
+) -> Option<validated::ExprData> {
+    default_constant(db, parameter.default(db)?).ok()
+}
+
+/// Evaluates the default value `default`, or returns the expression that
+/// cannot be evaluated and why.
+fn default_constant(
+    db: &dyn crate::Db,
+    default: syntax::Tree,
+) -> Result<validated::ExprData, (syntax::Expr, String)> {
+    let data = default.data(db);
+    constant(db, &data.tables, data.root_expr)
+}
+
+fn constant(
+    db: &dyn crate::Db,
+    tables: &syntax::Tables,
+    expr: syntax::Expr,
+) -> Result<validated::ExprData, (syntax::Expr, String)> {
+    let at_expr = |result: Result<validated::ExprData, String>| result.map_err(|m| (expr, m));
+    match &tables[expr] {
+        syntax::ExprData::BooleanLiteral(b) => Ok(validated::ExprData::BooleanLiteral(*b)),
+        syntax::ExprData::IntegerLiteral(integer) => {
+            at_expr(literal::integer_literal(db, *integer))
+        }
+        syntax::ExprData::FloatLiteral(word) => at_expr(literal::float_literal(db, *word)),
+        syntax::ExprData::Parenthesized(inner) => constant(db, tables, *inner),
+
+        syntax::ExprData::Unary(op, rhs) => {
+            let rhs = constant(db, tables, *rhs)?;
+            fold::fold_unary(Validator::validated_op(*op), &rhs)
+                .ok_or_else(|| (expr, format!("cannot apply `{}` to this value", op)))
+        }
+
+        syntax::ExprData::Op(lhs, op, rhs) => {
+            let lhs = constant(db, tables, *lhs)?;
+            let rhs = constant(db, tables, *rhs)?;
+            match (op, lhs, rhs) {
+                (
+                    syntax::op::Op::AndAnd,
+                    validated::ExprData::BooleanLiteral(lhs),
+                    validated::ExprData::BooleanLiteral(rhs),
+                ) => Ok(validated::ExprData::BooleanLiteral(lhs && rhs)),
+                (
+                    syntax::op::Op::OrOr,
+                    validated::ExprData::BooleanLiteral(lhs),
+                    validated::ExprData::BooleanLiteral(rhs),
+                ) => Ok(validated::ExprData::BooleanLiteral(lhs || rhs)),
+                (syntax::op::Op::AndAnd | syntax::op::Op::OrOr, ..) => {
+                    Err((expr, format!("cannot apply `{}` to these values", op)))
+                }
+                (_, lhs, rhs) => {
+                    match fold::fold_op(&lhs, Validator::validated_op(*op), &rhs, None) {
+                        Some(Folded::Literal(value)) => Ok(value),
+                        Some(Folded::Overflow(width)) => Err((
+                            expr,
+                            format!("the default value does not fit in {}", width.description()),
+                        )),
+                        None => Err((expr, format!("cannot apply `{}` to these values", op))),
+                    }
+                }
+            }
+        }
+
+        // FIXME: support other expressions, evaluated each time the function is called
+        _ => Err((
+            expr,
+            "default values must be constant expressions".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,47 @@
+//! Finds recursive functions, i.e., functions that are part of a cycle in
+//! the [call graph](super::call_graph).
+
+use dada_collections::Set;
+use dada_ir::filename::Filename;
+use dada_ir::function::Function;
+
+/// Salsa input: whether to report functions that call themselves.
+///
+/// Defaults to false if not explicitly set.
+#[salsa::memoized(in crate::Jar)]
+pub fn debug_recursion(_db: &dyn crate::Db, _filename: Filename) -> bool {
+    false // default: off
+}
+
+/// True if `function` calls itself, directly or through other functions.
+#[salsa::memoized(in crate::Jar)]
+#[tracing::instrument(level = "debug", skip(db))]
+pub(crate) fn is_recursive(db: &dyn crate::Db, function: Function) -> bool {
+    let mut visited: Set<Function> = Set::default();
+    let mut stack = super::call_graph::callees(db, function).clone();
+    while let Some(callee) = stack.pop() {
+        if callee == function {
+            return true;
+        }
+        if visited.insert(callee) {
+            stack.extend(super::call_graph::callees(db, callee));
+        }
+    }
+    false
+}
+
+/// If [`debug_recursion`] is set, reports each function in `filename` that
+/// calls itself directly.
+#[salsa::memoized(in crate::Jar)]
+pub(crate) fn report_recursion(db: &dyn crate::Db, filename: Filename) {
+    if !debug_recursion(db, filename) {
+        return;
+    }
+
+    for (&function, callees) in super::call_graph::call_graph(db, filename) {
+        if callees.contains(&function) {
+            let name = function.name(db);
+            dada_ir::note!(name.span(db), "function `{}` calls itself", name.as_str(db)).emit(db);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Reports local variables that re-declare a name that is already bound.
+//!
+//! Re-declaring a variable in the same scope is always an error. Shadowing
+//! a variable from an enclosing scope is legal, but since it is sometimes a
+//! mistake it can be reported as a warning by setting [`warn_shadowing`].
+
+use dada_ir::filename::Filename;
+use dada_ir::span::FileSpan;
+use dada_ir::word::Word;
+
+/// Salsa input: whether to warn about variables that shadow a variable
+/// from an enclosing scope.
+///
+/// Defaults to false if not explicitly set.
+#[salsa::memoized(in crate::Jar)]
+pub fn warn_shadowing(_db: &dyn crate::Db, _filename: Filename) -> bool {
+    false // default: off
+}
+
+/// Reports that the variable `name` declared at `span` shadows the variable
+/// declared at `shadowed_span`. If `same_scope` is true, both are declared
+/// in the same scope, which is an error.
+pub(crate) fn report(
+    db: &dyn crate::Db,
+    name: Word,
+    span: FileSpan,
+    shadowed_span: FileSpan,
+    same_scope: bool,
+) {
+    let name = name.as_str(db);
+    if same_scope {
+        dada_ir::error!(span, "`{}` is already declared in this scope", name)
+            .primary_label(format!("`{}` is declared again here", name))
+            .secondary_label(shadowed_span, format!("`{}` was first declared here", name))
+            .emit(db);
+    } else if warn_shadowing(db, span.filename) {
+        dada_ir::warning!(
+            span,
+            "`{}` shadows a variable from an enclosing scope",
+            name
+        )
+        .primary_label(format!(
+            "this declaration of `{}` shadows the outer one",
+            name
+        ))
+        .secondary_label(
+            shadowed_span,
+            format!("the outer `{}` is declared here", name),
+        )
+        .emit(db);
+    }
+}
@@ -0,0 +1,46 @@
+//! Finds the functions marked `@test`, which a test runner calls one by one.
+
+use dada_ir::filename::Filename;
+use dada_ir::function::Function;
+use dada_ir::item::Item;
+use dada_parse::prelude::*;
+
+use super::name_lookup::Definition;
+
+/// Returns the functions in `filename` that are marked `@test`, in the
+/// order they are declared. A test is called without arguments, so a
+/// `@test` function with parameters is reported and left out.
+#[salsa::memoized(in crate::Jar ref)]
+#[allow(clippy::needless_lifetimes)]
+pub(crate) fn test_functions(db: &dyn crate::Db, filename: Filename) -> Vec<Function> {
+    let root_definitions = super::root_definitions(db, filename);
+    let mut tests = vec![];
+    for item in filename.items(db) {
+        let Item::Function(function) = *item else {
+            continue;
+        };
+        let Some(attribute) = function.attribute(db, "test") else {
+            continue;
+        };
+
+        // A function whose name is already taken is ignored by name
+        // resolution (which reports it), so it cannot be a test either.
+        if root_definitions.lookup(item.name(db)) != Some(Definition::Function(function)) {
+            continue;
+        }
+
+        if let Some(parameter) = function.parameters(db).first() {
+            dada_ir::error!(
+                parameter.decl_span(db).name_span.in_file(filename),
+                "`@test` functions cannot have parameters",
+            )
+            .primary_label("tests are called without arguments")
+            .secondary_label(attribute.span, "marked as a test here")
+            .emit(db);
+            continue;
+        }
+
+        tests.push(function);
+    }
+    tests
+}
@@ -0,0 +1,203 @@
+//! Detects code that can never execute because it follows an expression
+//! that always diverges (e.g., a `return`, or a `loop` that is never
+//! broken out of).
+//!
+//! These functions only look at the syntax tables, so that they can be
+//! used (and tested) independently of the validator.
+
+use dada_ir::code::syntax::{
+    self,
+    visit::{walk_expr, Visitor},
+};
+use dada_ir::word::Word;
+
+/// A sequence of expressions that come after an expression that always diverges.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct UnreachableCode<'me> {
+    /// The first expression in the block that always diverges.
+    pub(crate) diverging_expr: syntax::Expr,
+
+    /// All the expressions that follow `diverging_expr` (never empty).
+    pub(crate) unreachable_exprs: &'me [syntax::Expr],
+}
+
+/// Finds the expressions in the block `exprs` that can never execute, if any.
+pub(crate) fn find_unreachable_code<'me>(
+    tables: &syntax::Tables,
+    exprs: &'me [syntax::Expr],
+) -> Option<UnreachableCode<'me>> {
+    let diverging_index = exprs.iter().position(|expr| diverges(tables, *expr))?;
+    let unreachable_exprs = &exprs[diverging_index + 1..];
+
+    // Statements that failed to parse have already been reported.
+    if unreachable_exprs
+        .iter()
+        .all(|expr| matches!(tables[*expr], syntax::ExprData::Error))
+    {
+        return None;
+    }
+    Some(UnreachableCode {
+        diverging_expr: exprs[diverging_index],
+        unreachable_exprs,
+    })
+}
+
+/// True if `expr` can never complete normally, because it (always) reaches
+/// a `return`, `break`, or `continue`, or a `loop` with no `break`.
+pub(crate) fn diverges(tables: &syntax::Tables, expr: syntax::Expr) -> bool {
+    match &tables[expr] {
+        syntax::ExprData::Return(_)
+        | syntax::ExprData::Break(..)
+        | syntax::ExprData::Continue(_) => true,
+
+        syntax::ExprData::Loop(label, body_expr) => !breaks_out(tables, *label, *body_expr),
+
+        syntax::ExprData::Seq(statements, tail) => statements
+            .iter()
+            .chain(tail)
+            .any(|expr| diverges(tables, *expr)),
+        syntax::ExprData::If(_, then_expr, Some(else_expr)) => {
+            diverges(tables, *then_expr) && diverges(tables, *else_expr)
+        }
+        syntax::ExprData::Parenthesized(expr) | syntax::ExprData::Atomic(expr) => {
+            diverges(tables, *expr)
+        }
+        _ => false,
+    }
+}
+
+/// True if `expr` (the body of a loop labeled `label`) may contain a `break`
+/// that exits that loop. An unlabeled `break` within a nested loop exits that
+/// loop instead, but a `break 'label` may exit this loop from any depth.
+/// Breaks within closures do not count.
+fn breaks_out(tables: &syntax::Tables, label: Option<Word>, expr: syntax::Expr) -> bool {
+    let mut finder = BreakFinder {
+        tables,
+        label,
+        nested: false,
+        found: false,
+    };
+    finder.visit_expr(expr);
+    finder.found
+}
+
+struct BreakFinder<'t> {
+    tables: &'t syntax::Tables,
+    label: Option<Word>,
+
+    /// True while visiting the body of a nested loop.
+    nested: bool,
+
+    found: bool,
+}
+
+impl<'t> Visitor<'t> for BreakFinder<'t> {
+    fn tables(&self) -> &'t syntax::Tables {
+        self.tables
+    }
+
+    fn visit_expr(&mut self, expr: syntax::Expr) {
+        let tables = self.tables;
+        match &tables[expr] {
+            syntax::ExprData::Break(None, _) => self.found |= !self.nested,
+            syntax::ExprData::Break(Some(label), _) => self.found |= self.label == Some(*label),
+
+            syntax::ExprData::Loop(label, body_expr) => self.visit_nested_body(*label, *body_expr),
+            syntax::ExprData::While(label, condition_expr, body_expr) => {
+                self.visit_expr(*condition_expr);
+                self.visit_nested_body(*label, *body_expr);
+            }
+            syntax::ExprData::For(label, _, iterable_expr, body_expr) => {
+                self.visit_expr(*iterable_expr);
+                self.visit_nested_body(*label, *body_expr);
+            }
+
+            syntax::ExprData::Closure(..) => {}
+
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+impl BreakFinder<'_> {
+    /// Visits the body of a nested loop labeled `label`, looking for a
+    /// labeled `break` that exits the outer loop.
+    fn visit_nested_body(&mut self, label: Option<Word>, body_expr: syntax::Expr) {
+        // If the nested loop has the same label, it shadows the outer one.
+        if self.label.is_none() || self.label == label {
+            return;
+        }
+
+        let nested = std::mem::replace(&mut self.nested, true);
+        self.visit_expr(body_expr);
+        self.nested = nested;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dada_ir::code::syntax::ExprData;
+
+    #[test]
+    fn code_after_return_is_unreachable() {
+        let mut tables = syntax::Tables::default();
+        let return_expr = tables.add(ExprData::Return(None));
+        let after_expr = tables.add(ExprData::BooleanLiteral(true));
+        let exprs = [return_expr, after_expr];
+        assert_eq!(
+            find_unreachable_code(&tables, &exprs),
+            Some(UnreachableCode {
+                diverging_expr: return_expr,
+                unreachable_exprs: &[after_expr],
+            })
+        );
+    }
+
+    #[test]
+    fn nothing_after_return() {
+        let mut tables = syntax::Tables::default();
+        let before_expr = tables.add(ExprData::BooleanLiteral(true));
+        let return_expr = tables.add(ExprData::Return(None));
+        let exprs = [before_expr, return_expr];
+        assert_eq!(find_unreachable_code(&tables, &exprs), None);
+    }
+
+    #[test]
+    fn parse_errors_after_return_are_not_reported() {
+        let mut tables = syntax::Tables::default();
+        let return_expr = tables.add(ExprData::Return(None));
+        let error_expr = tables.add(ExprData::Error);
+        let exprs = [return_expr, error_expr];
+        assert_eq!(find_unreachable_code(&tables, &exprs), None);
+    }
+
+    #[test]
+    fn loop_without_break_diverges() {
+        let mut tables = syntax::Tables::default();
+        let body_expr = tables.add(ExprData::Seq(vec![], None));
+        let loop_expr = tables.add(ExprData::Loop(None, body_expr));
+        assert!(diverges(&tables, loop_expr));
+    }
+
+    #[test]
+    fn loop_with_break_completes() {
+        let mut tables = syntax::Tables::default();
+        let condition_expr = tables.add(ExprData::BooleanLiteral(true));
+        let break_expr = tables.add(ExprData::Break(None, None));
+        let if_expr = tables.add(ExprData::If(condition_expr, break_expr, None));
+        let body_expr = tables.add(ExprData::Seq(vec![], Some(if_expr)));
+        let loop_expr = tables.add(ExprData::Loop(None, body_expr));
+        assert!(!diverges(&tables, loop_expr));
+    }
+
+    #[test]
+    fn break_from_nested_loop_does_not_count() {
+        let mut tables = syntax::Tables::default();
+        let break_expr = tables.add(ExprData::Break(None, None));
+        let inner_loop_expr = tables.add(ExprData::Loop(None, break_expr));
+        let outer_loop_expr = tables.add(ExprData::Loop(None, inner_loop_expr));
+        assert!(!diverges(&tables, inner_loop_expr));
+        assert!(diverges(&tables, outer_loop_expr));
+    }
+}
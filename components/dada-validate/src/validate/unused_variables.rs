@@ -0,0 +1,69 @@
+//! Warns about local variables that are declared but never read.
+//!
+//! A variable counts as read if any place in the validated tables refers
+//! to it; assigning to it (e.g., `x := 22`) does not count. Variables whose
+//! name begins with `_` are exempt.
+
+use dada_id::prelude::*;
+use dada_ir::code::validated;
+use dada_ir::function::Function;
+use dada_parse::prelude::*;
+
+pub(crate) fn check(
+    db: &dyn crate::Db,
+    function: Function,
+    tree_data: &validated::TreeData,
+    origins: &validated::Origins,
+) {
+    let tables = &tree_data.tables;
+    let num_local_variables = usize::from(tree_data.max_local_variable());
+    let mut read = vec![false; num_local_variables];
+    let mut assignments = vec![0_usize; num_local_variables];
+
+    for place in validated::Place::max_key(tables).iter() {
+        if let validated::PlaceData::LocalVariable(local_variable) = tables[place] {
+            read[usize::from(local_variable)] = true;
+        }
+    }
+
+    for target_place in validated::TargetPlace::max_key(tables).iter() {
+        if let validated::TargetPlaceData::LocalVariable(local_variable) = tables[target_place] {
+            assignments[usize::from(local_variable)] += 1;
+        }
+    }
+
+    let syntax_tree = function.syntax_tree(db);
+    for local_variable in tree_data.max_local_variable().iter() {
+        if read[usize::from(local_variable)] {
+            continue;
+        }
+
+        // Only variables declared by the user are reported; parameters
+        // and compiler-introduced temporaries are not.
+        let decl = match origins[local_variable] {
+            validated::LocalVariableOrigin::LocalVariable(decl) => decl,
+            validated::LocalVariableOrigin::Temporary(_)
+            | validated::LocalVariableOrigin::Parameter(_) => continue,
+        };
+
+        let name = match local_variable.data(tables).name {
+            Some(name) => name.as_str(db),
+            None => continue,
+        };
+        if name.starts_with('_') {
+            continue;
+        }
+
+        let span = syntax_tree.spans(db)[decl]
+            .name_span
+            .in_file(function.filename(db));
+        let message = if assignments[usize::from(local_variable)] > 1 {
+            format!("variable `{}` is assigned but never read", name)
+        } else {
+            format!("unused variable `{}`", name)
+        };
+        dada_ir::warning!(span, "{}", message)
+            .primary_label(format!("if this is intentional, name it `_{}`", name))
+            .emit(db);
+    }
+}
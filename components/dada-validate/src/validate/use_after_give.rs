@@ -0,0 +1,261 @@
+//! Reports uses of a variable after its value was explicitly given away.
+//!
+//! Only variables declared `my` are tracked, since giving from them always
+//! moves the value (giving from an `our` or leased variable copies or
+//! subleases instead). Only explicit `x.give` expressions count as gives;
+//! reassigning the variable makes it usable again. We walk the validated
+//! tree in evaluation order, like [`super::definite_assignment`].
+
+use dada_id::prelude::*;
+use dada_ir::code::{syntax, validated};
+use dada_ir::function::Function;
+use dada_ir::storage::Specifier;
+use dada_parse::prelude::*;
+
+pub(crate) fn check(
+    db: &dyn crate::Db,
+    function: Function,
+    tree_data: &validated::TreeData,
+    origins: &validated::Origins,
+) {
+    let num_local_variables = usize::from(tree_data.max_local_variable());
+    let mut checker = Checker {
+        db,
+        function,
+        tables: &tree_data.tables,
+        origins,
+        given: Given {
+            variables: vec![None; num_local_variables],
+        },
+    };
+    checker.check_expr(tree_data.root_expr);
+}
+
+/// For each local variable, the `x.give` expression that may have given
+/// away its value, if any.
+#[derive(Clone)]
+struct Given {
+    variables: Vec<Option<validated::Expr>>,
+}
+
+impl Given {
+    fn get(&self, local_variable: validated::LocalVariable) -> Option<validated::Expr> {
+        self.variables[usize::from(local_variable)]
+    }
+
+    fn insert(&mut self, local_variable: validated::LocalVariable, give_expr: validated::Expr) {
+        self.variables[usize::from(local_variable)] = Some(give_expr);
+    }
+
+    fn remove(&mut self, local_variable: validated::LocalVariable) {
+        self.variables[usize::from(local_variable)] = None;
+    }
+
+    /// After a `return`, `break`, or `continue`, the code is unreachable
+    /// and nothing has been given.
+    fn unreachable(&mut self) {
+        self.variables.iter_mut().for_each(|v| *v = None);
+    }
+
+    /// Keeps the variables given on either incoming path.
+    fn union(&mut self, other: &Given) {
+        for (v, o) in self.variables.iter_mut().zip(&other.variables) {
+            if v.is_none() {
+                *v = *o;
+            }
+        }
+    }
+}
+
+struct Checker<'me> {
+    db: &'me dyn crate::Db,
+    function: Function,
+    tables: &'me validated::Tables,
+    origins: &'me validated::Origins,
+    given: Given,
+}
+
+impl Checker<'_> {
+    fn check_expr(&mut self, expr: validated::Expr) {
+        match expr.data(self.tables) {
+            validated::ExprData::BooleanLiteral(_)
+            | validated::ExprData::SignedIntegerLiteral(_)
+            | validated::ExprData::UnsignedIntegerLiteral(_)
+            | validated::ExprData::IntegerLiteral(_)
+            | validated::ExprData::FloatLiteral(_)
+            | validated::ExprData::CharLiteral(_)
+            | validated::ExprData::StringLiteral(_)
+            | validated::ExprData::Error => {}
+
+            validated::ExprData::Concatenate(exprs)
+            | validated::ExprData::Tuple(exprs)
+            | validated::ExprData::List(exprs)
+            | validated::ExprData::Seq(exprs) => {
+                for expr in exprs {
+                    self.check_expr(*expr);
+                }
+            }
+
+            validated::ExprData::Await(expr)
+            | validated::ExprData::Share(expr)
+            | validated::ExprData::Atomic(expr)
+            | validated::ExprData::Unary(_, expr)
+            | validated::ExprData::Declare(_, expr) => self.check_expr(*expr),
+
+            validated::ExprData::Call(func_expr, named_exprs) => {
+                self.check_expr(*func_expr);
+                for named_expr in named_exprs {
+                    self.check_expr(named_expr.data(self.tables).expr);
+                }
+            }
+
+            validated::ExprData::Reserve(place)
+            | validated::ExprData::Lease(place)
+            | validated::ExprData::Shlease(place) => self.check_place(*place),
+
+            validated::ExprData::Give(place) => {
+                self.check_place(*place);
+                if let Some(local_variable) = self.explicitly_given_variable(expr, *place) {
+                    self.given.insert(local_variable, expr);
+                }
+            }
+
+            validated::ExprData::Range(start_expr, end_expr, _) => {
+                for expr in start_expr.iter().chain(end_expr) {
+                    self.check_expr(*expr);
+                }
+            }
+
+            validated::ExprData::Closure(_, captured_places, body_expr) => {
+                for place in captured_places {
+                    self.check_place(*place);
+                }
+
+                // FIXME: the body may run any number of times (or never), so we
+                // only check it on its own and ignore what it gives.
+                let given = self.given.clone();
+                self.check_expr(*body_expr);
+                self.given = given;
+            }
+
+            validated::ExprData::If(condition_expr, then_expr, else_expr) => {
+                self.check_expr(*condition_expr);
+                let given = self.given.clone();
+                self.check_expr(*then_expr);
+                let then_given = std::mem::replace(&mut self.given, given);
+                self.check_expr(*else_expr);
+                self.given.union(&then_given);
+            }
+
+            validated::ExprData::Loop(body_expr) => {
+                // FIXME: a value given in one iteration and used in the next
+                // is not reported; that requires iterating to a fixed point.
+                let given = self.given.clone();
+                self.check_expr(*body_expr);
+                self.given.union(&given);
+            }
+
+            validated::ExprData::Break {
+                from_expr: _,
+                with_value,
+            } => {
+                self.check_expr(*with_value);
+                self.given.unreachable();
+            }
+
+            validated::ExprData::Continue(_) => self.given.unreachable(),
+
+            validated::ExprData::Return(with_value) => {
+                self.check_expr(*with_value);
+                self.given.unreachable();
+            }
+
+            validated::ExprData::Op(lhs_expr, _, rhs_expr) => {
+                self.check_expr(*lhs_expr);
+                self.check_expr(*rhs_expr);
+            }
+
+            validated::ExprData::AssignTemporary(local_variable, expr) => {
+                self.check_expr(*expr);
+                self.given.remove(*local_variable);
+            }
+
+            validated::ExprData::AssignFromPlace(target_place, place) => {
+                self.check_place(*place);
+                match target_place.data(self.tables) {
+                    validated::TargetPlaceData::LocalVariable(local_variable) => {
+                        self.given.remove(*local_variable);
+                    }
+                    validated::TargetPlaceData::Dot(owner_place, _) => {
+                        self.check_place(*owner_place);
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_place(&mut self, place: validated::Place) {
+        match place.data(self.tables) {
+            validated::PlaceData::LocalVariable(local_variable) => {
+                if let Some(give_expr) = self.given.get(*local_variable) {
+                    self.report_use_after_give(place, *local_variable, give_expr);
+
+                    // Only report the first use.
+                    self.given.remove(*local_variable);
+                }
+            }
+            validated::PlaceData::Function(_)
+            | validated::PlaceData::Intrinsic(_)
+            | validated::PlaceData::Class(_) => {}
+            validated::PlaceData::Dot(owner_place, _) => self.check_place(*owner_place),
+            validated::PlaceData::Index(owner_place, index_place) => {
+                self.check_place(*index_place);
+                self.check_place(*owner_place);
+            }
+        }
+    }
+
+    /// If `give_expr` is an explicit `x.give` of a variable `x` declared `my`,
+    /// returns `x`. Values are also given implicitly (e.g., when passed as an
+    /// argument), but those are not tracked.
+    fn explicitly_given_variable(
+        &self,
+        give_expr: validated::Expr,
+        place: validated::Place,
+    ) -> Option<validated::LocalVariable> {
+        let validated::PlaceData::LocalVariable(local_variable) = place.data(self.tables) else {
+            return None;
+        };
+        let specifier = local_variable.data(self.tables).specifier?;
+        if specifier.specifier(self.db) != Specifier::My {
+            return None;
+        }
+
+        let origin = self.origins[give_expr];
+        let syntax_tree = self.function.syntax_tree(self.db);
+        match syntax_tree.data(self.db).tables[origin.syntax_expr] {
+            syntax::ExprData::Give(_) => Some(*local_variable),
+            _ => None,
+        }
+    }
+
+    fn report_use_after_give(
+        &self,
+        place: validated::Place,
+        local_variable: validated::LocalVariable,
+        give_expr: validated::Expr,
+    ) {
+        let name = match local_variable.data(self.tables).name {
+            Some(name) => name.as_str(self.db),
+            None => return,
+        };
+        let spans = self.function.spans(self.db);
+        let filename = self.function.filename(self.db);
+        let use_span = spans[self.origins[place].syntax_expr].in_file(filename);
+        let give_span = spans[self.origins[give_expr].syntax_expr].in_file(filename);
+        dada_ir::error!(use_span, "`{}` is used after it was given", name)
+            .primary_label(format!("`{}` is used here", name))
+            .secondary_label(give_span, format!("`{}` was given here", name))
+            .emit(self.db);
+    }
+}
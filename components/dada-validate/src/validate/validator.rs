@@ -1,12 +1,16 @@
+use dada_collections::Map;
 use dada_id::prelude::*;
+use dada_ir::class::SELF_PARAMETER;
 use dada_ir::code::syntax;
 use dada_ir::code::syntax::LocalVariableDecl;
 use dada_ir::code::validated;
 use dada_ir::code::validated::ExprOrigin;
 use dada_ir::code::validated::LocalVariableOrigin;
+use dada_ir::code::validated::NamedExprOrigin;
 use dada_ir::diagnostic::ErrorReported;
 use dada_ir::effect::Effect;
 use dada_ir::function::Function;
+use dada_ir::item::Item;
 use dada_ir::kw::Keyword;
 use dada_ir::origin_table::HasOriginIn;
 use dada_ir::origin_table::PushOriginIn;
@@ -14,16 +18,23 @@ use dada_ir::return_type::ReturnTypeKind;
 use dada_ir::span::FileSpan;
 use dada_ir::span::Span;
 use dada_ir::storage::Atomic;
+use dada_ir::storage::Mutable;
 use dada_ir::storage::Specifier;
+use dada_ir::storage::Weak;
+use dada_ir::word::SpannedOptionalWord;
+use dada_ir::word::SpannedWord;
 use dada_ir::word::Word;
 use dada_lex::prelude::*;
 use dada_parse::prelude::*;
 use std::rc::Rc;
-use std::str::FromStr;
 
+use super::literal;
 use super::name_lookup::Definition;
 use super::name_lookup::Scope;
+use super::unreachable;
 
+mod for_loop;
+mod operator_methods;
 mod string_literals;
 
 pub(crate) struct Validator<'me> {
@@ -33,13 +44,35 @@ pub(crate) struct Validator<'me> {
     syntax_tree: &'me syntax::TreeData,
     tables: &'me mut validated::Tables,
     origins: &'me mut validated::Origins,
-    loop_stack: Vec<validated::Expr>,
+    loop_stack: Vec<LoopScope>,
+    closure: Option<ClosureScope>,
     scope: Scope<'me>,
     effect: Effect,
     effect_span: Rc<dyn Fn(&Validator<'_>) -> FileSpan + 'me>,
     synthesized: bool,
 }
 
+/// A loop that encloses the expression being validated.
+#[derive(Copy, Clone, Debug)]
+struct LoopScope {
+    /// The label on the loop (`'a: loop { ... }`), if any.
+    label: Option<Word>,
+
+    /// The (validated) loop expression that `break` and `continue` target.
+    expr: validated::Expr,
+
+    /// The syntax expression for the loop.
+    syntax_expr: syntax::Expr,
+}
+
+/// A closure whose body encloses the expression being validated.
+#[derive(Copy, Clone, Debug)]
+struct ClosureScope {
+    /// Local variables that come before this one were declared outside
+    /// of the closure; the closure captures those that it refers to.
+    first_local_variable: validated::LocalVariable,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum ExprMode {
     Specifier(Specifier),
@@ -74,6 +107,7 @@ impl<'me> Validator<'me> {
             tables,
             origins,
             loop_stack: vec![],
+            closure: None,
             scope,
             effect: function.effect(db),
             effect_span: Rc::new(move |_| function.effect_span(db)),
@@ -90,6 +124,7 @@ impl<'me> Validator<'me> {
             tables: self.tables,
             origins: self.origins,
             loop_stack: self.loop_stack.clone(),
+            closure: self.closure,
             scope: self.scope.subscope(),
             effect: self.effect,
             effect_span: self.effect_span.clone(),
@@ -101,11 +136,159 @@ impl<'me> Validator<'me> {
         (self.effect_span)(self)
     }
 
-    fn with_loop_expr(mut self, e: validated::Expr) -> Self {
-        self.loop_stack.push(e);
+    /// Pushes the loop `e` (validated from `syntax_expr`) onto the loop stack.
+    /// Warns if its label is already used by an enclosing loop.
+    fn with_loop_expr(
+        mut self,
+        syntax_expr: syntax::Expr,
+        label: Option<Word>,
+        e: validated::Expr,
+    ) -> Self {
+        if let Some(label) = label {
+            if let Some(outer) = self
+                .loop_stack
+                .iter()
+                .rev()
+                .find(|loop_scope| loop_scope.label == Some(label))
+            {
+                dada_ir::warning!(
+                    self.label_span(syntax_expr, label),
+                    "label `'{}` shadows the label of an enclosing loop",
+                    label.as_str(self.db),
+                )
+                .primary_label("`break` and `continue` will refer to this loop")
+                .secondary_label(
+                    self.label_span(outer.syntax_expr, label),
+                    "the enclosing loop is labeled here",
+                )
+                .emit(self.db);
+            }
+        }
+
+        self.loop_stack.push(LoopScope {
+            label,
+            expr: e,
+            syntax_expr,
+        });
         self
     }
 
+    /// The span of `'label` at the start of the loop `loop_expr`.
+    fn label_span(&self, loop_expr: syntax::Expr, label: Word) -> FileSpan {
+        let span = self.span(loop_expr);
+        FileSpan {
+            end: span.start + 1 + label.as_str(self.db).len(),
+            ..span
+        }
+    }
+
+    /// Finds the loop targeted by a `break` or `continue` with the given label
+    /// (or the innermost loop, if there is no label). Reports an error if there
+    /// is no such loop.
+    fn loop_target(
+        &self,
+        expr: syntax::Expr,
+        keyword: Keyword,
+        label: Option<Word>,
+    ) -> Result<validated::Expr, ErrorReported> {
+        let loop_scope = match label {
+            None => self.loop_stack.last(),
+            Some(label) => self
+                .loop_stack
+                .iter()
+                .rev()
+                .find(|loop_scope| loop_scope.label == Some(label)),
+        };
+
+        match (loop_scope, label) {
+            (Some(loop_scope), _) => Ok(loop_scope.expr),
+            (None, None) => Err(dada_ir::error!(
+                self.span(expr),
+                "{} can only be used inside of a loop",
+                keyword,
+            )
+            .emit(self.db)),
+            (None, Some(label)) => Err(dada_ir::error!(
+                self.span(expr),
+                "no enclosing loop is labeled `'{}`",
+                label.as_str(self.db),
+            )
+            .emit(self.db)),
+        }
+    }
+
+    /// True if `lv` is declared outside of the closure being validated (if any),
+    /// and hence captured by it.
+    fn is_captured(&self, lv: validated::LocalVariable) -> bool {
+        match self.closure {
+            Some(closure) => lv < closure.first_local_variable,
+            None => false,
+        }
+    }
+
+    /// In a method, reports an error if `dot_expr` accesses a field of `self`
+    /// (`owner_expr`) that the class does not declare.
+    fn check_self_field(
+        &self,
+        dot_expr: syntax::Expr,
+        owner_expr: syntax::Expr,
+        field_name: Word,
+    ) -> Result<(), ErrorReported> {
+        let Some(class) = self.function.class(self.db) else {
+            return Ok(());
+        };
+        let syntax::ExprData::Id(name) = owner_expr.data(self.syntax_tables()) else {
+            return Ok(());
+        };
+        if name.as_str(self.db) != SELF_PARAMETER {
+            return Ok(());
+        }
+
+        // A local variable named `self` shadows the parameter.
+        match self.scope.lookup(*name) {
+            Some(Definition::LocalVariable(lv))
+                if matches!(self.origins[lv], LocalVariableOrigin::Parameter(_)) => {}
+            _ => return Ok(()),
+        }
+
+        if class
+            .fields(self.db)
+            .iter()
+            .any(|field| field.name(self.db) == field_name)
+        {
+            return Ok(());
+        }
+
+        Err(dada_ir::error!(
+            self.span(dot_expr),
+            "class `{}` has no field named `{}`",
+            class.name(self.db).as_str(self.db),
+            field_name.as_str(self.db),
+        )
+        .emit(self.db))
+    }
+
+    /// Reports an error if `lv` was declared `atomic` but `expr` accesses it
+    /// outside of an atomic section.
+    fn check_atomic_access(&self, expr: syntax::Expr, lv: validated::LocalVariable) {
+        if lv.data(self.tables).atomic == Atomic::No || self.effect.is_atomic() {
+            return;
+        }
+
+        let mut diagnostic = dada_ir::error!(
+            self.span(expr),
+            "atomic variables can only be accessed inside of an atomic section",
+        );
+        match self.origins[lv] {
+            LocalVariableOrigin::LocalVariable(decl) | LocalVariableOrigin::Parameter(decl) => {
+                let atomic_span = self.syntax_tree_entity.spans(self.db)[decl].atomic_span;
+                diagnostic = diagnostic.secondary_label(atomic_span, "declared `atomic` here");
+            }
+            LocalVariableOrigin::Temporary(_) => {}
+        }
+        diagnostic.emit(self.db);
+    }
+
     pub(crate) fn with_effect(
         mut self,
         effect: Effect,
@@ -144,8 +327,7 @@ impl<'me> Validator<'me> {
     }
 
     fn span(&self, e: impl HasOriginIn<syntax::Spans, Origin = Span>) -> FileSpan {
-        self.function.syntax_tree(self.db).spans(self.db)[e]
-            .in_file(self.function.filename(self.db))
+        self.function.spans(self.db)[e].in_file(self.function.filename(self.db))
     }
 
     fn empty_tuple(&mut self, origin: syntax::Expr) -> validated::Expr {
@@ -153,6 +335,29 @@ impl<'me> Validator<'me> {
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
+    /// Brings the generic type parameters of the function into scope,
+    /// reporting any name that is declared more than once.
+    pub(crate) fn validate_generics(&mut self, generics: &[SpannedWord]) {
+        for (index, &generic) in generics.iter().enumerate() {
+            let name = generic.word(self.db);
+            let previous = generics[..index]
+                .iter()
+                .find(|previous| previous.word(self.db) == name);
+            if let Some(previous) = previous {
+                dada_ir::error!(
+                    generic.span(self.db),
+                    "already have a type parameter named `{}`",
+                    name.as_str(self.db),
+                )
+                .primary_label("ignoring this type parameter for now")
+                .secondary_label(previous.span(self.db), "the type parameter is here")
+                .emit(self.db);
+            } else {
+                self.scope.insert_type_parameter(name, generic);
+            }
+        }
+    }
+
     pub(crate) fn validate_parameter(&mut self, decl: LocalVariableDecl) {
         let decl_data = decl.data(self.syntax_tables());
         let local_variable = self.add(
@@ -160,16 +365,53 @@ impl<'me> Validator<'me> {
                 name: Some(decl_data.name),
                 specifier: Some(decl_data.specifier),
                 atomic: decl_data.atomic,
+                mutable: decl_data.mutable,
             },
             validated::LocalVariableOrigin::Parameter(decl),
         );
-        self.scope.insert(decl_data.name, local_variable);
+        self.insert_local_variable(decl, local_variable);
+    }
+
+    /// Brings `local_variable`, declared by `decl`, into scope, reporting if
+    /// it shadows another local variable (see [`super::shadowing`]).
+    fn insert_local_variable(
+        &mut self,
+        decl: LocalVariableDecl,
+        local_variable: validated::LocalVariable,
+    ) {
+        let name = decl.data(self.syntax_tables()).name;
+        let same_scope = match self.scope.lookup(name) {
+            Some(Definition::LocalVariable(lv)) => self.scope.declared_here(lv),
+            _ => false,
+        };
+        let shadowed = match self.scope.insert(name, local_variable) {
+            Some(Definition::LocalVariable(lv)) => lv,
+            _ => return,
+        };
+        match self.origins[shadowed] {
+            LocalVariableOrigin::LocalVariable(shadowed_decl)
+            | LocalVariableOrigin::Parameter(shadowed_decl) => {
+                let spans = self.syntax_tree_entity.spans(self.db);
+                let filename = self.function.filename(self.db);
+                super::shadowing::report(
+                    self.db,
+                    name,
+                    spans[decl].name_span.in_file(filename),
+                    spans[shadowed_decl].name_span.in_file(filename),
+                    same_scope,
+                );
+            }
+            LocalVariableOrigin::Temporary(_) => {}
+        }
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn give_validated_root_expr(&mut self, expr: syntax::Expr) -> validated::Expr {
+        if let syntax::ExprData::Seq(_, Some(tail_expr)) = expr.data(self.syntax_tables()) {
+            self.check_tuple_arity(*tail_expr);
+        }
         let validated_expr = self.give_validated_expr(expr);
-        if self.function.return_type(self.db).kind(self.db) == ReturnTypeKind::Value {
+        if self.function.return_type(self.db).kind(self.db).returns_value() {
             if let validated::ExprData::Seq(exprs) = validated_expr.data(self.tables) {
                 if exprs.is_empty() {
                     dada_ir::error!(
@@ -192,6 +434,82 @@ impl<'me> Validator<'me> {
         validated_expr
     }
 
+    /// If the function returns a tuple type like `(Int, Int)` and the returned
+    /// `value_expr` is a tuple literal, checks that it has the declared number of elements.
+    fn check_tuple_arity(&self, value_expr: syntax::Expr) {
+        let return_type = self.function.return_type(self.db);
+        let Some(arity) = return_type.tuple_arity(self.db) else {
+            return;
+        };
+        let syntax::ExprData::Tuple(elements) = value_expr.data(self.syntax_tables()) else {
+            return;
+        };
+        if elements.len() != arity {
+            dada_ir::error!(
+                self.span(value_expr),
+                "expected a tuple with {} elements, found {}",
+                arity,
+                elements.len(),
+            )
+            .primary_label(format!("this tuple has {} elements", elements.len()))
+            .secondary_label(
+                return_type.span(self.db),
+                format!("the return type has {} elements", arity),
+            )
+            .emit(self.db);
+        }
+    }
+
+    /// Inside a `const` function, reports an error if `assign_expr` assigns to
+    /// `lhs_expr` when that may modify state outside the function. Assigning
+    /// to a local variable is fine, but the object owning a field may be
+    /// reachable from the caller.
+    fn check_const_assignment(&self, assign_expr: syntax::Expr, lhs_expr: syntax::Expr) {
+        if !self.effect.is_const() {
+            return;
+        }
+        if let syntax::ExprData::Id(_) = lhs_expr.data(self.syntax_tables()) {
+            return;
+        }
+        dada_ir::error!(
+            self.span(assign_expr),
+            "`const` functions cannot modify state outside the function",
+        )
+        .primary_label("assignment is here")
+        .secondary_label(self.effect_span(), "fn declared `const`")
+        .emit(self.db);
+    }
+
+    /// Reports an error if `assign_expr` assigns to a local variable that
+    /// was declared `shared`.
+    fn check_shared_assignment(&self, assign_expr: syntax::Expr, mut lhs_expr: syntax::Expr) {
+        while let syntax::ExprData::Parenthesized(expr) = lhs_expr.data(self.syntax_tables()) {
+            lhs_expr = *expr;
+        }
+        let syntax::ExprData::Id(name) = lhs_expr.data(self.syntax_tables()) else {
+            return;
+        };
+        let Some(Definition::LocalVariable(lv)) = self.scope.lookup(*name) else {
+            return;
+        };
+        if self.tables[lv].mutable == Mutable::Yes {
+            return;
+        }
+        let LocalVariableOrigin::LocalVariable(decl) = self.origins[lv] else {
+            return;
+        };
+        let name = name.as_str(self.db);
+        let name_span = self.syntax_tree_entity.spans(self.db)[decl].name_span;
+        dada_ir::error!(
+            self.op_span(assign_expr),
+            "cannot assign to `{}`, which is declared `shared`",
+            name,
+        )
+        .primary_label("assignment is here")
+        .secondary_label(name_span, format!("`{}` is declared `shared` here", name))
+        .emit(self.db);
+    }
+
     #[tracing::instrument(level = "debug", skip(self, expr))]
     fn give_validated_expr(&mut self, expr: syntax::Expr) -> validated::Expr {
         self.validate_expr_in_mode(expr, ExprMode::give())
@@ -205,7 +523,7 @@ impl<'me> Validator<'me> {
     fn validate_expr_in_mode(&mut self, expr: syntax::Expr, mode: ExprMode) -> validated::Expr {
         tracing::trace!("expr.data = {:?}", expr.data(self.syntax_tables()));
         match expr.data(self.syntax_tables()) {
-            syntax::ExprData::Dot(..) | syntax::ExprData::Id(_) => {
+            syntax::ExprData::Dot(..) | syntax::ExprData::Index(..) | syntax::ExprData::Id(_) => {
                 let place = self.validate_expr_as_place(expr);
                 self.place_to_expr(place, expr.synthesized(), mode)
             }
@@ -214,78 +532,29 @@ impl<'me> Validator<'me> {
                 self.add(validated::ExprData::BooleanLiteral(*b), expr)
             }
 
-            syntax::ExprData::IntegerLiteral(w, suffix) => {
-                let raw_str = w.as_str(self.db);
-                let without_underscore: String = raw_str.chars().filter(|&c| c != '_').collect();
-                let parse_error = |this: &mut Validator, e| {
-                    dada_ir::error!(this.span(expr), "{}", e,).emit(this.db);
-                    this.add(validated::ExprData::Error, expr)
-                };
-                match suffix {
-                    Some(suffix) => {
-                        let suffix_str = suffix.as_str(self.db);
-                        match suffix_str {
-                            "u" => match u64::from_str(&without_underscore) {
-                                Ok(v) => {
-                                    self.add(validated::ExprData::UnsignedIntegerLiteral(v), expr)
-                                }
-                                Err(e) => parse_error(
-                                    self,
-                                    format!(
-                                        "`{}` is not a valid integer: {}",
-                                        &without_underscore, e
-                                    ),
-                                ),
-                            },
-                            "i" => match i64::from_str(&without_underscore) {
-                                Ok(v) => {
-                                    self.add(validated::ExprData::SignedIntegerLiteral(v), expr)
-                                }
-                                Err(e) => parse_error(
-                                    self,
-                                    format!(
-                                        "`{}` is not a valid integer: {}",
-                                        &without_underscore, e
-                                    ),
-                                ),
-                            },
-                            _ => parse_error(
-                                self,
-                                format!("`{}` is not a valid integer suffxi", suffix_str),
-                            ),
-                        }
+            syntax::ExprData::IntegerLiteral(integer) => {
+                match literal::integer_literal(self.db, *integer) {
+                    Ok(data) => self.add(data, expr),
+                    Err(message) => {
+                        dada_ir::error!(self.span(expr), "{}", message).emit(self.db);
+                        self.add(validated::ExprData::Error, expr)
                     }
-                    None => match u64::from_str(&without_underscore) {
-                        Ok(v) => self.add(validated::ExprData::IntegerLiteral(v), expr),
-                        Err(e) => parse_error(
-                            self,
-                            format!("`{}` is not a valid integer: {}", &without_underscore, e),
-                        ),
-                    },
                 }
             }
 
-            syntax::ExprData::FloatLiteral(w_int, w_frac) => {
-                let raw_int_str = w_int.as_str(self.db);
-                let raw_frac_str = w_frac.as_str(self.db);
-                let int_chars = raw_int_str.chars();
-                let frac_chars = raw_frac_str.chars();
-                let all_chars = int_chars.chain(Some('.')).chain(frac_chars);
-                let all_chars = all_chars.filter(|&c| c != '_');
-                let full_str: String = all_chars.collect();
-                match f64::from_str(&full_str) {
-                    Ok(v) => self.add(validated::ExprData::FloatLiteral(eq_float::F64(v)), expr),
-                    Err(e) => {
-                        dada_ir::error!(
-                            self.span(expr),
-                            "`{}.{}` is not a valid float: {}",
-                            w_int.as_str(self.db),
-                            w_frac.as_str(self.db),
-                            e,
-                        )
-                        .emit(self.db);
-                        self.add(validated::ExprData::Error, expr)
-                    }
+            syntax::ExprData::FloatLiteral(w) => match literal::float_literal(self.db, *w) {
+                Ok(data) => self.add(data, expr),
+                Err(message) => {
+                    dada_ir::error!(self.span(expr), "{}", message).emit(self.db);
+                    self.add(validated::ExprData::Error, expr)
+                }
+            },
+
+            syntax::ExprData::CharLiteral(word) => {
+                let word_str = word.as_str(self.db);
+                match self.char_literal(expr, word_str) {
+                    Some(ch) => self.add(validated::ExprData::CharLiteral(ch), expr),
+                    None => self.add(validated::ExprData::Error, expr),
                 }
             }
 
@@ -295,15 +564,31 @@ impl<'me> Validator<'me> {
                 // where no margin stripping is required, that code invokes this function,
                 // and so our job is only to deal with escape sequences.
                 let word_str = word.as_str(self.db);
-                let escaped = self.support_escape(expr, word_str);
+                let escaped = self.support_escape(word_str);
                 let word = Word::from(self.db, escaped);
                 self.add(validated::ExprData::StringLiteral(word), expr)
             }
 
             syntax::ExprData::Await(future_expr) => {
-                if !self.effect.permits_await() {
+                if self.closure.is_some() {
+                    // Closures cannot be declared `async`, so their bodies
+                    // can never await, even within an async function.
+                    let await_span = self.span(expr).trailing_keyword(self.db, Keyword::Await);
+                    dada_ir::error!(await_span, "await is not permitted inside closures")
+                        .primary_label("await is here")
+                        .emit(self.db);
+                } else if !self.effect.permits_await() {
                     let await_span = self.span(expr).trailing_keyword(self.db, Keyword::Await);
                     match self.effect {
+                        Effect::Const => {
+                            dada_ir::error!(
+                                await_span,
+                                "await is not permitted inside `const` functions",
+                            )
+                            .primary_label("await is here")
+                            .secondary_label(self.effect_span(), "fn declared `const`")
+                            .emit(self.db);
+                        }
                         Effect::Atomic => {
                             dada_ir::error!(
                                 await_span,
@@ -320,9 +605,10 @@ impl<'me> Validator<'me> {
                             )
                             .primary_label("await is here")
                             .secondary_label(self.effect_span(), "fn not declared `async`")
+                            .suggestion(self.effect_span(), "async fn")
                             .emit(self.db);
                         }
-                        Effect::Async => {
+                        Effect::Async | Effect::Generator => {
                             unreachable!();
                         }
                     }
@@ -332,14 +618,75 @@ impl<'me> Validator<'me> {
                 self.add(validated::ExprData::Await(validated_future_expr), expr)
             }
 
+            syntax::ExprData::Yield(value_expr) => {
+                let yield_span = self.span(expr).leading_keyword(self.db, Keyword::Yield);
+                if self.closure.is_some() {
+                    dada_ir::error!(yield_span, "yield is not permitted inside closures")
+                        .primary_label("yield is here")
+                        .emit(self.db);
+                } else if !self.effect.permits_yield() {
+                    match self.effect {
+                        Effect::Atomic => {
+                            dada_ir::error!(
+                                yield_span,
+                                "yield is not permitted inside atomic sections",
+                            )
+                            .primary_label("yield is here")
+                            .secondary_label(self.effect_span(), "atomic section entered here")
+                            .emit(self.db);
+                        }
+                        Effect::Default => {
+                            dada_ir::error!(
+                                yield_span,
+                                "yield is not permitted outside of generator functions",
+                            )
+                            .primary_label("yield is here")
+                            .secondary_label(self.effect_span(), "fn not declared `gen`")
+                            .suggestion(self.effect_span(), "gen fn")
+                            .emit(self.db);
+                        }
+                        Effect::Const | Effect::Async => {
+                            dada_ir::error!(
+                                yield_span,
+                                "yield is not permitted outside of generator functions",
+                            )
+                            .primary_label("yield is here")
+                            .secondary_label(self.effect_span(), "fn not declared `gen`")
+                            .emit(self.db);
+                        }
+                        Effect::Generator => {
+                            unreachable!();
+                        }
+                    }
+                }
+
+                // FIXME: there is no generator protocol yet, so generator functions
+                // cannot be called and the yielded value goes nowhere.
+                if let Some(value_expr) = value_expr {
+                    let _validated_value_expr = self.give_validated_expr(*value_expr);
+                }
+                self.add(validated::ExprData::Error, expr)
+            }
+
             syntax::ExprData::Call(func_expr, named_exprs) => {
                 let validated_func_expr = self.reserve_validated_expr(*func_expr);
                 let validated_named_exprs = self.validate_named_exprs(named_exprs);
+                let mut seen: Map<Word, SpannedOptionalWord> = Map::default();
                 let mut name_required = false;
                 for named_expr in &validated_named_exprs {
                     let name = named_expr.data(self.tables).name;
-                    if name.word(self.db).is_some() {
+                    if let Some(word) = name.word(self.db) {
                         name_required = true;
+                        if let Some(previous_name) = seen.insert(word, name) {
+                            dada_ir::error!(
+                                name.span(self.db),
+                                "argument `{}` is specified more than once",
+                                word.as_str(self.db),
+                            )
+                            .primary_label("duplicate argument")
+                            .secondary_label(previous_name.span(self.db), "first specified here")
+                            .emit(self.db);
+                        }
                     } else if name_required {
                         dada_ir::error!(name.span(self.db), "parameter name required",)
                             .primary_label("parameter name required here")
@@ -353,20 +700,28 @@ impl<'me> Validator<'me> {
                 )
             }
 
+            syntax::ExprData::ClassLiteral(class_name, named_exprs) => {
+                self.validate_class_literal(expr, *class_name, named_exprs)
+            }
+
             syntax::ExprData::Share(target_expr) => {
+                self.check_permission_target(*target_expr, Keyword::Share);
                 let validated_target_expr = self.give_validated_expr(*target_expr);
                 self.add(validated::ExprData::Share(validated_target_expr), expr)
             }
 
             syntax::ExprData::Lease(target_expr) => {
+                self.check_permission_target(*target_expr, Keyword::Lease);
                 self.validate_permission_expr(expr, *target_expr, validated::ExprData::Lease)
             }
 
             syntax::ExprData::Shlease(target_expr) => {
+                self.check_permission_target(*target_expr, Keyword::Shlease);
                 self.validate_permission_expr(expr, *target_expr, validated::ExprData::Shlease)
             }
 
             syntax::ExprData::Give(target_expr) => {
+                self.check_permission_target(*target_expr, Keyword::Give);
                 if self.is_place_expression(*target_expr) {
                     self.validate_permission_expr(expr, *target_expr, validated::ExprData::Give)
                 } else {
@@ -375,23 +730,45 @@ impl<'me> Validator<'me> {
             }
 
             syntax::ExprData::Var(decl, initializer_expr) => {
-                let decl_data = decl.data(self.syntax_tables());
-                let local_variable = self.add(
-                    validated::LocalVariableData {
-                        name: Some(decl_data.name),
-                        specifier: Some(decl_data.specifier),
-                        atomic: decl_data.atomic,
-                    },
-                    validated::LocalVariableOrigin::LocalVariable(*decl),
-                );
-                self.scope.insert(decl_data.name, local_variable);
+                self.validate_var(expr, *decl, *initializer_expr)
+            }
 
-                let target_place = self.add(
-                    validated::TargetPlaceData::LocalVariable(local_variable),
-                    expr.synthesized(),
-                );
+            syntax::ExprData::VarElse(decl, initializer_expr, else_expr) => {
+                // The `else` block runs when the binding fails, so the variable is
+                // not in scope there, only in the code that follows. The only
+                // pattern for now is the variable name, which fails to bind when
+                // the initializer is an error.
+                let validated_else_expr = self
+                    .subscope()
+                    .validate_expr_and_exit(*else_expr, ExprMode::give());
+                if !self.diverges(*else_expr) {
+                    let else_span = self.function.spans(self.db).else_span(expr).unwrap();
+                    dada_ir::error!(
+                        else_span.in_file(self.function.filename(self.db)),
+                        "`else` block must diverge"
+                    )
+                    .primary_label(
+                        "the `else` block must end in `return`, `break`, `continue`, or `loop`",
+                    )
+                    .emit(self.db);
+                }
 
-                self.validated_assignment(target_place, *initializer_expr, expr)
+                let first_var_expr = validated::Expr::max_key(self.tables);
+                let validated_var_expr = self.validate_var(expr, *decl, *initializer_expr);
+                let binding_fails = validated::Expr::max_key(self.tables)
+                    .iter()
+                    .filter(|&e| e >= first_var_expr)
+                    .any(|e| matches!(self.tables[e], validated::ExprData::Error));
+                if binding_fails {
+                    // The `else` block diverges, so the variable is declared
+                    // for the code that follows but never assigned.
+                    self.add(
+                        validated::ExprData::Seq(vec![validated_else_expr, validated_var_expr]),
+                        expr,
+                    )
+                } else {
+                    validated_var_expr
+                }
             }
 
             syntax::ExprData::Parenthesized(parenthesized_expr) => {
@@ -406,51 +783,126 @@ impl<'me> Validator<'me> {
                 self.add(validated::ExprData::Tuple(validated_exprs), expr)
             }
 
+            syntax::ExprData::List(element_exprs) => {
+                // FIXME: check that the elements all have the same type
+                let validated_exprs = element_exprs
+                    .iter()
+                    .map(|expr| self.reserve_validated_expr(*expr))
+                    .collect();
+                self.add(validated::ExprData::List(validated_exprs), expr)
+            }
+
+            syntax::ExprData::Range(start_expr, end_expr, inclusive) => {
+                if *inclusive && end_expr.is_none() {
+                    // With no upper bound, the range ends with the `..=` operator.
+                    let range_span = self.span(expr);
+                    let op_len = syntax::op::Op::DotDotEqual.as_str().len();
+                    let op_span = FileSpan {
+                        start: (usize::from(range_span.end) - op_len).into(),
+                        ..range_span
+                    };
+                    dada_ir::error!(op_span, "inclusive ranges must have an upper bound")
+                        .primary_label("expected an upper bound after `..=`")
+                        .emit(self.db);
+                    return self.add(validated::ExprData::Error, expr);
+                }
+
+                let validated_start_expr = start_expr.map(|e| self.reserve_validated_expr(e));
+                let validated_end_expr = end_expr.map(|e| self.reserve_validated_expr(e));
+                self.add(
+                    validated::ExprData::Range(
+                        validated_start_expr,
+                        validated_end_expr,
+                        *inclusive,
+                    ),
+                    expr,
+                )
+            }
+
+            syntax::ExprData::Closure(parameter_decls, body_expr) => {
+                self.validate_closure(expr, parameter_decls, *body_expr)
+            }
+
             syntax::ExprData::Concatenate(exprs) => self.concatenate(expr, exprs),
 
             syntax::ExprData::If(condition_expr, then_expr, else_expr) => {
+                self.check_condition_is_boolean(*condition_expr);
                 let validated_condition_expr = self.give_validated_expr(*condition_expr);
                 let validated_then_expr = self.subscope().validate_expr_and_exit(*then_expr, mode);
                 let validated_else_expr = match else_expr {
                     None => self.empty_tuple(expr),
                     Some(else_expr) => self.subscope().validate_expr_and_exit(*else_expr, mode),
                 };
-                self.add(
-                    validated::ExprData::If(
-                        validated_condition_expr,
-                        validated_then_expr,
-                        validated_else_expr,
+
+                // Both branches are validated (so that their errors are reported)
+                // even if the condition means that only one of them can execute.
+                match self.constant_condition(*condition_expr, else_expr.is_some()) {
+                    Some(true) => {
+                        self.add(validated::ExprData::Seq(vec![validated_then_expr]), expr)
+                    }
+                    Some(false) => {
+                        self.add(validated::ExprData::Seq(vec![validated_else_expr]), expr)
+                    }
+                    None => self.add(
+                        validated::ExprData::If(
+                            validated_condition_expr,
+                            validated_then_expr,
+                            validated_else_expr,
+                        ),
+                        expr,
                     ),
-                    expr,
-                )
+                }
             }
 
             syntax::ExprData::Atomic(atomic_expr) => {
-                let validated_atomic_expr = self
-                    .subscope()
-                    .with_effect(Effect::Atomic, |this| {
-                        this.span(expr).leading_keyword(this.db, Keyword::Atomic)
-                    })
-                    .validate_expr_and_exit(*atomic_expr, mode);
+                if self.effect.is_const() {
+                    dada_ir::error!(
+                        self.span(expr).leading_keyword(self.db, Keyword::Atomic),
+                        "atomic sections are not permitted inside `const` functions",
+                    )
+                    .primary_label("atomic section is here")
+                    .secondary_label(self.effect_span(), "fn declared `const`")
+                    .emit(self.db);
+                } else if self.effect.is_atomic() {
+                    dada_ir::warning!(
+                        self.span(expr).leading_keyword(self.db, Keyword::Atomic),
+                        "redundant atomic section",
+                    )
+                    .primary_label("this atomic section has no effect")
+                    .secondary_label(self.effect_span(), "already inside of this atomic section")
+                    .emit(self.db);
+                }
+
+                // Inside a `const` function, keep the `const` effect so that the body is checked as well.
+                let validated_atomic_expr = if self.effect.is_const() {
+                    self.subscope().validate_expr_and_exit(*atomic_expr, mode)
+                } else {
+                    self.subscope()
+                        .with_effect(Effect::Atomic, |this| {
+                            this.span(expr).leading_keyword(this.db, Keyword::Atomic)
+                        })
+                        .validate_expr_and_exit(*atomic_expr, mode)
+                };
                 self.add(validated::ExprData::Atomic(validated_atomic_expr), expr)
             }
 
-            syntax::ExprData::Loop(body_expr) => {
+            syntax::ExprData::Loop(label, body_expr) => {
                 // Create the `validated::Expr` up front with "Error" to start; we are going to replace this later
                 // with the actual loop.
                 let loop_expr = self.add(validated::ExprData::Error, expr);
 
                 let validated_body_expr = self
                     .subscope()
-                    .with_loop_expr(loop_expr)
+                    .with_loop_expr(expr, *label, loop_expr)
                     .validate_expr_and_exit(*body_expr, ExprMode::Specifier(Specifier::My));
 
                 self.tables[loop_expr] = validated::ExprData::Loop(validated_body_expr);
+                self.check_break_values(loop_expr);
 
                 loop_expr
             }
 
-            syntax::ExprData::While(condition_expr, body_expr) => {
+            syntax::ExprData::While(label, condition_expr, body_expr) => {
                 // while C { E }
                 //
                 // lowers to
@@ -460,12 +912,13 @@ impl<'me> Validator<'me> {
                 let loop_expr = self.add(validated::ExprData::Error, expr);
 
                 // lower the condition C
+                self.check_condition_is_boolean(*condition_expr);
                 let validated_condition_expr = self.give_validated_expr(*condition_expr);
 
                 // lower the body E, in a subscope so that `break` breaks out from `loop_expr`
                 let validated_body_expr = self
                     .subscope()
-                    .with_loop_expr(loop_expr)
+                    .with_loop_expr(expr, *label, loop_expr)
                     .validate_expr_and_exit(*body_expr, mode);
 
                 let if_break_expr = {
@@ -496,31 +949,63 @@ impl<'me> Validator<'me> {
                 loop_expr
             }
 
+            syntax::ExprData::For(label, decl, iterable_expr, body_expr) => {
+                self.validate_for(expr, *label, *decl, *iterable_expr, *body_expr)
+            }
+
+            syntax::ExprData::Match(scrutinee_expr, arms) => {
+                self.validate_match(expr, *scrutinee_expr, arms, mode)
+            }
+
+            syntax::ExprData::Op(
+                lhs_expr,
+                op @ (syntax::op::Op::AndAnd | syntax::op::Op::OrOr),
+                rhs_expr,
+            ) => self.validate_logical_op(expr, *lhs_expr, *op, *rhs_expr),
+
             syntax::ExprData::Op(lhs_expr, op, rhs_expr) => {
-                let validated_lhs_expr = self.give_validated_expr(*lhs_expr);
-                let validated_rhs_expr = self.give_validated_expr(*rhs_expr);
-                let validated_op = self.validated_op(*op);
-                self.add(
-                    validated::ExprData::Op(validated_lhs_expr, validated_op, validated_rhs_expr),
-                    expr,
-                )
+                let result = try {
+                    self.check_chained_comparison(expr, *lhs_expr, *op)?;
+
+                    match self.validate_operator_method_call(expr, *lhs_expr, *op, *rhs_expr)? {
+                        Some(call_expr) => call_expr,
+                        None => {
+                            let validated_lhs_expr = self.give_validated_expr(*lhs_expr);
+                            let validated_rhs_expr = self.give_validated_expr(*rhs_expr);
+                            let validated_op = Self::validated_op(*op);
+                            self.add(
+                                validated::ExprData::Op(
+                                    validated_lhs_expr,
+                                    validated_op,
+                                    validated_rhs_expr,
+                                ),
+                                expr,
+                            )
+                        }
+                    }
+                };
+                self.or_error(result, expr)
             }
 
             syntax::ExprData::Unary(op, rhs_expr) => {
                 let validated_rhs_expr = self.give_validated_expr(*rhs_expr);
-                let validated_op = self.validated_op(*op);
+                let validated_op = Self::validated_op(*op);
                 self.add(
                     validated::ExprData::Unary(validated_op, validated_rhs_expr),
                     expr,
                 )
             }
 
-            syntax::ExprData::OpEq(..) => {
+            syntax::ExprData::OpEq(lhs_expr, ..) => {
+                self.check_const_assignment(expr, *lhs_expr);
+                self.check_shared_assignment(expr, *lhs_expr);
                 let result = self.validate_op_eq(expr);
                 self.or_error(result, expr)
             }
 
             syntax::ExprData::Assign(lhs_expr, rhs_expr) => {
+                self.check_const_assignment(expr, *lhs_expr);
+                self.check_shared_assignment(expr, *lhs_expr);
                 let result = try {
                     let (validated_lhs_opt_temp_expr, validated_lhs_place) =
                         self.validate_expr_as_target_place(*lhs_expr, ExprMode::Reserve)?;
@@ -534,16 +1019,44 @@ impl<'me> Validator<'me> {
             }
 
             syntax::ExprData::Error => self.add(validated::ExprData::Error, expr),
-            syntax::ExprData::Seq(exprs) => {
-                let validated_exprs: Vec<_> = exprs
+            syntax::ExprData::Seq(statements, tail) => {
+                let exprs: Vec<_> = statements.iter().copied().chain(*tail).collect();
+                self.warn_unreachable_code(&exprs);
+
+                // Functions defined in the block can be called from anywhere within it.
+                for &expr in &exprs {
+                    if let syntax::ExprData::Fn(function) = expr.data(self.syntax_tables()) {
+                        let name = function.name(self.db).word(self.db);
+                        self.scope.insert_function(name, *function);
+                    }
+                }
+
+                let mut validated_exprs: Vec<_> = statements
                     .iter()
-                    .map(|expr| self.give_validated_expr(*expr))
+                    .map(|&statement| {
+                        let validated_statement = self.give_validated_expr(statement);
+                        self.warn_if_unused_value(statement);
+                        validated_statement
+                    })
                     .collect();
+
+                // The value of the block is its last expression, so a block
+                // without a tail ends with `()`.
+                match tail {
+                    Some(tail_expr) => validated_exprs.push(self.give_validated_expr(*tail_expr)),
+                    None if !statements.is_empty() => validated_exprs.push(self.empty_tuple(expr)),
+                    None => {}
+                }
                 self.add(validated::ExprData::Seq(validated_exprs), expr)
             }
             syntax::ExprData::Return(with_value) => {
-                match (self.function.return_type(self.db).kind(self.db), with_value) {
-                    (ReturnTypeKind::Value, None) => {
+                // FIXME: closures have no declared return type, so anything may be returned.
+                let return_type_kind = match self.closure {
+                    Some(_) => None,
+                    None => Some(self.function.return_type(self.db).kind(self.db)),
+                };
+                match (return_type_kind, with_value) {
+                    (Some(kind), None) if kind.returns_value() => {
                         dada_ir::error!(self.span(expr), "return requires an expression")
                             .primary_label(
                                 "cannot just have `return` without an expression afterwards",
@@ -554,7 +1067,7 @@ impl<'me> Validator<'me> {
                             )
                             .emit(self.db);
                     }
-                    (ReturnTypeKind::Unit, Some(return_expr)) => {
+                    (Some(kind), Some(return_expr)) if !kind.returns_value() => {
                         dada_ir::error!(
                             self.span(*return_expr),
                             "cannot return a value in this function"
@@ -562,10 +1075,16 @@ impl<'me> Validator<'me> {
                         .primary_label("can only write `return` (without a value) in this function")
                         .secondary_label(
                             self.function.return_type(self.db).span(self.db),
-                            "because function doesn't have `->` here",
+                            match kind {
+                                ReturnTypeKind::ExplicitUnit => "because function returns `()`",
+                                _ => "because function doesn't have `->` here",
+                            },
                         )
                         .emit(self.db);
                     }
+                    (Some(_), Some(return_expr)) => {
+                        self.check_tuple_arity(*return_expr);
+                    }
                     _ => {}
                 }
                 let validated_expr = if let Some(return_expr) = with_value {
@@ -575,9 +1094,344 @@ impl<'me> Validator<'me> {
                 };
                 self.add(validated::ExprData::Return(validated_expr), expr)
             }
+
+            syntax::ExprData::Break(label, with_value) => {
+                let result = try {
+                    let from_expr = self.loop_target(expr, Keyword::Break, *label)?;
+                    let validated_value_expr = match with_value {
+                        Some(value_expr) => self.give_validated_expr(*value_expr),
+                        None => self.empty_tuple(expr),
+                    };
+                    self.add(
+                        validated::ExprData::Break {
+                            from_expr,
+                            with_value: validated_value_expr,
+                        },
+                        expr,
+                    )
+                };
+                self.or_error(result, expr)
+            }
+
+            syntax::ExprData::Continue(label) => {
+                let result = try {
+                    let from_expr = self.loop_target(expr, Keyword::Continue, *label)?;
+                    self.add(validated::ExprData::Continue(from_expr), expr)
+                };
+                self.or_error(result, expr)
+            }
+
+            syntax::ExprData::Fn(function) => {
+                // The nested function is validated on its own, like any other;
+                // this reports its errors along with those of the enclosing function.
+                crate::validate::validate_function(self.db, *function);
+                self.empty_tuple(expr)
+            }
         }
     }
 
+    /// Checks that the `break`s that exit `loop_expr` either all give it a value
+    /// or all do not. (A loop whose `break`s have no value evaluates to `()`.)
+    fn check_break_values(&self, loop_expr: validated::Expr) {
+        let mut breaks = validated::Expr::max_key(self.tables)
+            .iter()
+            .filter(|&expr| expr > loop_expr && !self.origins[expr].synthesized)
+            .filter_map(|expr| match self.tables[expr] {
+                validated::ExprData::Break { from_expr, .. } if from_expr == loop_expr => {
+                    let syntax_expr = self.origins[expr].syntax_expr;
+                    match self.syntax_tables()[syntax_expr] {
+                        syntax::ExprData::Break(_, with_value) => {
+                            Some((syntax_expr, with_value.is_some()))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            });
+
+        let Some((first_expr, first_has_value)) = breaks.next() else {
+            return;
+        };
+        let conflict = breaks.find(|&(_, has_value)| has_value != first_has_value);
+        let Some((conflicting_expr, _)) = conflict else {
+            return;
+        };
+
+        let describe = |has_value| {
+            if has_value {
+                "this `break` gives the loop a value"
+            } else {
+                "this `break` does not give the loop a value"
+            }
+        };
+        dada_ir::error!(
+            self.span(conflicting_expr),
+            "every `break` from a loop must agree on whether it gives a value",
+        )
+        .primary_label(describe(!first_has_value))
+        .secondary_label(self.span(first_expr), describe(first_has_value))
+        .emit(self.db);
+    }
+
+    fn validate_closure(
+        &mut self,
+        closure_expr: syntax::Expr,
+        parameter_decls: &[LocalVariableDecl],
+        body_expr: syntax::Expr,
+    ) -> validated::Expr {
+        let first_local_variable = validated::LocalVariable::max_key(self.tables);
+        let first_place = validated::Place::max_key(self.tables);
+
+        // The body is validated in a fresh scope that binds the parameters.
+        // It is not inside of any loop: `break` and `continue` cannot
+        // leave the closure.
+        let mut body_scope = self.subscope();
+        body_scope.loop_stack.clear();
+        body_scope.closure = Some(ClosureScope {
+            first_local_variable,
+        });
+        let mut parameters = vec![];
+        for &decl in parameter_decls {
+            let decl_data = decl.data(body_scope.syntax_tables());
+            let local_variable = body_scope.add(
+                validated::LocalVariableData {
+                    name: Some(decl_data.name),
+                    specifier: Some(decl_data.specifier),
+                    atomic: decl_data.atomic,
+                    mutable: decl_data.mutable,
+                },
+                validated::LocalVariableOrigin::Parameter(decl),
+            );
+            body_scope.insert_local_variable(decl, local_variable);
+            parameters.push(local_variable);
+        }
+        let validated_body_expr = body_scope.validate_expr_and_exit(body_expr, ExprMode::give());
+
+        // Any variable from outside of the closure that is referenced within
+        // the body (including within nested closures) is captured. Captured
+        // variables are shleased when the closure is created.
+        let mut captured_variables = vec![];
+        let places = validated::Place::range(
+            usize::from(first_place),
+            usize::from(validated::Place::max_key(self.tables)),
+        );
+        for place in places {
+            if let validated::PlaceData::LocalVariable(lv) = self.tables[place] {
+                if lv < first_local_variable && !captured_variables.contains(&lv) {
+                    captured_variables.push(lv);
+                }
+            }
+        }
+        let captures = captured_variables
+            .into_iter()
+            .map(|lv| {
+                self.add(
+                    validated::PlaceData::LocalVariable(lv),
+                    closure_expr.synthesized(),
+                )
+            })
+            .collect();
+
+        self.add(
+            validated::ExprData::Closure(parameters, captures, validated_body_expr),
+            closure_expr,
+        )
+    }
+
+    fn validate_match(
+        &mut self,
+        match_expr: syntax::Expr,
+        scrutinee_expr: syntax::Expr,
+        arms: &[syntax::MatchArm],
+        mode: ExprMode,
+    ) -> validated::Expr {
+        // match S { 22 => E1, x => E2 }
+        //
+        // lowers to
+        //
+        // temp = S.share
+        // if temp == 22 { E1 } else { x = temp; E2 }
+        //
+        // If no arm matches, the result is `()`. The scrutinee is shared so
+        // that comparing against it does not give away the value.
+
+        // The scrutinee is validated exactly once, before any of the arms.
+        let validated_scrutinee_expr = self.give_validated_expr(scrutinee_expr);
+        let shared_scrutinee_expr = self.add(
+            validated::ExprData::Share(validated_scrutinee_expr),
+            scrutinee_expr.synthesized(),
+        );
+        let (scrutinee_assign_expr, scrutinee_place) =
+            self.store_validated_expr_in_temporary(shared_scrutinee_expr);
+        let scrutinee_variable = match self.tables[scrutinee_place] {
+            validated::PlaceData::LocalVariable(lv) => lv,
+            _ => unreachable!("temporaries are always local variables"),
+        };
+
+        // Validate each arm (in order) into an optional condition and a body.
+        let mut validated_arms = vec![];
+        for &arm in arms {
+            let syntax::MatchArmData { pattern, body } = arm.data(self.syntax_tables());
+            let arm_expr = match pattern.data(self.syntax_tables()) {
+                syntax::PatternData::Binding(decl) => {
+                    let mut arm_scope = self.subscope();
+                    let decl_data = decl.data(arm_scope.syntax_tables());
+                    let local_variable = arm_scope.add(
+                        validated::LocalVariableData {
+                            name: Some(decl_data.name),
+                            specifier: Some(decl_data.specifier),
+                            atomic: decl_data.atomic,
+                            mutable: decl_data.mutable,
+                        },
+                        validated::LocalVariableOrigin::LocalVariable(*decl),
+                    );
+                    arm_scope.insert_local_variable(*decl, local_variable);
+                    let target_place = arm_scope.add(
+                        validated::TargetPlaceData::LocalVariable(local_variable),
+                        body.synthesized(),
+                    );
+                    let source_place = arm_scope.add(
+                        validated::PlaceData::LocalVariable(scrutinee_variable),
+                        body.synthesized(),
+                    );
+                    let bind_expr = arm_scope.add(
+                        validated::ExprData::AssignFromPlace(target_place, source_place),
+                        body.synthesized(),
+                    );
+                    let validated_body_expr = arm_scope.validate_expr_in_mode(*body, mode);
+                    let arm_body_expr = arm_scope.seq(Some(bind_expr), validated_body_expr);
+                    (None, arm_scope.exit(arm_body_expr))
+                }
+
+                syntax::PatternData::Literal(literal_expr) => {
+                    let scrutinee_place = self.add(
+                        validated::PlaceData::LocalVariable(scrutinee_variable),
+                        literal_expr.synthesized(),
+                    );
+                    let validated_scrutinee_expr = self.add(
+                        validated::ExprData::Give(scrutinee_place),
+                        literal_expr.synthesized(),
+                    );
+                    let validated_literal_expr = self.give_validated_expr(*literal_expr);
+                    let condition_expr = self.add(
+                        validated::ExprData::Op(
+                            validated_scrutinee_expr,
+                            validated::op::Op::EqualEqual,
+                            validated_literal_expr,
+                        ),
+                        literal_expr.synthesized(),
+                    );
+                    let validated_body_expr = self.subscope().validate_expr_and_exit(*body, mode);
+                    (Some(condition_expr), validated_body_expr)
+                }
+            };
+            validated_arms.push(arm_expr);
+        }
+
+        // Fold the arms, from last to first, into a chain of `if`s.
+        let mut result_expr = self.empty_tuple(match_expr);
+        for (condition_expr, body_expr) in validated_arms.into_iter().rev() {
+            result_expr = match condition_expr {
+                Some(condition_expr) => self.add(
+                    validated::ExprData::If(condition_expr, body_expr, result_expr),
+                    match_expr.synthesized(),
+                ),
+                None => body_expr,
+            };
+        }
+
+        self.add(
+            validated::ExprData::Seq(vec![scrutinee_assign_expr, result_expr]),
+            match_expr,
+        )
+    }
+
+    /// Declares a fresh local variable for `decl` and assigns it the value of
+    /// `initializer_expr`.
+    fn validate_var(
+        &mut self,
+        expr: syntax::Expr,
+        decl: LocalVariableDecl,
+        initializer_expr: syntax::Expr,
+    ) -> validated::Expr {
+        let decl_data = decl.data(self.syntax_tables());
+        if decl_data.weak == Weak::Yes {
+            self.check_weak_initializer(initializer_expr);
+        }
+
+        // FIXME: `weak` variables currently hold their value like any other variable.
+        let local_variable = self.add(
+            validated::LocalVariableData {
+                name: Some(decl_data.name),
+                specifier: Some(decl_data.specifier),
+                atomic: decl_data.atomic,
+                mutable: decl_data.mutable,
+            },
+            validated::LocalVariableOrigin::LocalVariable(decl),
+        );
+        self.insert_local_variable(decl, local_variable);
+
+        let target_place = self.add(
+            validated::TargetPlaceData::LocalVariable(local_variable),
+            expr.synthesized(),
+        );
+
+        self.validated_assignment(target_place, initializer_expr, expr)
+    }
+
+    /// Reports an error unless the initializer of a `weak` variable is a
+    /// `share` or `lease` expression: a weak variable must not own its value.
+    fn check_weak_initializer(&self, initializer_expr: syntax::Expr) {
+        match initializer_expr.data(self.syntax_tables()) {
+            syntax::ExprData::Share(_)
+            | syntax::ExprData::Lease(_)
+            | syntax::ExprData::Shlease(_) => {}
+            _ => {
+                dada_ir::error!(
+                    self.span(initializer_expr),
+                    "`weak` variables must be initialized with `share` or `lease`",
+                )
+                .primary_label("a `weak` variable cannot own its value")
+                .emit(self.db);
+            }
+        }
+    }
+
+    /// True if `expr` can never complete normally; see [`unreachable::diverges`].
+    fn diverges(&self, expr: syntax::Expr) -> bool {
+        unreachable::diverges(self.syntax_tables(), expr)
+    }
+
+    /// Warns about any expressions in a block that come after one that always diverges.
+    fn warn_unreachable_code(&self, exprs: &[syntax::Expr]) {
+        let Some(unreachable::UnreachableCode {
+            diverging_expr,
+            unreachable_exprs,
+        }) = unreachable::find_unreachable_code(self.syntax_tables(), exprs) else {
+            return;
+        };
+
+        let unreachable_span = FileSpan {
+            end: self.span(*unreachable_exprs.last().unwrap()).end,
+            ..self.span(unreachable_exprs[0])
+        };
+        let diverging_span = self.span(diverging_expr);
+        let (diverging_span, diverging_label) = match diverging_expr.data(self.syntax_tables()) {
+            syntax::ExprData::Return(_) => (diverging_span, "because of this `return`"),
+            syntax::ExprData::Break(..) => (diverging_span, "because of this `break`"),
+            syntax::ExprData::Continue(_) => (diverging_span, "because of this `continue`"),
+            syntax::ExprData::Loop(..) => (
+                diverging_span.leading_keyword(self.db, Keyword::Loop),
+                "because this `loop` never ends",
+            ),
+            _ => (diverging_span, "because this never finishes"),
+        };
+        dada_ir::warning!(unreachable_span, "unreachable code")
+            .primary_label("this code can never execute")
+            .secondary_label(diverging_span, diverging_label)
+            .emit(self.db);
+    }
+
     fn validate_op_eq(
         &mut self,
         op_eq_expr: syntax::Expr,
@@ -609,7 +1463,7 @@ impl<'me> Validator<'me> {
 
         // `temp_value = x + <rhs>` or `temp_value = temp_leased_owner.x + <rhs>`
         let (temporary_assign_expr, temporary_place) = {
-            let validated_op = self.validated_op(op);
+            let validated_op = Self::validated_op(op);
 
             // `x` or `temp_leased_owner.x`
             let validated_lhs_expr = {
@@ -715,6 +1569,7 @@ impl<'me> Validator<'me> {
     ) -> Result<(Option<validated::Expr>, validated::TargetPlace), ErrorReported> {
         match expr.data(self.syntax_tables()) {
             syntax::ExprData::Dot(owner, field_name) => {
+                self.check_self_field(expr, *owner, *field_name)?;
                 let (assign_expr, owner_place) =
                     self.validate_expr_in_temporary(*owner, owner_mode);
                 let place = self.add(
@@ -725,14 +1580,37 @@ impl<'me> Validator<'me> {
             }
 
             syntax::ExprData::Id(name) => match self.scope.lookup(*name) {
+                Some(Definition::LocalVariable(lv)) if self.is_captured(lv) => {
+                    let mut diagnostic = dada_ir::error!(
+                        self.span(expr),
+                        "cannot assign to `{}` from inside of a closure",
+                        name.as_str(self.db),
+                    )
+                    .primary_label("this variable is captured from outside of the closure");
+                    match self.origins[lv] {
+                        LocalVariableOrigin::LocalVariable(decl)
+                        | LocalVariableOrigin::Parameter(decl) => {
+                            let name_span = self.syntax_tree_entity.spans(self.db)[decl].name_span;
+                            diagnostic = diagnostic.secondary_label(
+                                name_span,
+                                format!("`{}` is declared here", name.as_str(self.db)),
+                            );
+                        }
+                        LocalVariableOrigin::Temporary(_) => {}
+                    }
+                    Err(diagnostic.emit(self.db))
+                }
+
                 Some(Definition::LocalVariable(lv)) => {
+                    self.check_atomic_access(expr, lv);
                     let place = self.add(validated::TargetPlaceData::LocalVariable(lv), expr);
                     Ok((None, place))
                 }
 
                 Some(definition @ Definition::Function(_))
                 | Some(definition @ Definition::Class(_))
-                | Some(definition @ Definition::Intrinsic(_)) => Err(dada_ir::error!(
+                | Some(definition @ Definition::Intrinsic(_))
+                | Some(definition @ Definition::TypeParameter(_)) => Err(dada_ir::error!(
                     self.span(expr),
                     "you can only assign to local variables or fields, not {} like `{}`",
                     definition.plural_description(),
@@ -740,18 +1618,24 @@ impl<'me> Validator<'me> {
                 )
                 .emit(self.db)),
 
-                None => Err(dada_ir::error!(
-                    self.span(expr),
-                    "can't find anything named `{}`",
-                    name.as_str(self.db)
-                )
-                .emit(self.db)),
+                None => Err(self.report_unknown_name(expr, *name)),
             },
 
             syntax::ExprData::Parenthesized(target_expr) => {
                 self.validate_expr_as_target_place(*target_expr, owner_mode)
             }
 
+            // FIXME: an index is a valid assignment target, but places in the
+            // validated tree (and in BIR) do not support it yet.
+            syntax::ExprData::Index(..) => {
+                let _ = self.give_validated_expr(expr);
+                Err(dada_ir::error!(
+                    self.span(expr),
+                    "assigning to an element of a list is not yet supported",
+                )
+                .emit(self.db))
+            }
+
             _ => {
                 let _ = self.give_validated_expr(expr);
                 Err(dada_ir::error!(
@@ -855,9 +1739,36 @@ impl<'me> Validator<'me> {
         self.or_error(validated_data, perm_expr)
     }
 
+    /// Reports an error if the target of `share`, `lease`, `shlease`, or `give`
+    /// (given by `keyword`) is the result of an operator, like `(1 + 2).give`.
+    /// Places like `x.y` are the intended targets, but temporary objects like
+    /// `Pair(22, 44).lease` are allowed too (the permission keeps them alive).
+    fn check_permission_target(&self, target_expr: syntax::Expr, keyword: Keyword) {
+        let mut operand_expr = target_expr;
+        while let syntax::ExprData::Parenthesized(inner_expr) =
+            operand_expr.data(self.syntax_tables())
+        {
+            operand_expr = *inner_expr;
+        }
+
+        if let syntax::ExprData::Op(..) | syntax::ExprData::Unary(..) =
+            operand_expr.data(self.syntax_tables())
+        {
+            dada_ir::error!(
+                self.span(target_expr),
+                "can only {} a place, like a variable or field",
+                keyword,
+            )
+            .primary_label("this is a temporary value")
+            .emit(self.db);
+        }
+    }
+
     fn is_place_expression(&self, expr: syntax::Expr) -> bool {
         match expr.data(self.syntax_tables()) {
-            syntax::ExprData::Id(_) | syntax::ExprData::Dot(..) => true,
+            syntax::ExprData::Id(_) | syntax::ExprData::Dot(..) | syntax::ExprData::Index(..) => {
+                true
+            }
             syntax::ExprData::Parenthesized(parenthesized_expr) => {
                 self.is_place_expression(*parenthesized_expr)
             }
@@ -878,22 +1789,25 @@ impl<'me> Validator<'me> {
                         self.add(validated::PlaceData::Function(f), expr)
                     }
                     Some(Definition::LocalVariable(lv)) => {
+                        self.check_atomic_access(expr, lv);
                         self.add(validated::PlaceData::LocalVariable(lv), expr)
                     }
                     Some(Definition::Intrinsic(i)) => {
                         self.add(validated::PlaceData::Intrinsic(i), expr)
                     }
-                    None => {
+                    Some(Definition::TypeParameter(_)) => {
                         return Err(dada_ir::error!(
                             self.span(expr),
-                            "can't find anything named `{}`",
+                            "cannot use the type parameter `{}` as a value",
                             name.as_str(self.db)
                         )
                         .emit(self.db))
                     }
+                    None => return Err(self.report_unknown_name(expr, *name)),
                 },
             )),
             syntax::ExprData::Dot(owner_expr, field) => {
+                self.check_self_field(expr, *owner_expr, *field)?;
                 let (opt_temporary_expr, validated_owner_place) =
                     self.validate_expr_as_place(*owner_expr)?;
                 Ok((
@@ -904,6 +1818,24 @@ impl<'me> Validator<'me> {
                     ),
                 ))
             }
+            syntax::ExprData::Index(base_expr, index_expr) => {
+                let (opt_temporary_expr, validated_base_place) =
+                    self.validate_expr_as_place(*base_expr)?;
+
+                // FIXME: once we have types, check that the base can be indexed
+                // and that the index is an integer. For now, both (along with
+                // the bounds) are checked when the place is accessed.
+                let (index_assign_expr, validated_index_place) =
+                    self.validate_expr_in_temporary(*index_expr, ExprMode::give());
+
+                Ok((
+                    Some(self.seq(opt_temporary_expr, index_assign_expr)),
+                    self.add(
+                        validated::PlaceData::Index(validated_base_place, validated_index_place),
+                        expr,
+                    ),
+                ))
+            }
             syntax::ExprData::Parenthesized(parenthesized_expr) => {
                 self.validate_expr_as_place(*parenthesized_expr)
             }
@@ -927,6 +1859,78 @@ impl<'me> Validator<'me> {
     }
 
     /// Creates a temporary to store the result of validating some expression.
+    /// Reports that `name` (used by `expr`) is not in scope. If this is a nested
+    /// function and an enclosing function declares a variable with that name,
+    /// explains that nested functions cannot capture it.
+    fn report_unknown_name(&self, expr: syntax::Expr, name: Word) -> ErrorReported {
+        let declaration = self.enclosing_functions().into_iter().find_map(|function| {
+            let tables = &function.syntax_tree(self.db).data(self.db).tables;
+            let decl = syntax::LocalVariableDecl::max_key(tables)
+                .iter()
+                .find(|&decl| tables[decl].name == name)?;
+            let name_span = function.spans(self.db)[decl].name_span;
+            Some(name_span.in_file(function.filename(self.db)))
+        });
+
+        match declaration {
+            Some(declaration_span) => dada_ir::error!(
+                self.span(expr),
+                "nested functions cannot capture variables like `{}`",
+                name.as_str(self.db),
+            )
+            .primary_label(format!(
+                "`{}` belongs to an enclosing function",
+                name.as_str(self.db)
+            ))
+            .secondary_label(
+                declaration_span,
+                format!("`{}` is declared here", name.as_str(self.db)),
+            )
+            .emit(self.db),
+            None => dada_ir::error!(
+                self.span(expr),
+                "can't find anything named `{}`",
+                name.as_str(self.db)
+            )
+            .emit(self.db),
+        }
+    }
+
+    /// The functions whose bodies define the function being validated, innermost first.
+    /// This is empty unless it is a nested function.
+    fn enclosing_functions(&self) -> Vec<Function> {
+        let span = self.function.span(self.db);
+        let encloses = |function: Function| {
+            let outer = function.span(self.db);
+            function != self.function && outer.start <= span.start && span.end <= outer.end
+        };
+
+        let mut enclosing: Vec<Function> = self
+            .function
+            .filename(self.db)
+            .items(self.db)
+            .iter()
+            .filter_map(|item| match *item {
+                Item::Function(function) if encloses(function) => Some(function),
+                _ => None,
+            })
+            .collect();
+        let mut index = 0;
+        while let Some(&function) = enclosing.get(index) {
+            let tables = &function.syntax_tree(self.db).data(self.db).tables;
+            for expr in syntax::Expr::max_key(tables).iter() {
+                if let syntax::ExprData::Fn(nested) = tables[expr] {
+                    if encloses(nested) {
+                        enclosing.push(nested);
+                    }
+                }
+            }
+            index += 1;
+        }
+        enclosing.reverse();
+        enclosing
+    }
+
     fn store_validated_expr_in_temporary(
         &mut self,
         validated_expr: validated::Expr,
@@ -938,6 +1942,7 @@ impl<'me> Validator<'me> {
                 name: None,
                 specifier: None,
                 atomic: Atomic::No,
+                mutable: Mutable::Yes,
             },
             validated::LocalVariableOrigin::Temporary(origin.syntax_expr),
         );
@@ -952,6 +1957,143 @@ impl<'me> Validator<'me> {
         (assign_expr, validated_place)
     }
 
+    /// Validates `Class { field: expr, ... }`. The field expressions are evaluated
+    /// in the order they are written, stored into temporaries, and then passed to
+    /// the class constructor in the order that the class declares its fields.
+    fn validate_class_literal(
+        &mut self,
+        expr: syntax::Expr,
+        class_name: Word,
+        named_exprs: &[syntax::NamedExpr],
+    ) -> validated::Expr {
+        let mut seen: Map<Word, SpannedOptionalWord> = Map::default();
+        let mut fields = vec![];
+        let mut assign_exprs = vec![];
+        let mut has_error = false;
+        for &named_expr in named_exprs {
+            let syntax::NamedExprData { name, expr } = named_expr.data(self.syntax_tables());
+            let (name, field_expr) = (*name, *expr);
+            let (assign_expr, temporary_place) =
+                self.validate_expr_in_temporary(field_expr, ExprMode::give());
+            assign_exprs.push(assign_expr);
+
+            let word = match name.word(self.db) {
+                Some(word) => word,
+                None => {
+                    dada_ir::error!(name.span(self.db), "field name required")
+                        .primary_label("write `name: expr` to specify a field")
+                        .emit(self.db);
+                    has_error = true;
+                    continue;
+                }
+            };
+
+            if let Some(previous_name) = seen.insert(word, name) {
+                dada_ir::error!(
+                    name.span(self.db),
+                    "field `{}` is specified more than once",
+                    word.as_str(self.db),
+                )
+                .primary_label("duplicate field")
+                .secondary_label(previous_name.span(self.db), "first specified here")
+                .emit(self.db);
+                has_error = true;
+                continue;
+            }
+
+            fields.push((word, named_expr, field_expr, temporary_place));
+        }
+
+        let class = match self.scope.lookup(class_name) {
+            Some(Definition::Class(c)) => c,
+            Some(definition) => {
+                dada_ir::error!(
+                    self.span(expr),
+                    "class literals can only be used with classes, not {} like `{}`",
+                    definition.plural_description(),
+                    class_name.as_str(self.db),
+                )
+                .emit(self.db);
+                let error_expr = self.add(validated::ExprData::Error, expr);
+                return self.seq(assign_exprs, error_expr);
+            }
+            None => {
+                dada_ir::error!(
+                    self.span(expr),
+                    "can't find anything named `{}`",
+                    class_name.as_str(self.db)
+                )
+                .emit(self.db);
+                let error_expr = self.add(validated::ExprData::Error, expr);
+                return self.seq(assign_exprs, error_expr);
+            }
+        };
+
+        let db = self.db;
+        let class_fields = class.fields(db);
+        for (word, named_expr, _, _) in &fields {
+            if !class_fields.iter().any(|p| p.name(db) == *word) {
+                let name = named_expr.data(self.syntax_tables()).name;
+                dada_ir::error!(
+                    name.span(self.db),
+                    "class `{}` has no field named `{}`",
+                    class_name.as_str(self.db),
+                    word.as_str(self.db),
+                )
+                .emit(self.db);
+                has_error = true;
+            }
+        }
+
+        let mut validated_named_exprs = vec![];
+        for parameter in class_fields {
+            let field_name = parameter.name(db);
+            match fields.iter().find(|field| field.0 == field_name) {
+                Some(&(_, named_expr, field_expr, temporary_place)) => {
+                    let name = named_expr.data(self.syntax_tables()).name;
+                    let given_expr = self.add(
+                        validated::ExprData::Give(temporary_place),
+                        field_expr.synthesized(),
+                    );
+                    validated_named_exprs.push(self.add(
+                        validated::NamedExprData {
+                            name,
+                            expr: given_expr,
+                        },
+                        named_expr,
+                    ));
+                }
+                None => {
+                    dada_ir::error!(
+                        self.span(expr),
+                        "missing field `{}` for class `{}`",
+                        field_name.as_str(self.db),
+                        class_name.as_str(self.db),
+                    )
+                    .emit(self.db);
+                    has_error = true;
+                }
+            }
+        }
+
+        if has_error {
+            let error_expr = self.add(validated::ExprData::Error, expr);
+            return self.seq(assign_exprs, error_expr);
+        }
+
+        let class_place = self.add(validated::PlaceData::Class(class), expr.synthesized());
+        let class_expr = self.place_to_expr(
+            Ok((None, class_place)),
+            expr.synthesized(),
+            ExprMode::Reserve,
+        );
+        let call_expr = self.add(
+            validated::ExprData::Call(class_expr, validated_named_exprs),
+            expr,
+        );
+        self.seq(assign_exprs, call_expr)
+    }
+
     fn validate_named_exprs(
         &mut self,
         named_exprs: &[syntax::NamedExpr],
@@ -974,25 +2116,235 @@ impl<'me> Validator<'me> {
         )
     }
 
-    fn validated_op(&self, op: syntax::op::Op) -> validated::op::Op {
+    /// Warns if `statement`, whose value is discarded, obviously produces a value
+    /// that is not `()`, like `a + b` or a call to a function declared with `->`.
+    ///
+    /// FIXME: once we have types, check for any statement whose type is not `()`.
+    fn warn_if_unused_value(&self, statement: syntax::Expr) {
+        let mut expr = statement;
+        while let syntax::ExprData::Parenthesized(inner_expr)
+        | syntax::ExprData::Await(inner_expr) = self.syntax_tables()[expr]
+        {
+            expr = inner_expr;
+        }
+
+        if let Some(function) = self.called_function(expr) {
+            if function.return_type(self.db).kind(self.db).returns_value() {
+                dada_ir::warning!(self.span(statement), "unused result")
+                    .primary_label(format!(
+                        "the value returned by `{}` is never used",
+                        function.name(self.db).as_str(self.db)
+                    ))
+                    .emit(self.db);
+            }
+            return;
+        }
+
+        let produces_value = match &self.syntax_tables()[expr] {
+            syntax::ExprData::BooleanLiteral(_)
+            | syntax::ExprData::IntegerLiteral(_)
+            | syntax::ExprData::FloatLiteral(_)
+            | syntax::ExprData::CharLiteral(_)
+            | syntax::ExprData::StringLiteral(_)
+            | syntax::ExprData::Concatenate(_)
+            | syntax::ExprData::List(_)
+            | syntax::ExprData::Range(..)
+            | syntax::ExprData::Closure(..)
+            | syntax::ExprData::Unary(..) => true,
+            syntax::ExprData::Tuple(exprs) => !exprs.is_empty(),
+
+            // `a && f()` runs `f()` only if `a` is true, so it may be used for its effect.
+            syntax::ExprData::Op(_, op, _) => {
+                !matches!(op, syntax::op::Op::AndAnd | syntax::op::Op::OrOr)
+            }
+            _ => false,
+        };
+        if produces_value {
+            dada_ir::warning!(self.span(statement), "unused result")
+                .primary_label("the value of this expression is never used")
+                .emit(self.db);
+        }
+    }
+
+    /// If `expr` is a call like `f(...)` where `f` names a function, returns that function.
+    fn called_function(&self, expr: syntax::Expr) -> Option<Function> {
+        let syntax::ExprData::Call(callee_expr, _) = self.syntax_tables()[expr] else {
+            return None;
+        };
+        let syntax::ExprData::Id(name) = self.syntax_tables()[callee_expr] else {
+            return None;
+        };
+        match self.scope.lookup(name)? {
+            Definition::Function(function) => Some(function),
+            _ => None,
+        }
+    }
+
+    /// Reports an error if `condition_expr` is obviously not a boolean.
+    ///
+    /// FIXME: once we have types, check the type of the condition instead;
+    /// for now we only catch literals.
+    fn check_condition_is_boolean(&self, condition_expr: syntax::Expr) {
+        let mut expr = condition_expr;
+        while let syntax::ExprData::Parenthesized(inner_expr) = self.syntax_tables()[expr] {
+            expr = inner_expr;
+        }
+        let kind = match self.syntax_tables()[expr] {
+            syntax::ExprData::IntegerLiteral(_) => "an integer",
+            syntax::ExprData::FloatLiteral(_) => "a float",
+            syntax::ExprData::CharLiteral(_) => "a character",
+            syntax::ExprData::StringLiteral(_) | syntax::ExprData::Concatenate(_) => "a string",
+            _ => return,
+        };
+        dada_ir::error!(
+            self.span(condition_expr),
+            "condition must be a boolean, found {}",
+            kind
+        )
+        .primary_label(format!("this is {kind}, not a boolean"))
+        .emit(self.db);
+    }
+
+    /// If `condition_expr` is a `true` or `false` literal, warns that one branch
+    /// of the `if` never executes (`has_else` says if there is an `else` branch)
+    /// and returns the value of the condition.
+    fn constant_condition(&self, condition_expr: syntax::Expr, has_else: bool) -> Option<bool> {
+        let syntax::ExprData::BooleanLiteral(value) = self.syntax_tables()[condition_expr] else {
+            return None;
+        };
+        let label = match (value, has_else) {
+            (true, true) => "so the `else` branch is never executed",
+            (true, false) => "so the body of the `if` is always executed",
+            (false, _) => "so the body of the `if` is never executed",
+        };
+        dada_ir::warning!(self.span(condition_expr), "condition is always `{}`", value)
+            .primary_label(label)
+            .emit(self.db);
+        Some(value)
+    }
+
+    /// Reports an error for `a < b < c`, which would compare the boolean result of
+    /// `a < b` against `c`. Comparisons are left-associative, so only the left-hand
+    /// side can be an (unparenthesized) comparison; `(a < b) == c` is fine.
+    fn check_chained_comparison(
+        &self,
+        op_expr: syntax::Expr,
+        lhs_expr: syntax::Expr,
+        op: syntax::op::Op,
+    ) -> Result<(), ErrorReported> {
+        if !op.is_comparison() {
+            return Ok(());
+        }
+
+        match self.syntax_tables()[lhs_expr] {
+            syntax::ExprData::Op(_, lhs_op, _) if lhs_op.is_comparison() => {
+                let lhs_span = self.span(lhs_expr);
+                Err(dada_ir::error!(
+                    self.op_span(op_expr),
+                    "comparison operators cannot be chained"
+                )
+                .primary_label("add parentheses or use `&&` to combine comparisons")
+                .secondary_label(self.op_span(lhs_expr), "first comparison")
+                .suggestion(lhs_span, format!("({})", lhs_span.snippet(self.db)))
+                .emit(self.db))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the span of the operator in the `Op` or `OpEq` expression `expr`
+    /// (or of the entire expression, if it has no operator).
+    fn op_span(&self, expr: syntax::Expr) -> FileSpan {
+        let spans = self.function.spans(self.db);
+        spans
+            .op_span(expr)
+            .unwrap_or(spans[expr])
+            .in_file(self.function.filename(self.db))
+    }
+
+    /// Lowers `a && b` to `if a { if b { true } else { false } } else { false }`
+    /// and `a || b` to `if a { true } else { if b { true } else { false } }`, so that
+    /// `b` is only evaluated when needed. Testing `b` with an `if`, rather than
+    /// yielding it directly, checks that both operands are booleans.
+    fn validate_logical_op(
+        &mut self,
+        expr: syntax::Expr,
+        lhs_expr: syntax::Expr,
+        op: syntax::op::Op,
+        rhs_expr: syntax::Expr,
+    ) -> validated::Expr {
+        let validated_lhs_expr = self.give_validated_expr(lhs_expr);
+        let validated_rhs_expr = self
+            .subscope()
+            .validate_expr_and_exit(rhs_expr, ExprMode::give());
+
+        let origin = expr.synthesized();
+        let true_expr = self.add(validated::ExprData::BooleanLiteral(true), origin);
+        let false_expr = self.add(validated::ExprData::BooleanLiteral(false), origin);
+        let rhs_bool_expr = self.add(
+            validated::ExprData::If(validated_rhs_expr, true_expr, false_expr),
+            origin,
+        );
+
+        // The value of the whole expression if `a` alone decides it.
+        let short_circuit_value = op == syntax::op::Op::OrOr;
+        let short_circuit_expr = self.add(
+            validated::ExprData::BooleanLiteral(short_circuit_value),
+            origin,
+        );
+
+        let data = match op {
+            syntax::op::Op::AndAnd => {
+                validated::ExprData::If(validated_lhs_expr, rhs_bool_expr, short_circuit_expr)
+            }
+            syntax::op::Op::OrOr => {
+                validated::ExprData::If(validated_lhs_expr, short_circuit_expr, rhs_bool_expr)
+            }
+            _ => unreachable!("not a logical op: {op:?}"),
+        };
+        self.add(data, expr)
+    }
+
+    /// The validated form of the binary or unary operator `op`. Compound
+    /// assignments like `+=` become the operator that they apply, like `+`.
+    pub(super) fn validated_op(op: syntax::op::Op) -> validated::op::Op {
         match op {
             // Compound binops become a binop + assignment
             syntax::op::Op::PlusEqual => validated::op::Op::Plus,
             syntax::op::Op::MinusEqual => validated::op::Op::Minus,
             syntax::op::Op::TimesEqual => validated::op::Op::Times,
             syntax::op::Op::DividedByEqual => validated::op::Op::DividedBy,
+            syntax::op::Op::BitAndEqual => validated::op::Op::BitAnd,
+            syntax::op::Op::BitOrEqual => validated::op::Op::BitOr,
+            syntax::op::Op::BitXorEqual => validated::op::Op::BitXor,
+            syntax::op::Op::ShiftLeftEqual => validated::op::Op::ShiftLeft,
+            syntax::op::Op::ShiftRightEqual => validated::op::Op::ShiftRight,
 
             // Binops
             syntax::op::Op::EqualEqual => validated::op::Op::EqualEqual,
+            syntax::op::Op::NotEqual => validated::op::Op::NotEqual,
             syntax::op::Op::GreaterEqual => validated::op::Op::GreaterEqual,
             syntax::op::Op::LessEqual => validated::op::Op::LessEqual,
             syntax::op::Op::Plus => validated::op::Op::Plus,
             syntax::op::Op::Minus => validated::op::Op::Minus,
             syntax::op::Op::Times => validated::op::Op::Times,
             syntax::op::Op::DividedBy => validated::op::Op::DividedBy,
+            syntax::op::Op::BitAnd => validated::op::Op::BitAnd,
+            syntax::op::Op::BitOr => validated::op::Op::BitOr,
+            syntax::op::Op::BitXor => validated::op::Op::BitXor,
+            syntax::op::Op::ShiftLeft => validated::op::Op::ShiftLeft,
+            syntax::op::Op::ShiftRight => validated::op::Op::ShiftRight,
             syntax::op::Op::LessThan => validated::op::Op::LessThan,
             syntax::op::Op::GreaterThan => validated::op::Op::GreaterThan,
 
+            // Unary ops
+            syntax::op::Op::Not => validated::op::Op::Not,
+
+            // Logical ops are lowered to `if` by `validate_logical_op`.
+            syntax::op::Op::AndAnd | syntax::op::Op::OrOr => {
+                unreachable!("logical op `{}` should have been lowered", op)
+            }
+
             // These are parsed into other syntax elements and should not appear
             // at this stage of compilation.
             syntax::op::Op::ColonEqual
@@ -1002,7 +2354,11 @@ impl<'me> Validator<'me> {
             | syntax::op::Op::RightAngle
             | syntax::op::Op::Dot
             | syntax::op::Op::Equal
-            | syntax::op::Op::RightArrow => {
+            | syntax::op::Op::RightArrow
+            | syntax::op::Op::FatArrow
+            | syntax::op::Op::DotDot
+            | syntax::op::Op::DotDotEqual
+            | syntax::op::Op::Pipe => {
                 unreachable!("unexpected op")
             }
         }
@@ -1042,10 +2398,10 @@ impl IntoOrigin for syntax::Expr {
 }
 
 impl IntoOrigin for syntax::NamedExpr {
-    type Origin = syntax::NamedExpr;
+    type Origin = NamedExprOrigin;
 
     fn into_origin(self) -> Self::Origin {
-        self
+        NamedExprOrigin::Real(self)
     }
 
     fn synthesized(self) -> Self::Origin {
@@ -1053,6 +2409,18 @@ impl IntoOrigin for syntax::NamedExpr {
     }
 }
 
+impl IntoOrigin for NamedExprOrigin {
+    type Origin = NamedExprOrigin;
+
+    fn into_origin(self) -> Self::Origin {
+        self
+    }
+
+    fn synthesized(self) -> Self::Origin {
+        self
+    }
+}
+
 impl IntoOrigin for ExprOrigin {
     type Origin = ExprOrigin;
 
@@ -0,0 +1,297 @@
+use dada_ir::intrinsic::Intrinsic;
+
+use super::*;
+
+/// How a `for` loop steps through the values it iterates over: a counter
+/// that goes up by one each time around the loop.
+struct Iteration {
+    /// Temporary holding the next value of the counter.
+    counter: validated::LocalVariable,
+
+    /// The loop continues while `counter op bound`; if `None`, it never stops.
+    bound: Option<(validated::Place, validated::op::Op)>,
+
+    /// When iterating over a list, the list that the counter indexes;
+    /// otherwise the loop variable is the counter itself.
+    list: Option<validated::Place>,
+}
+
+impl Validator<'_> {
+    /// Validates `for x in iterable { body }`. A range `a..b` lowers to
+    ///
+    /// ```text
+    /// counter = a
+    /// end = b
+    /// loop {
+    ///     if counter < end {} else { break }
+    ///     current = counter
+    ///     counter = current + 1
+    ///     x = current
+    ///     body
+    /// }
+    /// ```
+    ///
+    /// (`<=` for `a..=b`, and no `if` for `a..`). Anything else is taken
+    /// to be a list, which lowers the same way with the counter starting at
+    /// `0` and ending at `len(list)`, and with `x = list[current]`.
+    /// The counter is advanced before the body runs so that `continue`
+    /// moves on to the next value.
+    pub(super) fn validate_for(
+        &mut self,
+        for_expr: syntax::Expr,
+        label: Option<Word>,
+        decl: LocalVariableDecl,
+        iterable_expr: syntax::Expr,
+        body_expr: syntax::Expr,
+    ) -> validated::Expr {
+        // The iterable is validated first, in the enclosing scope, so that
+        // it cannot refer to the loop variable.
+        let mut init_exprs = vec![];
+        let iteration = match self.range_bounds(iterable_expr) {
+            Some((None, _, _)) => Err(dada_ir::error!(
+                self.span(iterable_expr),
+                "cannot iterate over a range with no start"
+            )
+            .primary_label("`for` loops count up from the start of the range")
+            .emit(self.db)),
+            Some((Some(start_expr), end_expr, inclusive)) if end_expr.is_some() || !inclusive => {
+                Ok(self.validate_range_iteration(&mut init_exprs, start_expr, end_expr, inclusive))
+            }
+            _ => self.validate_list_iteration(&mut init_exprs, iterable_expr),
+        };
+
+        let loop_expr = self.add(validated::ExprData::Error, for_expr);
+
+        // The loop variable is a fresh local variable that is only
+        // in scope within the body; `break` and `continue` in the body
+        // refer to `loop_expr`.
+        let mut body_scope = self.subscope().with_loop_expr(for_expr, label, loop_expr);
+        let decl_data = decl.data(body_scope.syntax_tables());
+        let local_variable = body_scope.add(
+            validated::LocalVariableData {
+                name: Some(decl_data.name),
+                specifier: Some(decl_data.specifier),
+                atomic: decl_data.atomic,
+                mutable: decl_data.mutable,
+            },
+            validated::LocalVariableOrigin::LocalVariable(decl),
+        );
+        body_scope.insert_local_variable(decl, local_variable);
+
+        let mut loop_body_exprs = match &iteration {
+            Ok(iteration) => body_scope.next_value(for_expr, loop_expr, iteration, local_variable),
+            Err(ErrorReported) => vec![],
+        };
+        loop_body_exprs
+            .push(body_scope.validate_expr_in_mode(body_expr, ExprMode::Specifier(Specifier::My)));
+        let loop_body = body_scope.add(
+            validated::ExprData::Seq(loop_body_exprs),
+            for_expr.synthesized(),
+        );
+        let loop_body = body_scope.exit(loop_body);
+
+        if iteration.is_ok() {
+            self.tables[loop_expr] = validated::ExprData::Loop(loop_body);
+        }
+        self.seq(init_exprs, loop_expr)
+    }
+
+    /// If `expr` is a range (possibly in parentheses), its start, end, and
+    /// whether it is inclusive.
+    fn range_bounds(
+        &self,
+        expr: syntax::Expr,
+    ) -> Option<(Option<syntax::Expr>, Option<syntax::Expr>, bool)> {
+        match self.syntax_tables()[expr] {
+            syntax::ExprData::Range(start_expr, end_expr, inclusive) => {
+                Some((start_expr, end_expr, inclusive))
+            }
+            syntax::ExprData::Parenthesized(parenthesized_expr) => {
+                self.range_bounds(parenthesized_expr)
+            }
+            _ => None,
+        }
+    }
+
+    /// Stores the bounds of the range `start..end` in temporaries
+    /// (pushing their assignments onto `init_exprs`).
+    fn validate_range_iteration(
+        &mut self,
+        init_exprs: &mut Vec<validated::Expr>,
+        start_expr: syntax::Expr,
+        end_expr: Option<syntax::Expr>,
+        inclusive: bool,
+    ) -> Iteration {
+        let (start_assign_expr, counter) = self.validate_counter(start_expr);
+        init_exprs.push(start_assign_expr);
+
+        let bound = end_expr.map(|end_expr| {
+            let (end_assign_expr, end_place) =
+                self.validate_expr_in_temporary(end_expr, ExprMode::give());
+            init_exprs.push(end_assign_expr);
+            let op = if inclusive {
+                validated::op::Op::LessEqual
+            } else {
+                validated::op::Op::LessThan
+            };
+            (end_place, op)
+        });
+
+        Iteration {
+            counter,
+            bound,
+            list: None,
+        }
+    }
+
+    /// Stores the list being iterated over (unless it is already in a place),
+    /// its length, and a counter starting at `0` in temporaries (pushing their
+    /// assignments onto `init_exprs`).
+    ///
+    /// FIXME: once we have types, check that `iterable_expr` is a list.
+    /// For now, `len` reports an error when the loop starts if it is not.
+    fn validate_list_iteration(
+        &mut self,
+        init_exprs: &mut Vec<validated::Expr>,
+        iterable_expr: syntax::Expr,
+    ) -> Result<Iteration, ErrorReported> {
+        let (opt_list_assign_expr, list_place) = if self.is_place_expression(iterable_expr) {
+            self.validate_expr_as_place(iterable_expr)?
+        } else {
+            let (list_assign_expr, list_place) =
+                self.validate_expr_in_temporary(iterable_expr, ExprMode::give());
+            (Some(list_assign_expr), list_place)
+        };
+        init_exprs.extend(opt_list_assign_expr);
+
+        // len(list)
+        let origin = iterable_expr.synthesized();
+        let len_place = self.add(validated::PlaceData::Intrinsic(Intrinsic::Len), origin);
+        let len_expr = self.add(validated::ExprData::Reserve(len_place), origin);
+        let list_expr = self.add(validated::ExprData::Reserve(list_place), origin);
+        let name = SpannedOptionalWord::new(self.db, None, self.span(iterable_expr));
+        let argument = self.add(
+            validated::NamedExprData {
+                name,
+                expr: list_expr,
+            },
+            NamedExprOrigin::Synthesized(iterable_expr),
+        );
+        let call_expr = self.add(validated::ExprData::Call(len_expr, vec![argument]), origin);
+        let (len_assign_expr, len_place) = self.store_validated_expr_in_temporary(call_expr);
+        init_exprs.push(len_assign_expr);
+
+        let zero_expr = self.add(validated::ExprData::IntegerLiteral(0), origin);
+        let (zero_assign_expr, counter) = self.store_validated_expr_in_counter(zero_expr);
+        init_exprs.push(zero_assign_expr);
+
+        Ok(Iteration {
+            counter,
+            bound: Some((len_place, validated::op::Op::LessThan)),
+            list: Some(list_place),
+        })
+    }
+
+    /// Validates `start_expr` and stores it in a new temporary for the counter.
+    fn validate_counter(
+        &mut self,
+        start_expr: syntax::Expr,
+    ) -> (validated::Expr, validated::LocalVariable) {
+        let validated_start_expr = self.validate_expr_in_mode(start_expr, ExprMode::give());
+        self.store_validated_expr_in_counter(validated_start_expr)
+    }
+
+    /// Stores `validated_expr` in a new temporary for the counter.
+    fn store_validated_expr_in_counter(
+        &mut self,
+        validated_expr: validated::Expr,
+    ) -> (validated::Expr, validated::LocalVariable) {
+        let (assign_expr, counter_place) = self.store_validated_expr_in_temporary(validated_expr);
+        let validated::PlaceData::LocalVariable(counter) = self.tables[counter_place] else {
+            unreachable!("temporaries are stored in local variables");
+        };
+        (assign_expr, counter)
+    }
+
+    /// The expressions that start each time around the loop: they break out
+    /// of `loop_expr` if the counter is past its bound, advance the counter,
+    /// and assign the current value to `local_variable`.
+    fn next_value(
+        &mut self,
+        for_expr: syntax::Expr,
+        loop_expr: validated::Expr,
+        iteration: &Iteration,
+        local_variable: validated::LocalVariable,
+    ) -> Vec<validated::Expr> {
+        let origin = for_expr.synthesized();
+        let mut exprs = vec![];
+
+        // if counter < bound {} else { break }
+        if let Some((bound_place, op)) = iteration.bound {
+            let counter_expr = self.give_local_variable(iteration.counter, origin);
+            let bound_expr = self.add(validated::ExprData::Give(bound_place), origin);
+            let condition_expr = self.add(
+                validated::ExprData::Op(counter_expr, op, bound_expr),
+                origin,
+            );
+            let empty_tuple = self.add(validated::ExprData::Tuple(vec![]), origin);
+            let break_expr = self.add(
+                validated::ExprData::Break {
+                    from_expr: loop_expr,
+                    with_value: empty_tuple,
+                },
+                origin,
+            );
+            exprs.push(self.add(
+                validated::ExprData::If(condition_expr, empty_tuple, break_expr),
+                origin,
+            ));
+        }
+
+        // current = counter
+        let counter_expr = self.give_local_variable(iteration.counter, origin);
+        let (current_assign_expr, current_place) =
+            self.store_validated_expr_in_temporary(counter_expr);
+        exprs.push(current_assign_expr);
+
+        // counter = current + 1
+        let current_expr = self.add(validated::ExprData::Give(current_place), origin);
+        let one_expr = self.add(validated::ExprData::IntegerLiteral(1), origin);
+        let next_expr = self.add(
+            validated::ExprData::Op(current_expr, validated::op::Op::Plus, one_expr),
+            origin,
+        );
+        exprs.push(self.add(
+            validated::ExprData::AssignTemporary(iteration.counter, next_expr),
+            origin,
+        ));
+
+        // x = current, or x = list[current]
+        let value_place = match iteration.list {
+            Some(list_place) => self.add(
+                validated::PlaceData::Index(list_place, current_place),
+                origin,
+            ),
+            None => current_place,
+        };
+        let target_place = self.add(
+            validated::TargetPlaceData::LocalVariable(local_variable),
+            origin,
+        );
+        exprs.push(self.add(
+            validated::ExprData::AssignFromPlace(target_place, value_place),
+            origin,
+        ));
+
+        exprs
+    }
+
+    fn give_local_variable(
+        &mut self,
+        local_variable: validated::LocalVariable,
+        origin: ExprOrigin,
+    ) -> validated::Expr {
+        let place = self.add(validated::PlaceData::LocalVariable(local_variable), origin);
+        self.add(validated::ExprData::Give(place), origin)
+    }
+}
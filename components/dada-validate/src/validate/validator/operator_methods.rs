@@ -0,0 +1,109 @@
+use dada_ir::class::Class;
+use dada_ir::code::validated::NamedExprOrigin;
+use dada_ir::ty::TyData;
+
+use super::*;
+
+impl Validator<'_> {
+    /// If `lhs_expr` is known to be an instance of a class and `op` can be
+    /// overloaded, validates `lhs op rhs` as a call to the class's method for
+    /// `op`, like `add(lhs, rhs)` for `+`. Reports an error if the class has
+    /// no such method. Returns `None` (validating nothing) otherwise, so that
+    /// the built-in operators (e.g., on numbers) are unaffected.
+    pub(super) fn validate_operator_method_call(
+        &mut self,
+        op_expr: syntax::Expr,
+        lhs_expr: syntax::Expr,
+        op: syntax::op::Op,
+        rhs_expr: syntax::Expr,
+    ) -> Result<Option<validated::Expr>, ErrorReported> {
+        let Some(method_name) = op.method_name() else {
+            return Ok(None);
+        };
+        let Some(class) = self.operand_class(lhs_expr) else {
+            return Ok(None);
+        };
+
+        let db = self.db;
+        let Some(&method) = class
+            .methods(db)
+            .iter()
+            .find(|method| method.name(db).as_str(db) == method_name)
+        else {
+            return Err(dada_ir::error!(
+                self.op_span(op_expr),
+                "class `{}` has no method `{}`",
+                class.name(db).as_str(db),
+                method_name,
+            )
+            .primary_label(format!("`{op}` requires a method named `{method_name}`"))
+            .emit(db));
+        };
+
+        let method_place = self.add(
+            validated::PlaceData::Function(method),
+            op_expr.synthesized(),
+        );
+        let method_expr = self.add(
+            validated::ExprData::Reserve(method_place),
+            op_expr.synthesized(),
+        );
+        let argument_exprs = [lhs_expr, rhs_expr]
+            .into_iter()
+            .map(|operand_expr| {
+                let validated_operand_expr = self.reserve_validated_expr(operand_expr);
+                let name = SpannedOptionalWord::new(db, None, self.span(operand_expr));
+                self.add(
+                    validated::NamedExprData {
+                        name,
+                        expr: validated_operand_expr,
+                    },
+                    NamedExprOrigin::Synthesized(operand_expr),
+                )
+            })
+            .collect();
+        Ok(Some(self.add(
+            validated::ExprData::Call(method_expr, argument_exprs),
+            op_expr,
+        )))
+    }
+
+    /// The class that `expr` is known to be an instance of, if any: a parameter
+    /// declared with a class type (like `p: Point`), `self` in a method, or a
+    /// value constructed from the class right there.
+    ///
+    /// FIXME: once we have types, use the type of `expr` instead.
+    fn operand_class(&self, expr: syntax::Expr) -> Option<Class> {
+        match self.syntax_tables()[expr] {
+            syntax::ExprData::Parenthesized(inner_expr) => self.operand_class(inner_expr),
+            syntax::ExprData::ClassLiteral(class_name, _) => self.class_named(class_name),
+            syntax::ExprData::Call(callee_expr, _) => match self.syntax_tables()[callee_expr] {
+                syntax::ExprData::Id(class_name) => self.class_named(class_name),
+                _ => None,
+            },
+            syntax::ExprData::Id(name) => {
+                let Some(Definition::LocalVariable(lv)) = self.scope.lookup(name) else {
+                    return None;
+                };
+                let LocalVariableOrigin::Parameter(decl) = self.origins[lv] else {
+                    return None;
+                };
+                match self.syntax_tables()[decl].ty {
+                    Some(ty) => match ty.data(self.db) {
+                        TyData::Named(ty_name) => self.class_named(*ty_name),
+                    },
+                    None if name.as_str(self.db) == SELF_PARAMETER => self.function.class(self.db),
+                    None => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn class_named(&self, name: Word) -> Option<Class> {
+        match self.scope.lookup(name)? {
+            Definition::Class(class) => Some(class),
+            _ => None,
+        }
+    }
+}
@@ -24,7 +24,9 @@ impl Validator<'_> {
         )
     }
 
-    pub(super) fn support_escape(&self, expr: syntax::Expr, s: &str) -> String {
+    /// Interprets the escapes in `s`. Unrecognized escapes were already reported
+    /// by the lexer, so they are left as is.
+    pub(super) fn support_escape(&self, s: &str) -> String {
         let mut buffer = String::new();
         let mut chars = s.chars().peekable();
         while let Some(ch) = chars.next() {
@@ -36,14 +38,21 @@ impl Validator<'_> {
                             chars.next();
                             continue;
                         }
-                        _ => {
-                            // FIXME: it'd be better to compute the exact offset within the string
-                            // literal, but that's a *touch* tricky to do since `s` may be some
-                            // stripped subset. We'd either have to track original span info for `s`
-                            // or else recompute it.
-                            dada_ir::error!(self.span(expr), "unrecognized escape `\\{c}`",)
-                                .emit(self.db);
+                        'u' => {
+                            let mut text = String::from("\\");
+                            for c in chars.by_ref() {
+                                text.push(c);
+                                if c == '}' {
+                                    break;
+                                }
+                            }
+                            match unicode_escape(&text[2..]) {
+                                Some(c) => buffer.push(c),
+                                None => buffer.push_str(&text),
+                            }
+                            continue;
                         }
+                        _ => {}
                     }
                 }
             }
@@ -66,6 +75,33 @@ impl Validator<'_> {
         }
     }
 
+    /// Interprets the contents of a character literal, which the parser has
+    /// already checked to be a single (possibly escaped) character. Reports an
+    /// error and returns `None` if the escape is not recognized.
+    pub(super) fn char_literal(&self, expr: syntax::Expr, s: &str) -> Option<char> {
+        let mut chars = s.chars();
+        let ch = chars.next()?;
+        if ch != '\\' {
+            return Some(ch);
+        }
+
+        let escaped = chars.next()?;
+        let result = match escaped {
+            'n' => Some('\n'),
+            'r' => Some('\r'),
+            't' => Some('\t'),
+            '0' => Some('\0'),
+            '\\' | '\'' | '"' => Some(escaped),
+            'u' => unicode_escape(chars.as_str()),
+            _ => None,
+        };
+
+        if result.is_none() {
+            dada_ir::error!(self.span(expr), "unrecognized escape `{s}`").emit(self.db);
+        }
+        result
+    }
+
     /// If the initial string begins with a literal (not escaped) newline, we
     /// need to strip margin. Otherwise, we do nothing but modify escapes.
     fn should_strip_margin(&self, exprs: &[syntax::Expr]) -> bool {
@@ -115,7 +151,7 @@ impl Validator<'_> {
                 }
 
                 // Finally, expand escapes.
-                let escaped = self.support_escape(*expr, without_margin);
+                let escaped = self.support_escape(without_margin);
                 let word = Word::from(self.db, escaped);
                 validated_exprs.push(self.add(validated::ExprData::StringLiteral(word), *expr));
             } else {
@@ -192,3 +228,14 @@ impl Validator<'_> {
         .collect()
     }
 }
+
+/// Interprets the braces of an escape like `\u{1F600}`, which must have between
+/// 1 and 6 hex digits giving a valid character.
+fn unicode_escape(braces: &str) -> Option<char> {
+    braces
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+        .filter(|digits| (1..=6).contains(&digits.len()))
+        .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+        .and_then(char::from_u32)
+}